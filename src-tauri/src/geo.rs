@@ -0,0 +1,734 @@
+// Shared geodesy helpers for map and mission calculations
+// NASA JPL Power of 10 compliant implementation
+
+use crate::map_features::Coordinate;
+
+// ===== CONSTANTS =====
+
+pub const EARTH_RADIUS_KM: f64 = 6371.0;
+// WGS-84 ellipsoid
+const WGS84_SEMI_MAJOR_KM: f64 = 6378.137;
+const WGS84_FLATTENING: f64 = 1.0 / 298.257_223_563;
+// NASA JPL Rule 2: Fixed loop bounds
+const VINCENTY_MAX_ITERATIONS: usize = 200;
+const VINCENTY_TOLERANCE_RAD: f64 = 1e-12;
+
+// ===== DISTANCE =====
+
+// NASA JPL Rule 4: Function under 60 lines
+pub fn haversine_distance(coord1: &Coordinate, coord2: &Coordinate) -> f64 {
+    let lat1_rad = coord1.lat.to_radians();
+    let lat2_rad = coord2.lat.to_radians();
+    let delta_lat = (coord2.lat - coord1.lat).to_radians();
+    let delta_lng = (coord2.lng - coord1.lng).to_radians();
+    
+    let a = (delta_lat / 2.0).sin().powi(2)
+        + lat1_rad.cos() * lat2_rad.cos() * (delta_lng / 2.0).sin().powi(2);
+    let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
+    
+    EARTH_RADIUS_KM * c
+}
+
+// Great-circle distance in meters
+pub fn distance_m(coord1: &Coordinate, coord2: &Coordinate) -> f64 {
+    haversine_distance(coord1, coord2) * 1000.0
+}
+
+// Latitude/longitude within WGS84 ranges
+pub fn is_valid_coordinate(coord: &Coordinate) -> bool {
+    coord.lat.is_finite()
+        && coord.lng.is_finite()
+        && (-90.0..=90.0).contains(&coord.lat)
+        && (-180.0..=180.0).contains(&coord.lng)
+}
+
+// ===== GREAT CIRCLE =====
+
+// Forward azimuth (0-360°, clockwise from north) at `from` of the WGS-84 geodesic to `to`
+pub fn initial_bearing_deg(from: &Coordinate, to: &Coordinate) -> f64 {
+    match vincenty_azimuths(from, to) {
+        Some((initial, _)) => initial.to_degrees().rem_euclid(360.0),
+        None => spherical_bearing_deg(from, to),
+    }
+}
+
+// Heading on arrival at `to` along the WGS-84 geodesic
+pub fn final_bearing_deg(from: &Coordinate, to: &Coordinate) -> f64 {
+    match vincenty_azimuths(from, to) {
+        Some((_, arrival)) => arrival.to_degrees().rem_euclid(360.0),
+        None => (spherical_bearing_deg(to, from) + 180.0).rem_euclid(360.0),
+    }
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+// Vincenty's inverse: geodesic azimuths (radians) at `from` and on arrival at `to`.
+// None when the iteration fails to converge, which happens for nearly antipodal points.
+fn vincenty_azimuths(from: &Coordinate, to: &Coordinate) -> Option<(f64, f64)> {
+    let f = WGS84_FLATTENING;
+    let delta_lng = normalize_longitude(to.lng - from.lng).to_radians();
+    let (sin_u1, cos_u1) = ((1.0 - f) * from.lat.to_radians().tan()).atan().sin_cos();
+    let (sin_u2, cos_u2) = ((1.0 - f) * to.lat.to_radians().tan()).atan().sin_cos();
+
+    let mut lambda = delta_lng;
+    for _ in 0..VINCENTY_MAX_ITERATIONS {
+        let (sin_lambda, cos_lambda) = lambda.sin_cos();
+        let sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+            + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2))
+        .sqrt();
+        if sin_sigma == 0.0 {
+            // Coincident points
+            return Some((0.0, 0.0));
+        }
+        let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+        let sigma = sin_sigma.atan2(cos_sigma);
+        let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+        let cos2_alpha = 1.0 - sin_alpha * sin_alpha;
+        // Both points on the equator
+        let cos_2sigma_m = if cos2_alpha == 0.0 {
+            0.0
+        } else {
+            cos_sigma - 2.0 * sin_u1 * sin_u2 / cos2_alpha
+        };
+        let c = f / 16.0 * cos2_alpha * (4.0 + f * (4.0 - 3.0 * cos2_alpha));
+        let previous = lambda;
+        lambda = delta_lng + (1.0 - c) * f * sin_alpha
+            * (sigma + c * sin_sigma
+                * (cos_2sigma_m + c * cos_sigma * (2.0 * cos_2sigma_m * cos_2sigma_m - 1.0)));
+
+        if (lambda - previous).abs() < VINCENTY_TOLERANCE_RAD {
+            let (sin_lambda, cos_lambda) = lambda.sin_cos();
+            let initial = (cos_u2 * sin_lambda)
+                .atan2(cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda);
+            let arrival = (cos_u1 * sin_lambda)
+                .atan2(cos_u1 * sin_u2 * cos_lambda - sin_u1 * cos_u2);
+            return Some((initial, arrival));
+        }
+    }
+    None
+}
+
+// Forward azimuth on the sphere, the fallback where Vincenty does not converge
+fn spherical_bearing_deg(from: &Coordinate, to: &Coordinate) -> f64 {
+    let (lat1, lat2) = (from.lat.to_radians(), to.lat.to_radians());
+    let delta_lng = normalize_longitude(to.lng - from.lng).to_radians();
+
+    let y = delta_lng.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * delta_lng.cos();
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+// Halfway point along the great circle from `from` to `to`
+pub fn great_circle_midpoint(from: &Coordinate, to: &Coordinate) -> Coordinate {
+    let (lat1, lat2) = (from.lat.to_radians(), to.lat.to_radians());
+    let lng1 = from.lng.to_radians();
+    let delta_lng = normalize_longitude(to.lng - from.lng).to_radians();
+
+    let bx = lat2.cos() * delta_lng.cos();
+    let by = lat2.cos() * delta_lng.sin();
+    let lat = (lat1.sin() + lat2.sin()).atan2(((lat1.cos() + bx).powi(2) + by * by).sqrt());
+    let lng = lng1 + by.atan2(lat1.cos() + bx);
+    Coordinate {
+        lat: lat.to_degrees(),
+        lng: normalize_longitude(lng.to_degrees()),
+        alt: match (from.alt, to.alt) {
+            (Some(a), Some(b)) => Some((a + b) / 2.0),
+            _ => None,
+        },
+    }
+}
+
+// ===== MAGNETIC DECLINATION =====
+
+// WMM2025 Gauss coefficients (nT) and their secular variation (nT/year)
+mod wmm {
+    pub const EPOCH_YEAR: f64 = 2025.0;
+    pub const VALID_YEARS: f64 = 5.0;
+    pub const MAX_DEGREE: usize = 12;
+    // Geomagnetic reference radius
+    pub const REFERENCE_RADIUS_KM: f64 = 6371.2;
+    // (g, h, ġ, ḣ) for n = 1..=12 and m = 0..=n, in that order
+    pub const COEFFICIENTS: [(f64, f64, f64, f64); 90] = [
+        // n = 1
+        (-29351.8, 0.0, 12.0, 0.0),
+        (-1410.8, 4545.4, 9.7, -21.5),
+        // n = 2
+        (-2556.6, 0.0, -11.6, 0.0),
+        (2951.1, -3133.6, -5.2, -27.7),
+        (1649.3, -815.1, -8.0, -12.1),
+        // n = 3
+        (1361.0, 0.0, -1.3, 0.0),
+        (-2404.1, -56.6, -4.2, 4.0),
+        (1243.8, 237.5, 0.4, -0.3),
+        (453.6, -549.5, -15.6, -4.1),
+        // n = 4
+        (895.0, 0.0, -1.6, 0.0),
+        (799.5, 278.6, -2.4, -1.1),
+        (55.7, -133.9, -6.0, 4.1),
+        (-281.1, 212.0, 5.6, 1.6),
+        (12.1, -375.6, -7.0, -4.4),
+        // n = 5
+        (-233.2, 0.0, 0.6, 0.0),
+        (368.9, 45.4, 1.4, -0.5),
+        (187.2, 220.2, 0.0, 2.2),
+        (-138.7, -122.9, 0.6, 0.4),
+        (-142.0, 43.0, 2.2, 1.7),
+        (20.9, 106.1, 0.9, 1.9),
+        // n = 6
+        (64.4, 0.0, -0.2, 0.0),
+        (63.8, -18.4, -0.4, 0.3),
+        (76.9, 16.8, 0.9, -1.6),
+        (-115.7, 48.8, 1.2, -0.4),
+        (-40.9, -59.8, -0.9, 0.9),
+        (14.9, 10.9, 0.3, 0.7),
+        (-60.7, 72.7, 0.9, 0.9),
+        // n = 7
+        (79.5, 0.0, 0.0, 0.0),
+        (-77.0, -48.9, -0.1, 0.6),
+        (-8.8, -14.4, -0.1, 0.5),
+        (59.3, -1.0, 0.5, -0.8),
+        (15.8, 23.4, -0.1, 0.0),
+        (2.5, -7.4, -0.8, -1.0),
+        (-11.1, -25.1, -0.8, 0.6),
+        (14.2, -2.3, 0.8, -0.2),
+        // n = 8
+        (23.2, 0.0, -0.1, 0.0),
+        (10.8, 7.1, 0.2, -0.2),
+        (-17.5, -12.6, 0.0, 0.5),
+        (2.0, 11.4, 0.5, -0.4),
+        (-21.7, -9.7, -0.1, 0.4),
+        (16.9, 12.7, 0.3, -0.5),
+        (15.0, 0.7, 0.2, -0.6),
+        (-16.8, -5.2, 0.0, 0.3),
+        (0.9, 3.9, 0.2, 0.2),
+        // n = 9
+        (4.6, 0.0, 0.0, 0.0),
+        (7.8, -24.8, -0.1, -0.3),
+        (3.0, 12.2, 0.1, 0.3),
+        (-0.2, 8.3, 0.3, -0.3),
+        (-2.5, -3.3, -0.3, 0.3),
+        (-13.1, -5.2, 0.0, 0.2),
+        (2.4, 7.2, 0.3, -0.1),
+        (8.6, -0.6, -0.1, -0.2),
+        (-8.7, 0.8, 0.1, 0.4),
+        (-12.9, 10.0, -0.1, 0.1),
+        // n = 10
+        (-1.3, 0.0, 0.1, 0.0),
+        (-6.4, 3.3, 0.0, 0.0),
+        (0.2, 0.0, 0.1, 0.0),
+        (2.0, 2.4, 0.1, -0.2),
+        (-1.0, 5.3, 0.0, 0.1),
+        (-0.6, -9.1, -0.3, -0.1),
+        (-0.9, 0.4, 0.0, 0.1),
+        (1.5, -4.2, -0.1, 0.0),
+        (0.9, -3.8, -0.1, -0.1),
+        (-2.7, 0.9, 0.0, 0.2),
+        (-3.9, -9.1, 0.0, 0.0),
+        // n = 11
+        (2.9, 0.0, 0.0, 0.0),
+        (-1.5, 0.0, 0.0, 0.0),
+        (-2.5, 2.9, 0.0, 0.1),
+        (2.4, -0.6, 0.0, 0.0),
+        (-0.6, 0.2, 0.0, 0.1),
+        (-0.1, 0.5, -0.1, 0.0),
+        (-0.6, -0.3, 0.0, 0.0),
+        (-0.1, -1.2, 0.0, 0.1),
+        (1.1, -1.7, -0.1, 0.0),
+        (-1.0, -2.9, -0.1, 0.0),
+        (-0.2, -1.8, -0.1, 0.0),
+        (2.6, -2.3, -0.1, 0.0),
+        // n = 12
+        (-2.0, 0.0, 0.0, 0.0),
+        (-0.2, -1.3, 0.0, 0.0),
+        (0.3, 0.7, 0.0, 0.0),
+        (1.2, 1.0, 0.0, -0.1),
+        (-1.3, -1.4, 0.0, 0.1),
+        (0.6, 0.0, 0.0, 0.0),
+        (0.6, 0.6, 0.1, 0.0),
+        (0.5, -0.1, 0.0, 0.0),
+        (-0.1, 0.8, 0.0, 0.0),
+        (-0.4, 0.1, 0.0, 0.0),
+        (-0.2, -1.0, -0.1, 0.0),
+        (-1.3, 0.1, 0.0, 0.0),
+        (-0.7, 0.2, -0.1, -0.1),
+    ];
+}
+
+// Schmidt semi-normalized associated Legendre functions P[n][m] of sin(latitude)
+// and their derivatives with respect to colatitude
+type LegendreTable = [[f64; wmm::MAX_DEGREE + 1]; wmm::MAX_DEGREE + 1];
+
+// NASA JPL Rule 4: Function under 60 lines
+// Declination (degrees, east positive) at `coord` on the ellipsoid in decimal
+// year `year`, from the full degree-12 World Magnetic Model
+pub fn magnetic_declination_deg(coord: &Coordinate, year: f64) -> Result<f64, String> {
+    let years = year - wmm::EPOCH_YEAR;
+    if !(years.is_finite() && (0.0..=wmm::VALID_YEARS).contains(&years)) {
+        return Err(format!(
+            "Magnetic model is valid from {} to {}",
+            wmm::EPOCH_YEAR,
+            wmm::EPOCH_YEAR + wmm::VALID_YEARS
+        ));
+    }
+    if !is_valid_coordinate(coord) || coord.lat.abs() == 90.0 {
+        return Err("Declination is undefined at the poles and for invalid coordinates".to_string());
+    }
+
+    // Geodetic latitude on the ellipsoid to geocentric radius and latitude
+    let lat = coord.lat.to_radians();
+    let e2 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+    let prime_vertical_km = WGS84_SEMI_MAJOR_KM / (1.0 - e2 * lat.sin().powi(2)).sqrt();
+    let p = prime_vertical_km * lat.cos();
+    let z = prime_vertical_km * (1.0 - e2) * lat.sin();
+    let radius_km = p.hypot(z);
+    let geocentric_lat = (z / radius_km).asin();
+    let (legendre, legendre_derivative) = schmidt_legendre(geocentric_lat);
+
+    // North, east and down field components in the geocentric frame
+    let (mut north, mut east, mut down) = (0.0, 0.0, 0.0);
+    let mut coefficients = wmm::COEFFICIENTS.iter();
+    for n in 1..=wmm::MAX_DEGREE {
+        let radial = (wmm::REFERENCE_RADIUS_KM / radius_km).powi(n as i32 + 2);
+        for m in 0..=n {
+            let (g, h, g_dot, h_dot) = coefficients.next().copied().unwrap_or_default();
+            let (g, h) = (g + g_dot * years, h + h_dot * years);
+            let (sin_ml, cos_ml) = (m as f64 * coord.lng.to_radians()).sin_cos();
+            let cosine_term = g * cos_ml + h * sin_ml;
+            north += radial * cosine_term * legendre_derivative[n][m];
+            east += radial * m as f64 * (g * sin_ml - h * cos_ml) * legendre[n][m];
+            down -= radial * (n as f64 + 1.0) * cosine_term * legendre[n][m];
+        }
+    }
+    east /= geocentric_lat.cos();
+
+    // Rotate north into the geodetic frame; east is unaffected
+    let psi = geocentric_lat - lat;
+    let north = north * psi.cos() - down * psi.sin();
+    Ok(east.atan2(north).to_degrees())
+}
+
+// Recursions as in the WMM technical report, in Gauss normalization then
+// scaled to Schmidt semi-normalization
+fn schmidt_legendre(geocentric_lat: f64) -> (LegendreTable, LegendreTable) {
+    let (cos_theta, sin_theta) = geocentric_lat.sin_cos();
+    let mut p = [[0.0; wmm::MAX_DEGREE + 1]; wmm::MAX_DEGREE + 1];
+    let mut dp = [[0.0; wmm::MAX_DEGREE + 1]; wmm::MAX_DEGREE + 1];
+    let mut schmidt = [[0.0; wmm::MAX_DEGREE + 1]; wmm::MAX_DEGREE + 1];
+    p[0][0] = 1.0;
+    schmidt[0][0] = 1.0;
+    for n in 1..=wmm::MAX_DEGREE {
+        schmidt[n][0] = schmidt[n - 1][0] * (2 * n - 1) as f64 / n as f64;
+        for m in 0..=n {
+            if n == m {
+                p[n][m] = sin_theta * p[n - 1][m - 1];
+                dp[n][m] = sin_theta * dp[n - 1][m - 1] + cos_theta * p[n - 1][m - 1];
+            } else if n == 1 {
+                p[n][m] = cos_theta * p[n - 1][m];
+                dp[n][m] = cos_theta * dp[n - 1][m] - sin_theta * p[n - 1][m];
+            } else {
+                let k = ((n - 1).pow(2) - m * m) as f64 / ((2 * n - 1) * (2 * n - 3)) as f64;
+                p[n][m] = cos_theta * p[n - 1][m] - k * p[n - 2][m];
+                dp[n][m] = cos_theta * dp[n - 1][m] - sin_theta * p[n - 1][m] - k * dp[n - 2][m];
+            }
+            if m > 0 {
+                let doubled = if m == 1 { 2.0 } else { 1.0 };
+                schmidt[n][m] = schmidt[n][m - 1] * ((n - m + 1) as f64 * doubled / (n + m) as f64).sqrt();
+            }
+        }
+    }
+    for n in 0..=wmm::MAX_DEGREE {
+        for m in 0..=n {
+            p[n][m] *= schmidt[n][m];
+            dp[n][m] *= schmidt[n][m];
+        }
+    }
+    (p, dp)
+}
+
+// Current date as a decimal year, e.g. 2025.5 in early July 2025
+pub fn current_decimal_year() -> f64 {
+    const SECONDS_PER_YEAR: f64 = 31_556_952.0;
+    let seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |elapsed| elapsed.as_secs_f64());
+    1970.0 + seconds / SECONDS_PER_YEAR
+}
+
+// ===== AREA =====
+
+// NASA JPL Rule 4: Function under 60 lines
+// Area in km² enclosed by `points` on the WGS-84 ellipsoid (closing edge implied).
+// Latitudes are mapped to authalic latitudes, which makes the spherical shoelace
+// sum R²/2 · Σ (λ2 - λ1)(sin β1 + sin β2) equal-area on the ellipsoid.
+pub fn polygon_area_km2(points: &[Coordinate]) -> f64 {
+    if points.len() < 3 {
+        return 0.0;
+    }
+    let e2 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+    let q_pole = authalic_q(1.0, e2);
+    let authalic_radius_km = WGS84_SEMI_MAJOR_KM * (q_pole / 2.0).sqrt();
+
+    let mut sum = 0.0;
+    for (index, from) in points.iter().enumerate() {
+        let to = &points[(index + 1) % points.len()];
+        let delta_lng = normalize_longitude(to.lng - from.lng).to_radians();
+        let sin_beta_from = authalic_q(from.lat.to_radians().sin(), e2) / q_pole;
+        let sin_beta_to = authalic_q(to.lat.to_radians().sin(), e2) / q_pole;
+        sum += delta_lng * (sin_beta_from + sin_beta_to);
+    }
+    (sum * authalic_radius_km * authalic_radius_km / 2.0).abs()
+}
+
+// Snyder's q(φ) for the authalic latitude, from sin φ and the squared eccentricity
+fn authalic_q(sin_lat: f64, e2: f64) -> f64 {
+    let e = e2.sqrt();
+    let e_sin = e * sin_lat;
+    (1.0 - e2) * (sin_lat / (1.0 - e_sin * e_sin) - ((1.0 - e_sin) / (1.0 + e_sin)).ln() / (2.0 * e))
+}
+
+// ===== GNOMONIC PROJECTION =====
+
+// East/north position of `point` in the gnomonic projection centered on `center`,
+// in Earth radii. Great circles project to straight lines, so segment tests in
+// this plane hold at any latitude. None for points 90° or more from the center.
+pub fn gnomonic_project(center: &Coordinate, point: &Coordinate) -> Option<(f64, f64)> {
+    let (lat0, lat) = (center.lat.to_radians(), point.lat.to_radians());
+    let delta_lng = normalize_longitude(point.lng - center.lng).to_radians();
+
+    let cos_c = lat0.sin() * lat.sin() + lat0.cos() * lat.cos() * delta_lng.cos();
+    if cos_c <= 1e-9 {
+        return None;
+    }
+    let east = lat.cos() * delta_lng.sin() / cos_c;
+    let north = (lat0.cos() * lat.sin() - lat0.sin() * lat.cos() * delta_lng.cos()) / cos_c;
+    Some((east, north))
+}
+
+// Inverse of `gnomonic_project`
+pub fn gnomonic_unproject(center: &Coordinate, east: f64, north: f64) -> Coordinate {
+    let lat0 = center.lat.to_radians();
+    let rho = east.hypot(north);
+    if rho < 1e-15 {
+        return Coordinate { lat: center.lat, lng: center.lng, alt: None };
+    }
+
+    let (sin_c, cos_c) = rho.atan().sin_cos();
+    let lat = (cos_c * lat0.sin() + north * sin_c * lat0.cos() / rho).asin();
+    let delta_lng = (east * sin_c).atan2(rho * lat0.cos() * cos_c - north * lat0.sin() * sin_c);
+    Coordinate {
+        lat: lat.to_degrees(),
+        lng: normalize_longitude(center.lng + delta_lng.to_degrees()),
+        alt: None,
+    }
+}
+
+// ===== LOCAL TANGENT PLANE =====
+
+// Meters north/east of `origin` to reach `target` (equirectangular, valid for mission-scale distances)
+pub fn local_offset_m(origin: &Coordinate, target: &Coordinate) -> (f64, f64) {
+    let earth_radius_m = EARTH_RADIUS_KM * 1000.0;
+    let delta_lng = normalize_longitude(target.lng - origin.lng);
+
+    let north_m = (target.lat - origin.lat).to_radians() * earth_radius_m;
+    let east_m = delta_lng.to_radians() * earth_radius_m * origin.lat.to_radians().cos();
+    (north_m, east_m)
+}
+
+// Coordinate reached by moving `north_m`/`east_m` from `origin` (inverse of `local_offset_m`)
+pub fn offset_coordinate(origin: &Coordinate, north_m: f64, east_m: f64) -> Coordinate {
+    let earth_radius_m = EARTH_RADIUS_KM * 1000.0;
+    let cos_lat = origin.lat.to_radians().cos().max(1e-12);
+
+    Coordinate {
+        lat: origin.lat + (north_m / earth_radius_m).to_degrees(),
+        lng: normalize_longitude(origin.lng + (east_m / (earth_radius_m * cos_lat)).to_degrees()),
+        alt: origin.alt,
+    }
+}
+
+// Initial compass bearing (0-360°, clockwise from north) from `from` to `to`
+pub fn bearing_deg(from: &Coordinate, to: &Coordinate) -> f64 {
+    let (north_m, east_m) = local_offset_m(from, to);
+    east_m.atan2(north_m).to_degrees().rem_euclid(360.0)
+}
+
+// Absolute heading change (0-180°) between two bearings
+pub fn turn_angle_deg(bearing_in: f64, bearing_out: f64) -> f64 {
+    let delta = (bearing_out - bearing_in).rem_euclid(360.0);
+    if delta > 180.0 { 360.0 - delta } else { delta }
+}
+
+// Rotate a north/east offset clockwise by `angle_deg` (compass convention)
+pub fn rotate_offset(north_m: f64, east_m: f64, angle_deg: f64) -> (f64, f64) {
+    let (sin, cos) = angle_deg.to_radians().sin_cos();
+    (north_m * cos - east_m * sin, east_m * cos + north_m * sin)
+}
+
+// Wrap a longitude into [-180, 180]
+pub fn normalize_longitude(lng: f64) -> f64 {
+    let wrapped = (lng + 180.0).rem_euclid(360.0) - 180.0;
+    if wrapped == -180.0 && lng > 0.0 { 180.0 } else { wrapped }
+}
+
+// Point at `fraction` (0..=1) along the straight segment from `from` to `to`
+pub fn interpolate(from: &Coordinate, to: &Coordinate, fraction: f64) -> Coordinate {
+    let (north_m, east_m) = local_offset_m(from, to);
+    let mut point = offset_coordinate(from, north_m * fraction, east_m * fraction);
+    point.alt = match (from.alt, to.alt) {
+        (Some(a), Some(b)) => Some(a + (b - a) * fraction),
+        _ => None,
+    };
+    point
+}
+
+// ===== SPLINES =====
+
+// `samples` + 1 points along the uniform Catmull-Rom curve from `p1` to `p2`, with
+// `p0` and `p3` shaping the end tangents. Altitude changes linearly along the curve.
+pub fn catmull_rom_points(
+    p0: &Coordinate,
+    p1: &Coordinate,
+    p2: &Coordinate,
+    p3: &Coordinate,
+    samples: usize,
+) -> Vec<Coordinate> {
+    let (a, c, d) = (local_offset_m(p1, p0), local_offset_m(p1, p2), local_offset_m(p1, p3));
+    // p1 is the origin of the local frame
+    let blend = |a: f64, c: f64, d: f64, t: f64| {
+        0.5 * ((c - a) * t + (2.0 * a + 4.0 * c - d) * t * t + (-a - 3.0 * c + d) * t * t * t)
+    };
+
+    (0..=samples)
+        .map(|i| {
+            let t = i as f64 / samples.max(1) as f64;
+            let mut point = offset_coordinate(p1, blend(a.0, c.0, d.0, t), blend(a.1, c.1, d.1, t));
+            point.alt = match (p1.alt, p2.alt) {
+                (Some(from), Some(to)) => Some(from + (to - from) * t),
+                _ => None,
+            };
+            point
+        })
+        .collect()
+}
+
+// ===== DATUM TRANSFORMATION =====
+
+// Reference ellipsoid of a datum
+struct Ellipsoid {
+    semi_major_m: f64,
+    inverse_flattening: f64,
+}
+
+// 7-parameter Helmert transform from WGS-84 to a datum, position vector
+// convention: translations in meters, rotations in arc-seconds, scale in ppm
+struct HelmertParams {
+    tx: f64,
+    ty: f64,
+    tz: f64,
+    rx: f64,
+    ry: f64,
+    rz: f64,
+    scale_ppm: f64,
+}
+
+struct Datum {
+    name: &'static str,
+    ellipsoid: Ellipsoid,
+    from_wgs84: HelmertParams,
+}
+
+pub const WGS84_DATUM: &str = "WGS84";
+const WGS84_ELLIPSOID: Ellipsoid = Ellipsoid { semi_major_m: 6_378_137.0, inverse_flattening: 298.257_223_563 };
+// Datums with published parameters against WGS-84. NAD83 uses the
+// NAD83(CORS96)-ITRF96 relation, ED50 the European mean shift and OSGB36
+// the Ordnance Survey parameters.
+const DATUMS: [Datum; 3] = [
+    Datum {
+        name: "NAD83",
+        ellipsoid: Ellipsoid { semi_major_m: 6_378_137.0, inverse_flattening: 298.257_222_101 },
+        from_wgs84: HelmertParams {
+            tx: 0.9910, ty: -1.9072, tz: -0.5129,
+            rx: -0.025_79, ry: -0.009_65, rz: -0.011_66,
+            scale_ppm: 0.0,
+        },
+    },
+    Datum {
+        name: "ED50",
+        ellipsoid: Ellipsoid { semi_major_m: 6_378_388.0, inverse_flattening: 297.0 },
+        from_wgs84: HelmertParams {
+            tx: 87.0, ty: 98.0, tz: 121.0,
+            rx: 0.0, ry: 0.0, rz: 0.0,
+            scale_ppm: 0.0,
+        },
+    },
+    Datum {
+        name: "OSGB36",
+        ellipsoid: Ellipsoid { semi_major_m: 6_377_563.396, inverse_flattening: 299.324_964_6 },
+        from_wgs84: HelmertParams {
+            tx: -446.448, ty: 125.157, tz: -542.060,
+            rx: -0.1502, ry: -0.2470, rz: -0.8421,
+            scale_ppm: 20.4894,
+        },
+    },
+];
+// NASA JPL Rule 2: fixed iteration count for the ECEF to geodetic latitude
+const GEODETIC_ITERATIONS: usize = 5;
+
+// Canonical name of a supported datum, accepting e.g. "wgs-84" or "ED-50"
+pub fn datum_name(name: &str) -> Result<&'static str, String> {
+    let normalized: String = name.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_uppercase();
+    std::iter::once(WGS84_DATUM)
+        .chain(DATUMS.iter().map(|datum| datum.name))
+        .find(|known| *known == normalized)
+        .ok_or_else(|| format!("Unsupported datum {name}"))
+}
+
+// Transform between WGS-84 and one of DATUMS, in either direction. Altitude
+// is ellipsoidal height; a coordinate without one is taken at 0 m.
+pub fn transform_datum(coord: &Coordinate, from_datum: &str, to_datum: &str) -> Result<Coordinate, String> {
+    let (from, to) = (datum_name(from_datum)?, datum_name(to_datum)?);
+    if from == to {
+        return Ok(coord.clone());
+    }
+    let find = |name: &str| DATUMS.iter().find(|datum| datum.name == name);
+    let (source, target, inverse) = match (find(from), find(to)) {
+        (None, Some(datum)) => (&WGS84_ELLIPSOID, &datum.ellipsoid, Some((&datum.from_wgs84, false))),
+        (Some(datum), None) => (&datum.ellipsoid, &WGS84_ELLIPSOID, Some((&datum.from_wgs84, true))),
+        _ => (&WGS84_ELLIPSOID, &WGS84_ELLIPSOID, None),
+    };
+    let (params, inverse) = inverse
+        .ok_or_else(|| format!("No transformation from {from} to {to}; only conversions to and from WGS84 are supported"))?;
+
+    let ecef = geodetic_to_ecef(coord, source);
+    let (lat, lng, height) = ecef_to_geodetic(helmert(ecef, params, inverse), target);
+    Ok(Coordinate { lat, lng, alt: coord.alt.map(|_| height) })
+}
+
+fn geodetic_to_ecef(coord: &Coordinate, ellipsoid: &Ellipsoid) -> [f64; 3] {
+    let f = 1.0 / ellipsoid.inverse_flattening;
+    let e2 = f * (2.0 - f);
+    let (lat, lng) = (coord.lat.to_radians(), coord.lng.to_radians());
+    let height = coord.alt.unwrap_or(0.0);
+    let prime_vertical = ellipsoid.semi_major_m / (1.0 - e2 * lat.sin().powi(2)).sqrt();
+    [
+        (prime_vertical + height) * lat.cos() * lng.cos(),
+        (prime_vertical + height) * lat.cos() * lng.sin(),
+        (prime_vertical * (1.0 - e2) + height) * lat.sin(),
+    ]
+}
+
+// Latitude and longitude in degrees and ellipsoidal height in meters
+fn ecef_to_geodetic(ecef: [f64; 3], ellipsoid: &Ellipsoid) -> (f64, f64, f64) {
+    let f = 1.0 / ellipsoid.inverse_flattening;
+    let e2 = f * (2.0 - f);
+    let [x, y, z] = ecef;
+    let p = x.hypot(y);
+    let mut lat = z.atan2(p * (1.0 - e2));
+    let mut prime_vertical = ellipsoid.semi_major_m;
+    for _ in 0..GEODETIC_ITERATIONS {
+        prime_vertical = ellipsoid.semi_major_m / (1.0 - e2 * lat.sin().powi(2)).sqrt();
+        lat = (z + e2 * prime_vertical * lat.sin()).atan2(p);
+    }
+    let height = p / lat.cos() - prime_vertical;
+    (lat.to_degrees(), y.atan2(x).to_degrees(), height)
+}
+
+// Apply the transform, or its inverse by negating every parameter; the
+// rotations are small enough that the error stays below a millimeter
+fn helmert(ecef: [f64; 3], params: &HelmertParams, inverse: bool) -> [f64; 3] {
+    let sign = if inverse { -1.0 } else { 1.0 };
+    let arcsec = |value: f64| sign * (value / 3600.0).to_radians();
+    let (rx, ry, rz) = (arcsec(params.rx), arcsec(params.ry), arcsec(params.rz));
+    let scale = 1.0 + sign * params.scale_ppm * 1e-6;
+    let [x, y, z] = ecef;
+    [
+        sign * params.tx + scale * (x - rz * y + ry * z),
+        sign * params.ty + scale * (rz * x + y - rx * z),
+        sign * params.tz + scale * (-ry * x + rx * y + z),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(lat: f64, lng: f64) -> Coordinate {
+        Coordinate { lat, lng, alt: None }
+    }
+
+    fn bearing_error_deg(actual: f64, expected: f64) -> f64 {
+        (actual - expected + 180.0).rem_euclid(360.0) - 180.0
+    }
+
+    #[test]
+    fn san_francisco_to_new_york_bearings() {
+        let san_francisco = point(37.7749, -122.4194);
+        let new_york = point(40.7128, -74.0060);
+
+        // Geodesic azimuths on WGS-84: 69°55'24" out, 101°41'22" on arrival
+        let initial = initial_bearing_deg(&san_francisco, &new_york);
+        let arrival = final_bearing_deg(&san_francisco, &new_york);
+        assert!(bearing_error_deg(initial, 69.9235).abs() < 0.1);
+        assert!(bearing_error_deg(arrival, 101.6894).abs() < 0.1);
+
+        // The midpoint sits halfway along the great circle
+        let midpoint = great_circle_midpoint(&san_francisco, &new_york);
+        let total = haversine_distance(&san_francisco, &new_york);
+        assert!((haversine_distance(&san_francisco, &midpoint) - total / 2.0).abs() < 1e-6);
+        assert!((haversine_distance(&midpoint, &new_york) - total / 2.0).abs() < 1e-6);
+        let off_track = bearing_error_deg(
+            spherical_bearing_deg(&san_francisco, &midpoint),
+            spherical_bearing_deg(&san_francisco, &new_york),
+        );
+        assert!(off_track.abs() < 1e-9);
+    }
+
+    #[test]
+    fn bearings_along_meridians_and_near_antipodes() {
+        assert!(initial_bearing_deg(&point(10.0, 5.0), &point(20.0, 5.0)).abs() < 1e-9);
+        assert!((initial_bearing_deg(&point(10.0, 5.0), &point(-20.0, 5.0)) - 180.0).abs() < 1e-9);
+
+        // Vincenty cannot settle here; the spherical fallback still answers
+        let bearing = initial_bearing_deg(&point(0.0, 0.0), &point(0.5, 179.7));
+        assert!(bearing.is_finite() && (0.0..360.0).contains(&bearing));
+    }
+
+    #[test]
+    fn nad83_control_point_shifts_to_wgs84() {
+        // Meades Ranch, Kansas, on the ellipsoid. The NAD83(CORS96) to ITRF96
+        // parameters move it 0.75 m north and 0.72 m west.
+        let nad83 = Coordinate { lat: 39.224_079_444, lng: -98.541_807_222, alt: Some(0.0) };
+        let wgs84 = transform_datum(&nad83, "NAD83", "WGS-84").unwrap();
+        let expected = point(39.224_086_231, -98.541_815_601);
+        assert!(distance_m(&wgs84, &expected) < 1.0);
+        let shift_m = distance_m(&nad83, &wgs84);
+        assert!((0.9..1.2).contains(&shift_m), "{shift_m}");
+        assert!((wgs84.alt.unwrap() + 1.03).abs() < 0.05);
+
+        let back = transform_datum(&wgs84, "wgs84", "nad-83").unwrap();
+        assert!(distance_m(&back, &nad83) < 0.001);
+        assert!(transform_datum(&nad83, "NAD83", "ED50").is_err());
+        assert!(transform_datum(&nad83, "NAD27", "WGS84").is_err());
+    }
+
+    #[test]
+    fn declination_matches_wmm2025_reference_values() {
+        // WMM2025 declinations for 2025.0 at sea level, to 0.1°
+        let references = [
+            ("London", 51.5074, -0.1278, 0.9),
+            ("Boulder", 40.015, -105.27, 7.8),
+            ("New York", 40.7128, -74.006, -12.5),
+            ("Sydney", -33.87, 151.21, 12.8),
+        ];
+        for (name, lat, lng, expected) in references {
+            let declination = magnetic_declination_deg(&point(lat, lng), 2025.0).unwrap();
+            assert!((declination - expected).abs() < 0.5, "{name}: {declination:.2}°");
+        }
+
+        // Secular variation moves London east by roughly 0.2° a year
+        let later = magnetic_declination_deg(&point(51.5074, -0.1278), 2028.0).unwrap();
+        let now = magnetic_declination_deg(&point(51.5074, -0.1278), 2025.0).unwrap();
+        assert!(later > now);
+        assert!(magnetic_declination_deg(&point(51.5, 0.0), 2031.0).is_err());
+        assert!(magnetic_declination_deg(&point(90.0, 0.0), 2025.0).is_err());
+    }
+}
@@ -0,0 +1,240 @@
+// Geofence storage and mission route conflict checking backend
+// NASA JPL Power of 10 compliant implementation
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::State;
+
+use crate::geo::{gnomonic_project, gnomonic_unproject, great_circle_midpoint, is_valid_coordinate};
+use crate::map_features::Coordinate;
+use crate::{AppState, MissionItem};
+
+// ===== TYPE DEFINITIONS =====
+
+// A flight leg crossing a polygon edge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteConflict {
+    pub from_item_id: String,
+    pub to_item_id: String,
+    // Index into the supplied no-fly zones; None for the stored geofence boundary
+    pub polygon_index: Option<usize>,
+    // Where the leg crosses the polygon's edges, in flight order
+    pub crossings: Vec<Coordinate>,
+}
+
+// NASA JPL Rule 2: bound the polygons checked by one request
+const MAX_POLYGON_VERTICES: usize = 1000;
+const MAX_NO_FLY_ZONES: usize = 100;
+// Ceilings are metres above home
+const MAX_CEILING_M: f64 = 10_000.0;
+
+// ===== STATE MANAGEMENT =====
+
+pub struct GeofenceState {
+    // Inclusion boundary the vehicle must stay within
+    boundary: Mutex<Option<Vec<Coordinate>>>,
+    // Highest altitude above home the vehicle may be sent to
+    ceiling_m: Mutex<Option<f64>>,
+}
+
+impl GeofenceState {
+    pub fn new() -> Self {
+        Self {
+            boundary: Mutex::new(None),
+            ceiling_m: Mutex::new(None),
+        }
+    }
+}
+
+// ===== GEOFENCE COMMANDS =====
+
+#[tauri::command]
+pub async fn set_geofence(
+    boundary: Vec<Coordinate>,
+    state: State<'_, GeofenceState>,
+) -> Result<(), String> {
+    validate_polygon(&boundary, "Geofence boundary")?;
+    *state.boundary.lock().map_err(|_| "Failed to lock geofence")? = Some(boundary);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_geofence(
+    state: State<'_, GeofenceState>,
+) -> Result<Option<Vec<Coordinate>>, String> {
+    Ok(state.boundary.lock().map_err(|_| "Failed to lock geofence")?.clone())
+}
+
+#[tauri::command]
+pub async fn clear_geofence(
+    state: State<'_, GeofenceState>,
+) -> Result<(), String> {
+    *state.boundary.lock().map_err(|_| "Failed to lock geofence")? = None;
+    *state.ceiling_m.lock().map_err(|_| "Failed to lock geofence ceiling")? = None;
+    Ok(())
+}
+
+// None removes the ceiling
+#[tauri::command]
+pub async fn set_geofence_ceiling(
+    ceiling_m: Option<f64>,
+    state: State<'_, GeofenceState>,
+) -> Result<(), String> {
+    store_geofence_ceiling(&state, ceiling_m)
+}
+
+pub fn store_geofence_ceiling(geofence: &GeofenceState, ceiling_m: Option<f64>) -> Result<(), String> {
+    if ceiling_m.map_or(false, |ceiling| !(ceiling > 0.0 && ceiling <= MAX_CEILING_M)) {
+        return Err(format!("Geofence ceiling must be between 0 and {MAX_CEILING_M} m above home"));
+    }
+    *geofence.ceiling_m.lock().map_err(|_| "Failed to lock geofence ceiling")? = ceiling_m;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_geofence_ceiling(
+    state: State<'_, GeofenceState>,
+) -> Result<Option<f64>, String> {
+    geofence_ceiling(&state)
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+// Every leg between consecutive flight path items that crosses the geofence
+// boundary or one of `no_fly_zones`, even when both endpoints are outside it
+#[tauri::command]
+pub async fn check_mission_route(
+    no_fly_zones: Vec<Vec<Coordinate>>,
+    state: State<'_, AppState>,
+    geofence: State<'_, GeofenceState>,
+) -> Result<Vec<RouteConflict>, String> {
+    if no_fly_zones.len() > MAX_NO_FLY_ZONES {
+        return Err(format!("At most {MAX_NO_FLY_ZONES} no-fly zones can be checked"));
+    }
+    for (index, zone) in no_fly_zones.iter().enumerate() {
+        validate_polygon(zone, &format!("No-fly zone {index}"))?;
+    }
+
+    let legs: Vec<(MissionItem, MissionItem)> = {
+        let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+        let path: Vec<&MissionItem> = items.iter().filter(|item| item.is_on_flight_path()).collect();
+        path.windows(2).map(|pair| (pair[0].clone(), pair[1].clone())).collect()
+    };
+    let boundary = geofence.boundary.lock().map_err(|_| "Failed to lock geofence")?.clone();
+
+    let polygons = boundary.iter()
+        .map(|polygon| (None, polygon))
+        .chain(no_fly_zones.iter().enumerate().map(|(index, zone)| (Some(index), zone)));
+    let mut conflicts = Vec::new();
+    for (polygon_index, polygon) in polygons {
+        for (from, to) in &legs {
+            let crossings = leg_crossings(&from.location(), &to.location(), polygon);
+            if !crossings.is_empty() {
+                conflicts.push(RouteConflict {
+                    from_item_id: from.id.clone(),
+                    to_item_id: to.id.clone(),
+                    polygon_index,
+                    crossings,
+                });
+            }
+        }
+    }
+
+    Ok(conflicts)
+}
+
+// ===== HELPER FUNCTIONS =====
+
+// Whether `point` lies inside the stored boundary; true when none is set
+pub fn is_within_geofence(geofence: &GeofenceState, point: &Coordinate) -> Result<bool, String> {
+    let boundary = geofence.boundary.lock().map_err(|_| "Failed to lock geofence")?;
+    Ok(boundary.as_ref().map_or(true, |polygon| point_in_polygon(point, polygon)))
+}
+
+pub fn geofence_ceiling(geofence: &GeofenceState) -> Result<Option<f64>, String> {
+    Ok(*geofence.ceiling_m.lock().map_err(|_| "Failed to lock geofence ceiling")?)
+}
+
+// Even-odd ray cast eastward from `point` in a gnomonic frame centered on it,
+// where the polygon's edges are straight. A vertex 90° or more away means the
+// polygon cannot contain the point.
+fn point_in_polygon(point: &Coordinate, polygon: &[Coordinate]) -> bool {
+    let projected: Option<Vec<(f64, f64)>> = polygon.iter()
+        .map(|vertex| gnomonic_project(point, vertex))
+        .collect();
+    let projected = match projected {
+        Some(projected) => projected,
+        None => return false,
+    };
+
+    let edges = projected.iter().zip(projected.iter().cycle().skip(1));
+    edges.fold(false, |inside, (&(x0, y0), &(x1, y1))| {
+        let crosses_ray = (y0 > 0.0) != (y1 > 0.0) && x0 - y0 * (x1 - x0) / (y1 - y0) > 0.0;
+        inside != crosses_ray
+    })
+}
+
+fn validate_polygon(polygon: &[Coordinate], label: &str) -> Result<(), String> {
+    if !(3..=MAX_POLYGON_VERTICES).contains(&polygon.len()) {
+        return Err(format!("{label} needs 3 to {MAX_POLYGON_VERTICES} vertices"));
+    }
+    if !polygon.iter().all(is_valid_coordinate) {
+        return Err(format!("{label} contains invalid coordinates"));
+    }
+    Ok(())
+}
+
+// Points where the leg crosses an edge of the closed `polygon`, ordered from
+// `from` to `to`. Works in a gnomonic frame centered on the leg, where the leg
+// and every edge are straight; edges reaching 90° from the leg are skipped.
+fn leg_crossings(from: &Coordinate, to: &Coordinate, polygon: &[Coordinate]) -> Vec<Coordinate> {
+    let center = great_circle_midpoint(from, to);
+    let (start, end) = match (gnomonic_project(&center, from), gnomonic_project(&center, to)) {
+        (Some(start), Some(end)) => (start, end),
+        _ => return Vec::new(),
+    };
+
+    let mut hits: Vec<f64> = (0..polygon.len())
+        .filter_map(|i| {
+            let a = gnomonic_project(&center, &polygon[i])?;
+            let b = gnomonic_project(&center, &polygon[(i + 1) % polygon.len()])?;
+            segment_intersection(start, end, a, b)
+        })
+        .collect();
+    hits.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    hits.into_iter()
+        .map(|t| {
+            let mut crossing = gnomonic_unproject(
+                &center,
+                start.0 + (end.0 - start.0) * t,
+                start.1 + (end.1 - start.1) * t,
+            );
+            crossing.alt = match (from.alt, to.alt) {
+                (Some(a), Some(b)) => Some(a + (b - a) * t),
+                _ => None,
+            };
+            crossing
+        })
+        .collect()
+}
+
+// Fraction along p0->p1 where it meets q0->q1; None when they miss or are parallel
+fn segment_intersection(p0: (f64, f64), p1: (f64, f64), q0: (f64, f64), q1: (f64, f64)) -> Option<f64> {
+    let r = (p1.0 - p0.0, p1.1 - p0.1);
+    let s = (q1.0 - q0.0, q1.1 - q0.1);
+    let denom = r.0 * s.1 - r.1 * s.0;
+    if denom.abs() < 1e-18 {
+        return None;
+    }
+
+    let qp = (q0.0 - p0.0, q0.1 - p0.1);
+    let t = (qp.0 * s.1 - qp.1 * s.0) / denom;
+    let u = (qp.0 * r.1 - qp.1 * r.0) / denom;
+    ((0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)).then(|| t)
+}
+
+// ===== MODULE REGISTRATION =====
+
+pub fn init() -> GeofenceState {
+    GeofenceState::new()
+}
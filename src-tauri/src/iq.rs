@@ -0,0 +1,218 @@
+// IQ imbalance model and calibration
+// NASA JPL Power of 10 compliant implementation
+
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+// ===== TYPE DEFINITIONS =====
+
+// Imbalance between the receiver's I and Q paths, which the correction stage
+// removes. `gain_imbalance` is the fractional excess gain of I over Q.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct IqCorrection {
+    pub dc_offset_i: f64,
+    pub dc_offset_q: f64,
+    pub gain_imbalance: f64,
+    pub phase_imbalance_deg: f64,
+}
+
+// ===== CONSTANTS =====
+
+pub const MAX_DC_OFFSET: f64 = 1.0;
+pub const MAX_GAIN_IMBALANCE: f64 = 0.5;
+pub const MAX_PHASE_IMBALANCE_DEG: f64 = 30.0;
+// Test tone used for calibration, exactly on a bin so it does not leak
+const CALIBRATION_SAMPLES: usize = 1024;
+const CALIBRATION_TONE_BIN: usize = 64;
+// Typical RTL-SDR front-end imbalance, applied to the self-test tone
+const MOCK_RECEIVER_IMBALANCE: IqCorrection = IqCorrection {
+    dc_offset_i: 0.012,
+    dc_offset_q: -0.008,
+    gain_imbalance: 0.04,
+    phase_imbalance_deg: 1.5,
+};
+// A correction that leaves the image above this has failed
+const MAX_CORRECTED_IMAGE_DB: f64 = -60.0;
+// How far the self-test estimate may stray from the imbalance it was given
+const SELF_TEST_TOLERANCE: f64 = 1e-6;
+
+// ===== IMAGE MODEL =====
+
+pub fn validate(correction: &IqCorrection) -> Result<(), String> {
+    if !(-MAX_DC_OFFSET..=MAX_DC_OFFSET).contains(&correction.dc_offset_i)
+        || !(-MAX_DC_OFFSET..=MAX_DC_OFFSET).contains(&correction.dc_offset_q)
+    {
+        return Err(format!("DC offsets must be within ±{MAX_DC_OFFSET}"));
+    }
+    if !(-MAX_GAIN_IMBALANCE..=MAX_GAIN_IMBALANCE).contains(&correction.gain_imbalance) {
+        return Err(format!("Gain imbalance must be within ±{MAX_GAIN_IMBALANCE}"));
+    }
+    if !(-MAX_PHASE_IMBALANCE_DEG..=MAX_PHASE_IMBALANCE_DEG).contains(&correction.phase_imbalance_deg) {
+        return Err(format!("Phase imbalance must be within ±{MAX_PHASE_IMBALANCE_DEG}°"));
+    }
+    Ok(())
+}
+
+// Level of the mirror image relative to the signal, the negated image
+// rejection ratio; None for balanced paths
+pub fn image_level_db(correction: &IqCorrection) -> Option<f64> {
+    let gain = 1.0 + correction.gain_imbalance;
+    let phase = correction.phase_imbalance_deg.to_radians();
+    let image = 1.0 - 2.0 * gain * phase.cos() + gain * gain;
+    let signal = 1.0 + 2.0 * gain * phase.cos() + gain * gain;
+    if image <= f64::EPSILON {
+        return None;
+    }
+    Some(10.0 * (image / signal).log10())
+}
+
+// Add to each bin the image of the bin mirrored about the center frequency;
+// magnitudes are in dB, so the two are summed as powers
+pub fn with_image(magnitudes: &[f64], image_db: f64) -> Vec<f64> {
+    let bins = magnitudes.len();
+    (0..bins)
+        .map(|i| {
+            let mirror = magnitudes[(bins - i) % bins] + image_db;
+            10.0 * (10f64.powf(magnitudes[i] / 10.0) + 10f64.powf(mirror / 10.0)).log10()
+        })
+        .collect()
+}
+
+// ===== CALIBRATION =====
+
+// Self-test of the calibration on a synthetic tone passed through
+// MOCK_RECEIVER_IMBALANCE. It checks the estimator and the Gram-Schmidt
+// correction; the mock SDR has no captured IQ, so this does not measure the
+// attached receiver.
+#[tauri::command]
+pub fn iq_calibration_self_test() -> Result<IqCorrection, String> {
+    let (i, q) = test_tone(&MOCK_RECEIVER_IMBALANCE);
+    let correction = calibrate_tone(i, q)?;
+    let error = (correction.dc_offset_i - MOCK_RECEIVER_IMBALANCE.dc_offset_i).abs()
+        .max((correction.dc_offset_q - MOCK_RECEIVER_IMBALANCE.dc_offset_q).abs())
+        .max((correction.gain_imbalance - MOCK_RECEIVER_IMBALANCE.gain_imbalance).abs())
+        .max((correction.phase_imbalance_deg - MOCK_RECEIVER_IMBALANCE.phase_imbalance_deg).abs());
+    if error > SELF_TEST_TOLERANCE {
+        return Err(format!("IQ calibration self-test estimate is off by {error:.2e}"));
+    }
+    Ok(correction)
+}
+
+// Estimate the imbalance from a capture of the test tone: the DC bin gives
+// the offsets, and the tone and its mirror give gain and phase. The estimate
+// is checked by correcting the tone and measuring what is left of the image.
+fn calibrate_tone(mut i: Vec<f64>, mut q: Vec<f64>) -> Result<IqCorrection, String> {
+    let correction = estimate_imbalance(&i, &q)?;
+
+    orthogonalize(&mut i, &mut q, &correction);
+    let residual_db = tone_image_db(&i, &q);
+    if residual_db > MAX_CORRECTED_IMAGE_DB {
+        return Err(format!("Calibration left the image at {residual_db:.1} dB; retry with a cleaner tone"));
+    }
+    Ok(correction)
+}
+
+// Unit tone at CALIBRATION_TONE_BIN through a receiver with `imbalance`
+fn test_tone(imbalance: &IqCorrection) -> (Vec<f64>, Vec<f64>) {
+    let phase = imbalance.phase_imbalance_deg.to_radians();
+    (0..CALIBRATION_SAMPLES)
+        .map(|n| {
+            let theta = 2.0 * PI * (CALIBRATION_TONE_BIN * n) as f64 / CALIBRATION_SAMPLES as f64;
+            (
+                (1.0 + imbalance.gain_imbalance) * theta.cos() + imbalance.dc_offset_i,
+                (theta + phase).sin() + imbalance.dc_offset_q,
+            )
+        })
+        .unzip()
+}
+
+// With I = (1 + e) cos t and Q = sin(t + p), the tone bin holds
+// K1 = ((1 + e) + exp(jp)) / 2 and its mirror K2 = ((1 + e) - exp(-jp)) / 2,
+// so K1 + conj(K2) = 1 + e and K1 - conj(K2) = exp(jp)
+fn estimate_imbalance(i: &[f64], q: &[f64]) -> Result<IqCorrection, String> {
+    let (dc_i, dc_q) = dft_bin(i, q, 0);
+    let (k1_re, k1_im) = dft_bin(i, q, CALIBRATION_TONE_BIN);
+    let (k2_re, k2_im) = dft_bin(i, q, CALIBRATION_SAMPLES - CALIBRATION_TONE_BIN);
+    if k1_re.hypot(k1_im) < 0.1 {
+        return Err("Calibration tone not found".to_string());
+    }
+
+    let gain = k1_re + k2_re;
+    let (rotation_re, rotation_im) = (k1_re - k2_re, k1_im + k2_im);
+    let correction = IqCorrection {
+        dc_offset_i: dc_i,
+        dc_offset_q: dc_q,
+        gain_imbalance: gain / rotation_re.hypot(rotation_im) - 1.0,
+        phase_imbalance_deg: rotation_im.atan2(rotation_re).to_degrees(),
+    };
+    validate(&correction)?;
+    Ok(correction)
+}
+
+// Gram-Schmidt: remove the DC offsets, scale I to unit gain, then remove the
+// part of Q that projects onto I and rescale what remains
+pub fn orthogonalize(i: &mut [f64], q: &mut [f64], correction: &IqCorrection) {
+    let phase = correction.phase_imbalance_deg.to_radians();
+    for (i, q) in i.iter_mut().zip(q.iter_mut()) {
+        *i = (*i - correction.dc_offset_i) / (1.0 + correction.gain_imbalance);
+        *q = (*q - correction.dc_offset_q - *i * phase.sin()) / phase.cos();
+    }
+}
+
+// Mirror-to-tone power ratio at the calibration bins
+fn tone_image_db(i: &[f64], q: &[f64]) -> f64 {
+    let (k1_re, k1_im) = dft_bin(i, q, CALIBRATION_TONE_BIN);
+    let (k2_re, k2_im) = dft_bin(i, q, CALIBRATION_SAMPLES - CALIBRATION_TONE_BIN);
+    let ratio = (k2_re * k2_re + k2_im * k2_im) / (k1_re * k1_re + k1_im * k1_im);
+    10.0 * ratio.max(1e-20).log10()
+}
+
+// Normalized DFT of I + jQ at one bin
+fn dft_bin(i: &[f64], q: &[f64], bin: usize) -> (f64, f64) {
+    let n = i.len() as f64;
+    let (re, im) = i.iter().zip(q.iter()).enumerate().fold((0.0, 0.0), |(re, im), (k, (x, y))| {
+        let angle = -2.0 * PI * (bin * k) as f64 / n;
+        let (sin, cos) = angle.sin_cos();
+        (re + x * cos - y * sin, im + x * sin + y * cos)
+    });
+    (re / n, im / n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gain_imbalance_adds_a_ghost_at_the_mirror_bin() {
+        let bins = 256;
+        let mut line = vec![-100.0; bins];
+        line[40] = -20.0;
+
+        assert_eq!(image_level_db(&IqCorrection::default()), None);
+        let imbalanced = IqCorrection { gain_imbalance: 0.1, ..IqCorrection::default() };
+        let image_db = image_level_db(&imbalanced).unwrap();
+        // (1.1 - 1) / (1.1 + 1) in amplitude
+        assert!((image_db - 20.0 * (0.1_f64 / 2.1).log10()).abs() < 1e-9, "got {image_db}");
+
+        let ghosted = with_image(&line, image_db);
+        let ghost = ghosted[bins - 40];
+        assert!((ghost - (-20.0 + image_db)).abs() < 0.01, "ghost at {ghost} dB");
+        assert!(ghost > -100.0 + 40.0);
+        // The tone itself and the rest of the floor barely move
+        assert!((ghosted[40] + 20.0).abs() < 0.01);
+        assert!((ghosted[100] + 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn self_test_recovers_the_injected_imbalance() {
+        let correction = iq_calibration_self_test().unwrap();
+        assert!(correction.dc_offset_i != 0.0 && correction.dc_offset_q != 0.0);
+        assert!(correction.gain_imbalance != 0.0 && correction.phase_imbalance_deg != 0.0);
+        assert!((correction.gain_imbalance - 0.04).abs() < 1e-9);
+        assert!((correction.phase_imbalance_deg - 1.5).abs() < 1e-9);
+
+        // A capture with no tone cannot be calibrated
+        let silence = vec![0.0; CALIBRATION_SAMPLES];
+        assert!(calibrate_tone(silence.clone(), silence).is_err());
+    }
+}
@@ -2,18 +2,271 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use serde::{Deserialize, Serialize};
-use std::process::Command;
-use std::sync::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::process::Stdio;
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::Command;
 use tauri::Manager;
 use tauri::State;
 
+mod geo;
+mod geofence;
+mod iq;
 mod map_features;
 mod mavlink;
+mod mavlink_relay;
+mod mavlink_transport;
+mod mission_analysis;
+mod mission_edit;
+mod mission_history;
+mod mission_io;
+mod mission_sim;
+mod mission_survey;
+mod mission_templates;
+mod param_metadata;
+mod performance;
+mod sdr;
+mod terrain;
+mod tile_cache;
+mod window;
+
+use map_features::{Coordinate, ViewportBounds};
+use iq::IqCorrection;
+use window::WindowFunction;
 
 // Application state for mission data
 #[derive(Default)]
 struct AppState {
+    // Items of the active mission; all item commands operate on this list
     mission_items: Mutex<Vec<MissionItem>>,
+    // Mission library keyed by mission ID (the active entry's items live in `mission_items`)
+    missions: Mutex<HashMap<String, Mission>>,
+    active_mission_id: Mutex<String>,
+    // Last mission downloaded from the vehicle, used for local/vehicle diffs
+    vehicle_mission: Mutex<Option<Vec<MissionItem>>>,
+    // Reject reorders/deletes that break takeoff-first / terminal-last rules
+    strict_structure: Mutex<bool>,
+    // Items captured by `copy_mission_items` for pasting
+    clipboard: Mutex<Option<Vec<MissionItem>>>,
+    // Undo/redo history of the active mission; cleared when switching missions
+    undo_stack: Mutex<mission_history::UndoStack>,
+    // Set while the vehicle flies the uploaded mission; edits are rejected until cleared
+    mission_lock: Mutex<MissionLockStatus>,
+    // Bumped on every change to the active mission's items, including undo/redo and switches
+    mission_revision: Mutex<u64>,
+    // Content hash of the mission last uploaded to the vehicle
+    uploaded_mission_hash: Mutex<Option<String>>,
+    // Item selected in the map or list view, shared between views
+    selected_item_id: Mutex<Option<String>>,
+    // Latest density-altitude performance estimates, reported as validation warnings
+    performance_advisory: Mutex<Option<performance::PerformanceEstimates>>,
+    // Read by the SDR emission loop on every frame
+    sdr_config: RwLock<SdrConfig>,
+    // SDR_FFT_BINS coefficients of the configured window, recomputed when it changes
+    sdr_window: RwLock<Vec<f64>>,
+    // Recent FFT frames for the waterfall display
+    waterfall: Mutex<WaterfallBuffer>,
+    // Gain chosen by the AGC loop while it is enabled
+    sdr_agc_gain_db: Mutex<f64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+struct MissionLockStatus {
+    locked: bool,
+    reason: Option<String>,
+}
+
+// Active mission items together with the edit lock, so the UI can disable editing
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct MissionData {
+    items: Vec<MissionItem>,
+    lock: MissionLockStatus,
+    revision: u64,
+    content_hash: String,
+}
+
+// Criteria for query_mission_items; unset fields match every item and the
+// rest are ANDed
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+struct MissionFilter {
+    item_types: Option<Vec<String>>,
+    min_alt: Option<f64>,
+    max_alt: Option<f64>,
+    // Only positioned items can fall inside the bounds
+    bounds: Option<ViewportBounds>,
+    // Case-insensitive
+    name_contains: Option<String>,
+    // Whether the item has a non-empty `action`
+    has_action: Option<bool>,
+    offset: usize,
+    limit: Option<usize>,
+}
+
+// One page of matching items and how many items matched in total
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct MissionQueryResult {
+    items: Vec<MissionItem>,
+    total_count: usize,
+}
+
+// Change counter and content hash of the active mission, for sync detection
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct MissionRevision {
+    revision: u64,
+    content_hash: String,
+}
+
+// SDR receiver tuning, within the RTL-SDR ranges
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+struct SdrConfig {
+    center_frequency_hz: f64,
+    sample_rate_hz: f64,
+    gain_db: f64,
+    ppm_correction: i32,
+    // Applied to each FFT frame against spectral leakage
+    #[serde(default)]
+    window: WindowFunction,
+    // The emission loop steers the gain toward `agc_target_dbm`; `gain_db`
+    // must then be 0
+    #[serde(default)]
+    agc_enabled: bool,
+    #[serde(default = "default_agc_target_dbm")]
+    agc_target_dbm: f64,
+    #[serde(default)]
+    iq_correction: IqCorrection,
+}
+
+fn default_agc_target_dbm() -> f64 {
+    DEFAULT_SDR_AGC_TARGET_DBM
+}
+
+impl Default for SdrConfig {
+    fn default() -> Self {
+        Self {
+            center_frequency_hz: 100_000_000.0, // 100 MHz
+            sample_rate_hz: 2_000_000.0, // 2 MS/s
+            gain_db: 0.0,
+            ppm_correction: 0,
+            window: WindowFunction::default(),
+            agc_enabled: false,
+            agc_target_dbm: DEFAULT_SDR_AGC_TARGET_DBM,
+            iq_correction: IqCorrection::default(),
+        }
+    }
+}
+
+// Ring buffer of FFT magnitude lines, oldest first, with each line's emit time
+struct WaterfallBuffer {
+    lines: VecDeque<Vec<f64>>,
+    timestamps_ms: VecDeque<u64>,
+    max_lines: usize,
+}
+
+impl Default for WaterfallBuffer {
+    fn default() -> Self {
+        Self {
+            lines: VecDeque::with_capacity(DEFAULT_WATERFALL_LINES),
+            timestamps_ms: VecDeque::with_capacity(DEFAULT_WATERFALL_LINES),
+            max_lines: DEFAULT_WATERFALL_LINES,
+        }
+    }
+}
+
+impl WaterfallBuffer {
+    fn push(&mut self, line: Vec<f64>, timestamp_ms: u64) {
+        if self.lines.len() >= self.max_lines {
+            self.lines.pop_front();
+            self.timestamps_ms.pop_front();
+        }
+        self.lines.push_back(line);
+        self.timestamps_ms.push_back(timestamp_ms);
+    }
+
+    // Shrinking drops the oldest lines
+    fn resize(&mut self, max_lines: usize) {
+        let excess = self.lines.len().saturating_sub(max_lines);
+        self.lines.drain(..excess);
+        self.timestamps_ms.drain(..excess);
+        self.max_lines = max_lines;
+    }
+
+    fn snapshot(&self, count: usize) -> WaterfallSnapshot {
+        let skip = self.lines.len().saturating_sub(count);
+        WaterfallSnapshot {
+            lines: self.lines.iter().skip(skip).cloned().collect(),
+            timestamp_ms_start: self.timestamps_ms.get(skip).copied(),
+            timestamp_ms_end: self.timestamps_ms.back().copied(),
+        }
+    }
+}
+
+// The most recent waterfall lines, oldest first; the timestamps are unset while empty
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct WaterfallSnapshot {
+    lines: Vec<Vec<f64>>,
+    timestamp_ms_start: Option<u64>,
+    timestamp_ms_end: Option<u64>,
+}
+
+// Exit code reported in `cli-terminated` when a command is killed on timeout
+const CLI_TIMEOUT_EXIT_CODE: i32 = -2;
+// Grace period for draining output pipes after a timeout kill
+const CLI_OUTPUT_DRAIN_MS: u64 = 500;
+
+const DEFAULT_MISSION_ID: &str = "default";
+// Item types understood by the mission planner
+const MISSION_ITEM_TYPES: [&str; 10] = [
+    "takeoff", "waypoint", "loiter", "land", "rtl", "camera_trigger", "roi", "roi_cancel", "jump",
+    "delay",
+];
+const MAX_MISSION_NAME_LENGTH: usize = 100;
+// NASA JPL Rule 2: upper bound on items in one mission
+const MAX_MISSION_ITEMS: usize = 1000;
+// Error returned by every mutating mission command while the mission is locked
+const MISSION_LOCKED_ERROR: &str = "mission locked: vehicle executing";
+const MIN_LOITER_RADIUS_M: f64 = 5.0;
+const MAX_ITEM_NOTES_LENGTH: usize = 2000;
+const MAX_ITEM_TAGS: usize = 20;
+const MAX_ITEM_TAG_LENGTH: usize = 50;
+const MIN_SDR_SAMPLE_RATE_HZ: f64 = 0.5e6;
+const MAX_SDR_SAMPLE_RATE_HZ: f64 = 3.2e6;
+const MAX_SDR_GAIN_DB: f64 = 49.6;
+const MAX_SDR_PPM_CORRECTION: i32 = 1000;
+const DEFAULT_SDR_AGC_TARGET_DBM: f64 = -40.0;
+const MIN_SDR_AGC_TARGET_DBM: f64 = -120.0;
+const MAX_SDR_AGC_TARGET_DBM: f64 = 0.0;
+// AGC gain change per emitted frame, and the error it tolerates
+const SDR_AGC_STEP_DB: f64 = 1.0;
+const SDR_AGC_DEADBAND_DB: f64 = 0.5;
+const SDR_FFT_INTERVAL_MS: u64 = 100;
+const SDR_FFT_BINS: usize = 256;
+// Windowed magnitudes are floored here instead of reaching -inf at zero coefficients
+const SDR_MAGNITUDE_FLOOR_DB: f64 = -140.0;
+const DEFAULT_WATERFALL_LINES: usize = 512;
+const MIN_WATERFALL_LINES: u32 = 100;
+const MAX_WATERFALL_LINES: u32 = 4096;
+
+// Allowlist of executables that may be spawned from the CLI panel
+#[derive(Default)]
+struct AllowedCommands {
+    patterns: Vec<String>,
+}
+
+// On-disk format of allowed_commands.toml
+#[derive(Deserialize)]
+struct AllowedCommandsConfig {
+    commands: Vec<String>,
+}
+
+impl AllowedCommands {
+    fn is_permitted(&self, executable: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| wildcard_match(pattern, executable))
+    }
 }
 
 // Mission item structure
@@ -25,6 +278,184 @@ struct MissionItem {
     name: String,
     params: WaypointParams,
     position: Option<Position>,
+    // Loiter items only; None on a loiter item means an unlimited loiter at the default radius
+    #[serde(default)]
+    loiter_params: Option<LoiterParams>,
+    // Camera trigger items only
+    #[serde(default)]
+    camera_params: Option<CameraParams>,
+    // Operator annotations; kept in saved missions but not sent to the vehicle
+    #[serde(default)]
+    notes: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+impl MissionItem {
+    // Planned location of the item. `params` is authoritative since
+    // `update_waypoint_params` does not touch `position`.
+    fn location(&self) -> Coordinate {
+        Coordinate {
+            lat: self.params.lat,
+            lng: self.params.lng,
+            alt: Some(self.params.alt),
+        }
+    }
+
+    fn is_takeoff(&self) -> bool {
+        self.item_type == "takeoff"
+    }
+
+    // Land and RTL end the flight; nothing after them is executed
+    fn is_terminal(&self) -> bool {
+        self.item_type == "land" || self.item_type == "rtl"
+    }
+
+    // ROI items point the camera at their position; the vehicle does not fly there
+    fn is_roi(&self) -> bool {
+        self.item_type == "roi"
+    }
+
+    // Positioned items the vehicle actually flies through
+    fn is_on_flight_path(&self) -> bool {
+        self.position.is_some() && !self.is_roi()
+    }
+
+    fn is_camera_trigger(&self) -> bool {
+        self.item_type == "camera_trigger"
+    }
+
+    // Type-specific parameters must match the item type
+    fn validate_params(&self) -> Result<(), String> {
+        self.validate_loiter()?;
+        self.validate_camera_trigger()?;
+        self.validate_jump()?;
+        self.validate_delay()?;
+        if self.params.spline && self.item_type != "waypoint" {
+            return Err(format!("Spline paths are not allowed on {} items", self.item_type));
+        }
+        match (self.item_type.as_str(), &self.position) {
+            ("roi", None) => Err("ROI items need the position to point at".to_string()),
+            ("roi_cancel", Some(_)) => Err("ROI cancel items have no position".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    fn is_loiter(&self) -> bool {
+        self.item_type.starts_with("loiter")
+    }
+
+    // Loiter parameters are only meaningful on loiter items and must match the loiter type.
+    // A time loiter without a duration is allowed but flagged by `validate_mission`.
+    fn validate_loiter(&self) -> Result<(), String> {
+        let loiter = match (&self.loiter_params, self.is_loiter()) {
+            (None, _) => return Ok(()),
+            (Some(_), false) => {
+                return Err(format!("Loiter parameters are not allowed on {} items", self.item_type));
+            }
+            (Some(loiter), true) => loiter,
+        };
+
+        if !(loiter.radius_m.is_finite() && loiter.radius_m >= MIN_LOITER_RADIUS_M) {
+            return Err(format!("Loiter radius must be at least {} m", MIN_LOITER_RADIUS_M));
+        }
+        match (loiter.loiter_type, loiter.duration_s, loiter.turns) {
+            (LoiterType::Unlimited, None, None) => Ok(()),
+            (LoiterType::Unlimited, _, _) => Err("Unlimited loiters take no duration or turns".to_string()),
+            (LoiterType::Time, _, Some(_)) => Err("Time loiters take no turns".to_string()),
+            (LoiterType::Time, Some(time), None) if !(time.is_finite() && time > 0.0) => {
+                Err("Loiter duration must be positive".to_string())
+            }
+            (LoiterType::Time, _, None) => Ok(()),
+            (LoiterType::Turns, Some(_), _) => Err("Turns loiters take no duration".to_string()),
+            (LoiterType::Turns, None, Some(turns)) if turns.is_finite() && turns > 0.0 => Ok(()),
+            (LoiterType::Turns, None, _) => Err("Turns loiters need a positive number of turns".to_string()),
+        }
+    }
+
+    fn is_jump(&self) -> bool {
+        self.item_type == "jump"
+    }
+
+    fn validate_jump(&self) -> Result<(), String> {
+        let params = &self.params;
+        let has_jump_fields = params.target_item_id.is_some() || params.repeat_count.is_some();
+        if !self.is_jump() && has_jump_fields {
+            return Err(format!("Jump parameters are not allowed on {} items", self.item_type));
+        }
+        if !self.is_jump() {
+            return Ok(());
+        }
+        if self.position.is_some() {
+            return Err("Jump items have no position".to_string());
+        }
+        if params.target_item_id.is_none() || params.repeat_count.is_none() {
+            return Err("Jump items need a target item and a repeat count".to_string());
+        }
+        Ok(())
+    }
+
+    fn is_delay(&self) -> bool {
+        self.item_type == "delay"
+    }
+
+    fn validate_delay(&self) -> Result<(), String> {
+        match (self.is_delay(), self.params.delay_s) {
+            (false, None) => Ok(()),
+            (false, Some(_)) => Err(format!("Delays are not allowed on {} items", self.item_type)),
+            (true, _) if self.position.is_some() => Err("Delay items have no position".to_string()),
+            (true, Some(delay)) if delay.is_finite() && delay > 0.0 => Ok(()),
+            (true, _) => Err("Delay items need a positive delay".to_string()),
+        }
+    }
+
+    // Seconds the vehicle holds at a delay item; 0 for every other item
+    fn delay_duration_s(&self) -> f64 {
+        if self.is_delay() { self.params.delay_s.unwrap_or(0.0) } else { 0.0 }
+    }
+
+    // A jump may only go back to an item that comes before it
+    fn validate_jump_target(&self, preceding: &[MissionItem]) -> Result<(), String> {
+        match &self.params.target_item_id {
+            Some(target) if self.is_jump() && !preceding.iter().any(|i| &i.id == target) => {
+                Err(format!("Jump target {} must be an existing item before the jump", target))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn validate_camera_trigger(&self) -> Result<(), String> {
+        let camera = match (&self.camera_params, self.is_camera_trigger()) {
+            (Some(_), false) => {
+                return Err(format!("Camera parameters are not allowed on {} items", self.item_type));
+            }
+            (_, false) => return Ok(()),
+            (None, true) => return Err("Camera trigger items need camera parameters".to_string()),
+            (Some(camera), true) => camera,
+        };
+        if self.position.is_some() {
+            return Err("Camera trigger items have no position".to_string());
+        }
+
+        if camera.shutter_ms == Some(0) || camera.iso == Some(0) {
+            return Err("Shutter time and ISO must be positive".to_string());
+        }
+        if let Some(aperture) = camera.aperture {
+            if !(aperture.is_finite() && aperture > 0.0) {
+                return Err("Aperture must be a positive f-number".to_string());
+            }
+        }
+        match (camera.trigger_type, camera.distance_m, camera.interval_s) {
+            (CameraTriggerType::Distance, Some(distance), None) if distance.is_finite() && distance >= 0.0 => Ok(()),
+            (CameraTriggerType::Distance, _, _) => {
+                Err("Distance triggers need a trigger distance (0 stops triggering)".to_string())
+            }
+            (CameraTriggerType::Time, None, Some(interval)) if interval.is_finite() && interval > 0.0 => Ok(()),
+            (CameraTriggerType::Time, _, _) => Err("Time triggers need a positive interval".to_string()),
+            (CameraTriggerType::Manual, None, None) => Ok(()),
+            (CameraTriggerType::Manual, _, _) => Err("Manual triggers take no distance or interval".to_string()),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -34,6 +465,61 @@ struct WaypointParams {
     alt: f64,
     speed: Option<f64>,
     action: Option<String>,
+    // Jump items only: DO_JUMP back to `target_item_id`, `repeat_count` times (0 = forever)
+    target_item_id: Option<String>,
+    repeat_count: Option<u32>,
+    // Waypoints only: curve the leg into this item (MAV_CMD_NAV_SPLINE_WAYPOINT)
+    #[serde(default)]
+    spline: bool,
+    // Delay items only: seconds the vehicle holds before continuing
+    delay_s: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum LoiterType {
+    Unlimited,
+    Time,
+    Turns,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct LoiterParams {
+    loiter_type: LoiterType,
+    radius_m: f64,
+    // Time loiters only
+    duration_s: Option<f64>,
+    // Turns loiters only
+    turns: Option<f32>,
+    clockwise: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum CameraTriggerType {
+    Distance,
+    Time,
+    Manual,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct CameraParams {
+    trigger_type: CameraTriggerType,
+    // Distance triggers only: photo spacing along track, 0 stops triggering
+    distance_m: Option<f64>,
+    // Time triggers only: seconds between photos
+    #[serde(default)]
+    interval_s: Option<f64>,
+    // Optional exposure settings sent with DO_DIGICAM_CONFIGURE
+    shutter_ms: Option<u16>,
+    aperture: Option<f32>,
+    iso: Option<u32>,
+}
+
+impl CameraParams {
+    fn has_exposure_settings(&self) -> bool {
+        self.shutter_ms.is_some() || self.aperture.is_some() || self.iso.is_some()
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -43,6 +529,22 @@ struct Position {
     alt: f64,
 }
 
+// Named mission stored in the mission library
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct Mission {
+    id: String,
+    name: String,
+    items: Vec<MissionItem>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct MissionSummary {
+    id: String,
+    name: String,
+    item_count: usize,
+    active: bool,
+}
+
 // Basic health check command
 #[tauri::command]
 fn health_check() -> String {
@@ -86,29 +588,146 @@ fn get_loaded_plugins() -> Vec<serde_json::Value> {
     ]
 }
 
+#[tauri::command]
+fn get_sdr_config(state: State<AppState>) -> Result<SdrConfig, String> {
+    let config = state.sdr_config.read().map_err(|_| "Failed to lock SDR config")?;
+    Ok(*config)
+}
+
+// Takes effect from the next emitted FFT frame
+#[tauri::command]
+fn set_sdr_config(config: SdrConfig, state: State<AppState>) -> Result<(), String> {
+    update_sdr_config(&state, |_| config)
+}
+
+// Derive, validate and install a new config under one lock, so concurrent
+// updates cannot interleave
+fn update_sdr_config(state: &AppState, update: impl FnOnce(SdrConfig) -> SdrConfig) -> Result<(), String> {
+    let mut current = state.sdr_config.write().map_err(|_| "Failed to lock SDR config")?;
+    let config = update(*current);
+    validate_sdr_config(&config)?;
+    if current.window != config.window {
+        *state.sdr_window.write().map_err(|_| "Failed to lock SDR window")? =
+            window::coefficients(config.window, SDR_FFT_BINS);
+    }
+    *current = config;
+    Ok(())
+}
+
+fn validate_sdr_config(config: &SdrConfig) -> Result<(), String> {
+    if !(config.center_frequency_hz.is_finite() && config.center_frequency_hz > 0.0) {
+        return Err("Center frequency must be positive".to_string());
+    }
+    if !(MIN_SDR_SAMPLE_RATE_HZ..=MAX_SDR_SAMPLE_RATE_HZ).contains(&config.sample_rate_hz) {
+        return Err(format!(
+            "Sample rate must be between {MIN_SDR_SAMPLE_RATE_HZ} and {MAX_SDR_SAMPLE_RATE_HZ} Hz"
+        ));
+    }
+    if !(0.0..=MAX_SDR_GAIN_DB).contains(&config.gain_db) {
+        return Err(format!("Gain must be between 0 and {MAX_SDR_GAIN_DB} dB"));
+    }
+    if config.ppm_correction.abs() > MAX_SDR_PPM_CORRECTION {
+        return Err(format!("PPM correction must be within ±{MAX_SDR_PPM_CORRECTION}"));
+    }
+    if config.agc_enabled && config.gain_db != 0.0 {
+        return Err("Gain must be 0 while AGC is enabled; AGC sets the gain itself".to_string());
+    }
+    validate_agc_target(config.agc_target_dbm)?;
+    iq::validate(&config.iq_correction)
+}
+
+// Hand the gain to the AGC loop, which starts from the current manual gain
+#[tauri::command]
+fn enable_agc(target_dbm: f64, state: State<AppState>) -> Result<(), String> {
+    start_agc(&state, target_dbm)
+}
+
+fn start_agc(state: &AppState, target_dbm: f64) -> Result<(), String> {
+    validate_agc_target(target_dbm)?;
+    let mut config = state.sdr_config.write().map_err(|_| "Failed to lock SDR config")?;
+    if !config.agc_enabled {
+        *state.sdr_agc_gain_db.lock().map_err(|_| "Failed to lock AGC gain")? = config.gain_db;
+    }
+    config.agc_enabled = true;
+    config.agc_target_dbm = target_dbm;
+    config.gain_db = 0.0;
+    Ok(())
+}
+
+// Keep the gain the AGC loop settled on as the manual gain
+#[tauri::command]
+fn disable_agc(state: State<AppState>) -> Result<(), String> {
+    stop_agc(&state)
+}
+
+fn stop_agc(state: &AppState) -> Result<(), String> {
+    let mut config = state.sdr_config.write().map_err(|_| "Failed to lock SDR config")?;
+    if config.agc_enabled {
+        config.gain_db = *state.sdr_agc_gain_db.lock().map_err(|_| "Failed to lock AGC gain")?;
+        config.agc_enabled = false;
+    }
+    Ok(())
+}
+
+fn validate_agc_target(target_dbm: f64) -> Result<(), String> {
+    if !(MIN_SDR_AGC_TARGET_DBM..=MAX_SDR_AGC_TARGET_DBM).contains(&target_dbm) {
+        return Err(format!(
+            "AGC target must be between {MIN_SDR_AGC_TARGET_DBM} and {MAX_SDR_AGC_TARGET_DBM} dBm"
+        ));
+    }
+    Ok(())
+}
+
+// One AGC step: move the gain toward the target level by SDR_AGC_STEP_DB
+fn agc_adjusted_gain(gain_db: f64, average_dbm: f64, target_dbm: f64) -> f64 {
+    let error_db = target_dbm - average_dbm;
+    let step = if error_db > SDR_AGC_DEADBAND_DB {
+        SDR_AGC_STEP_DB
+    } else if error_db < -SDR_AGC_DEADBAND_DB {
+        -SDR_AGC_STEP_DB
+    } else {
+        0.0
+    };
+    (gain_db + step).clamp(0.0, MAX_SDR_GAIN_DB)
+}
+
+// Up to `lines` of the most recent waterfall lines
+#[tauri::command]
+fn get_waterfall_snapshot(lines: u32, state: State<AppState>) -> Result<WaterfallSnapshot, String> {
+    if lines == 0 {
+        return Err("Line count must be positive".to_string());
+    }
+    let waterfall = state.waterfall.lock().map_err(|_| "Failed to lock waterfall")?;
+    Ok(waterfall.snapshot(lines as usize))
+}
+
+#[tauri::command]
+fn set_waterfall_depth(lines: u32, state: State<AppState>) -> Result<(), String> {
+    if !(MIN_WATERFALL_LINES..=MAX_WATERFALL_LINES).contains(&lines) {
+        return Err(format!(
+            "Waterfall depth must be between {MIN_WATERFALL_LINES} and {MAX_WATERFALL_LINES} lines"
+        ));
+    }
+    state.waterfall.lock().map_err(|_| "Failed to lock waterfall")?.resize(lines as usize);
+    Ok(())
+}
+
+// Get the executables permitted by the CLI allowlist
+#[tauri::command]
+fn get_allowed_commands(allowed: State<AllowedCommands>) -> Result<Vec<String>, String> {
+    Ok(allowed.patterns.clone())
+}
+
 // CLI command execution
 #[tauri::command]
 async fn run_cli_command(
     app_handle: tauri::AppHandle,
+    allowed: State<'_, AllowedCommands>,
     command: String,
+    timeout_secs: Option<u64>,
 ) -> Result<(), String> {
-    // Validate command
-    if command.trim().is_empty() {
-        return Err("Empty command".to_string());
-    }
-
-    // Execute command based on platform
-    let output = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .args(["/C", &command])
-            .output()
-            .map_err(|e| format!("Failed to execute command: {e}"))?
-    } else {
-        Command::new("sh")
-            .args(["-c", &command])
-            .output()
-            .map_err(|e| format!("Failed to execute command: {e}"))?
-    };
+    let mut child = prepare_cli_command(&command, &allowed)?;
+    let output = execute_cli_command(&mut child, timeout_secs).await?;
 
     // Emit stdout
     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -133,30 +752,287 @@ async fn run_cli_command(
     }
 
     // Emit termination event
-    let exit_code = output.status.code().unwrap_or(-1);
     app_handle
         .emit_all("cli-terminated", serde_json::json!({
-            "code": exit_code
+            "code": output.exit_code
         }))
         .map_err(|e| format!("Failed to emit termination: {e}"))?;
 
     Ok(())
 }
 
+// Validate a command line against the allowlist and build its process. The
+// executable is spawned directly rather than through a shell, so nothing in
+// the arguments is interpreted.
+fn prepare_cli_command(command: &str, allowed: &AllowedCommands) -> Result<Command, String> {
+    if command.trim().is_empty() {
+        return Err("Empty command".to_string());
+    }
+
+    // Without a shell these would only reach the executable as literal text,
+    // which is never what the user meant
+    if contains_shell_operators(command) {
+        return Err("Command chaining, redirection and substitution are not permitted".to_string());
+    }
+
+    // Only spawn executables present in the allowlist
+    let tokens = split_command_line(command)?;
+    let (program, args) = tokens.split_first().ok_or("Empty command")?;
+    let executable = extract_executable_name(program)?;
+    if !allowed.is_permitted(&executable) {
+        return Err(format!("Command not permitted: {executable}"));
+    }
+
+    let mut child = Command::new(program);
+    child.args(args);
+    Ok(child)
+}
+
+// Output of a finished or timed out CLI command
+struct CliOutput {
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+    exit_code: i32,
+}
+
+async fn execute_cli_command(command: &mut Command, timeout_secs: Option<u64>) -> Result<CliOutput, String> {
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to execute command: {e}"))?;
+
+    let stdout_task = collect_output(child.stdout.take());
+    let stderr_task = collect_output(child.stderr.take());
+
+    // Race the child against the optional timeout; kill() sends SIGKILL on
+    // Unix and TerminateProcess on Windows
+    let exit_code = match timeout_secs {
+        Some(secs) => match tokio::time::timeout(Duration::from_secs(secs), child.wait()).await {
+            Ok(status) => status
+                .map_err(|e| format!("Failed to wait for command: {e}"))?
+                .code()
+                .unwrap_or(-1),
+            Err(_) => {
+                child.kill().await
+                    .map_err(|e| format!("Failed to kill timed out command: {e}"))?;
+                CLI_TIMEOUT_EXIT_CODE
+            }
+        },
+        None => child.wait().await
+            .map_err(|e| format!("Failed to wait for command: {e}"))?
+            .code()
+            .unwrap_or(-1),
+    };
+
+    // Grandchildren of a killed command may keep the pipes open, so only
+    // drain them for a bounded time after a timeout
+    let drain_limit = if exit_code == CLI_TIMEOUT_EXIT_CODE {
+        Duration::from_millis(CLI_OUTPUT_DRAIN_MS)
+    } else {
+        Duration::MAX
+    };
+    Ok(CliOutput {
+        stdout: drain_output(stdout_task, drain_limit).await,
+        stderr: drain_output(stderr_task, drain_limit).await,
+        exit_code,
+    })
+}
+
+// Read a child output pipe to completion on a background task
+fn collect_output<R>(stream: Option<R>) -> tokio::task::JoinHandle<Vec<u8>>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut buffer = Vec::new();
+        if let Some(mut stream) = stream {
+            // Partial output is still worth showing if the pipe errors
+            let _ = stream.read_to_end(&mut buffer).await;
+        }
+        buffer
+    })
+}
+
+// Wait for collected output, giving up after `limit`
+async fn drain_output(task: tokio::task::JoinHandle<Vec<u8>>, limit: Duration) -> Vec<u8> {
+    match tokio::time::timeout(limit, task).await {
+        Ok(Ok(buffer)) => buffer,
+        _ => Vec::new(),
+    }
+}
+
+// Split a command line on whitespace; single or double quotes group an
+// argument containing spaces
+fn split_command_line(command: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote: Option<char> = None;
+    for c in command.chars() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), c) => current.get_or_insert_with(String::new).push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => tokens.extend(current.take()),
+            (None, c) => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err("Unterminated quote in command".to_string());
+    }
+    tokens.extend(current);
+    Ok(tokens)
+}
+
+// The allowlist name of a program. Paths are refused outright: the allowlist
+// names executables found on PATH, and a directory prefix would let any file
+// with an allowed name run.
+fn extract_executable_name(program: &str) -> Result<String, String> {
+    if program.contains(['/', '\\']) {
+        return Err(format!("Command must name an executable without a path: {program}"));
+    }
+
+    // Windows resolves "tool.exe" and "tool" to the same executable
+    let name = if program.to_ascii_lowercase().ends_with(".exe") {
+        &program[..program.len() - 4]
+    } else {
+        program
+    };
+
+    if name.is_empty() {
+        Err("Empty command".to_string())
+    } else {
+        Ok(name.to_string())
+    }
+}
+
+// Detect shell operators that would run anything besides the first executable
+fn contains_shell_operators(command: &str) -> bool {
+    const OPERATORS: [&str; 8] = [";", "&", "|", "`", "$(", ">", "<", "\n"];
+    OPERATORS.iter().any(|op| command.contains(op))
+}
+
+// Match a name against a glob pattern supporting `*` and `?`
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = backtrack {
+            // Let the last `*` absorb one more character and retry
+            p = star_p + 1;
+            n = star_n + 1;
+            backtrack = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+// Load the CLI allowlist from the bundled allowed_commands.toml
+fn load_allowed_commands(app: &tauri::App) -> AllowedCommands {
+    let path = match app.path_resolver().resolve_resource("allowed_commands.toml") {
+        Some(path) => path,
+        None => {
+            eprintln!("allowed_commands.toml not found; CLI execution is disabled");
+            return AllowedCommands::default();
+        }
+    };
+
+    let config = std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|contents| {
+            toml::from_str::<AllowedCommandsConfig>(&contents).map_err(|e| e.to_string())
+        });
+
+    match config {
+        Ok(config) => AllowedCommands {
+            patterns: config.commands,
+        },
+        Err(e) => {
+            eprintln!("Failed to load {}: {e}; CLI execution is disabled", path.display());
+            AllowedCommands::default()
+        }
+    }
+}
+
 // Get mission data
 #[tauri::command]
-fn get_mission_data(state: State<AppState>) -> Result<Vec<MissionItem>, String> {
+fn get_mission_data(state: State<AppState>) -> Result<MissionData, String> {
     let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
-    Ok(items.clone())
+    let lock = state.mission_lock.lock().map_err(|_| "Failed to lock mission lock")?;
+    let MissionRevision { revision, content_hash } = mission_revision(&state, &items)?;
+    Ok(MissionData {
+        items: items.clone(),
+        lock: lock.clone(),
+        revision,
+        content_hash,
+    })
+}
+
+// Get the active mission's revision counter and content hash
+#[tauri::command]
+fn get_mission_revision(state: State<AppState>) -> Result<MissionRevision, String> {
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    mission_revision(&state, &items)
+}
+
+// Whether the active mission differs from the one last uploaded to the vehicle
+#[tauri::command]
+fn is_vehicle_mission_stale(state: State<AppState>) -> Result<bool, String> {
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let uploaded = state.uploaded_mission_hash.lock()
+        .map_err(|_| "Failed to lock uploaded mission hash")?;
+    let uploaded = uploaded.as_deref().ok_or("No mission has been uploaded to the vehicle")?;
+    Ok(uploaded != mission_content_hash(&items))
+}
+
+// Get a single mission item by ID
+#[tauri::command]
+fn get_mission_item(
+    state: State<AppState>,
+    item_id: String,
+) -> Result<MissionItem, String> {
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    items.iter()
+        .find(|i| i.id == item_id)
+        .cloned()
+        .ok_or_else(|| "Mission item not found".to_string())
 }
 
 // Add mission item
 #[tauri::command]
 fn add_mission_item(
     state: State<AppState>,
-    item: MissionItem,
+    mut item: MissionItem,
 ) -> Result<String, String> {
+    item.validate_params()?;
     let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+
+    // Generate the ID on the backend when the caller leaves it empty,
+    // otherwise reject IDs that would shadow an existing item
+    if item.id.trim().is_empty() {
+        item.id = generate_mission_item_id();
+    } else if items.iter().any(|i| i.id == item.id) {
+        return Err(format!("Duplicate id: mission item {} already exists", item.id));
+    }
+    item.validate_jump_target(&items)?;
+
+    mission_history::record_snapshot(&state, &items, "Add mission item")?;
     let item_id = item.id.clone();
     items.push(item);
     Ok(item_id)
@@ -171,59 +1047,564 @@ fn update_waypoint_params(
 ) -> Result<(), String> {
     let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
     
-    if let Some(item) = items.iter_mut().find(|i| i.id == item_id) {
-        item.params = params;
+    if let Some(index) = items.iter().position(|i| i.id == item_id) {
+        let updated = MissionItem { params, ..items[index].clone() };
+        updated.validate_params()?;
+        updated.validate_jump_target(&items[..index])?;
+        mission_history::record_snapshot(&state, &items, "Update waypoint parameters")?;
+        items[index] = updated;
         Ok(())
     } else {
         Err("Mission item not found".to_string())
     }
 }
 
-// Reorder mission item
+// Set or clear the loiter parameters of a loiter item
+#[tauri::command]
+fn update_loiter_params(
+    state: State<AppState>,
+    item_id: String,
+    loiter_params: Option<LoiterParams>,
+) -> Result<(), String> {
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+
+    let index = items.iter()
+        .position(|i| i.id == item_id)
+        .ok_or("Mission item not found")?;
+    let updated = MissionItem { loiter_params, ..items[index].clone() };
+    updated.validate_params()?;
+    mission_history::record_snapshot(&state, &items, "Update loiter parameters")?;
+    items[index] = updated;
+    Ok(())
+}
+
+// Set the camera parameters of a camera trigger item
+#[tauri::command]
+fn update_camera_params(
+    state: State<AppState>,
+    item_id: String,
+    camera_params: CameraParams,
+) -> Result<(), String> {
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+
+    let index = items.iter()
+        .position(|i| i.id == item_id)
+        .ok_or("Mission item not found")?;
+    let updated = MissionItem { camera_params: Some(camera_params), ..items[index].clone() };
+    updated.validate_params()?;
+    mission_history::record_snapshot(&state, &items, "Update camera parameters")?;
+    items[index] = updated;
+    Ok(())
+}
+
+// Replace the notes and tags of a mission item
+#[tauri::command]
+fn set_mission_item_metadata(
+    app_handle: tauri::AppHandle,
+    state: State<AppState>,
+    item_id: String,
+    notes: Option<String>,
+    tags: Vec<String>,
+) -> Result<MissionItem, String> {
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let item = apply_item_metadata(&state, &mut items, &item_id, notes, &tags)?;
+    notify_mission_changed(&app_handle, &state, &items)?;
+    Ok(item)
+}
+
+fn apply_item_metadata(
+    state: &AppState,
+    items: &mut [MissionItem],
+    item_id: &str,
+    notes: Option<String>,
+    tags: &[String],
+) -> Result<MissionItem, String> {
+    let notes = notes
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+    if notes.as_ref().map_or(false, |n| n.chars().count() > MAX_ITEM_NOTES_LENGTH) {
+        return Err(format!("Notes exceed {} characters", MAX_ITEM_NOTES_LENGTH));
+    }
+    let tags = normalize_tags(tags)?;
+
+    let index = items.iter()
+        .position(|i| i.id == item_id)
+        .ok_or("Mission item not found")?;
+    mission_history::record_snapshot(state, items, "Update item notes and tags")?;
+    items[index].notes = notes;
+    items[index].tags = tags;
+    Ok(items[index].clone())
+}
+
+// Items of the active mission whose name, notes or tags contain `query` and that carry
+// every tag in `tags`; both comparisons ignore case
+#[tauri::command]
+fn find_mission_items(
+    state: State<AppState>,
+    query: String,
+    tags: Vec<String>,
+) -> Result<Vec<MissionItem>, String> {
+    let query = query.trim().to_lowercase();
+    let required: Vec<String> = tags.iter().map(|t| t.trim().to_lowercase()).collect();
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+
+    Ok(items.iter()
+        .filter(|item| {
+            let item_tags: Vec<String> = item.tags.iter().map(|t| t.to_lowercase()).collect();
+            let matches_query = query.is_empty()
+                || item.name.to_lowercase().contains(&query)
+                || item.notes.as_ref().map_or(false, |n| n.to_lowercase().contains(&query))
+                || item_tags.iter().any(|t| t.contains(&query));
+            matches_query && required.iter().all(|tag| item_tags.contains(tag))
+        })
+        .cloned()
+        .collect())
+}
+
+// A page of the active mission's items matching `filter`, in mission order
+#[tauri::command]
+fn query_mission_items(
+    state: State<AppState>,
+    filter: MissionFilter,
+) -> Result<MissionQueryResult, String> {
+    // NASA JPL Rule 2: a page never exceeds the mission size limit
+    let limit = filter.limit.unwrap_or(MAX_MISSION_ITEMS).min(MAX_MISSION_ITEMS);
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let matches = filter_items(&items, &filter)?;
+
+    Ok(MissionQueryResult {
+        items: matches.iter().skip(filter.offset).take(limit).map(|item| (*item).clone()).collect(),
+        total_count: matches.len(),
+    })
+}
+
+// Every item matching `filter`, in mission order; pagination is left to the caller
+fn filter_items<'a>(items: &'a [MissionItem], filter: &MissionFilter) -> Result<Vec<&'a MissionItem>, String> {
+    if let (Some(min), Some(max)) = (filter.min_alt, filter.max_alt) {
+        if min > max {
+            return Err(format!("Minimum altitude {min} m is above maximum altitude {max} m"));
+        }
+    }
+    let name = filter.name_contains.as_ref().map(|n| n.trim().to_lowercase());
+    Ok(items.iter()
+        .filter(|item| {
+            let has_action = item.params.action.as_ref().map_or(false, |a| !a.trim().is_empty());
+            filter.item_types.as_ref().map_or(true, |types| types.contains(&item.item_type))
+                && filter.min_alt.map_or(true, |min| item.params.alt >= min)
+                && filter.max_alt.map_or(true, |max| item.params.alt <= max)
+                && filter.bounds.as_ref().map_or(true, |bounds| item_in_bounds(item, bounds))
+                && name.as_ref().map_or(true, |n| item.name.to_lowercase().contains(n))
+                && filter.has_action.map_or(true, |wanted| has_action == wanted)
+        })
+        .collect())
+}
+
+// Number of items of each type in the active mission
+#[tauri::command]
+fn count_mission_items_by_type(state: State<AppState>) -> Result<HashMap<String, u32>, String> {
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let mut counts = HashMap::new();
+    for item in items.iter() {
+        *counts.entry(item.item_type.clone()).or_insert(0) += 1;
+    }
+    Ok(counts)
+}
+
+fn item_in_bounds(item: &MissionItem, bounds: &ViewportBounds) -> bool {
+    item.position.is_some() && map_features::is_in_viewport(&item.location(), bounds)
+}
+
+// Trim tags, drop empty ones and case-insensitive duplicates
+fn normalize_tags(tags: &[String]) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if tag.chars().count() > MAX_ITEM_TAG_LENGTH {
+            return Err(format!("Tag {tag} exceeds {} characters", MAX_ITEM_TAG_LENGTH));
+        }
+        if !normalized.iter().any(|t| t.to_lowercase() == tag.to_lowercase()) {
+            normalized.push(tag.to_string());
+        }
+    }
+    if normalized.len() > MAX_ITEM_TAGS {
+        return Err(format!("Items can have at most {} tags", MAX_ITEM_TAGS));
+    }
+    Ok(normalized)
+}
+
+// Reorder mission item. `new_index` refers to the list before the move: the item
+// is inserted before whatever is currently at `new_index`, and an index of the
+// list length (or beyond) moves it to the end. Moving onto itself is a no-op.
 #[tauri::command]
 fn reorder_mission_item(
     state: State<AppState>,
     item_id: String,
     new_index: usize,
+) -> Result<(), String> {
+    move_mission_item(&state, &item_id, |_| new_index)
+}
+
+// Swap an item with its predecessor; no-op for the first item
+#[tauri::command]
+fn move_mission_item_up(
+    state: State<AppState>,
+    item_id: String,
+) -> Result<(), String> {
+    move_mission_item(&state, &item_id, slot_above)
+}
+
+// Swap an item with its successor; no-op for the last item
+#[tauri::command]
+fn move_mission_item_down(
+    state: State<AppState>,
+    item_id: String,
+) -> Result<(), String> {
+    move_mission_item(&state, &item_id, slot_below)
+}
+
+// Insert-before index one place up or down from `current`
+fn slot_above(current: usize) -> usize {
+    current.saturating_sub(1)
+}
+
+fn slot_below(current: usize) -> usize {
+    current + 2
+}
+
+// Move an item to the insert-before index chosen by `target` from its current index
+fn move_mission_item(
+    state: &AppState,
+    item_id: &str,
+    target: impl FnOnce(usize) -> usize,
 ) -> Result<(), String> {
     let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
-    
-    // Find current index
     let current_index = items.iter().position(|i| i.id == item_id)
         .ok_or("Mission item not found")?;
-    
-    // Remove and reinsert at new position
-    let item = items.remove(current_index);
-    let insert_index = new_index.min(items.len());
-    items.insert(insert_index, item);
-    
+    let insert_before = target(current_index).min(items.len());
+    if insert_before == current_index || insert_before == current_index + 1 {
+        return Ok(());
+    }
+
+    // Removing the item shifts everything after it down by one
+    let mut reordered = items.clone();
+    let item = reordered.remove(current_index);
+    let insert_index = if insert_before > current_index { insert_before - 1 } else { insert_before };
+    reordered.insert(insert_index, item);
+    mission_analysis::enforce_structure(state, &items, &reordered)?;
+
+    mission_history::record_snapshot(state, &items, "Reorder mission item")?;
+    *items = reordered;
     Ok(())
 }
 
 // Delete mission item
 #[tauri::command]
 fn delete_mission_item(
+    app_handle: tauri::AppHandle,
     state: State<AppState>,
     item_id: String,
 ) -> Result<(), String> {
     let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
-    items.retain(|i| i.id != item_id);
+    if items.iter().any(|i| i.id == item_id) {
+        let remaining: Vec<MissionItem> = items.iter().filter(|i| i.id != item_id).cloned().collect();
+        mission_analysis::enforce_structure(&state, &items, &remaining)?;
+        mission_history::record_snapshot(&state, &items, "Delete mission item")?;
+        *items = remaining;
+        clear_removed_selection(&app_handle, &state, &items)?;
+    }
     Ok(())
 }
 
-// Select mission item (this is handled by frontend, but we provide the command for consistency)
+// Select a mission item, or deselect with None, and broadcast it to all views
 #[tauri::command]
-fn select_mission_item(item_id: Option<String>) -> Result<(), String> {
-    // This is primarily handled by the frontend state
-    // Backend can use this for logging or analytics
-    if let Some(id) = item_id {
-        println!("Mission item selected: {id}");
-    } else {
-        println!("Mission item deselected");
+fn select_mission_item(
+    app_handle: tauri::AppHandle,
+    state: State<AppState>,
+    item_id: Option<String>,
+) -> Result<Option<MissionItem>, String> {
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let item = match item_id {
+        Some(id) => Some(items.iter()
+            .find(|i| i.id == id)
+            .cloned()
+            .ok_or_else(|| format!("Mission item {id} not found"))?),
+        None => None,
+    };
+
+    *state.selected_item_id.lock().map_err(|_| "Failed to lock selection")? =
+        item.as_ref().map(|i| i.id.clone());
+    emit_item_selected(&app_handle, item.as_ref())?;
+    Ok(item)
+}
+
+// Currently selected item, for views opened after the selection was made
+#[tauri::command]
+fn get_selected_mission_item(state: State<AppState>) -> Result<Option<MissionItem>, String> {
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let selected = state.selected_item_id.lock().map_err(|_| "Failed to lock selection")?;
+    Ok(selected.as_ref().and_then(|id| items.iter().find(|i| &i.id == id)).cloned())
+}
+
+// Create a new, empty mission in the library
+#[tauri::command]
+fn create_mission(
+    state: State<AppState>,
+    name: String,
+) -> Result<MissionSummary, String> {
+    let name = validate_mission_name(&name)?;
+    let mut missions = state.missions.lock().map_err(|_| "Failed to lock missions")?;
+
+    let mission = Mission {
+        id: format!("mission-set-{}", uuid::Uuid::new_v4()),
+        name,
+        items: Vec::new(),
+    };
+    let summary = MissionSummary {
+        id: mission.id.clone(),
+        name: mission.name.clone(),
+        item_count: 0,
+        active: false,
+    };
+    missions.insert(mission.id.clone(), mission);
+
+    Ok(summary)
+}
+
+// List all missions in the library
+#[tauri::command]
+fn list_missions(state: State<AppState>) -> Result<Vec<MissionSummary>, String> {
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let missions = state.missions.lock().map_err(|_| "Failed to lock missions")?;
+    let active_id = state.active_mission_id.lock().map_err(|_| "Failed to lock active mission")?;
+
+    let mut summaries: Vec<MissionSummary> = missions
+        .values()
+        .map(|mission| {
+            let active = mission.id == *active_id;
+            MissionSummary {
+                id: mission.id.clone(),
+                name: mission.name.clone(),
+                item_count: if active { items.len() } else { mission.items.len() },
+                active,
+            }
+        })
+        .collect();
+    summaries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(summaries)
+}
+
+// Make another mission the target of all item commands
+#[tauri::command]
+fn switch_active_mission(
+    app_handle: tauri::AppHandle,
+    state: State<AppState>,
+    mission_id: String,
+) -> Result<Vec<MissionItem>, String> {
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let mut missions = state.missions.lock().map_err(|_| "Failed to lock missions")?;
+    let mut active_id = state.active_mission_id.lock().map_err(|_| "Failed to lock active mission")?;
+
+    if !missions.contains_key(&mission_id) {
+        return Err(format!("Mission {mission_id} not found"));
+    }
+    ensure_mission_unlocked(&state)?;
+
+    if *active_id != mission_id {
+        // Park the working set in the library, then load the requested mission
+        let previous_items = std::mem::take(&mut *items);
+        if let Some(previous) = missions.get_mut(&*active_id) {
+            previous.items = previous_items;
+        }
+        if let Some(next) = missions.get_mut(&mission_id) {
+            *items = std::mem::take(&mut next.items);
+        }
+        *active_id = mission_id.clone();
+
+        // History belongs to the mission that was being edited
+        state.undo_stack.lock().map_err(|_| "Failed to lock undo history")?.clear();
+        bump_mission_revision(&state)?;
+    }
+
+    emit_mission_changed(&app_handle, &state, &active_id, &items)?;
+    Ok(items.clone())
+}
+
+// Rename a mission in the library
+#[tauri::command]
+fn rename_mission(
+    state: State<AppState>,
+    mission_id: String,
+    name: String,
+) -> Result<(), String> {
+    let name = validate_mission_name(&name)?;
+    let mut missions = state.missions.lock().map_err(|_| "Failed to lock missions")?;
+
+    let mission = missions.get_mut(&mission_id)
+        .ok_or_else(|| format!("Mission {mission_id} not found"))?;
+    mission.name = name;
+    Ok(())
+}
+
+// Delete a mission from the library
+#[tauri::command]
+fn delete_mission(
+    state: State<AppState>,
+    mission_id: String,
+) -> Result<(), String> {
+    let mut missions = state.missions.lock().map_err(|_| "Failed to lock missions")?;
+    let active_id = state.active_mission_id.lock().map_err(|_| "Failed to lock active mission")?;
+
+    if *active_id == mission_id {
+        return Err("Cannot delete the active mission; switch to another mission first".to_string());
+    }
+
+    missions.remove(&mission_id)
+        .map(|_| ())
+        .ok_or_else(|| format!("Mission {mission_id} not found"))
+}
+
+// Validate and normalize a mission name
+fn validate_mission_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Mission name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_MISSION_NAME_LENGTH {
+        return Err(format!("Mission name exceeds {MAX_MISSION_NAME_LENGTH} characters"));
+    }
+    Ok(name.to_string())
+}
+
+// Broadcast the active mission's items to the frontend
+fn emit_mission_changed(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+    mission_id: &str,
+    items: &[MissionItem],
+) -> Result<(), String> {
+    clear_removed_selection(app_handle, state, items)?;
+    let MissionRevision { revision, content_hash } = mission_revision(state, items)?;
+    app_handle
+        .emit_all("mission-changed", serde_json::json!({
+            "mission_id": mission_id,
+            "items": items,
+            "revision": revision,
+            "content_hash": content_hash
+        }))
+        .map_err(|e| format!("Failed to emit mission change: {e}"))
+}
+
+fn emit_item_selected(app_handle: &tauri::AppHandle, item: Option<&MissionItem>) -> Result<(), String> {
+    app_handle
+        .emit_all("mission-item-selected", serde_json::json!({
+            "item_id": item.map(|i| &i.id),
+            "item": item
+        }))
+        .map_err(|e| format!("Failed to emit selection: {e}"))
+}
+
+// Deselect (and broadcast it) when the selected item is no longer in `items`
+fn clear_removed_selection(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+    items: &[MissionItem],
+) -> Result<(), String> {
+    let mut selected = state.selected_item_id.lock().map_err(|_| "Failed to lock selection")?;
+    let removed = selected.as_ref().map(|id| !items.iter().any(|i| &i.id == id)).unwrap_or(false);
+    if removed {
+        *selected = None;
+        drop(selected);
+        emit_item_selected(app_handle, None)?;
     }
     Ok(())
 }
 
+// Broadcast the active mission after an in-place edit
+fn notify_mission_changed(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+    items: &[MissionItem],
+) -> Result<(), String> {
+    let active_id = state.active_mission_id.lock()
+        .map_err(|_| "Failed to lock active mission")?;
+    emit_mission_changed(app_handle, state, &active_id, items)
+}
+
+// Items of a library mission; the active mission's items live in `mission_items`
+fn mission_items_by_id(state: &AppState, mission_id: &str) -> Result<Vec<MissionItem>, String> {
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let missions = state.missions.lock().map_err(|_| "Failed to lock missions")?;
+    let active_id = state.active_mission_id.lock().map_err(|_| "Failed to lock active mission")?;
+
+    if *active_id == mission_id {
+        return Ok(items.clone());
+    }
+    missions.get(mission_id)
+        .map(|mission| mission.items.clone())
+        .ok_or_else(|| format!("Mission {mission_id} not found"))
+}
+
+// Reject edits while the vehicle executes the uploaded mission
+fn ensure_mission_unlocked(state: &AppState) -> Result<(), String> {
+    let lock = state.mission_lock.lock().map_err(|_| "Failed to lock mission lock")?;
+    if lock.locked {
+        return Err(MISSION_LOCKED_ERROR.to_string());
+    }
+    Ok(())
+}
+
+// Lock the mission with `reason`, or unlock it with None
+fn set_mission_lock(state: &AppState, reason: Option<String>) -> Result<(), String> {
+    let mut lock = state.mission_lock.lock().map_err(|_| "Failed to lock mission lock")?;
+    *lock = MissionLockStatus {
+        locked: reason.is_some(),
+        reason,
+    };
+    Ok(())
+}
+
+// Record a change to the active mission's items
+fn bump_mission_revision(state: &AppState) -> Result<u64, String> {
+    let mut revision = state.mission_revision.lock().map_err(|_| "Failed to lock mission revision")?;
+    *revision += 1;
+    Ok(*revision)
+}
+
+fn mission_revision(state: &AppState, items: &[MissionItem]) -> Result<MissionRevision, String> {
+    let revision = *state.mission_revision.lock().map_err(|_| "Failed to lock mission revision")?;
+    Ok(MissionRevision {
+        revision,
+        content_hash: mission_content_hash(items),
+    })
+}
+
+// FNV-1a over the items' JSON; field order is fixed by the struct definitions,
+// so equal missions hash equally across runs
+fn mission_content_hash(items: &[MissionItem]) -> String {
+    let json = serde_json::to_vec(items).unwrap_or_default();
+    let hash = json.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+// Generate a unique mission item ID
+fn generate_mission_item_id() -> String {
+    format!("mission-{}", uuid::Uuid::new_v4())
+}
+
+// Initialize the mission library with the default mission as active
+fn initialize_mission_library() -> HashMap<String, Mission> {
+    let mut missions = HashMap::new();
+    missions.insert(DEFAULT_MISSION_ID.to_string(), Mission {
+        id: DEFAULT_MISSION_ID.to_string(),
+        name: "Default Mission".to_string(),
+        // Items of the active mission are held in `AppState::mission_items`
+        items: Vec::new(),
+    });
+    missions
+}
+
 // Initialize default mission data
 fn initialize_mission_data() -> Vec<MissionItem> {
     vec![
@@ -237,12 +1618,20 @@ fn initialize_mission_data() -> Vec<MissionItem> {
                 alt: 100.0,
                 speed: Some(5.0),
                 action: None,
+                target_item_id: None,
+                repeat_count: None,
+                spline: false,
+                delay_s: None,
             },
             position: Some(Position {
                 lat: 37.7749,
                 lng: -122.4194,
                 alt: 100.0,
             }),
+            loiter_params: None,
+            camera_params: None,
+            notes: None,
+            tags: Vec::new(),
         },
         MissionItem {
             id: "mission-2".to_string(),
@@ -254,12 +1643,20 @@ fn initialize_mission_data() -> Vec<MissionItem> {
                 alt: 150.0,
                 speed: Some(10.0),
                 action: None,
+                target_item_id: None,
+                repeat_count: None,
+                spline: false,
+                delay_s: None,
             },
             position: Some(Position {
                 lat: 37.7849,
                 lng: -122.4094,
                 alt: 150.0,
             }),
+            loiter_params: None,
+            camera_params: None,
+            notes: None,
+            tags: Vec::new(),
         },
     ]
 }
@@ -268,66 +1665,244 @@ fn main() {
     tauri::Builder::default()
         .manage(AppState {
             mission_items: Mutex::new(initialize_mission_data()),
+            missions: Mutex::new(initialize_mission_library()),
+            active_mission_id: Mutex::new(DEFAULT_MISSION_ID.to_string()),
+            vehicle_mission: Mutex::new(None),
+            strict_structure: Mutex::new(false),
+            clipboard: Mutex::new(None),
+            undo_stack: Mutex::new(mission_history::UndoStack::default()),
+            mission_lock: Mutex::new(MissionLockStatus::default()),
+            mission_revision: Mutex::new(0),
+            uploaded_mission_hash: Mutex::new(None),
+            selected_item_id: Mutex::new(None),
+            performance_advisory: Mutex::new(None),
+            sdr_config: RwLock::new(SdrConfig::default()),
+            sdr_window: RwLock::new(window::coefficients(WindowFunction::default(), SDR_FFT_BINS)),
+            waterfall: Mutex::new(WaterfallBuffer::default()),
+            sdr_agc_gain_db: Mutex::new(0.0),
         })
         .manage(map_features::init())
         .manage(mavlink::init())
+        .manage(sdr::init())
+        .manage(terrain::init())
+        .manage(tile_cache::init())
+        .manage(geofence::init())
+        .manage(mission_sim::init())
         .invoke_handler(tauri::generate_handler![
             health_check,
             ping,
             get_app_info,
             get_loaded_plugins,
+            get_sdr_config,
+            set_sdr_config,
+            enable_agc,
+            disable_agc,
+            window::get_window_coefficients,
+            iq::iq_calibration_self_test,
+            get_waterfall_snapshot,
+            set_waterfall_depth,
+            sdr::detect_signal_peaks,
+            sdr::annotate_signal,
+            sdr::get_signal_annotations,
+            sdr::start_frequency_scan,
+            sdr::stop_frequency_scan,
+            sdr::add_sdr_bookmark,
+            sdr::remove_sdr_bookmark,
+            sdr::get_sdr_bookmarks,
+            sdr::load_sdr_bookmark,
+            get_allowed_commands,
             run_cli_command,
             get_mission_data,
+            get_mission_revision,
+            is_vehicle_mission_stale,
+            get_mission_item,
             add_mission_item,
             update_waypoint_params,
+            update_loiter_params,
+            update_camera_params,
+            set_mission_item_metadata,
+            find_mission_items,
+            query_mission_items,
+            count_mission_items_by_type,
             reorder_mission_item,
+            move_mission_item_up,
+            move_mission_item_down,
             delete_mission_item,
             select_mission_item,
+            get_selected_mission_item,
+            // Mission library commands
+            create_mission,
+            list_missions,
+            switch_active_mission,
+            rename_mission,
+            delete_mission,
+            // Mission analysis commands
+            mission_analysis::diff_missions,
+            mission_analysis::diff_with_vehicle_mission,
+            mission_analysis::calculate_mission_stats,
+            mission_analysis::get_mission_eta,
+            mission_analysis::check_mission_structure,
+            mission_analysis::validate_mission,
+            mission_analysis::set_strict_mission_structure,
+            performance::calculate_density_altitude,
+            performance::get_isa_temperature,
+            performance::get_performance_estimates,
+            performance::clear_performance_advisory,
+            mission_analysis::estimate_photo_coverage,
+            mission_analysis::calculate_survey_photos,
+            // Mission editing commands
+            mission_edit::reverse_mission,
+            mission_edit::bulk_delete_mission_items,
+            mission_edit::clear_mission,
+            mission_edit::bulk_update_altitude,
+            mission_edit::bulk_update_speed,
+            mission_edit::adjust_mission_items,
+            mission_edit::translate_mission,
+            mission_edit::move_mission_to,
+            mission_edit::transform_mission,
+            mission_edit::auto_assign_speeds,
+            mission_edit::dedupe_waypoints,
+            mission_edit::set_roi_for_range,
+            mission_edit::duplicate_mission_item,
+            mission_edit::copy_mission_items,
+            mission_edit::paste_mission_items,
+            mission_edit::renumber_waypoints,
+            // Mission history commands
+            mission_history::undo_mission,
+            mission_history::redo_mission,
+            // Mission import/export commands
+            mission_io::export_mission_geojson,
+            mission_io::import_mission_geojson,
+            mission_io::import_mission_csv,
+            mission_io::export_mission_qgc_plan,
+            mission_io::import_mission_qgc_plan,
+            mission_io::export_mission_kml,
+            mission_io::export_mission_gpx,
+            // Survey generation commands
+            mission_survey::generate_survey_grid,
+            mission_survey::generate_spiral_search,
+            // Mission simulation commands
+            mission_sim::simulate_mission,
+            mission_sim::cancel_mission_simulation,
+            // Mission template commands
+            mission_templates::save_mission_template,
+            mission_templates::apply_mission_template,
+            mission_templates::list_mission_templates,
+            // Terrain commands
+            terrain::check_terrain_clearance,
+            terrain::generate_elevation_profile,
+            terrain::import_srtm_tile,
+            // Offline tile cache commands
+            tile_cache::cache_tiles,
+            tile_cache::get_cached_tile,
+            tile_cache::get_cache_stats,
+            tile_cache::set_tile_cache_limit,
+            // Geofence commands
+            geofence::set_geofence,
+            geofence::get_geofence,
+            geofence::clear_geofence,
+            geofence::set_geofence_ceiling,
+            geofence::get_geofence_ceiling,
+            geofence::check_mission_route,
             // Map features commands
             map_features::convert_coordinates,
+            map_features::convert_datum,
             map_features::fetch_map_data_batch,
             map_features::update_gps_position,
             map_features::start_measurement,
             map_features::add_measurement_point,
+            map_features::close_measurement_polygon,
+            map_features::measure_bearing_range,
+            map_features::measure_magnetic_bearing,
+            map_features::get_magnetic_declination,
+            map_features::magnetic_to_true_bearing,
+            map_features::add_rally_point,
+            map_features::remove_rally_point,
+            map_features::get_rally_points,
+            map_features::start_adsb_feed,
+            map_features::stop_adsb_feed,
             // MAVLink drone commands
             mavlink::connect_drone,
             mavlink::disconnect_drone,
+            mavlink::get_connection_status,
+            mavlink::start_tlog_recording,
+            mavlink::stop_tlog_recording,
+            mavlink::get_tlog_info,
+            mavlink::get_recent_messages,
+            mavlink::get_message_statistics,
+            mavlink::get_statustext_log,
             mavlink::get_vehicle_info,
+            mavlink::set_connect_timeout,
+            mavlink::upload_mission_to_vehicle,
+            mavlink::unlock_mission,
+            mavlink::upload_rally_points_to_vehicle,
             mavlink::get_drone_parameters,
             mavlink::set_drone_parameter,
+            mavlink::sync_parameters_from_vehicle,
+            mavlink::refresh_parameters,
+            mavlink::export_parameters,
+            mavlink::search_parameters,
+            mavlink::load_parameter_metadata,
+            mavlink::get_parameter_metadata,
+            mavlink::load_param_file,
+            mavlink::apply_param_file,
+            mavlink::are_parameters_synced,
+            mavlink::set_command_timeout,
             mavlink::test_motor,
+            mavlink::test_all_motors_sequence,
             mavlink::emergency_stop,
+            mavlink::reset_emergency_stop,
+            mavlink::get_preflight_status,
+            mavlink::get_ekf_status,
+            mavlink::set_ekf_variance_threshold,
+            mavlink::get_vibration_status,
+            mavlink::get_vibration_history,
+            mavlink::get_esc_telemetry,
+            mavlink::get_latest_attitude,
+            mavlink::set_battery_thresholds,
+            mavlink::get_latest_vfr,
+            mavlink::get_gps_status,
+            mavlink::get_telemetry_rates,
+            mavlink::set_telemetry_rates,
+            mavlink::start_mavlink_console,
+            mavlink::stop_mavlink_console,
+            mavlink::mavlink_console_write,
+            mavlink::start_mavlink_relay,
+            mavlink::stop_mavlink_relay,
+            mavlink::get_relay_stats,
+            mavlink::arm_vehicle,
+            mavlink::disarm_vehicle,
+            mavlink::get_available_flight_modes,
+            mavlink::set_flight_mode,
+            mavlink::goto_location,
+            mavlink::command_takeoff,
+            mavlink::reboot_vehicle,
             mavlink::calibrate_accelerometer,
-            mavlink::calibrate_gyroscope
+            mavlink::calibrate_gyroscope,
+            mavlink::calibrate_magnetometer,
+            mavlink::calibrate_escs,
+            mavlink::advance_calibration_step,
+            mavlink::confirm_calibration_step
         ])
         .setup(|app| {
             // Initialize application
             println!("Modular C2 Frontend backend initialized");
+
+            // Load the CLI executable allowlist from the resource directory
+            app.manage(load_allowed_commands(app));
+            terrain::load_cached_tiles(app);
+            sdr::load_bookmarks(app);
             
             // Set up periodic SDR data emission (mock data for now)
             let app_handle = app.handle();
             std::thread::spawn(move || {
                 loop {
-                    std::thread::sleep(std::time::Duration::from_millis(100));
-                    
-                    // Generate mock FFT data
-                    let mut magnitudes = vec![];
-                    for i in 0..256 {
-                        let freq = i as f64 / 256.0;
-                        let magnitude = -80.0 + 30.0 * (freq * std::f64::consts::PI * 4.0).sin() 
-                            + rand::random::<f64>() * 10.0 - 5.0;
-                        magnitudes.push(magnitude);
-                    }
-                    
-                    let fft_data = serde_json::json!({
-                        "centerFrequency": 100_000_000.0, // 100 MHz
-                        "sampleRate": 2_000_000.0, // 2 MS/s
-                        "magnitudes": magnitudes,
-                        "timestamp": std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .map(|d| d.as_millis() as u64)
-                            .unwrap_or(0)
-                    });
+                    std::thread::sleep(std::time::Duration::from_millis(SDR_FFT_INTERVAL_MS));
+                    let state = app_handle.state::<AppState>();
+                    let fft_data = match sdr_fft_frame(&state) {
+                        Some(fft_data) => fft_data,
+                        None => continue,
+                    };
                     
                     // Emit FFT data
                     let _ = app_handle.emit_all("sdr-fft-data", fft_data);
@@ -343,6 +1918,67 @@ fn main() {
         });
 }
 
+// Build one mock FFT frame from the current SDR config; the emission loop calls
+// this every SDR_FFT_INTERVAL_MS so config changes apply from the next frame
+fn sdr_fft_frame(state: &AppState) -> Option<serde_json::Value> {
+    let config = match state.sdr_config.read() {
+        Ok(config) => *config,
+        Err(_) => return None,
+    };
+    let coefficients = match state.sdr_window.read() {
+        Ok(coefficients) => coefficients.clone(),
+        Err(_) => return None,
+    };
+    let gain_db = if config.agc_enabled {
+        match state.sdr_agc_gain_db.lock() {
+            Ok(gain) => *gain,
+            Err(_) => return None,
+        }
+    } else {
+        config.gain_db
+    };
+
+    // Generate mock FFT data
+    let mut magnitudes: Vec<f64> = (0..SDR_FFT_BINS)
+        .map(|i| {
+            let freq = i as f64 / SDR_FFT_BINS as f64;
+            -80.0 + 30.0 * (freq * std::f64::consts::PI * 4.0).sin()
+                + rand::random::<f64>() * 10.0 - 5.0 + gain_db
+        })
+        .collect();
+    // The mock has no IQ samples to correct, so it shows the image the imbalance would leave
+    if let Some(image_db) = iq::image_level_db(&config.iq_correction) {
+        magnitudes = iq::with_image(&magnitudes, image_db);
+    }
+    for (magnitude, coefficient) in magnitudes.iter_mut().zip(coefficients.iter()) {
+        // Scale the amplitude; the magnitudes are in dB
+        *magnitude = (*magnitude + 20.0 * coefficient.abs().log10()).max(SDR_MAGNITUDE_FLOOR_DB);
+    }
+
+    if config.agc_enabled {
+        let average_dbm = magnitudes.iter().sum::<f64>() / magnitudes.len() as f64;
+        if let Ok(mut gain) = state.sdr_agc_gain_db.lock() {
+            *gain = agc_adjusted_gain(*gain, average_dbm, config.agc_target_dbm);
+        }
+    }
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    if let Ok(mut waterfall) = state.waterfall.lock() {
+        waterfall.push(magnitudes.clone(), timestamp);
+    }
+
+    Some(serde_json::json!({
+        "centerFrequency": config.center_frequency_hz,
+        "sampleRate": config.sample_rate_hz,
+        "gainDb": gain_db,
+        "magnitudes": magnitudes,
+        "timestamp": timestamp
+    }))
+}
+
 // Mock random number generator for FFT data
 mod rand {
     pub fn random<T>() -> T
@@ -357,4 +1993,311 @@ mod rand {
         let value = ((time % 1000) as f64) / 1000.0;
         T::from(value)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowlist(patterns: &[&str]) -> AllowedCommands {
+        AllowedCommands {
+            patterns: patterns.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn rejects_executable_missing_from_allowlist() {
+        let result = prepare_cli_command("rm -rf /tmp/olympus-cli-test", &allowlist(&["echo"]));
+        assert_eq!(result.err(), Some("Command not permitted: rm".to_string()));
+    }
+
+    #[test]
+    fn rejects_paths_to_allowed_names() {
+        let allowed = allowlist(&["echo"]);
+        assert!(prepare_cli_command("/tmp/x/echo hi", &allowed).is_err());
+        assert!(prepare_cli_command("..\\echo hi", &allowed).is_err());
+    }
+
+    // Commands are spawned without a shell, so shell built-ins can never run
+    #[test]
+    fn bundled_allowlist_names_no_shell_builtins() {
+        let config: AllowedCommandsConfig =
+            toml::from_str(include_str!("../allowed_commands.toml")).unwrap();
+        for builtin in ["cd", "dir", "copy", "del", "type", "set"] {
+            assert!(!config.commands.iter().any(|c| c == builtin), "{builtin} is a shell built-in");
+        }
+    }
+
+    #[test]
+    fn splits_quoted_arguments() {
+        assert_eq!(
+            split_command_line("echo 'a b' \"c\" d").unwrap(),
+            vec!["echo", "a b", "c", "d"]
+        );
+        assert!(split_command_line("echo 'open").is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn runs_allowed_executable_without_a_shell() {
+        let mut command = prepare_cli_command("echo 'hello world' $HOME", &allowlist(&["echo"])).unwrap();
+        let output = execute_cli_command(&mut command, None).await.unwrap();
+        assert_eq!(output.exit_code, 0);
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "hello world $HOME\n");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn kills_command_at_timeout() {
+        let mut command = prepare_cli_command("sleep 10", &allowlist(&["sleep"])).unwrap();
+        let started = std::time::Instant::now();
+        let output = execute_cli_command(&mut command, Some(1)).await.unwrap();
+        assert_eq!(output.exit_code, CLI_TIMEOUT_EXIT_CODE);
+        assert!(started.elapsed() < Duration::from_millis(1500), "took {:?}", started.elapsed());
+    }
+
+    // Four items with ids "a" to "d"
+    fn lettered_mission(state: &AppState) -> Vec<String> {
+        let template = initialize_mission_data().remove(1);
+        let items: Vec<MissionItem> = ["a", "b", "c", "d"].iter()
+            .map(|id| MissionItem { id: id.to_string(), ..template.clone() })
+            .collect();
+        *state.mission_items.lock().unwrap() = items;
+        mission_order(state)
+    }
+
+    fn mission_order(state: &AppState) -> Vec<String> {
+        state.mission_items.lock().unwrap().iter().map(|item| item.id.clone()).collect()
+    }
+
+    #[test]
+    fn reorder_inserts_before_the_original_index() {
+        let state = AppState::default();
+        let cases: [(&str, usize, [&str; 4]); 6] = [
+            ("c", 0, ["c", "a", "b", "d"]),
+            ("b", 4, ["a", "c", "d", "b"]),
+            ("b", 3, ["a", "c", "b", "d"]),
+            ("b", 99, ["a", "c", "d", "b"]),
+            // Onto itself, or just after itself, leaves the order alone
+            ("b", 1, ["a", "b", "c", "d"]),
+            ("b", 2, ["a", "b", "c", "d"]),
+        ];
+        for (id, new_index, expected) in cases {
+            lettered_mission(&state);
+            move_mission_item(&state, id, |_| new_index).unwrap();
+            assert_eq!(mission_order(&state), expected, "{id} to {new_index}");
+        }
+        assert!(move_mission_item(&state, "missing", |_| 0).is_err());
+    }
+
+    #[test]
+    fn move_up_and_down_stop_quietly_at_the_ends() {
+        let state = AppState::default();
+        let original = lettered_mission(&state);
+        let (up, down) = (slot_above, slot_below);
+
+        move_mission_item(&state, "a", up).unwrap();
+        move_mission_item(&state, "d", down).unwrap();
+        assert_eq!(mission_order(&state), original);
+
+        move_mission_item(&state, "c", up).unwrap();
+        assert_eq!(mission_order(&state), ["a", "c", "b", "d"]);
+        move_mission_item(&state, "a", down).unwrap();
+        assert_eq!(mission_order(&state), ["c", "a", "b", "d"]);
+    }
+
+    // Twenty items cycling waypoint/loiter/camera/waypoint, 10 m higher and
+    // 0.01° further north each, with an action on every fifth
+    fn mixed_mission() -> Vec<MissionItem> {
+        let template = initialize_mission_data().remove(1);
+        (0..20)
+            .map(|i| {
+                let item_type = ["waypoint", "loiter", "camera", "waypoint"][i % 4];
+                let lat = 37.0 + 0.01 * i as f64;
+                MissionItem {
+                    id: format!("item-{i}"),
+                    item_type: item_type.to_string(),
+                    name: format!("{} {i}", item_type.to_uppercase()),
+                    params: WaypointParams {
+                        lat,
+                        alt: 10.0 * i as f64,
+                        action: if i % 5 == 0 { Some("photo".to_string()) } else { Some(" ".to_string()) },
+                        ..template.params.clone()
+                    },
+                    position: Some(Position { lat, lng: template.params.lng, alt: 10.0 * i as f64 }),
+                    ..template.clone()
+                }
+            })
+            .collect()
+    }
+
+    fn search_indices(items: &[MissionItem], filter: MissionFilter) -> Vec<usize> {
+        filter_items(items, &filter).unwrap().iter()
+            .map(|item| item.id["item-".len()..].parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn each_search_filter_selects_its_subset() {
+        let items = mixed_mission();
+        let none = MissionFilter::default;
+
+        assert_eq!(search_indices(&items, none()), (0..20).collect::<Vec<_>>());
+        assert_eq!(
+            search_indices(&items, MissionFilter { item_types: Some(vec!["loiter".to_string()]), ..none() }),
+            [1, 5, 9, 13, 17]
+        );
+        assert_eq!(
+            search_indices(&items, MissionFilter { name_contains: Some("Camera 1".to_string()), ..none() }),
+            [10, 14, 18]
+        );
+        assert_eq!(
+            search_indices(&items, MissionFilter { min_alt: Some(150.0), ..none() }),
+            [15, 16, 17, 18, 19]
+        );
+        assert_eq!(search_indices(&items, MissionFilter { max_alt: Some(20.0), ..none() }), [0, 1, 2]);
+        let bounds = ViewportBounds { north: 37.065, south: 37.035, east: -122.0, west: -123.0 };
+        assert_eq!(
+            search_indices(&items, MissionFilter { bounds: Some(bounds), ..none() }),
+            [4, 5, 6]
+        );
+        assert_eq!(
+            search_indices(&items, MissionFilter { has_action: Some(true), ..none() }),
+            [0, 5, 10, 15]
+        );
+        // Filters are ANDed
+        assert_eq!(
+            search_indices(&items, MissionFilter {
+                item_types: Some(vec!["waypoint".to_string()]),
+                has_action: Some(true),
+                ..none()
+            }),
+            [0, 15]
+        );
+        assert!(filter_items(&items, &MissionFilter { min_alt: Some(50.0), max_alt: Some(10.0), ..none() }).is_err());
+    }
+
+    #[test]
+    fn sdr_config_change_applies_to_the_next_fft_frame() {
+        let state = std::sync::Arc::new(AppState::default());
+        let (frames, received) = std::sync::mpsc::channel();
+        let (resume, next_tick) = std::sync::mpsc::channel::<()>();
+        let emitter = {
+            let state = state.clone();
+            std::thread::spawn(move || {
+                for _ in 0..2 {
+                    frames.send(sdr_fft_frame(&state).unwrap()).unwrap();
+                    let _ = next_tick.recv();
+                }
+            })
+        };
+
+        let first = received.recv().unwrap();
+        assert_eq!(first["centerFrequency"], 100_000_000.0);
+
+        let tuned = SdrConfig { center_frequency_hz: 433_920_000.0, sample_rate_hz: 2_400_000.0, ..SdrConfig::default() };
+        update_sdr_config(&state, |_| tuned).unwrap();
+        resume.send(()).unwrap();
+
+        let next = received.recv().unwrap();
+        assert_eq!(next["centerFrequency"], 433_920_000.0);
+        assert_eq!(next["sampleRate"], 2_400_000.0);
+        resume.send(()).unwrap();
+        emitter.join().unwrap();
+
+        // Out-of-range settings are refused and leave the running config alone
+        let too_fast = SdrConfig { sample_rate_hz: 3.3e6, ..tuned };
+        assert!(update_sdr_config(&state, |_| too_fast).is_err());
+        assert!(update_sdr_config(&state, |_| SdrConfig { gain_db: 50.0, ..tuned }).is_err());
+        assert_eq!(sdr_fft_frame(&state).unwrap()["sampleRate"], 2_400_000.0);
+    }
+
+    #[test]
+    fn full_waterfall_returns_the_latest_rows_in_time_order() {
+        let mut waterfall = WaterfallBuffer::default();
+        waterfall.resize(MIN_WATERFALL_LINES as usize);
+        // One past capacity, so the first line is evicted
+        for i in 0..=MIN_WATERFALL_LINES as u64 {
+            waterfall.push(vec![i as f64; SDR_FFT_BINS], 1_000 + i * SDR_FFT_INTERVAL_MS);
+        }
+
+        for count in [1, 10, 99, 100, 500] {
+            let snapshot = waterfall.snapshot(count);
+            let expected = count.min(MIN_WATERFALL_LINES as usize);
+            assert_eq!(snapshot.lines.len(), expected);
+            assert_eq!(snapshot.lines.last().unwrap()[0], MIN_WATERFALL_LINES as f64);
+            assert_eq!(snapshot.lines[0][0], (MIN_WATERFALL_LINES as usize + 1 - expected) as f64);
+            let start = snapshot.timestamp_ms_start.unwrap();
+            let end = snapshot.timestamp_ms_end.unwrap();
+            assert!(start <= end);
+            assert_eq!(end - start, (expected as u64 - 1) * SDR_FFT_INTERVAL_MS);
+        }
+
+        // Shrinking keeps the newest lines
+        waterfall.resize(10);
+        let snapshot = waterfall.snapshot(100);
+        assert_eq!(snapshot.lines.len(), 10);
+        assert_eq!(snapshot.lines[0][0], (MIN_WATERFALL_LINES - 9) as f64);
+
+        let empty = WaterfallBuffer::default().snapshot(10);
+        assert!(empty.lines.is_empty());
+        assert_eq!((empty.timestamp_ms_start, empty.timestamp_ms_end), (None, None));
+    }
+
+    #[test]
+    fn agc_converges_on_the_target_within_fifty_frames() {
+        // The default state has no window coefficients, so frames are unwindowed
+        let state = AppState::default();
+        let manual = SdrConfig { gain_db: 5.0, ..SdrConfig::default() };
+        update_sdr_config(&state, |_| manual).unwrap();
+        assert!(update_sdr_config(&state, |_| SdrConfig { agc_enabled: true, ..manual }).is_err());
+
+        // The mock spectrum averages about -80 dBm before gain, 25 dB below the target
+        let target_dbm = -50.0;
+        start_agc(&state, target_dbm).unwrap();
+        assert_eq!(*state.sdr_agc_gain_db.lock().unwrap(), 5.0);
+        assert_eq!(state.sdr_config.read().unwrap().gain_db, 0.0);
+
+        let average = |frame: &serde_json::Value| {
+            let magnitudes = frame["magnitudes"].as_array().unwrap();
+            magnitudes.iter().map(|m| m.as_f64().unwrap()).sum::<f64>() / magnitudes.len() as f64
+        };
+        let first_error = (average(&sdr_fft_frame(&state).unwrap()) - target_dbm).abs();
+        let mut last_frame = serde_json::Value::Null;
+        for _ in 1..50 {
+            last_frame = sdr_fft_frame(&state).unwrap();
+        }
+        // Within the mock's ±5 dB noise plus one AGC step
+        let last_error = (average(&last_frame) - target_dbm).abs();
+        assert!(first_error > 15.0, "started {first_error} dB off");
+        assert!(last_error < 6.5, "ended {last_error} dB off");
+
+        let settled_gain = *state.sdr_agc_gain_db.lock().unwrap();
+        assert!((20.0..=40.0).contains(&settled_gain), "settled at {settled_gain} dB");
+        stop_agc(&state).unwrap();
+        let config = *state.sdr_config.read().unwrap();
+        assert!(!config.agc_enabled);
+        assert_eq!(config.gain_db, settled_gain);
+    }
+
+    #[test]
+    fn item_metadata_update_is_normalized_and_undoable() {
+        let state = AppState::default();
+        let mut items = initialize_mission_data();
+        let id = items[1].id.clone();
+        let tags = [" Survey ".to_string(), "survey".to_string(), "".to_string(), "photo".to_string()];
+
+        let updated = apply_item_metadata(&state, &mut items, &id, Some("  check wind  ".to_string()), &tags).unwrap();
+        assert_eq!(updated.notes.as_deref(), Some("check wind"));
+        assert_eq!(updated.tags, ["Survey", "photo"]);
+        assert_eq!(items[1].tags, updated.tags);
+
+        assert!(apply_item_metadata(&state, &mut items, "missing", None, &[]).is_err());
+        let too_long = "x".repeat(MAX_ITEM_NOTES_LENGTH + 1);
+        assert!(apply_item_metadata(&state, &mut items, &id, Some(too_long), &[]).is_err());
+
+        mission_history::apply_undo(&state, &mut items).unwrap();
+        assert!(items[1].notes.is_none());
+        assert!(items[1].tags.is_empty());
+    }
+}
@@ -2,9 +2,16 @@
 // NASA JPL Power of 10 compliant implementation
 
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use tauri::{Manager, State};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Mutex;
 use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::geo::{
+    final_bearing_deg, great_circle_midpoint, haversine_distance, initial_bearing_deg,
+    current_decimal_year, is_valid_coordinate, magnetic_declination_deg, polygon_area_km2, transform_datum, WGS84_DATUM,
+};
 
 // ===== TYPE DEFINITIONS =====
 
@@ -27,6 +34,8 @@ pub struct ConversionResult {
 pub struct FormatInfo {
     pub detected_format: String,
     pub confidence: f32,
+    // Geodetic datum of the returned coordinate
+    pub datum: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,6 +79,42 @@ pub struct Aircraft {
     pub speed: f64,
     pub altitude: f64,
     pub aircraft_type: String,
+    // Unix milliseconds of the last report; aircraft are dropped after ADSB_STALE_MS
+    #[serde(default)]
+    pub last_seen_ms: u64,
+}
+
+// Counts broadcast in `adsb-update` after each poll
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AdsbUpdate {
+    pub added: u32,
+    pub removed: u32,
+    pub updated: u32,
+    pub total: u32,
+}
+
+// dump1090 /data/aircraft.json
+#[derive(Debug, Deserialize)]
+struct Dump1090Feed {
+    #[serde(default)]
+    aircraft: Vec<Dump1090Aircraft>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Dump1090Aircraft {
+    hex: String,
+    flight: Option<String>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+    // Feet, or the string "ground"
+    altitude: Option<serde_json::Value>,
+    track: Option<f64>,
+    // Knots
+    speed: Option<f64>,
+    #[serde(rename = "type")]
+    aircraft_type: Option<String>,
+    // Seconds since the receiver last heard the aircraft
+    seen: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -84,8 +129,39 @@ pub struct WeatherTile {
 pub struct MeasurementData {
     pub points: Vec<Coordinate>,
     pub measurement_type: String,
+    // Kilometers; the perimeter once the polygon is closed
     pub total_distance: f64,
+    // Square kilometers, for "area" measurements with at least 3 points
     pub area: Option<f64>,
+    // Closed polygons accept no further points
+    #[serde(default)]
+    pub closed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BearingRange {
+    pub distance_km: f64,
+    // True bearings along the great circle, 0-360° clockwise from north
+    pub initial_bearing_deg: f64,
+    pub final_bearing_deg: f64,
+    pub midpoint: Coordinate,
+    // Declination at `from` and the bearings relative to magnetic north; None
+    // when the magnetic model does not cover the date
+    pub declination_deg: Option<f64>,
+    pub initial_magnetic_bearing_deg: Option<f64>,
+    pub final_magnetic_bearing_deg: Option<f64>,
+}
+
+// Alternative RTL destination; the altitudes are meters above home
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RallyPoint {
+    pub id: u8,
+    pub coordinate: Coordinate,
+    // Where the vehicle breaks off its loiter to land
+    pub break_altitude_m: f64,
+    pub land_altitude_m: f64,
+    // RALLY_FLAGS bits: 1 favorable wind, 2 land immediately
+    pub flags: u8,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -100,8 +176,13 @@ pub struct BatchOptions {
 
 pub struct MapFeaturesState {
     gps_position: Mutex<Option<GpsData>>,
+    // Keyed by lowercase ICAO hex code
     aircraft_cache: Mutex<HashMap<String, Aircraft>>,
     measurements: Mutex<Vec<MeasurementData>>,
+    // Kept sorted by id, the order they are uploaded in
+    rally_points: Mutex<Vec<RallyPoint>>,
+    // Bumped on every feed start/stop so stale polling tasks can exit
+    adsb_epoch: AtomicU64,
 }
 
 impl MapFeaturesState {
@@ -110,8 +191,24 @@ impl MapFeaturesState {
             gps_position: Mutex::new(None),
             aircraft_cache: Mutex::new(HashMap::new()),
             measurements: Mutex::new(Vec::new()),
+            rally_points: Mutex::new(Vec::new()),
+            adsb_epoch: AtomicU64::new(0),
         }
     }
+
+    pub fn rally_points(&self) -> Result<Vec<RallyPoint>, String> {
+        Ok(self.rally_points.lock()
+            .map_err(|e| format!("Rally points lock error: {e}"))?
+            .clone())
+    }
+
+    // Shared by the frontend command and the MAVLink position feed
+    pub fn set_gps_position(&self, position: GpsData) -> Result<(), String> {
+        let mut gps = self.gps_position.lock()
+            .map_err(|e| format!("GPS position lock error: {e}"))?;
+        *gps = Some(position);
+        Ok(())
+    }
 }
 
 // ===== COORDINATE CONVERSION =====
@@ -146,6 +243,7 @@ pub async fn convert_coordinates(
             format_info: Some(FormatInfo {
                 detected_format,
                 confidence: 0.95,
+                datum: WGS84_DATUM.to_string(),
             }),
         }),
         None => Ok(ConversionResult {
@@ -157,6 +255,19 @@ pub async fn convert_coordinates(
     }
 }
 
+// Convert between WGS84 and NAD83, ED50 or OSGB36
+#[tauri::command]
+pub async fn convert_datum(
+    coordinate: Coordinate,
+    from_datum: String,
+    to_datum: String,
+) -> Result<Coordinate, String> {
+    if !is_valid_coordinate(&coordinate) {
+        return Err("Invalid coordinate".to_string());
+    }
+    transform_datum(&coordinate, &from_datum, &to_datum)
+}
+
 // NASA JPL Rule 4: Function under 60 lines
 fn detect_coordinate_format(input: &str) -> String {
     let trimmed = input.trim();
@@ -262,7 +373,7 @@ pub async fn fetch_map_data_batch(
             .map_err(|e| format!("Aircraft cache lock error: {e}"))?;
         batch.adsb_aircraft = aircraft
             .values()
-            .filter(|a| is_in_viewport(&a.position, &viewport))
+            .filter(|a| is_in_viewport(&a.position, &viewport.bounds))
             .cloned()
             .collect();
     }
@@ -283,11 +394,11 @@ pub async fn fetch_map_data_batch(
 }
 
 // NASA JPL Rule 4: Function under 60 lines
-fn is_in_viewport(coord: &Coordinate, viewport: &Viewport) -> bool {
-    coord.lat >= viewport.bounds.south
-        && coord.lat <= viewport.bounds.north
-        && coord.lng >= viewport.bounds.west
-        && coord.lng <= viewport.bounds.east
+pub fn is_in_viewport(coord: &Coordinate, bounds: &ViewportBounds) -> bool {
+    coord.lat >= bounds.south
+        && coord.lat <= bounds.north
+        && coord.lng >= bounds.west
+        && coord.lng <= bounds.east
 }
 
 // NASA JPL Rule 4: Function under 60 lines
@@ -310,14 +421,14 @@ pub async fn update_gps_position(
     position: GpsData,
     state: State<'_, MapFeaturesState>,
 ) -> Result<(), String> {
-    let mut gps = state.gps_position.lock()
-        .map_err(|e| format!("GPS position lock error: {e}"))?;
-    *gps = Some(position);
-    Ok(())
+    state.set_gps_position(position)
 }
 
 // ===== MEASUREMENT COMMANDS =====
 
+// Closing click this close to the first point is treated as the first point
+const MEASUREMENT_SNAP_DISTANCE_KM: f64 = 0.005;
+
 #[tauri::command]
 pub async fn start_measurement(
     measurement_type: String,
@@ -328,6 +439,7 @@ pub async fn start_measurement(
         measurement_type,
         total_distance: 0.0,
         area: None,
+        closed: false,
     };
     
     let mut measurements = state.measurements.lock()
@@ -339,51 +451,454 @@ pub async fn start_measurement(
 
 #[tauri::command]
 pub async fn add_measurement_point(
-    _measurement_id: String,
+    measurement_id: String,
     point: Coordinate,
     state: State<'_, MapFeaturesState>,
 ) -> Result<MeasurementData, String> {
     let mut measurements = state.measurements.lock()
         .map_err(|e| format!("Measurements lock error: {e}"))?;
-    
-    // Find the measurement by ID (simplified for demo)
-    if let Some(measurement) = measurements.last_mut() {
-        measurement.points.push(point);
-        
-        // Calculate distance
-        if measurement.points.len() > 1 {
-            let last_idx = measurement.points.len() - 1;
-            let dist = haversine_distance(
-                &measurement.points[last_idx - 1],
-                &measurement.points[last_idx],
-            );
-            measurement.total_distance += dist;
-        }
-        
-        Ok(measurement.clone())
+    let measurement = find_measurement(&mut measurements, &measurement_id)?;
+    if measurement.closed {
+        return Err("Measurement polygon is closed".to_string());
+    }
+
+    measurement.points.push(point);
+
+    // Calculate distance
+    if measurement.points.len() > 1 {
+        let last_idx = measurement.points.len() - 1;
+        let dist = haversine_distance(
+            &measurement.points[last_idx - 1],
+            &measurement.points[last_idx],
+        );
+        measurement.total_distance += dist;
+    }
+    measurement.area = measurement_area(measurement);
+
+    Ok(measurement.clone())
+}
+
+// Close the polygon: the last point snaps onto the first when it is within
+// MEASUREMENT_SNAP_DISTANCE_KM, otherwise the first point is appended. The
+// distance becomes the perimeter and the area is final.
+#[tauri::command]
+pub async fn close_measurement_polygon(
+    measurement_id: String,
+    state: State<'_, MapFeaturesState>,
+) -> Result<MeasurementData, String> {
+    let mut measurements = state.measurements.lock()
+        .map_err(|e| format!("Measurements lock error: {e}"))?;
+    let measurement = find_measurement(&mut measurements, &measurement_id)?;
+    close_polygon(measurement)?;
+    Ok(measurement.clone())
+}
+
+// Snaps a final click near the first point onto it, then closes the ring
+fn close_polygon(measurement: &mut MeasurementData) -> Result<(), String> {
+    if measurement.closed {
+        return Ok(());
+    }
+
+    let first = match measurement.points.first() {
+        Some(first) => first.clone(),
+        None => return Err("Measurement has no points".to_string()),
+    };
+    let snaps = measurement.points.len() > 1 && measurement.points.last()
+        .map(|last| haversine_distance(last, &first) <= MEASUREMENT_SNAP_DISTANCE_KM)
+        .unwrap_or(false);
+    if snaps {
+        measurement.points.pop();
+    }
+    if measurement.points.len() < 3 {
+        return Err("A polygon needs at least 3 distinct points".to_string());
+    }
+    measurement.points.push(first);
+
+    measurement.total_distance = measurement.points.windows(2)
+        .map(|pair| haversine_distance(&pair[0], &pair[1]))
+        .sum();
+    measurement.area = Some(polygon_area_km2(&measurement.points));
+    measurement.closed = true;
+    Ok(())
+}
+
+// IDs are "measurement_<n>", 1-based in creation order
+fn find_measurement<'a>(
+    measurements: &'a mut [MeasurementData],
+    measurement_id: &str,
+) -> Result<&'a mut MeasurementData, String> {
+    measurement_id.strip_prefix("measurement_")
+        .and_then(|n| n.parse::<usize>().ok())
+        .and_then(|n| n.checked_sub(1))
+        .and_then(move |index| measurements.get_mut(index))
+        .ok_or_else(|| "Measurement not found".to_string())
+}
+
+fn measurement_area(measurement: &MeasurementData) -> Option<f64> {
+    if measurement.measurement_type == "area" && measurement.points.len() >= 3 {
+        Some(polygon_area_km2(&measurement.points))
     } else {
-        Err("Measurement not found".to_string())
+        None
     }
 }
 
-// NASA JPL Rule 4: Function under 60 lines
-fn haversine_distance(coord1: &Coordinate, coord2: &Coordinate) -> f64 {
-    const EARTH_RADIUS_KM: f64 = 6371.0;
-    
-    let lat1_rad = coord1.lat.to_radians();
-    let lat2_rad = coord2.lat.to_radians();
-    let delta_lat = (coord2.lat - coord1.lat).to_radians();
-    let delta_lng = (coord2.lng - coord1.lng).to_radians();
-    
-    let a = (delta_lat / 2.0).sin().powi(2)
-        + lat1_rad.cos() * lat2_rad.cos() * (delta_lng / 2.0).sin().powi(2);
-    let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
-    
-    EARTH_RADIUS_KM * c
+// ===== BEARING AND RANGE =====
+
+// Magnetic bearings use the declination at `from` on `date_year`, today by default
+#[tauri::command]
+pub async fn measure_bearing_range(
+    from: Coordinate,
+    to: Coordinate,
+    date_year: Option<f64>,
+) -> Result<BearingRange, String> {
+    validate_endpoints(&from, &to)?;
+    let initial = initial_bearing_deg(&from, &to);
+    let final_bearing = final_bearing_deg(&from, &to);
+    let declination = magnetic_declination_deg(&from, date_year.unwrap_or_else(current_decimal_year)).ok();
+    Ok(BearingRange {
+        distance_km: haversine_distance(&from, &to),
+        initial_bearing_deg: initial,
+        final_bearing_deg: final_bearing,
+        midpoint: great_circle_midpoint(&from, &to),
+        declination_deg: declination,
+        initial_magnetic_bearing_deg: declination.map(|d| true_to_magnetic(initial, d)),
+        final_magnetic_bearing_deg: declination.map(|d| true_to_magnetic(final_bearing, d)),
+    })
+}
+
+// Initial bearing relative to magnetic north at `from` (0-360°)
+#[tauri::command]
+pub async fn measure_magnetic_bearing(
+    from: Coordinate,
+    to: Coordinate,
+    date_year: f64,
+) -> Result<f64, String> {
+    validate_endpoints(&from, &to)?;
+    let declination = magnetic_declination_deg(&from, date_year)?;
+    Ok(true_to_magnetic(initial_bearing_deg(&from, &to), declination))
+}
+
+// Declination (degrees, east positive) at `coordinate` in decimal year `year`
+#[tauri::command]
+pub async fn get_magnetic_declination(
+    coordinate: Coordinate,
+    year: f64,
+) -> Result<f64, String> {
+    if !is_valid_coordinate(&coordinate) {
+        return Err("Invalid coordinate".to_string());
+    }
+    magnetic_declination_deg(&coordinate, year)
+}
+
+// True bearing of a compass bearing taken at `coordinate`, today by default
+#[tauri::command]
+pub async fn magnetic_to_true_bearing(
+    magnetic_bearing_deg: f64,
+    coordinate: Coordinate,
+    date_year: Option<f64>,
+) -> Result<f64, String> {
+    if !magnetic_bearing_deg.is_finite() {
+        return Err("Bearing must be a finite number".to_string());
+    }
+    if !is_valid_coordinate(&coordinate) {
+        return Err("Invalid coordinate".to_string());
+    }
+    let declination = magnetic_declination_deg(&coordinate, date_year.unwrap_or_else(current_decimal_year))?;
+    Ok(magnetic_to_true(magnetic_bearing_deg, declination))
+}
+
+// East declination puts magnetic north east of true north, so magnetic bearings are smaller
+pub fn true_to_magnetic(true_bearing_deg: f64, declination_deg: f64) -> f64 {
+    (true_bearing_deg - declination_deg).rem_euclid(360.0)
+}
+
+pub fn magnetic_to_true(magnetic_bearing_deg: f64, declination_deg: f64) -> f64 {
+    (magnetic_bearing_deg + declination_deg).rem_euclid(360.0)
+}
+
+fn validate_endpoints(from: &Coordinate, to: &Coordinate) -> Result<(), String> {
+    if !is_valid_coordinate(from) || !is_valid_coordinate(to) {
+        return Err("Invalid coordinates".to_string());
+    }
+    Ok(())
+}
+
+// ===== RALLY POINTS =====
+
+// NASA JPL Rule 2: bound the rally points stored and uploaded
+const MAX_RALLY_POINTS: usize = 50;
+const MAX_RALLY_DISTANCE_KM: f64 = 50.0;
+const RALLY_FLAG_MASK: u8 = 0b11;
+
+#[tauri::command]
+pub async fn add_rally_point(
+    point: RallyPoint,
+    state: State<'_, MapFeaturesState>,
+    app_state: State<'_, crate::AppState>,
+) -> Result<(), String> {
+    validate_rally_point(&point, &planned_home(&app_state)?)?;
+
+    let mut rally_points = state.rally_points.lock()
+        .map_err(|e| format!("Rally points lock error: {e}"))?;
+    if rally_points.iter().any(|existing| existing.id == point.id) {
+        return Err(format!("Rally point {} already exists", point.id));
+    }
+    if rally_points.len() >= MAX_RALLY_POINTS {
+        return Err(format!("At most {MAX_RALLY_POINTS} rally points are supported"));
+    }
+    rally_points.push(point);
+    rally_points.sort_by_key(|existing| existing.id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn remove_rally_point(
+    id: u8,
+    state: State<'_, MapFeaturesState>,
+) -> Result<(), String> {
+    let mut rally_points = state.rally_points.lock()
+        .map_err(|e| format!("Rally points lock error: {e}"))?;
+    let index = rally_points.iter()
+        .position(|point| point.id == id)
+        .ok_or_else(|| format!("Rally point {id} not found"))?;
+    rally_points.remove(index);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_rally_points(
+    state: State<'_, MapFeaturesState>,
+) -> Result<Vec<RallyPoint>, String> {
+    state.rally_points()
+}
+
+// The planned home position is the first mission item
+pub fn planned_home(app_state: &crate::AppState) -> Result<Coordinate, String> {
+    let items = app_state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    items.first()
+        .map(|home| home.location())
+        .ok_or_else(|| "Rally points need a home position; add a mission item first".to_string())
+}
+
+pub fn validate_rally_point(point: &RallyPoint, home: &Coordinate) -> Result<(), String> {
+    if !is_valid_coordinate(&point.coordinate) {
+        return Err(format!("Rally point {} has an invalid coordinate", point.id));
+    }
+    if !(point.break_altitude_m.is_finite() && point.land_altitude_m.is_finite()) {
+        return Err(format!("Rally point {} altitudes must be finite", point.id));
+    }
+    if point.land_altitude_m > point.break_altitude_m {
+        return Err(format!(
+            "Rally point {} land altitude {:.1} m is above its break altitude {:.1} m",
+            point.id, point.land_altitude_m, point.break_altitude_m
+        ));
+    }
+    if point.flags & !RALLY_FLAG_MASK != 0 {
+        return Err(format!("Rally point {} has unknown flags {:#04x}", point.id, point.flags));
+    }
+
+    let distance_km = haversine_distance(home, &point.coordinate);
+    if distance_km > MAX_RALLY_DISTANCE_KM {
+        return Err(format!(
+            "Rally point {} is {distance_km:.1} km from home; the limit is {MAX_RALLY_DISTANCE_KM:.0} km",
+            point.id
+        ));
+    }
+    Ok(())
+}
+
+// ===== ADS-B FEED =====
+
+const ADSB_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const ADSB_REQUEST_TIMEOUT: Duration = Duration::from_secs(4);
+const ADSB_STALE_MS: u64 = 60_000;
+// NASA JPL Rule 2: bound the aircraft taken from one feed response
+const MAX_TRACKED_AIRCRAFT: usize = 5000;
+const FEET_TO_METERS: f64 = 0.3048;
+
+// Poll a dump1090 receiver at `endpoint_url` (host[:port]) every 5 seconds,
+// replacing any feed already running
+#[tauri::command]
+pub async fn start_adsb_feed(
+    app_handle: tauri::AppHandle,
+    endpoint_url: String,
+    state: State<'_, MapFeaturesState>,
+) -> Result<(), String> {
+    let endpoint = endpoint_url.trim().trim_end_matches('/');
+    if endpoint.is_empty() || endpoint.contains("://") || endpoint.contains(char::is_whitespace) {
+        return Err("ADS-B endpoint must be a host[:port] without a scheme".to_string());
+    }
+    let url = format!("http://{endpoint}/data/aircraft.json");
+    let client = reqwest::Client::builder()
+        .timeout(ADSB_REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
+
+    let epoch = state.adsb_epoch.fetch_add(1, Ordering::SeqCst) + 1;
+    tauri::async_runtime::spawn(run_adsb_feed(app_handle, client, url, epoch));
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_adsb_feed(state: State<'_, MapFeaturesState>) -> Result<(), String> {
+    state.adsb_epoch.fetch_add(1, Ordering::SeqCst);
+    state.aircraft_cache.lock()
+        .map_err(|e| format!("Aircraft cache lock error: {e}"))?
+        .clear();
+    Ok(())
+}
+
+async fn run_adsb_feed(app_handle: tauri::AppHandle, client: reqwest::Client, url: String, epoch: u64) {
+    loop {
+        // A failed poll still sweeps stale aircraft
+        let reports = fetch_aircraft(&client, &url).await.unwrap_or_else(|e| {
+            eprintln!("ADS-B feed error: {e}");
+            Vec::new()
+        });
+
+        let state = app_handle.state::<MapFeaturesState>();
+        if state.adsb_epoch.load(Ordering::SeqCst) != epoch {
+            break;
+        }
+        let update = match state.aircraft_cache.lock() {
+            Ok(mut cache) => merge_aircraft(&mut cache, reports, current_time_ms()),
+            Err(e) => {
+                eprintln!("Aircraft cache lock error: {e}");
+                break;
+            }
+        };
+        if let Err(e) = app_handle.emit_all("adsb-update", update) {
+            eprintln!("Failed to emit ADS-B update: {e}");
+        }
+
+        tokio::time::sleep(ADSB_POLL_INTERVAL).await;
+    }
+}
+
+async fn fetch_aircraft(client: &reqwest::Client, url: &str) -> Result<Vec<Dump1090Aircraft>, String> {
+    let feed: Dump1090Feed = client.get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Failed to poll {url}: {e}"))?
+        .json()
+        .await
+        .map_err(|e| format!("Invalid dump1090 response from {url}: {e}"))?;
+    Ok(feed.aircraft)
+}
+
+// Upsert positioned reports by ICAO hex, then drop aircraft unseen for ADSB_STALE_MS
+fn merge_aircraft(
+    cache: &mut HashMap<String, Aircraft>,
+    reports: Vec<Dump1090Aircraft>,
+    now_ms: u64,
+) -> AdsbUpdate {
+    let mut update = AdsbUpdate::default();
+    for aircraft in reports.into_iter().take(MAX_TRACKED_AIRCRAFT).filter_map(|r| to_aircraft(r, now_ms)) {
+        match cache.insert(aircraft.id.clone(), aircraft) {
+            Some(_) => update.updated += 1,
+            None => update.added += 1,
+        }
+    }
+
+    let before = cache.len();
+    cache.retain(|_, aircraft| now_ms.saturating_sub(aircraft.last_seen_ms) <= ADSB_STALE_MS);
+    update.removed = (before - cache.len()) as u32;
+    update.total = cache.len() as u32;
+    update
+}
+
+fn to_aircraft(report: Dump1090Aircraft, now_ms: u64) -> Option<Aircraft> {
+    let id = report.hex.trim().to_lowercase();
+    let (lat, lng) = (report.lat?, report.lon?);
+    let position = Coordinate { lat, lng, alt: None };
+    if id.is_empty() || !is_valid_coordinate(&position) {
+        return None;
+    }
+
+    // "ground" and missing altitudes are reported as 0
+    let altitude_ft = report.altitude.as_ref().and_then(serde_json::Value::as_f64).unwrap_or(0.0);
+    let seen_ms = report.seen.filter(|s| s.is_finite() && *s > 0.0).map_or(0, |s| (s * 1000.0) as u64);
+    let callsign = report.flight.as_deref().map(str::trim).filter(|f| !f.is_empty());
+    Some(Aircraft {
+        callsign: callsign.unwrap_or(&id).to_string(),
+        position: Coordinate { alt: Some(altitude_ft * FEET_TO_METERS), ..position },
+        heading: report.track.unwrap_or(0.0),
+        speed: report.speed.unwrap_or(0.0),
+        altitude: altitude_ft,
+        aircraft_type: report.aircraft_type.unwrap_or_default(),
+        last_seen_ms: now_ms.saturating_sub(seen_ms),
+        id,
+    })
+}
+
+fn current_time_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
 }
 
 // ===== MODULE REGISTRATION =====
 
 pub fn init() -> MapFeaturesState {
     MapFeaturesState::new()
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(lat: f64, lng: f64) -> Coordinate {
+        Coordinate { lat, lng, alt: None }
+    }
+
+    #[test]
+    fn closed_one_degree_square_has_ellipsoidal_area() {
+        let mut measurement = MeasurementData {
+            points: vec![point(0.0, 0.0), point(0.0, 1.0), point(1.0, 1.0), point(1.0, 0.0)],
+            measurement_type: "area".to_string(),
+            total_distance: 0.0,
+            area: None,
+            closed: false,
+        };
+        // About 12,308 km² on WGS-84 (12,364 km² on the mean sphere)
+        let open_area = measurement_area(&measurement).unwrap();
+        assert!((open_area - 12_308.46).abs() / 12_308.46 < 0.001);
+
+        // A closing click a metre from the first point snaps onto it
+        measurement.points.push(point(0.000_01, 0.0));
+        close_polygon(&mut measurement).unwrap();
+        assert!(measurement.closed);
+        assert_eq!(measurement.points.len(), 5);
+        assert!((measurement.area.unwrap() - open_area).abs() < 1e-6);
+        assert!((measurement.total_distance - 444.8).abs() < 1.0);
+    }
+
+    fn feed(json: &str) -> Vec<Dump1090Aircraft> {
+        serde_json::from_str::<Dump1090Feed>(json).unwrap().aircraft
+    }
+
+    #[test]
+    fn aircraft_missing_from_the_feed_go_stale() {
+        let mut cache = HashMap::new();
+        let first = feed(r#"{"now": 1700000000.0, "aircraft": [
+            {"hex": "A1B2C3", "flight": "UAL123  ", "lat": 37.6, "lon": -122.4,
+             "altitude": 12000, "track": 90.0, "speed": 250.0, "type": "adsb_icao", "seen": 0.5},
+            {"hex": "abc999", "lat": 37.7, "lon": -122.3, "altitude": "ground"},
+            {"hex": "no-position", "altitude": 3000}
+        ]}"#);
+        let update = merge_aircraft(&mut cache, first, 1_000_000);
+        assert_eq!((update.added, update.updated, update.removed, update.total), (2, 0, 0, 2));
+        let united = &cache["a1b2c3"];
+        assert_eq!(united.callsign, "UAL123");
+        assert_eq!(united.last_seen_ms, 999_500);
+        assert_eq!(cache["abc999"].altitude, 0.0);
+
+        // Only the first aircraft is still reported a minute later
+        let second = feed(r#"{"aircraft": [{"hex": "a1b2c3", "lat": 37.65, "lon": -122.3, "altitude": 12500}]}"#);
+        let update = merge_aircraft(&mut cache, second, 1_000_000 + ADSB_STALE_MS + 1);
+        assert_eq!((update.added, update.updated, update.removed, update.total), (0, 1, 1, 1));
+        assert!(!cache.contains_key("abc999"));
+        assert_eq!(cache["a1b2c3"].position.lat, 37.65);
+    }
+}
@@ -2,11 +2,40 @@
 // NASA JPL Power of 10 compliant implementation
 // Safety-critical real-time communication with < 1ms emergency response
 
+use ::mavlink::ardupilotmega::{
+    MagCalStatus, MavAutopilot, MavCmd, MavFrame, MavMessage, MavModeFlag, MavParamType,
+    MavResult, MavState, MavSysStatusSensor, MavType, MotorTestOrder, MotorTestThrottleType,
+    EkfStatusFlags, MavSeverity, RallyFlags, SerialControlDev, SerialControlFlag, COMMAND_ACK_DATA,
+    COMMAND_INT_DATA, COMMAND_LONG_DATA, EKF_STATUS_REPORT_DATA,
+    HEARTBEAT_DATA, MAG_CAL_PROGRESS_DATA,
+    MAG_CAL_REPORT_DATA, MavMissionResult, MISSION_ACK_DATA, MISSION_COUNT_DATA, MISSION_ITEM_INT_DATA,
+    MISSION_REQUEST_INT_DATA, PARAM_REQUEST_LIST_DATA, PARAM_REQUEST_READ_DATA, PARAM_SET_DATA, PARAM_VALUE_DATA,
+    RALLY_POINT_DATA, SCALED_IMU_DATA, SERIAL_CONTROL_DATA, STATUSTEXT_DATA, SYS_STATUS_DATA, VIBRATION_DATA, ATTITUDE_DATA,
+    GLOBAL_POSITION_INT_DATA, BATTERY_STATUS_DATA, VFR_HUD_DATA, GPS_RAW_INT_DATA, GpsFixType,
+};
+use ::mavlink::MavHeader;
 use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::future::Future;
+use std::io::{BufWriter, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Duration, Instant};
-use std::collections::HashMap;
-use tauri::State;
+use std::collections::{HashMap, HashSet, VecDeque};
+use tauri::{Manager, State};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::geo::is_valid_coordinate;
+use crate::geofence::{geofence_ceiling, is_within_geofence, GeofenceState};
+use crate::map_features::{Coordinate, GpsData, MapFeaturesState};
+use crate::mavlink_relay::{RelayStats, RelayTarget, MAX_RELAY_TARGETS};
+use crate::mavlink_transport::{
+    is_serial_connection_string, is_tcp_connection_string, is_udp_connection_string, parse_serial_endpoint,
+    parse_tcp_endpoint, parse_udp_endpoint, LinkEvent, VehicleLink,
+};
+use crate::mission_io::{plan_commands, PlannedCommand};
+use crate::param_metadata::{self, ParamBitLabel, ParamValueLabel, ParameterDefinitions, ParameterMetadata};
+use crate::AppState;
 
 // ===== TYPE DEFINITIONS =====
 
@@ -20,6 +49,8 @@ pub struct VehicleInfo {
     pub capabilities: Vec<String>,
     pub armed: bool,
     pub flight_mode: String,
+    // Latest battery telemetry; filled in by get_vehicle_info
+    pub battery: Option<BatteryInfo>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +62,280 @@ pub struct Parameter {
     pub min_value: Option<f32>,
     pub max_value: Option<f32>,
     pub units: Option<String>,
+    // The fields below are filled in from the firmware's parameter metadata
+    pub increment: Option<f32>,
+    pub default_value: Option<f32>,
+    pub values: Vec<ParamValueLabel>,
+    pub bitmask: Vec<ParamBitLabel>,
+}
+
+// Sensor bitmasks from the vehicle's latest SYS_STATUS (MAV_SYS_STATUS_SENSOR bits)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreflightStatus {
+    pub sensors_present: u32,
+    pub sensors_enabled: u32,
+    pub sensors_health: u32,
+    pub timestamp_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensorCheck {
+    pub sensor_name: String,
+    pub present: bool,
+    pub enabled: bool,
+    pub healthy: bool,
+}
+
+// Payload of `preflight-check` events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreflightCheckReport {
+    pub status: PreflightStatus,
+    // Every sensor the vehicle reports as present
+    pub checks: Vec<SensorCheck>,
+    // Names of enabled sensors that are not healthy
+    pub failing_checks: Vec<String>,
+}
+
+// Vehicle's latest EKF_STATUS_REPORT; `flags` holds EKF_STATUS_FLAGS bits
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EkfStatus {
+    pub velocity_variance: f32,
+    pub pos_horiz_variance: f32,
+    pub pos_vert_variance: f32,
+    pub compass_variance: f32,
+    pub terrain_alt_variance: f32,
+    pub flags: u16,
+    pub timestamp_ms: u64,
+}
+
+// Payload of `ekf-warning` and `ekf-critical` events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EkfAlert {
+    pub status: EkfStatus,
+    pub reasons: Vec<String>,
+}
+
+// Vehicle's latest VIBRATION report; accelerations in m/s², clipping counters
+// are cumulative per IMU
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VibrationData {
+    pub accel_x: f32,
+    pub accel_y: f32,
+    pub accel_z: f32,
+    pub clipping_0: u32,
+    pub clipping_1: u32,
+    pub clipping_2: u32,
+    pub timestamp_ms: u64,
+}
+
+// One ESC's latest ESC_TELEMETRY report; motor IDs start at 1
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EscTelemetry {
+    pub motor_id: u8,
+    pub rpm: u32,
+    pub voltage_mv: u16,
+    pub current_ca: u16,
+    pub temperature_cdeg: u16,
+    pub totalcurrent_mah: u32,
+    pub timestamp_ms: u64,
+}
+
+// Vehicle's latest VFR_HUD
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VfrData {
+    pub airspeed_ms: f32,
+    pub groundspeed_ms: f32,
+    pub heading_deg: i16,
+    pub throttle_pct: u16,
+    pub alt_m: f32,
+    pub climb_ms: f32,
+    pub timestamp_ms: u64,
+}
+
+// Vehicle's latest GPS_RAW_INT; values the receiver does not report are None
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GpsStatus {
+    // "no_gps", "no_fix", "2d", "3d", "dgps", "rtk_float", "rtk_fixed", "static" or "ppp"
+    pub fix_type: String,
+    pub has_3d_fix: bool,
+    pub satellites: Option<u8>,
+    pub hdop: Option<f32>,
+    pub vdop: Option<f32>,
+    pub ground_course_deg: Option<f32>,
+    pub timestamp_ms: u64,
+}
+
+// Maximum event rate per telemetry stream, whatever rate the vehicle sends at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TelemetryRates {
+    pub attitude_hz: u64,
+    pub vfr_hz: u64,
+    pub position_hz: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum TelemetryStream {
+    Attitude,
+    Vfr,
+    Position,
+    GpsStatus,
+    Esc,
+}
+
+impl TelemetryRates {
+    fn rate_hz(&self, stream: TelemetryStream) -> u64 {
+        match stream {
+            TelemetryStream::Attitude => self.attitude_hz,
+            TelemetryStream::Vfr => self.vfr_hz,
+            TelemetryStream::Position => self.position_hz,
+            TelemetryStream::GpsStatus => GPS_STATUS_RATE_HZ,
+            TelemetryStream::Esc => ESC_TELEMETRY_RATE_HZ,
+        }
+    }
+}
+
+// Primary battery from SYS_STATUS and BATTERY_STATUS; fields the vehicle does
+// not report are None, and cell voltages need BATTERY_STATUS
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatteryInfo {
+    pub voltage_v: f32,
+    pub current_a: Option<f32>,
+    pub consumed_mah: Option<i32>,
+    pub remaining_pct: Option<i8>,
+    pub cell_voltages: Vec<f32>,
+    pub timestamp_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BatteryThresholds {
+    pub warn_pct: f32,
+    pub critical_pct: f32,
+    pub warn_cell_v: f32,
+}
+
+// Payload of `battery-warning` and `battery-critical` events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatteryAlert {
+    pub battery: BatteryInfo,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BatteryLevel {
+    Normal,
+    Warning,
+    Critical,
+}
+
+// Alert level reached so far and the `vehicle-battery` throttle
+#[derive(Debug)]
+struct BatteryMonitor {
+    level: BatteryLevel,
+    last_emit_at: Option<Instant>,
+}
+
+impl Default for BatteryMonitor {
+    fn default() -> Self {
+        Self { level: BatteryLevel::Normal, last_emit_at: None }
+    }
+}
+
+// Vehicle's latest ATTITUDE converted to degrees and degrees per second
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AttitudeData {
+    pub roll_deg: f32,
+    pub pitch_deg: f32,
+    pub yaw_deg: f32,
+    pub roll_rate_dps: f32,
+    pub pitch_rate_dps: f32,
+    pub yaw_rate_dps: f32,
+    pub timestamp_ms: u64,
+}
+
+// Payload of `vibration-warning` events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VibrationWarning {
+    pub vibration: VibrationData,
+    pub reasons: Vec<String>,
+}
+
+// Payload of `esc-warning` events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscWarning {
+    pub motor_id: u8,
+    pub telemetry: EscTelemetry,
+    pub reasons: Vec<String>,
+}
+
+// Payload of `mavlink-console-output` events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsoleOutput {
+    pub data: String,
+    pub timestamp_ms: u64,
+}
+
+// A parameter of a .param file against the live cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamFileEntry {
+    pub param_id: String,
+    pub file_value: f32,
+    // None for a parameter the vehicle does not have
+    pub current_value: Option<f32>,
+    pub changed: bool,
+    pub unknown: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamFileDiff {
+    pub entries: Vec<ParamFileEntry>,
+    pub changed_count: u32,
+    pub unknown_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamApplyFailure {
+    pub param_id: String,
+    pub error: String,
+}
+
+// Outcome of `apply_param_file`; a dry run lists what would be written
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamApplyReport {
+    pub dry_run: bool,
+    pub planned: Vec<ParamFileEntry>,
+    pub applied_count: u32,
+    pub failures: Vec<ParamApplyFailure>,
+}
+
+// Payload of `param-apply-progress` events, one per parameter written
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamApplyProgress {
+    pub completed: u32,
+    pub total: u32,
+    pub param_id: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterMatch {
+    pub parameter: Parameter,
+    // False when the default is unknown
+    pub modified: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterSearchResult {
+    pub matches: Vec<ParameterMatch>,
+    // Matches before `limit` was applied
+    pub total_count: u32,
+}
+
+// Payload of `parameter-sync-progress` and `param-download-progress` events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterSyncProgress {
+    pub received: u32,
+    pub total: u32,
+    pub percent: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,14 +348,384 @@ pub struct CalibrationResult {
     pub message: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationProgress {
+    pub step: u8,
+    pub completion_pct: f32,
+    pub fitness: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationStep {
+    pub sensor_type: String,
+    pub step: u8,
+    pub total_steps: u8,
+    pub instruction: String,
+    pub completed: bool,
+    pub result: Option<CalibrationResult>,
+}
+
+// Interactive multi-step calibration driven by the operator
+#[derive(Debug)]
+pub struct CalibrationSession {
+    pub sensor_type: String,
+    pub current_step: u8,
+    pub total_steps: u8,
+    pub step_instructions: Vec<String>,
+    // Signalled by `confirm_calibration_step` once the vehicle is in position
+    confirm_tx: Option<oneshot::Sender<()>>,
+    // Resolves the `calibrate_accelerometer` call that started the session
+    completion_tx: Option<oneshot::Sender<CalibrationResult>>,
+    // Measured gravity vector (m/s²) for each completed orientation
+    samples: Vec<[f32; 3]>,
+}
+
+impl CalibrationSession {
+    // Record the measurement for the current step; the last step yields the result
+    fn record_sample(&mut self, sample: [f32; 3]) -> Option<CalibrationResult> {
+        self.samples.push(sample);
+        (self.current_step == self.total_steps).then(|| compute_accelerometer_result(&self.samples))
+    }
+}
+
+// One SCALED_IMU accelerometer reading in m/s², body frame
+#[derive(Debug, Clone, Copy)]
+struct ImuSample {
+    accel: [f32; 3],
+    timestamp_ms: u64,
+}
+
+// Onboard compass calibration, collecting the vehicle's MAG_CAL_REPORTs
+#[derive(Debug, Default)]
+pub struct MagCalSession {
+    // Compasses being calibrated, as the vehicle reports them
+    cal_mask: u8,
+    reports: Vec<MAG_CAL_REPORT_DATA>,
+    // Resolves the `calibrate_magnetometer` call that started the session
+    completion_tx: Option<oneshot::Sender<CalibrationResult>>,
+}
+
+impl MagCalSession {
+    fn record_progress(&mut self, progress: &MAG_CAL_PROGRESS_DATA) {
+        self.cal_mask |= progress.cal_mask;
+    }
+
+    // The result, once every compass being calibrated has reported
+    fn record_report(&mut self, report: &MAG_CAL_REPORT_DATA) -> Option<CalibrationResult> {
+        self.cal_mask |= report.cal_mask;
+        self.reports.retain(|other| other.compass_id != report.compass_id);
+        self.reports.push(report.clone());
+        let reported = self.reports.iter()
+            .fold(0u8, |mask, report| mask | 1u8.checked_shl(u32::from(report.compass_id)).unwrap_or(0));
+        if reported & self.cal_mask != self.cal_mask {
+            return None;
+        }
+        Some(mag_cal_result(&self.reports))
+    }
+}
+
+// Mission upload in progress: the vehicle requests each item by sequence
+// number, then reports the outcome in a MISSION_ACK
+#[derive(Debug)]
+pub struct MissionUploadSession {
+    target_system: u8,
+    target_component: u8,
+    // MISSION_ITEM_INT for each sequence number, home first
+    items: Vec<MavMessage>,
+    requested: Vec<bool>,
+    // Wakes the `upload_mission_to_vehicle` call waiting on the vehicle
+    event_tx: mpsc::UnboundedSender<MissionUploadEvent>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MissionUploadEvent {
+    Requested,
+    // `complete` is whether every item had been requested before the ACK
+    Acknowledged { result: MavMissionResult, complete: bool },
+}
+
+impl MissionUploadSession {
+    fn new(target: (u8, u8), items: Vec<MavMessage>, event_tx: mpsc::UnboundedSender<MissionUploadEvent>) -> Self {
+        Self {
+            target_system: target.0,
+            target_component: target.1,
+            requested: vec![false; items.len()],
+            items,
+            event_tx,
+        }
+    }
+
+    fn is_from_target(&self, header: &MavHeader) -> bool {
+        header.system_id == self.target_system && header.component_id == self.target_component
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConnectionStatus {
     pub connected: bool,
     pub connection_string: Option<String>,
     pub last_heartbeat: Option<u64>,
+    // Counters are kept in atomics on `MavlinkState` and filled in on snapshot
     pub messages_received: u64,
     pub messages_sent: u64,
+    // 0.6 * jitter score + 0.4 * (1 - packet loss)
     pub link_quality: f32,
+    // Standard deviation of recent heartbeat inter-arrival times
+    pub jitter_ms: f32,
+    // Messages missing from the received sequence numbers since connecting
+    pub packet_loss_ratio: f32,
+}
+
+// Where the vehicle is along its uploaded mission, from MISSION_CURRENT,
+// GLOBAL_POSITION_INT and VFR_HUD
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VehicleProgress {
+    pub current_seq: Option<u16>,
+    pub position: Option<Coordinate>,
+    // Position's altitude above mean sea level rather than above home
+    pub altitude_amsl_m: Option<f64>,
+    pub groundspeed_ms: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlogInfo {
+    pub recording: bool,
+    pub path: Option<String>,
+    pub bytes_written: u64,
+    pub start_time_ms: u64,
+}
+
+// Received message captured for the inspector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MavlogEntry {
+    pub msg_id: u32,
+    pub msg_name: String,
+    pub payload_json: String,
+    pub timestamp_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MessageStats {
+    pub count: u64,
+    pub last_timestamp_ms: u64,
+    pub average_interval_ms: f64,
+}
+
+// Vehicle STATUSTEXT explaining a refused arm or disarm, prefix removed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArmFailure {
+    pub severity: String,
+    pub message: String,
+    pub timestamp_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArmDisarmResult {
+    pub accepted: bool,
+    // How the vehicle answered when it refused
+    pub error: Option<String>,
+    pub failures: Vec<ArmFailure>,
+}
+
+// One motor of a test sequence; status is "passed", "failed" or "aborted".
+// RPM and current are the peaks its ESC reported during the test.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MotorTestResult {
+    pub motor_id: u8,
+    pub success: bool,
+    pub status: String,
+    pub error: Option<String>,
+    pub measured_rpm: Option<f32>,
+    pub current_draw_a: Option<f32>,
+}
+
+impl MotorTestResult {
+    fn aborted(motor_id: u8, reason: String) -> Self {
+        Self {
+            motor_id,
+            success: false,
+            status: "aborted".to_string(),
+            error: Some(reason),
+            measured_rpm: None,
+            current_draw_a: None,
+        }
+    }
+}
+
+// Vehicle STATUSTEXT, reassembled when it was sent in chunks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusText {
+    pub system_id: u8,
+    pub component_id: u8,
+    // "emergency", "alert", "critical", "error", "warning", "notice", "info" or "debug"
+    pub severity: String,
+    pub text: String,
+    pub timestamp_ms: u64,
+}
+
+// Chunks received so far of a multi-part STATUSTEXT
+#[derive(Debug)]
+struct PendingStatusText {
+    severity: MavSeverity,
+    text: Vec<u8>,
+    next_chunk_seq: u8,
+    started_at: Instant,
+}
+
+// (system ID, component ID, text ID) of a multi-part STATUSTEXT
+type StatusTextKey = (u8, u8, u16);
+
+// Metadata of the active telemetry log recording
+#[derive(Debug, Clone)]
+struct TlogRecording {
+    path: String,
+    start_time_ms: u64,
+}
+
+// Heartbeat arrival times and sequence-number accounting behind `link_quality`
+#[derive(Debug, Default)]
+struct LinkQualityTracker {
+    heartbeat_arrivals: VecDeque<Instant>,
+    last_sequence: Option<u8>,
+    expected_messages: u64,
+    lost_messages: u64,
+    // Quality last sent in a `link-quality` event
+    last_emitted_quality: Option<f32>,
+}
+
+impl LinkQualityTracker {
+    fn record_heartbeat(&mut self, at: Instant) {
+        if self.heartbeat_arrivals.len() >= HEARTBEAT_JITTER_WINDOW {
+            self.heartbeat_arrivals.pop_front();
+        }
+        self.heartbeat_arrivals.push_back(at);
+    }
+
+    // MAVLink sequence numbers count every message a system sends, so gaps are
+    // measured across all received messages rather than heartbeats alone
+    fn record_sequence(&mut self, sequence: u8) {
+        if let Some(last) = self.last_sequence {
+            let step = sequence.wrapping_sub(last);
+            if step > 0 {
+                self.expected_messages += u64::from(step);
+                self.lost_messages += u64::from(step - 1);
+            }
+        }
+        self.last_sequence = Some(sequence);
+    }
+
+    fn jitter_ms(&self) -> f32 {
+        let intervals: Vec<f64> = self.heartbeat_arrivals.iter()
+            .zip(self.heartbeat_arrivals.iter().skip(1))
+            .map(|(prev, next)| next.duration_since(*prev).as_secs_f64() * 1000.0)
+            .collect();
+        if intervals.len() < 2 {
+            return 0.0;
+        }
+        let mean = intervals.iter().sum::<f64>() / intervals.len() as f64;
+        let variance = intervals.iter().map(|i| (i - mean).powi(2)).sum::<f64>() / intervals.len() as f64;
+        variance.sqrt() as f32
+    }
+
+    fn packet_loss_ratio(&self) -> f32 {
+        if self.expected_messages == 0 {
+            return 0.0;
+        }
+        (self.lost_messages as f64 / self.expected_messages as f64) as f32
+    }
+
+    fn quality(&self) -> f32 {
+        let jitter_score = (1.0 - self.jitter_ms() / MAX_HEARTBEAT_JITTER_MS).clamp(0.0, 1.0);
+        0.6 * jitter_score + 0.4 * (1.0 - self.packet_loss_ratio())
+    }
+}
+
+// Last `connection-status` event, used to suppress insignificant updates
+#[derive(Debug, Default)]
+struct StatusPublisher {
+    last_emitted: Option<ConnectionStatus>,
+    last_emit_at: Option<Instant>,
+}
+
+// Outcome of a COMMAND_LONG as reported by the vehicle's COMMAND_ACK
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MavCmdResult {
+    Accepted,
+    TemporarilyRejected,
+    Denied,
+    Unsupported,
+    Failed,
+    InProgress,
+    Cancelled,
+}
+
+impl From<MavResult> for MavCmdResult {
+    fn from(result: MavResult) -> Self {
+        match result {
+            MavResult::MAV_RESULT_ACCEPTED => MavCmdResult::Accepted,
+            MavResult::MAV_RESULT_TEMPORARILY_REJECTED => MavCmdResult::TemporarilyRejected,
+            MavResult::MAV_RESULT_DENIED => MavCmdResult::Denied,
+            MavResult::MAV_RESULT_UNSUPPORTED => MavCmdResult::Unsupported,
+            MavResult::MAV_RESULT_FAILED => MavCmdResult::Failed,
+            MavResult::MAV_RESULT_IN_PROGRESS => MavCmdResult::InProgress,
+            MavResult::MAV_RESULT_CANCELLED => MavCmdResult::Cancelled,
+        }
+    }
+}
+
+impl MavCmdResult {
+    // Map a final result to Ok, or to an error naming the command
+    fn into_result(self, command: MavCmd) -> Result<(), String> {
+        let reason = match self {
+            MavCmdResult::Accepted => return Ok(()),
+            MavCmdResult::TemporarilyRejected => "temporarily rejected by the vehicle, retry later",
+            MavCmdResult::Denied => "denied by the vehicle",
+            MavCmdResult::Unsupported => "not supported by the vehicle",
+            MavCmdResult::Failed => "failed on the vehicle",
+            MavCmdResult::InProgress => "still in progress on the vehicle",
+            MavCmdResult::Cancelled => "cancelled by the vehicle",
+        };
+        Err(format!("{command:?} {reason}"))
+    }
+}
+
+// A PARAM_REQUEST_LIST transfer; the vehicle announces the total in every PARAM_VALUE
+#[derive(Debug)]
+struct ParameterSync {
+    total: Option<u16>,
+    received: HashSet<u16>,
+    done_tx: Option<oneshot::Sender<u32>>,
+    // Event reporting each received parameter
+    progress_event: &'static str,
+}
+
+// A COMMAND_LONG awaiting its COMMAND_ACK; MAVLink acks carry only the
+// command ID, so at most one command of each ID may be in flight
+#[derive(Debug)]
+struct PendingCommand {
+    command_id: u16,
+    target_system: u8,
+    target_component: u8,
+    sent_at: Instant,
+    result_tx: oneshot::Sender<MavCmdResult>,
+}
+
+impl PendingCommand {
+    // Acks must come from the addressed vehicle; component 0 is a broadcast
+    fn matches(&self, header: &MavHeader, command_id: u16) -> bool {
+        self.command_id == command_id
+            && self.target_system == header.system_id
+            && (self.target_component == 0 || self.target_component == header.component_id)
+    }
+}
+
+// A transport that has delivered its first vehicle heartbeat
+struct OpenedLink {
+    link: VehicleLink,
+    events: mpsc::UnboundedReceiver<LinkEvent>,
+    header: MavHeader,
+    heartbeat: HEARTBEAT_DATA,
+    raw: Vec<u8>,
 }
 
 #[derive(Debug, Clone)]
@@ -63,11 +738,77 @@ pub struct EmergencyStopGuard {
 
 pub struct MavlinkState {
     connection_status: Arc<RwLock<ConnectionStatus>>,
+    messages_received: Arc<AtomicU64>,
+    messages_sent: Arc<AtomicU64>,
+    // Bumped on every connect/disconnect so stale link tasks can exit
+    connection_epoch: Arc<AtomicU64>,
+    // Open transport; None while disconnected or on a simulated link
+    vehicle_link: Arc<Mutex<Option<VehicleLink>>>,
+    connect_timeout_ms: Arc<AtomicU64>,
+    // Set while the vehicle's heartbeats are overdue; cleared when they resume
+    heartbeat_lost: Arc<AtomicBool>,
+    // Stop flag of the running GCS heartbeat task
+    gcs_heartbeat_stop: Arc<Mutex<Option<Arc<AtomicBool>>>>,
+    link_tracker: Arc<Mutex<LinkQualityTracker>>,
+    status_publisher: Arc<Mutex<StatusPublisher>>,
+    // Telemetry log of received frames, in QGroundControl .tlog format
+    tlog_writer: Arc<Mutex<Option<BufWriter<File>>>>,
+    tlog_recording: Arc<Mutex<Option<TlogRecording>>>,
+    tlog_bytes_written: Arc<AtomicU64>,
+    // Most recent received messages and per-ID rates for the message inspector
+    message_log: Arc<Mutex<VecDeque<MavlogEntry>>>,
+    message_stats: Arc<Mutex<HashMap<u32, MessageStats>>>,
+    // Last STATUSTEXT_LOG_CAPACITY vehicle texts, oldest first
+    statustext_log: Arc<Mutex<VecDeque<StatusText>>>,
+    // Incomplete multi-part texts
+    statustext_chunks: Arc<Mutex<HashMap<StatusTextKey, PendingStatusText>>>,
     vehicle_info: Arc<RwLock<Option<VehicleInfo>>>,
+    vehicle_progress: Arc<RwLock<VehicleProgress>>,
+    preflight_status: Arc<RwLock<Option<PreflightStatus>>>,
+    ekf_status: Arc<RwLock<Option<EkfStatus>>>,
+    // Variances above this raise `ekf-warning`
+    ekf_variance_threshold: Arc<RwLock<f32>>,
+    // Shell output is forwarded only while the console is started
+    console_active: Arc<AtomicBool>,
+    // Received shell bytes not yet emitted, e.g. a split UTF-8 character
+    console_buffer: Arc<Mutex<Vec<u8>>>,
+    // Other ground stations receiving a copy of every vehicle frame
+    relay_targets: Arc<Mutex<Vec<RelayTarget>>>,
+    // Last VIBRATION_HISTORY_CAPACITY reports, oldest first
+    vibration_history: Arc<Mutex<VecDeque<VibrationData>>>,
+    // Latest report per ESC, by motor ID; ESCs that never reported are absent
+    esc_telemetry: Arc<RwLock<Vec<EscTelemetry>>>,
+    attitude: Arc<RwLock<Option<AttitudeData>>>,
+    vfr: Arc<RwLock<Option<VfrData>>>,
+    gps_status: Arc<RwLock<Option<GpsStatus>>>,
+    telemetry_rates: Arc<RwLock<TelemetryRates>>,
+    // When each telemetry stream last emitted, for the rate limits
+    telemetry_last_emit: Arc<Mutex<HashMap<TelemetryStream, Instant>>>,
+    battery: Arc<RwLock<Option<BatteryInfo>>>,
+    battery_thresholds: Arc<RwLock<BatteryThresholds>>,
+    battery_monitor: Arc<Mutex<BatteryMonitor>>,
+    // Sent commands awaiting COMMAND_ACK, keyed by command ID
+    pending_commands: Arc<Mutex<HashMap<u16, PendingCommand>>>,
+    // PARAM_SETs awaiting the echoed PARAM_VALUE, keyed by parameter ID
+    pending_param_sets: Arc<Mutex<HashMap<String, oneshot::Sender<f32>>>>,
+    command_timeout_ms: Arc<AtomicU64>,
     parameters: Arc<RwLock<HashMap<String, Parameter>>>,
+    // Set only once a full PARAM_REQUEST_LIST transfer has completed
+    parameters_synced: Arc<AtomicBool>,
+    parameter_sync: Arc<Mutex<Option<ParameterSync>>>,
+    // Definitions for the connected vehicle's firmware, loaded on demand
+    parameter_metadata: Arc<RwLock<ParameterDefinitions>>,
     emergency_stop: EmergencyStopGuard,
     motor_test_active: Arc<RwLock<bool>>,
     calibration_active: Arc<RwLock<bool>>,
+    calibration_session: Arc<Mutex<Option<CalibrationSession>>>,
+    mag_cal_session: Arc<Mutex<Option<MagCalSession>>>,
+    mission_upload: Arc<Mutex<Option<MissionUploadSession>>>,
+    // Last IMU_SAMPLE_CAPACITY accelerometer readings, for calibration
+    imu_samples: Arc<Mutex<VecDeque<ImuSample>>>,
+    // Armed flag and custom_mode the simulated vehicle reports in its heartbeat
+    mock_armed: Arc<AtomicBool>,
+    mock_custom_mode: Arc<AtomicU64>,
 }
 
 impl MavlinkState {
@@ -80,23 +821,243 @@ impl MavlinkState {
                 messages_received: 0,
                 messages_sent: 0,
                 link_quality: 0.0,
+                jitter_ms: 0.0,
+                packet_loss_ratio: 0.0,
             })),
+            messages_received: Arc::new(AtomicU64::new(0)),
+            messages_sent: Arc::new(AtomicU64::new(0)),
+            connection_epoch: Arc::new(AtomicU64::new(0)),
+            vehicle_link: Arc::new(Mutex::new(None)),
+            connect_timeout_ms: Arc::new(AtomicU64::new(DEFAULT_CONNECT_TIMEOUT_MS)),
+            heartbeat_lost: Arc::new(AtomicBool::new(false)),
+            gcs_heartbeat_stop: Arc::new(Mutex::new(None)),
+            link_tracker: Arc::new(Mutex::new(LinkQualityTracker::default())),
+            status_publisher: Arc::new(Mutex::new(StatusPublisher::default())),
+            tlog_writer: Arc::new(Mutex::new(None)),
+            tlog_recording: Arc::new(Mutex::new(None)),
+            tlog_bytes_written: Arc::new(AtomicU64::new(0)),
+            message_log: Arc::new(Mutex::new(VecDeque::with_capacity(MESSAGE_LOG_CAPACITY))),
+            message_stats: Arc::new(Mutex::new(HashMap::new())),
+            statustext_log: Arc::new(Mutex::new(VecDeque::with_capacity(STATUSTEXT_LOG_CAPACITY))),
+            statustext_chunks: Arc::new(Mutex::new(HashMap::new())),
             vehicle_info: Arc::new(RwLock::new(None)),
+            vehicle_progress: Arc::new(RwLock::new(VehicleProgress::default())),
+            preflight_status: Arc::new(RwLock::new(None)),
+            ekf_status: Arc::new(RwLock::new(None)),
+            ekf_variance_threshold: Arc::new(RwLock::new(DEFAULT_EKF_VARIANCE_THRESHOLD)),
+            console_active: Arc::new(AtomicBool::new(false)),
+            console_buffer: Arc::new(Mutex::new(Vec::new())),
+            relay_targets: Arc::new(Mutex::new(Vec::new())),
+            vibration_history: Arc::new(Mutex::new(VecDeque::with_capacity(VIBRATION_HISTORY_CAPACITY))),
+            esc_telemetry: Arc::new(RwLock::new(Vec::new())),
+            attitude: Arc::new(RwLock::new(None)),
+            vfr: Arc::new(RwLock::new(None)),
+            gps_status: Arc::new(RwLock::new(None)),
+            telemetry_rates: Arc::new(RwLock::new(DEFAULT_TELEMETRY_RATES)),
+            telemetry_last_emit: Arc::new(Mutex::new(HashMap::new())),
+            battery: Arc::new(RwLock::new(None)),
+            battery_thresholds: Arc::new(RwLock::new(DEFAULT_BATTERY_THRESHOLDS)),
+            battery_monitor: Arc::new(Mutex::new(BatteryMonitor::default())),
+            pending_commands: Arc::new(Mutex::new(HashMap::new())),
+            pending_param_sets: Arc::new(Mutex::new(HashMap::new())),
+            command_timeout_ms: Arc::new(AtomicU64::new(DEFAULT_COMMAND_TIMEOUT_MS)),
             parameters: Arc::new(RwLock::new(HashMap::new())),
+            parameters_synced: Arc::new(AtomicBool::new(false)),
+            parameter_sync: Arc::new(Mutex::new(None)),
+            parameter_metadata: Arc::new(RwLock::new(HashMap::new())),
             emergency_stop: EmergencyStopGuard {
                 active: Arc::new(RwLock::new(false)),
                 last_activation: Arc::new(Mutex::new(None)),
             },
             motor_test_active: Arc::new(RwLock::new(false)),
             calibration_active: Arc::new(RwLock::new(false)),
+            mock_armed: Arc::new(AtomicBool::new(false)),
+            mock_custom_mode: Arc::new(AtomicU64::new(0)),
+            calibration_session: Arc::new(Mutex::new(None)),
+            mag_cal_session: Arc::new(Mutex::new(None)),
+            mission_upload: Arc::new(Mutex::new(None)),
+            imu_samples: Arc::new(Mutex::new(VecDeque::with_capacity(IMU_SAMPLE_CAPACITY))),
         }
     }
 }
 
+// Operator confirmation window for each calibration step
+const CALIBRATION_STEP_TIMEOUT_SECS: u64 = 30;
+const STANDARD_GRAVITY: f32 = 9.80665;
+// Each orientation is measured by averaging SCALED_IMU over this window
+const ACCEL_SAMPLE_WINDOW_MS: u64 = 1000;
+const MIN_ACCEL_SAMPLES: usize = 5;
+const MOCK_IMU_INTERVAL_MS: u64 = 20;
+// A few seconds of SCALED_IMU at its usual rates
+const IMU_SAMPLE_CAPACITY: usize = 256;
+// Time allowed to rotate the vehicle until every compass has reported
+const MAG_CAL_TIMEOUT_SECS: u64 = 120;
+
+// Link monitoring
+const HEARTBEAT_INTERVAL_MS: u64 = 1000;
+const MAX_MISSED_HEARTBEATS: f32 = 3.0;
+const STATUS_EMIT_MIN_INTERVAL_MS: u64 = 500;
+const LINK_QUALITY_EMIT_DELTA: f32 = 0.01;
+// Heartbeats kept for the jitter estimate; jitter at or above the maximum scores 0
+const HEARTBEAT_JITTER_WINDOW: usize = 10;
+const MAX_HEARTBEAT_JITTER_MS: f32 = 500.0;
+// Change in quality that triggers a `link-quality` event
+const LINK_QUALITY_EVENT_DELTA: f32 = 0.05;
+const MESSAGE_COUNT_EMIT_DELTA: u64 = 100;
+// Our identity on the link (MAV_COMP_ID_MISSIONPLANNER)
+const GCS_SYSTEM_ID: u8 = 255;
+const GCS_COMPONENT_ID: u8 = 190;
+// Consecutive GCS heartbeat send failures before the link is declared lost
+const MAX_HEARTBEAT_SEND_FAILURES: u32 = 3;
+// A vehicle must send a heartbeat this soon after its transport opens
+const FIRST_HEARTBEAT_TIMEOUT_SECS: u64 = 5;
+// Without a heartbeat for this long the connection is dead
+const HEARTBEAT_TIMEOUT_MS: u64 = 5000;
+// How often the monitor rescores the link and checks heartbeat staleness
+const HEARTBEAT_CHECK_INTERVAL_MS: u64 = 500;
+// TCP connect wait; configurable within the bounds
+const DEFAULT_CONNECT_TIMEOUT_MS: u64 = 5000;
+const MIN_CONNECT_TIMEOUT_MS: u64 = 100;
+const MAX_CONNECT_TIMEOUT_MS: u64 = 60_000;
+// Connects to the built-in simulated vehicle instead of a transport
+const SIMULATED_CONNECTION_PREFIX: &str = "sim://";
+
+const MESSAGE_LOG_CAPACITY: usize = 1000;
+const STATUSTEXT_LOG_CAPACITY: usize = 200;
+const STATUSTEXT_CHUNK_LEN: usize = 50;
+// A multi-part text whose final chunk has not arrived by then is delivered as received
+const STATUSTEXT_CHUNK_TIMEOUT_MS: u64 = 2000;
+// NASA JPL Rule 2: bound the texts being reassembled at once
+const MAX_PENDING_STATUSTEXTS: usize = 16;
+// After a refused arm, STATUSTEXTs arriving this long after the ACK still explain it
+const ARM_FAILURE_COLLECT_MS: u64 = 2000;
+// Prefixes ArduPilot and PX4 put on arming check failures
+const ARM_FAILURE_PREFIXES: [&str; 4] = ["PreArm:", "Arm:", "Disarm:", "Preflight Fail:"];
+// MAV_CMD_COMPONENT_ARM_DISARM param2 that overrides the vehicle's checks
+const FORCE_ARM_DISARM_MAGIC: f32 = 21196.0;
+// A mode change is confirmed once a HEARTBEAT reports it; allow a few heartbeats
+const FLIGHT_MODE_CONFIRM_TIMEOUT_MS: u64 = 3000;
+const FLIGHT_MODE_POLL_INTERVAL_MS: u64 = 100;
+// Go-to altitude is relative to home
+const MAX_GOTO_ALTITUDE_M: f64 = 5000.0;
+const MAX_GOTO_GROUND_SPEED_MPS: f64 = 50.0;
+// A takeoff is complete within this of the target altitude
+const TAKEOFF_ALTITUDE_TOLERANCE_M: f64 = 1.0;
+// The climb times out after TAKEOFF_TIMEOUT_BASE_S plus the time to climb at
+// TAKEOFF_MIN_CLIMB_RATE_MPS
+const TAKEOFF_TIMEOUT_BASE_S: f64 = 15.0;
+const TAKEOFF_MIN_CLIMB_RATE_MPS: f64 = 0.5;
+const TAKEOFF_PROGRESS_INTERVAL_MS: u64 = 200;
+// How often a running motor test checks the emergency stop and samples its ESC
+const MOTOR_TEST_POLL_INTERVAL_MS: u64 = 50;
+
+// Display names of the MAV_SYS_STATUS_SENSOR bits, in bit order
+const SYS_STATUS_SENSORS: [(MavSysStatusSensor, &str); 31] = [
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_3D_GYRO, "Gyroscope"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_3D_ACCEL, "Accelerometer"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_3D_MAG, "Magnetometer"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_ABSOLUTE_PRESSURE, "Barometer"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_DIFFERENTIAL_PRESSURE, "Airspeed sensor"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_GPS, "GPS"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_OPTICAL_FLOW, "Optical flow"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_VISION_POSITION, "Vision position"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_LASER_POSITION, "Laser position"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_EXTERNAL_GROUND_TRUTH, "External ground truth"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_ANGULAR_RATE_CONTROL, "Angular rate control"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_ATTITUDE_STABILIZATION, "Attitude stabilization"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_YAW_POSITION, "Yaw position"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_Z_ALTITUDE_CONTROL, "Altitude control"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_XY_POSITION_CONTROL, "Position control"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_MOTOR_OUTPUTS, "Motor outputs"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_RC_RECEIVER, "RC receiver"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_3D_GYRO2, "Gyroscope 2"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_3D_ACCEL2, "Accelerometer 2"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_3D_MAG2, "Magnetometer 2"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_GEOFENCE, "Geofence"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_AHRS, "AHRS"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_TERRAIN, "Terrain"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_REVERSE_MOTOR, "Reverse motor"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_LOGGING, "Logging"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_BATTERY, "Battery"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_PROXIMITY, "Proximity"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_SATCOM, "Satellite communication"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_PREARM_CHECK, "Pre-arm check"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_OBSTACLE_AVOIDANCE, "Obstacle avoidance"),
+    (MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_PROPULSION, "Propulsion"),
+];
+// Upper bound on a full parameter download
+const PARAMETER_SYNC_TIMEOUT_SECS: u64 = 30;
+// PARAM_VALUE index of a reply to PARAM_SET / PARAM_REQUEST_READ by name
+const UNINDEXED_PARAM: u16 = u16::MAX;
+// A download round ends once no parameter has arrived for this long
+const PARAMETER_DOWNLOAD_IDLE_MS: u64 = 2000;
+const MAX_PARAMETER_RETRY_ROUNDS: u32 = 3;
+// More missing than this and the whole list is requested again instead
+const MAX_PARAMETER_READS_PER_ROUND: usize = 100;
+// Wait for the echoed PARAM_VALUE before resending a PARAM_SET
+const PARAM_SET_TIMEOUT_MS: u64 = 1500;
+const PARAM_SET_RETRIES: u32 = 3;
+// NASA JPL Rule 2: bound the parameters read from a .param file
+const MAX_PARAM_FILE_ENTRIES: usize = 5000;
+const MAX_PARAM_ID_LENGTH: usize = 16;
+const MAX_PARAMETER_SEARCH_RESULTS: usize = 2000;
+
+// EKF variances are normalized; above 1.0 the filter rejects its innovations
+const DEFAULT_EKF_VARIANCE_THRESHOLD: f32 = 1.0;
+// ArduPilot's recommended vibration limit; above 60 m/s² position estimates fail
+const VIBRATION_WARNING_MS2: f32 = 30.0;
+// 60 s of VIBRATION at its usual 10 Hz rate
+const VIBRATION_HISTORY_CAPACITY: usize = 600;
+// Vehicles stream ATTITUDE at up to 50 Hz; the UI needs far less
+const DEFAULT_TELEMETRY_RATES: TelemetryRates = TelemetryRates {
+    attitude_hz: 10,
+    vfr_hz: 4,
+    position_hz: 5,
+};
+const MAX_TELEMETRY_RATE_HZ: u64 = 50;
+const GPS_STATUS_RATE_HZ: u64 = 1;
+const ESC_TELEMETRY_RATE_HZ: u64 = 5;
+// Continuous limits of a typical multirotor ESC
+const ESC_TEMPERATURE_WARNING_CDEG: u16 = 8000;
+const ESC_CURRENT_WARNING_CA: u16 = 6000;
+// Typical GPS range error; horizontal accuracy is roughly HDOP times this
+const GPS_UERE_M: f64 = 5.0;
+const BATTERY_EMIT_INTERVAL_MS: u64 = 1000;
+const DEFAULT_BATTERY_THRESHOLDS: BatteryThresholds = BatteryThresholds {
+    warn_pct: 30.0,
+    critical_pct: 15.0,
+    warn_cell_v: 3.5,
+};
+// A level is left only once the reading recovers past its threshold by this
+// much, so readings hovering at a threshold alert once
+const BATTERY_HYSTERESIS_PCT: f32 = 5.0;
+const BATTERY_HYSTERESIS_CELL_V: f32 = 0.1;
+const MIN_WARN_CELL_V: f32 = 2.5;
+const MAX_WARN_CELL_V: f32 = 4.5;
+// NASA JPL Rule 2: bound the unemitted console output and one write
+const MAX_CONSOLE_BUFFER_BYTES: usize = 4096;
+const MAX_CONSOLE_WRITE_BYTES: usize = 4096;
+const SERIAL_CONTROL_DATA_BYTES: usize = 70;
+
+// COMMAND_ACK wait; configurable within the bounds
+const DEFAULT_COMMAND_TIMEOUT_MS: u64 = 5000;
+const MIN_COMMAND_TIMEOUT_MS: u64 = 100;
+const MAX_COMMAND_TIMEOUT_MS: u64 = 60_000;
+const EXECUTING_LOCK_REASON: &str = "Vehicle is armed and executing the uploaded mission";
+// Longest wait for the vehicle's next mission request or ACK
+const MISSION_UPLOAD_IDLE_MS: u64 = 2500;
+// MISSION_COUNT is resent while the vehicle has not requested anything
+const MISSION_COUNT_RETRIES: u32 = 3;
+// Time for the flight controller to restart before reconnecting
+const REBOOT_RECONNECT_DELAY_SECS: u64 = 10;
+const REBOOT_RECONNECT_ATTEMPTS: u32 = 5;
+const REBOOT_RECONNECT_INTERVAL_SECS: u64 = 5;
+
 // ===== CONNECTION COMMANDS =====
 
 #[tauri::command]
 pub async fn connect_drone(
+    app_handle: tauri::AppHandle,
     connection_string: String,
     state: State<'_, MavlinkState>,
 ) -> Result<bool, String> {
@@ -114,8 +1075,16 @@ pub async fn connect_drone(
         }
     }
 
-    // TODO: Implement actual MAVLink connection using rust-mavlink
-    // For now, mock the connection
+    // Real vehicles count as connected only once they send a heartbeat
+    let opened = if connection_string.starts_with(SIMULATED_CONNECTION_PREFIX) {
+        None
+    } else {
+        let connect_timeout = Duration::from_millis(state.connect_timeout_ms.load(Ordering::Relaxed));
+        Some(open_vehicle_link(&connection_string, connect_timeout).await?)
+    };
+    let vehicle_info = opened.as_ref()
+        .map_or_else(mock_vehicle_info, |opened| vehicle_info_from_heartbeat(&opened.header, &opened.heartbeat));
+
     {
         let mut status = state.connection_status.write()
             .map_err(|_| "Failed to update connection status")?;
@@ -123,31 +1092,42 @@ pub async fn connect_drone(
         status.connection_string = Some(connection_string);
         status.last_heartbeat = Some(get_timestamp());
         status.link_quality = 1.0;
+        status.jitter_ms = 0.0;
+        status.packet_loss_ratio = 0.0;
     }
+    state.messages_received.store(0, Ordering::Relaxed);
+    state.messages_sent.store(0, Ordering::Relaxed);
+    state.heartbeat_lost.store(false, Ordering::SeqCst);
+    *state.link_tracker.lock()
+        .map_err(|_| "Failed to reset link quality tracking")? = LinkQualityTracker::default();
 
-    // Mock vehicle info
-    {
-        let mut info = state.vehicle_info.write()
-            .map_err(|_| "Failed to update vehicle info")?;
-        *info = Some(VehicleInfo {
-            system_id: 1,
-            component_id: 1,
-            autopilot_type: "ArduPilot".to_string(),
-            vehicle_type: "Quadcopter".to_string(),
-            firmware_version: "4.5.0".to_string(),
-            capabilities: vec![
-                "MISSION".to_string(),
-                "PARAM".to_string(),
-                "FENCE".to_string(),
-                "RALLY".to_string(),
-            ],
-            armed: false,
-            flight_mode: "STABILIZE".to_string(),
-        });
-    }
+    *state.vehicle_info.write()
+        .map_err(|_| "Failed to update vehicle info")? = Some(vehicle_info);
 
-    // Load default parameters
+    // Load default parameters; they are stale until synced from the vehicle
     load_default_parameters(&state)?;
+    state.parameters_synced.store(false, Ordering::SeqCst);
+
+    let epoch = state.connection_epoch.fetch_add(1, Ordering::SeqCst) + 1;
+    match opened {
+        Some(opened) => {
+            *state.vehicle_link.lock().map_err(|_| "Failed to store vehicle link")? = Some(opened.link);
+            let heartbeat = MavMessage::HEARTBEAT(opened.heartbeat);
+            record_tlog_frame(&state, &opened.raw);
+            handle_incoming_message(&app_handle, &state, opened.header, &heartbeat);
+            publish_connection_status(&app_handle, &state);
+            start_gcs_heartbeat(&app_handle, &state)?;
+            tauri::async_runtime::spawn(run_vehicle_link(app_handle.clone(), epoch, opened.events));
+        }
+        None => {
+            state.mock_armed.store(false, Ordering::SeqCst);
+            state.mock_custom_mode.store(0, Ordering::SeqCst);
+            publish_connection_status(&app_handle, &state);
+            start_gcs_heartbeat(&app_handle, &state)?;
+            tauri::async_runtime::spawn(run_mock_link(app_handle.clone(), epoch));
+        }
+    }
+    tauri::async_runtime::spawn(run_heartbeat_monitor(app_handle, epoch));
 
     Ok(true)
 }
@@ -156,12 +1136,18 @@ pub async fn connect_drone(
 pub async fn disconnect_drone(
     state: State<'_, MavlinkState>,
 ) -> Result<(), String> {
+    ensure_no_active_operation(&state, "disconnect")?;
+    reset_connection(&state)
+}
+
+// Motor tests and calibrations must finish before the link goes away
+fn ensure_no_active_operation(state: &MavlinkState, action: &str) -> Result<(), String> {
     // Check if motor test is active
     {
         let motor_test = state.motor_test_active.read()
             .map_err(|_| "Failed to read motor test status")?;
         if *motor_test {
-            return Err("Cannot disconnect while motor test is active".to_string());
+            return Err(format!("Cannot {action} while motor test is active"));
         }
     }
 
@@ -170,17 +1156,33 @@ pub async fn disconnect_drone(
         let calibration = state.calibration_active.read()
             .map_err(|_| "Failed to read calibration status")?;
         if *calibration {
-            return Err("Cannot disconnect while calibration is active".to_string());
+            return Err(format!("Cannot {action} while calibration is active"));
         }
     }
 
-    // Disconnect
+    Ok(())
+}
+
+// Mark the connection down, close the link and clear all vehicle state
+fn reset_connection(state: &MavlinkState) -> Result<(), String> {
     {
         let mut status = state.connection_status.write()
             .map_err(|_| "Failed to update connection status")?;
         status.connected = false;
         status.connection_string = None;
         status.last_heartbeat = None;
+        status.link_quality = 0.0;
+        status.jitter_ms = 0.0;
+        status.packet_loss_ratio = 0.0;
+    }
+    // The link task publishes the disconnect on its next tick, respecting the rate limit
+    state.connection_epoch.fetch_add(1, Ordering::SeqCst);
+    stop_gcs_heartbeat(state)?;
+    close_vehicle_link(state)?;
+
+    // Auto-stop telemetry logging; not recording is fine here
+    if is_tlog_recording(state)? {
+        finish_tlog_recording(state)?;
     }
 
     // Clear vehicle info
@@ -189,14 +1191,78 @@ pub async fn disconnect_drone(
             .map_err(|_| "Failed to clear vehicle info")?;
         *info = None;
     }
+    *state.vehicle_progress.write()
+        .map_err(|_| "Failed to clear vehicle progress")? = VehicleProgress::default();
+    *state.preflight_status.write()
+        .map_err(|_| "Failed to clear preflight status")? = None;
+    *state.ekf_status.write()
+        .map_err(|_| "Failed to clear EKF status")? = None;
+    state.vibration_history.lock()
+        .map_err(|_| "Failed to clear vibration history")?
+        .clear();
+    *state.attitude.write()
+        .map_err(|_| "Failed to clear attitude")? = None;
+    *state.vfr.write()
+        .map_err(|_| "Failed to clear VFR telemetry")? = None;
+    *state.gps_status.write()
+        .map_err(|_| "Failed to clear GPS status")? = None;
+    state.esc_telemetry.write()
+        .map_err(|_| "Failed to clear ESC telemetry")?
+        .clear();
+    state.telemetry_last_emit.lock()
+        .map_err(|_| "Failed to clear telemetry rate limits")?
+        .clear();
+    *state.battery.write()
+        .map_err(|_| "Failed to clear battery")? = None;
+    *state.battery_monitor.lock()
+        .map_err(|_| "Failed to clear battery")? = BatteryMonitor::default();
+    state.statustext_chunks.lock()
+        .map_err(|_| "Failed to clear status text chunks")?
+        .clear();
+    state.console_active.store(false, Ordering::SeqCst);
+    state.console_buffer.lock()
+        .map_err(|_| "Failed to clear console buffer")?
+        .clear();
 
-    // Clear parameters
+    // Dropping the senders fails every command still awaiting an ack
+    state.pending_commands.lock()
+        .map_err(|_| "Failed to clear pending commands")?
+        .clear();
+    state.pending_param_sets.lock()
+        .map_err(|_| "Failed to clear pending parameter writes")?
+        .clear();
+
+    // Clear parameters; dropping the sync sender fails a download in progress
     {
         let mut params = state.parameters.write()
             .map_err(|_| "Failed to clear parameters")?;
         params.clear();
     }
+    state.parameters_synced.store(false, Ordering::SeqCst);
+    *state.parameter_sync.lock()
+        .map_err(|_| "Failed to clear parameter sync")? = None;
+
+    Ok(())
+}
 
+#[tauri::command]
+pub async fn get_connection_status(
+    state: State<'_, MavlinkState>,
+) -> Result<ConnectionStatus, String> {
+    connection_snapshot(&state)
+}
+
+#[tauri::command]
+pub async fn set_connect_timeout(
+    timeout_ms: u64,
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    if !(MIN_CONNECT_TIMEOUT_MS..=MAX_CONNECT_TIMEOUT_MS).contains(&timeout_ms) {
+        return Err(format!(
+            "Connect timeout must be between {MIN_CONNECT_TIMEOUT_MS} and {MAX_CONNECT_TIMEOUT_MS} ms"
+        ));
+    }
+    state.connect_timeout_ms.store(timeout_ms, Ordering::Relaxed);
     Ok(())
 }
 
@@ -207,43 +1273,290 @@ pub async fn get_vehicle_info(
     // Verify connection
     verify_connection(&state)?;
 
-    let info = state.vehicle_info.read()
-        .map_err(|_| "Failed to read vehicle info")?;
-    
-    info.clone()
-        .ok_or_else(|| "Vehicle info not available".to_string())
+    let mut info = state.vehicle_info.read()
+        .map_err(|_| "Failed to read vehicle info")?
+        .clone()
+        .ok_or_else(|| "Vehicle info not available".to_string())?;
+    info.battery = state.battery.read()
+        .map_err(|_| "Failed to read battery")?
+        .clone();
+    Ok(info)
 }
 
-// ===== PARAMETER COMMANDS =====
+// ===== TELEMETRY LOG COMMANDS =====
 
 #[tauri::command]
-pub async fn get_drone_parameters(
+pub async fn start_tlog_recording(
+    path: String,
     state: State<'_, MavlinkState>,
-) -> Result<Vec<Parameter>, String> {
-    // Verify connection
-    verify_connection(&state)?;
+) -> Result<(), String> {
+    if path.trim().is_empty() {
+        return Err("Telemetry log path cannot be empty".to_string());
+    }
 
-    let params = state.parameters.read()
-        .map_err(|_| "Failed to read parameters")?;
-    
-    Ok(params.values().cloned().collect())
+    let mut writer = state.tlog_writer.lock()
+        .map_err(|_| "Failed to lock telemetry log")?;
+    if writer.is_some() {
+        return Err("Telemetry log recording already in progress".to_string());
+    }
+
+    let file = File::create(&path)
+        .map_err(|e| format!("Failed to create telemetry log {}: {}", path, e))?;
+    *writer = Some(BufWriter::new(file));
+    state.tlog_bytes_written.store(0, Ordering::Relaxed);
+
+    let mut recording = state.tlog_recording.lock()
+        .map_err(|_| "Failed to lock telemetry log")?;
+    *recording = Some(TlogRecording {
+        path,
+        start_time_ms: get_timestamp(),
+    });
+
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn set_drone_parameter(
-    param_id: String,
-    value: f32,
+pub async fn stop_tlog_recording(
     state: State<'_, MavlinkState>,
-) -> Result<(), String> {
-    // Verify connection
-    verify_connection(&state)?;
+) -> Result<u64, String> {
+    if !is_tlog_recording(&state)? {
+        return Err("Telemetry log is not recording".to_string());
+    }
+    finish_tlog_recording(&state)
+}
 
-    // Validate parameter exists and value is in range
-    {
-        let params = state.parameters.read()
+#[tauri::command]
+pub async fn get_tlog_info(
+    state: State<'_, MavlinkState>,
+) -> Result<TlogInfo, String> {
+    let recording = state.tlog_recording.lock()
+        .map_err(|_| "Failed to lock telemetry log")?;
+
+    Ok(TlogInfo {
+        recording: recording.is_some(),
+        path: recording.as_ref().map(|r| r.path.clone()),
+        bytes_written: state.tlog_bytes_written.load(Ordering::Relaxed),
+        start_time_ms: recording.as_ref().map_or(0, |r| r.start_time_ms),
+    })
+}
+
+// ===== MESSAGE INSPECTOR COMMANDS =====
+
+// Up to `limit` most recent messages (oldest first), optionally of a single ID
+#[tauri::command]
+pub async fn get_recent_messages(
+    msg_id_filter: Option<u32>,
+    limit: u16,
+    state: State<'_, MavlinkState>,
+) -> Result<Vec<MavlogEntry>, String> {
+    let log = state.message_log.lock()
+        .map_err(|_| "Failed to lock message log")?;
+
+    let mut recent: Vec<MavlogEntry> = log.iter()
+        .rev()
+        .filter(|entry| msg_id_filter.map_or(true, |id| entry.msg_id == id))
+        .take(limit as usize)
+        .cloned()
+        .collect();
+    recent.reverse();
+
+    Ok(recent)
+}
+
+#[tauri::command]
+pub async fn get_message_statistics(
+    state: State<'_, MavlinkState>,
+) -> Result<HashMap<u32, MessageStats>, String> {
+    let stats = state.message_stats.lock()
+        .map_err(|_| "Failed to lock message statistics")?;
+    Ok(stats.clone())
+}
+
+// ===== STATUS TEXT COMMANDS =====
+
+// Recent vehicle texts, oldest first; kept across reconnects
+#[tauri::command]
+pub async fn get_statustext_log(
+    state: State<'_, MavlinkState>,
+) -> Result<Vec<StatusText>, String> {
+    let log = state.statustext_log.lock()
+        .map_err(|_| "Failed to lock status text log")?;
+    Ok(log.iter().cloned().collect())
+}
+
+// ===== MISSION UPLOAD COMMANDS =====
+
+#[tauri::command]
+pub async fn upload_mission_to_vehicle(
+    app_handle: tauri::AppHandle,
+    state: State<'_, MavlinkState>,
+    app_state: State<'_, AppState>,
+) -> Result<usize, String> {
+    // Verify connection
+    verify_connection(&state)?;
+
+    let report = crate::mission_analysis::validate_for_upload(&app_state)?;
+    if !report.valid {
+        let rules: Vec<String> = report.errors.iter()
+            .map(|e| format!("{} ({})", e.rule, e.message))
+            .collect();
+        return Err(format!("Mission failed validation: {}", rules.join(", ")));
+    }
+
+    let items = app_state.mission_items.lock().map_err(|_| "Failed to lock state")?.clone();
+    let home = items.first().ok_or("Cannot upload an empty mission")?;
+    let planned = plan_commands(&items)?;
+    let target = vehicle_target(&state)?;
+
+    // Sequence 0 is the home position; mission commands follow from 1
+    let mut messages = Vec::with_capacity(planned.len() + 1);
+    messages.push(mission_item_int(0, &PlannedCommand {
+        command: ::mavlink::ardupilotmega::MavCmd::MAV_CMD_NAV_WAYPOINT,
+        params: [0.0; 4],
+        lat: home.params.lat,
+        lng: home.params.lng,
+        alt: home.params.alt,
+        positioned: true,
+    }, target)?);
+    for (index, command) in planned.iter().enumerate() {
+        messages.push(mission_item_int(index + 1, command, target)?);
+    }
+    let count = MISSION_COUNT_DATA {
+        count: u16::try_from(messages.len()).map_err(|_| "Mission has too many items to upload".to_string())?,
+        target_system: target.0,
+        target_component: target.1,
+    };
+
+    // The vehicle pulls each item; nothing is recorded unless it accepts them all
+    let (event_tx, mut events) = mpsc::unbounded_channel();
+    {
+        let mut upload = state.mission_upload.lock().map_err(|_| "Failed to lock mission upload")?;
+        if upload.is_some() {
+            return Err("Mission upload already in progress".to_string());
+        }
+        *upload = Some(MissionUploadSession::new(target, messages, event_tx));
+    }
+    let outcome = await_mission_upload(&app_handle, &state, &count, &mut events).await;
+    if let Ok(mut upload) = state.mission_upload.lock() {
+        *upload = None;
+    }
+    outcome?;
+
+    *app_state.uploaded_mission_hash.lock()
+        .map_err(|_| "Failed to lock uploaded mission hash")? = Some(crate::mission_content_hash(&items));
+    *app_state.vehicle_mission.lock().map_err(|_| "Failed to lock vehicle mission")? = Some(items);
+    if is_vehicle_armed(&state)? {
+        crate::set_mission_lock(&app_state, Some(EXECUTING_LOCK_REASON.to_string()))?;
+    }
+    Ok(usize::from(count.count) - 1)
+}
+
+// Clear the mission lock; while the vehicle is still armed this requires `force`
+#[tauri::command]
+pub async fn unlock_mission(
+    force: bool,
+    state: State<'_, MavlinkState>,
+    app_state: State<'_, AppState>,
+) -> Result<(), String> {
+    if is_vehicle_armed(&state)? && !force {
+        return Err("Vehicle is still armed; force the unlock to edit the mission anyway".to_string());
+    }
+    crate::set_mission_lock(&app_state, None)
+}
+
+// Send every stored rally point as RALLY_POINT messages in id order,
+// returning how many were uploaded
+#[tauri::command]
+pub async fn upload_rally_points_to_vehicle(
+    state: State<'_, MavlinkState>,
+    app_state: State<'_, AppState>,
+    map_state: State<'_, crate::map_features::MapFeaturesState>,
+) -> Result<u8, String> {
+    verify_connection(&state)?;
+    let points = map_state.rally_points()?;
+    let home = crate::map_features::planned_home(&app_state)?;
+    send_rally_points(&state, &points, &home)
+}
+
+fn send_rally_points(
+    state: &MavlinkState,
+    points: &[crate::map_features::RallyPoint],
+    home: &Coordinate,
+) -> Result<u8, String> {
+    let (target_system, target_component) = vehicle_target(state)?;
+    let count = u8::try_from(points.len()).map_err(|_| "Too many rally points to upload".to_string())?;
+
+    // Build every message first so an invalid point uploads nothing
+    let mut messages = Vec::with_capacity(points.len());
+    for (idx, point) in (0u8..).zip(points) {
+        crate::map_features::validate_rally_point(point, home)?;
+        messages.push(MavMessage::RALLY_POINT(RALLY_POINT_DATA {
+            lat: (point.coordinate.lat * 1e7).round() as i32,
+            lng: (point.coordinate.lng * 1e7).round() as i32,
+            alt: rally_altitude(point.land_altitude_m, point.id)?,
+            break_alt: rally_altitude(point.break_altitude_m, point.id)?,
+            // 0 lets the vehicle pick the landing direction
+            land_dir: 0,
+            target_system,
+            target_component,
+            idx,
+            count,
+            flags: RallyFlags::from_bits_truncate(point.flags),
+        }));
+    }
+
+    for message in &messages {
+        send_message(state, message)?;
+    }
+    Ok(count)
+}
+
+// ===== PARAMETER COMMANDS =====
+
+#[tauri::command]
+pub async fn get_drone_parameters(
+    state: State<'_, MavlinkState>,
+) -> Result<Vec<Parameter>, String> {
+    // Verify connection
+    verify_connection(&state)?;
+
+    let params = state.parameters.read()
+        .map_err(|_| "Failed to read parameters")?;
+    
+    Ok(params.values().cloned().collect())
+}
+
+// Write a parameter to the vehicle and return the value it confirmed
+#[tauri::command]
+pub async fn set_drone_parameter(
+    app_handle: tauri::AppHandle,
+    param_id: String,
+    value: f32,
+    state: State<'_, MavlinkState>,
+) -> Result<f32, String> {
+    set_parameter_verified(&app_handle, &state, &param_id, value).await
+}
+
+// Validate against the cached parameter, write it and wait for the vehicle's
+// echo; the echoed PARAM_VALUE updates the cache in handle_param_value
+async fn set_parameter_verified(
+    app_handle: &tauri::AppHandle,
+    state: &MavlinkState,
+    param_id: &str,
+    value: f32,
+) -> Result<f32, String> {
+    // Verify connection
+    verify_connection(state)?;
+    if !state.parameters_synced.load(Ordering::SeqCst) {
+        eprintln!("WARNING: Setting {param_id} before parameters were synced from the vehicle");
+    }
+
+    // Validate parameter exists and value is in range
+    let param_type = {
+        let params = state.parameters.read()
             .map_err(|_| "Failed to read parameters")?;
         
-        if let Some(param) = params.get(&param_id) {
+        if let Some(param) = params.get(param_id) {
             // Check min/max bounds
             if let Some(min) = param.min_value {
                 if value < min {
@@ -255,213 +1568,4168 @@ pub async fn set_drone_parameter(
                     return Err(format!("Value {} is above maximum {}", value, max));
                 }
             }
+            param_type_from_name(&param.param_type).unwrap_or(MavParamType::MAV_PARAM_TYPE_REAL32)
         } else {
             return Err(format!("Parameter {} not found", param_id));
         }
+    };
+    let requested = encode_param_value(value, param_type)?;
+
+    let actual = write_parameter(app_handle, state, param_id, requested, param_type).await?;
+    if !param_values_match(actual, requested) {
+        eprintln!("Vehicle set {param_id} to {actual} instead of {requested}");
     }
 
-    // Update parameter
-    {
-        let mut params = state.parameters.write()
-            .map_err(|_| "Failed to update parameters")?;
-        
-        if let Some(param) = params.get_mut(&param_id) {
-            param.value = value;
+    let payload = serde_json::json!({
+        "param_id": param_id,
+        "value": actual,
+        "requested_value": requested,
+    });
+    if let Err(e) = app_handle.emit_all("param-changed", payload) {
+        eprintln!("Failed to emit parameter change: {e}");
+    }
+    Ok(actual)
+}
+
+// Send PARAM_SET until the vehicle echoes the parameter, resending on each
+// timeout. Returns the value the vehicle reports, which it may have clamped.
+async fn write_parameter(
+    app_handle: &tauri::AppHandle,
+    state: &MavlinkState,
+    param_id: &str,
+    value: f32,
+    param_type: MavParamType,
+) -> Result<f32, String> {
+    let (target_system, target_component) = vehicle_target(state)?;
+    let request = PARAM_SET_DATA {
+        param_value: value,
+        target_system,
+        target_component,
+        param_id: param_id_bytes(param_id),
+        param_type,
+    };
+    let timeout = Duration::from_millis(PARAM_SET_TIMEOUT_MS);
+
+    for _ in 0..=PARAM_SET_RETRIES {
+        let (echo_tx, echo_rx) = oneshot::channel();
+        {
+            let mut pending = state.pending_param_sets.lock()
+                .map_err(|_| "Failed to lock pending parameter writes")?;
+            if pending.get(param_id).map_or(false, |existing| !existing.is_closed()) {
+                return Err(format!("{param_id} is already being written"));
+            }
+            pending.insert(param_id.to_string(), echo_tx);
+        }
+        transmit_param_set(app_handle, state, request.clone())?;
+
+        match tokio::time::timeout(timeout, echo_rx).await {
+            Ok(Ok(actual)) => return Ok(actual),
+            Ok(Err(_)) => return Err(format!("Setting {param_id} aborted: vehicle disconnected")),
+            Err(_) => continue,
         }
     }
 
-    // TODO: Send PARAM_SET message via MAVLink
+    if let Ok(mut pending) = state.pending_param_sets.lock() {
+        pending.remove(param_id);
+    }
+    Err(format!(
+        "Vehicle did not confirm {param_id} after {} attempts",
+        PARAM_SET_RETRIES + 1
+    ))
+}
 
-    Ok(())
+// Download every parameter from the vehicle, replacing the in-memory values.
+// Returns the number of parameters the vehicle reported.
+#[tauri::command]
+pub async fn sync_parameters_from_vehicle(
+    app_handle: tauri::AppHandle,
+    state: State<'_, MavlinkState>,
+) -> Result<u32, String> {
+    verify_connection(&state)?;
+    let (target_system, target_component) = vehicle_target(&state)?;
+    let done_rx = begin_parameter_sync(&state, "parameter-sync-progress")?;
+
+    let request = PARAM_REQUEST_LIST_DATA { target_system, target_component };
+    if let Err(e) = transmit_param_request_list(&app_handle, &state, request) {
+        if let Ok(mut sync) = state.parameter_sync.lock() {
+            *sync = None;
+        }
+        return Err(e);
+    }
+
+    await_parameter_sync(&state, done_rx, Duration::from_secs(PARAMETER_SYNC_TIMEOUT_SECS)).await
 }
 
-// ===== MOTOR TEST COMMANDS =====
+// Wait for the running sync to collect every parameter, then mark the table synced
+async fn await_parameter_sync(
+    state: &MavlinkState,
+    done_rx: oneshot::Receiver<u32>,
+    timeout: Duration,
+) -> Result<u32, String> {
+    match tokio::time::timeout(timeout, done_rx).await {
+        Ok(Ok(count)) => {
+            state.parameters_synced.store(true, Ordering::SeqCst);
+            Ok(count)
+        }
+        Ok(Err(_)) => Err("Parameter sync aborted: vehicle disconnected".to_string()),
+        Err(_) => {
+            let sync = state.parameter_sync.lock()
+                .map_err(|_| "Failed to lock parameter sync")?
+                .take();
+            let (received, total) = sync.map_or((0, 0), |sync| {
+                (sync.received.len(), sync.total.unwrap_or(0))
+            });
+            Err(format!("Parameter sync timed out after receiving {received} of {total} parameters"))
+        }
+    }
+}
 
+// Download the full parameter table: request the list, then re-request the
+// missing indices each time the vehicle goes quiet, for a bounded number of
+// rounds. Returns how many parameters are still missing.
 #[tauri::command]
-pub async fn test_motor(
-    motor_id: u8,
-    throttle: u16,
-    duration_ms: u32,
+pub async fn refresh_parameters(
+    app_handle: tauri::AppHandle,
     state: State<'_, MavlinkState>,
-) -> Result<(), String> {
-    // Verify connection
+) -> Result<u32, String> {
     verify_connection(&state)?;
+    let target = vehicle_target(&state)?;
+    let mut done_rx = begin_parameter_sync(&state, "param-download-progress")?;
 
-    // Safety checks
-    if motor_id > 8 {
-        return Err("Invalid motor ID (must be 1-8)".to_string());
+    let complete = download_parameters(&app_handle, &state, &mut done_rx, target).await;
+    // A finished download has already ended its session; an unfinished one ends here
+    let session = state.parameter_sync.lock()
+        .map_err(|_| "Failed to lock parameter sync")?
+        .take();
+    if complete? {
+        state.parameters_synced.store(true, Ordering::SeqCst);
+        return Ok(0);
     }
-    if throttle > 100 {
-        return Err("Invalid throttle percentage (must be 0-100)".to_string());
+    match session.and_then(|session| session.total.map(|total| (total, session.received.len()))) {
+        Some((total, received)) => Ok(u32::from(total).saturating_sub(received as u32)),
+        None => Err("Vehicle did not send any parameters".to_string()),
     }
-    if duration_ms > 5000 {
-        return Err("Test duration too long (max 5 seconds)".to_string());
+}
+
+#[tauri::command]
+pub async fn are_parameters_synced(
+    state: State<'_, MavlinkState>,
+) -> Result<bool, String> {
+    Ok(state.parameters_synced.load(Ordering::SeqCst))
+}
+
+// Filter the parameter cache so the webview never needs the whole list.
+// `query` matches id or description case-insensitively, `group` is the id
+// prefix before the first underscore, and `modified_only` keeps values that
+// differ from the metadata default. Id prefix matches sort first.
+#[tauri::command]
+pub async fn search_parameters(
+    query: String,
+    group: Option<String>,
+    modified_only: bool,
+    limit: usize,
+    state: State<'_, MavlinkState>,
+) -> Result<ParameterSearchResult, String> {
+    let query = query.trim().to_uppercase();
+    let group = group.map(|group| group.trim().trim_end_matches('_').to_uppercase());
+    let params = state.parameters.read().map_err(|_| "Failed to read parameters")?;
+
+    let mut ranked: Vec<(u8, ParameterMatch)> = params.values()
+        .filter(|param| group.as_deref().map_or(true, |group| parameter_group(&param.id) == group))
+        .filter_map(|param| {
+            let modified = param.default_value.map_or(false, |default| !param_values_match(param.value, default));
+            if modified_only && !modified {
+                return None;
+            }
+            let rank = search_rank(param, &query)?;
+            Some((rank, ParameterMatch { parameter: param.clone(), modified }))
+        })
+        .collect();
+    drop(params);
+    ranked.sort_by(|(rank_a, a), (rank_b, b)| rank_a.cmp(rank_b).then_with(|| a.parameter.id.cmp(&b.parameter.id)));
+
+    let total_count = ranked.len() as u32;
+    let matches = ranked.into_iter()
+        .take(limit.min(MAX_PARAMETER_SEARCH_RESULTS))
+        .map(|(_, found)| found)
+        .collect();
+    Ok(ParameterSearchResult { matches, total_count })
+}
+
+// Compare a .param file with the live parameters without writing anything
+#[tauri::command]
+pub async fn load_param_file(
+    path: String,
+    state: State<'_, MavlinkState>,
+) -> Result<ParamFileDiff, String> {
+    param_file_diff(&state, &path)
+}
+
+// Write a .param file's values to the vehicle one at a time through the
+// verified write path; `only_changed` skips values the vehicle already has.
+// Failures are collected rather than stopping the rest, and unknown
+// parameters are reported as failures. A dry run only returns the plan.
+#[tauri::command]
+pub async fn apply_param_file(
+    app_handle: tauri::AppHandle,
+    path: String,
+    only_changed: bool,
+    dry_run: bool,
+    state: State<'_, MavlinkState>,
+) -> Result<ParamApplyReport, String> {
+    verify_connection(&state)?;
+    let diff = param_file_diff(&state, &path)?;
+    let (unknown, planned): (Vec<ParamFileEntry>, Vec<ParamFileEntry>) = diff.entries.into_iter()
+        .filter(|entry| entry.changed || entry.unknown || !only_changed)
+        .partition(|entry| entry.unknown);
+    let mut failures: Vec<ParamApplyFailure> = unknown.into_iter()
+        .map(|entry| ParamApplyFailure { param_id: entry.param_id, error: "Parameter not found on the vehicle".to_string() })
+        .collect();
+    if dry_run {
+        return Ok(ParamApplyReport { dry_run, planned, applied_count: 0, failures });
     }
 
-    // Check if already testing
-    {
-        let mut motor_test = state.motor_test_active.write()
-            .map_err(|_| "Failed to update motor test status")?;
-        if *motor_test {
-            return Err("Motor test already in progress".to_string());
+    let total = planned.len() as u32;
+    let mut applied_count = 0;
+    for (completed, entry) in (1u32..).zip(planned.iter()) {
+        let error = set_parameter_verified(&app_handle, &state, &entry.param_id, entry.file_value).await.err();
+        match &error {
+            Some(e) => failures.push(ParamApplyFailure { param_id: entry.param_id.clone(), error: e.clone() }),
+            None => applied_count += 1,
+        }
+        let progress = ParamApplyProgress { completed, total, param_id: entry.param_id.clone(), error };
+        if let Err(e) = app_handle.emit_all("param-apply-progress", progress) {
+            eprintln!("Failed to emit parameter apply progress: {e}");
         }
-        *motor_test = true;
     }
+    Ok(ParamApplyReport { dry_run, planned, applied_count, failures })
+}
 
-    // TODO: Send MAV_CMD_DO_MOTOR_TEST command
+// Write the parameter cache as a .param file loadable by QGroundControl and
+// Mission Planner. Returns the number of parameters written.
+#[tauri::command]
+pub async fn export_parameters(
+    path: String,
+    allow_partial: Option<bool>,
+    state: State<'_, MavlinkState>,
+) -> Result<u32, String> {
+    if path.trim().is_empty() {
+        return Err("Parameter file path cannot be empty".to_string());
+    }
+    let downloading = state.parameter_sync.lock()
+        .map_err(|_| "Failed to lock parameter sync")?
+        .is_some();
+    let complete = !downloading && state.parameters_synced.load(Ordering::SeqCst);
+    if !complete && !allow_partial.unwrap_or(false) {
+        return Err("Parameters have not been fully downloaded; pass allow_partial to export anyway".to_string());
+    }
 
-    // Simulate test duration
-    tokio::time::sleep(Duration::from_millis(duration_ms as u64)).await;
+    let (system_id, component_id) = vehicle_target(&state)?;
+    let mut params: Vec<Parameter> = state.parameters.read()
+        .map_err(|_| "Failed to read parameters")?
+        .values()
+        .cloned()
+        .collect();
+    if params.is_empty() {
+        return Err("No parameters to export".to_string());
+    }
+    params.sort_by(|a, b| a.id.cmp(&b.id));
 
-    // Clear motor test flag
-    {
-        let mut motor_test = state.motor_test_active.write()
-            .map_err(|_| "Failed to update motor test status")?;
-        *motor_test = false;
+    let contents = param_file_contents(system_id, component_id, &params);
+    std::fs::write(&path, contents).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(params.len() as u32)
+}
+
+// Load the firmware's parameter definitions and merge them into the
+// parameter cache. `firmware` defaults to the connected vehicle's.
+// Returns the number of definitions loaded.
+#[tauri::command]
+pub async fn load_parameter_metadata(
+    app_handle: tauri::AppHandle,
+    firmware: Option<String>,
+    state: State<'_, MavlinkState>,
+) -> Result<u32, String> {
+    let firmware = match firmware {
+        Some(firmware) => param_metadata::validate_firmware(&firmware)?,
+        None => connected_firmware(&state)?,
+    };
+    let definitions = param_metadata::load_definitions(&app_handle, firmware).await?;
+    let count = definitions.len() as u32;
+
+    let mut params = state.parameters.write().map_err(|_| "Failed to update parameters")?;
+    for param in params.values_mut() {
+        if let Some(metadata) = definitions.get(&param.id) {
+            param_metadata::apply_metadata(param, metadata);
+        }
+    }
+    drop(params);
+    *state.parameter_metadata.write().map_err(|_| "Failed to update parameter metadata")? = definitions;
+    Ok(count)
+}
+
+// Full definition of one parameter, including enum and bitmask labels.
+// Loads the connected vehicle's metadata if none is loaded yet.
+#[tauri::command]
+pub async fn get_parameter_metadata(
+    app_handle: tauri::AppHandle,
+    param_id: String,
+    state: State<'_, MavlinkState>,
+) -> Result<ParameterMetadata, String> {
+    let loaded = !state.parameter_metadata.read()
+        .map_err(|_| "Failed to read parameter metadata")?
+        .is_empty();
+    if !loaded {
+        load_parameter_metadata(app_handle, None, state.clone()).await?;
     }
+    state.parameter_metadata.read()
+        .map_err(|_| "Failed to read parameter metadata")?
+        .get(&param_id)
+        .cloned()
+        .ok_or_else(|| format!("No metadata for parameter {}", param_id))
+}
+
+fn connected_firmware(state: &MavlinkState) -> Result<&'static str, String> {
+    let info = state.vehicle_info.read()
+        .map_err(|_| "Failed to read vehicle info")?
+        .clone()
+        .ok_or("Vehicle info not available; connect or name the firmware")?;
+    param_metadata::firmware_for_vehicle(&info)
+}
+
+// ===== COMMAND PROTOCOL =====
 
+#[tauri::command]
+pub async fn set_command_timeout(
+    timeout_ms: u64,
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    if !(MIN_COMMAND_TIMEOUT_MS..=MAX_COMMAND_TIMEOUT_MS).contains(&timeout_ms) {
+        return Err(format!(
+            "Command timeout must be between {MIN_COMMAND_TIMEOUT_MS} and {MAX_COMMAND_TIMEOUT_MS} ms"
+        ));
+    }
+    state.command_timeout_ms.store(timeout_ms, Ordering::Relaxed);
     Ok(())
 }
 
+// ===== ARMING COMMANDS =====
+
 #[tauri::command]
-pub async fn emergency_stop(
+pub async fn get_preflight_status(
+    state: State<'_, MavlinkState>,
+) -> Result<PreflightCheckReport, String> {
+    verify_connection(&state)?;
+    let status = state.preflight_status.read()
+        .map_err(|_| "Failed to read preflight status")?
+        .ok_or("No SYS_STATUS received from the vehicle yet")?;
+    Ok(preflight_report(status))
+}
+
+#[tauri::command]
+pub async fn get_ekf_status(
+    state: State<'_, MavlinkState>,
+) -> Result<EkfStatus, String> {
+    verify_connection(&state)?;
+    state.ekf_status.read()
+        .map_err(|_| "Failed to read EKF status")?
+        .ok_or_else(|| "No EKF_STATUS_REPORT received from the vehicle yet".to_string())
+}
+
+#[tauri::command]
+pub async fn set_ekf_variance_threshold(
+    threshold: f32,
     state: State<'_, MavlinkState>,
 ) -> Result<(), String> {
-    // This must complete in < 1ms for safety
-    let start = Instant::now();
+    if !(threshold.is_finite() && threshold > 0.0) {
+        return Err("EKF variance threshold must be a positive number".to_string());
+    }
+    *state.ekf_variance_threshold.write()
+        .map_err(|_| "Failed to set EKF variance threshold")? = threshold;
+    Ok(())
+}
 
-    // Set emergency stop flag immediately
-    {
-        let mut active = state.emergency_stop.active.write()
-            .map_err(|_| "Critical: Failed to set emergency stop")?;
-        *active = true;
+// Refused unless every enabled sensor reports healthy; `force` overrides the
+// vehicle's own pre-arm checks but not these. When the vehicle refuses, the
+// result lists the pre-arm failures it reported. The armed flag follows the
+// vehicle's next HEARTBEAT.
+#[tauri::command]
+pub async fn arm_vehicle(
+    app_handle: tauri::AppHandle,
+    force: bool,
+    state: State<'_, MavlinkState>,
+) -> Result<ArmDisarmResult, String> {
+    verify_ready_to_arm(&state)?;
+    send_arm_disarm(&app_handle, &state, true, force).await
+}
+
+fn verify_ready_to_arm(state: &MavlinkState) -> Result<(), String> {
+    verify_connection(state)?;
+    verify_emergency_stop_clear(state)?;
+    if is_vehicle_armed(state)? {
+        return Err("Vehicle is already armed".to_string());
+    }
+
+    let status = state.preflight_status.read()
+        .map_err(|_| "Failed to read preflight status")?
+        .ok_or("Cannot arm: no preflight status received from the vehicle")?;
+    let report = preflight_report(status);
+    if !report.failing_checks.is_empty() {
+        return Err(format!("Cannot arm: preflight checks failing for {}", report.failing_checks.join(", ")));
+    }
+    let ekf = *state.ekf_status.read().map_err(|_| "Failed to read EKF status")?;
+    if ekf.map_or(false, |ekf| ekf_in_const_pos_mode(&ekf)) {
+        return Err("Cannot arm: EKF is in constant position mode".to_string());
+    }
+    Ok(())
+}
+
+// `force` disarms even in flight. The armed flag follows the vehicle's next
+// HEARTBEAT.
+#[tauri::command]
+pub async fn disarm_vehicle(
+    app_handle: tauri::AppHandle,
+    force: bool,
+    state: State<'_, MavlinkState>,
+) -> Result<ArmDisarmResult, String> {
+    verify_ready_to_disarm(&state)?;
+    send_arm_disarm(&app_handle, &state, false, force).await
+}
+
+// Disarming stays available while the emergency stop is latched
+fn verify_ready_to_disarm(state: &MavlinkState) -> Result<(), String> {
+    verify_connection(state)?;
+    if !is_vehicle_armed(state)? {
+        return Err("Vehicle is already disarmed".to_string());
+    }
+    Ok(())
+}
+
+// ===== FLIGHT MODE COMMANDS =====
+
+// Mode names accepted by `set_flight_mode` for the connected vehicle
+#[tauri::command]
+pub async fn get_available_flight_modes(
+    state: State<'_, MavlinkState>,
+) -> Result<Vec<String>, String> {
+    verify_connection(&state)?;
+    let (_, table) = connected_flight_mode_table(&state)?;
+    Ok(table.iter().map(|(_, name)| name.to_string()).collect())
+}
+
+// Send MAV_CMD_DO_SET_MODE and wait for a HEARTBEAT reporting the new mode
+#[tauri::command]
+pub async fn set_flight_mode(
+    app_handle: tauri::AppHandle,
+    mode: String,
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    verify_connection(&state)?;
+    let (autopilot_type, table) = connected_flight_mode_table(&state)?;
+    let (custom_mode, name) = table.iter()
+        .find(|(_, name)| name.eq_ignore_ascii_case(mode.trim()))
+        .copied()
+        .ok_or_else(|| {
+            let valid: Vec<&str> = table.iter().map(|(_, name)| *name).collect();
+            format!("Unknown flight mode '{}'; valid modes are {}", mode.trim(), valid.join(", "))
+        })?;
+    if current_flight_mode(&state)? == name {
+        return Ok(());
+    }
+
+    let base_mode = MavModeFlag::MAV_MODE_FLAG_CUSTOM_MODE_ENABLED.bits() as f32;
+    let params = if autopilot_type == "PX4" {
+        [base_mode, ((custom_mode >> 16) & 0xFF) as f32, (custom_mode >> 24) as f32, 0.0, 0.0, 0.0, 0.0]
+    } else {
+        [base_mode, custom_mode as f32, 0.0, 0.0, 0.0, 0.0, 0.0]
+    };
+    send_command_long(&app_handle, &state, MavCmd::MAV_CMD_DO_SET_MODE, params).await?;
+
+    let deadline = Instant::now() + Duration::from_millis(FLIGHT_MODE_CONFIRM_TIMEOUT_MS);
+    while Instant::now() < deadline {
+        if current_flight_mode(&state)? == name {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_millis(FLIGHT_MODE_POLL_INTERVAL_MS)).await;
+    }
+    Err(format!(
+        "Vehicle accepted {} but still reports {} after {} ms",
+        name,
+        current_flight_mode(&state)?,
+        FLIGHT_MODE_CONFIRM_TIMEOUT_MS
+    ))
+}
+
+// Autopilot type of the connected vehicle and its mode table
+fn connected_flight_mode_table(state: &MavlinkState) -> Result<(String, FlightModeTable), String> {
+    let info = state.vehicle_info.read().map_err(|_| "Failed to read vehicle info")?;
+    let info = info.as_ref().ok_or("Vehicle info not available")?;
+    flight_mode_table(&info.autopilot_type, &info.vehicle_type)
+        .map(|table| (info.autopilot_type.clone(), table))
+        .ok_or_else(|| format!("Flight modes are not known for {} {}", info.autopilot_type, info.vehicle_type))
+}
+
+// Mode named by the latest HEARTBEAT's custom_mode
+fn current_flight_mode(state: &MavlinkState) -> Result<String, String> {
+    let info = state.vehicle_info.read().map_err(|_| "Failed to read vehicle info")?;
+    info.as_ref()
+        .map(|info| info.flight_mode.clone())
+        .ok_or_else(|| "Vehicle info not available".to_string())
+}
+
+// ===== GUIDED COMMANDS =====
+
+// Fly to a point `alt` metres above home. Refused while disarmed, with the
+// emergency stop latched, or for a target outside the stored geofence or
+// above its ceiling. The vehicle must already be in its guided mode unless
+// `auto_switch` is set.
+// Emits `goto-accepted` or `goto-rejected` with the vehicle's answer.
+#[tauri::command]
+pub async fn goto_location(
+    app_handle: tauri::AppHandle,
+    lat: f64,
+    lng: f64,
+    alt: f64,
+    ground_speed: Option<f64>,
+    auto_switch: bool,
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    verify_connection(&state)?;
+    verify_emergency_stop_clear(&state)?;
+    if !is_vehicle_armed(&state)? {
+        return Err("Vehicle must be armed to fly to a location".to_string());
+    }
+    let target = goto_target(&app_handle.state::<GeofenceState>(), lat, lng, alt, ground_speed)?;
+
+    let (autopilot_type, _) = connected_flight_mode_table(&state)?;
+    let guided_mode = guided_mode(&autopilot_type)?;
+    let mode = current_flight_mode(&state)?;
+    if mode != guided_mode {
+        if !auto_switch {
+            return Err(format!("Vehicle is in {mode}; switch to {guided_mode} first or enable auto switch"));
+        }
+        set_flight_mode(app_handle.clone(), guided_mode.to_string(), state.clone()).await?;
     }
 
-    // Record activation time
-    {
-        let mut last = state.emergency_stop.last_activation.lock()
-            .map_err(|_| "Failed to record emergency stop time")?;
-        *last = Some(Instant::now());
+    let params = [ground_speed.map_or(-1.0, |speed| speed as f32), 0.0, 0.0, f32::NAN];
+    let result = send_command_int(&app_handle, &state, MavCmd::MAV_CMD_DO_REPOSITION, params, &target).await;
+    let payload = serde_json::json!({
+        "lat": lat,
+        "lng": lng,
+        "alt": alt,
+        "error": result.as_ref().err(),
+    });
+    let event = if result.is_ok() { "goto-accepted" } else { "goto-rejected" };
+    if let Err(e) = app_handle.emit_all(event, payload) {
+        eprintln!("Failed to emit {event}: {e}");
+    }
+    result
+}
+
+// The checked go-to target: inside the geofence and under its ceiling
+fn goto_target(
+    geofence: &GeofenceState,
+    lat: f64,
+    lng: f64,
+    alt: f64,
+    ground_speed: Option<f64>,
+) -> Result<Coordinate, String> {
+    let target = Coordinate { lat, lng, alt: Some(alt) };
+    if !is_valid_coordinate(&target) {
+        return Err("Invalid target coordinates".to_string());
+    }
+    if !(0.0..=MAX_GOTO_ALTITUDE_M).contains(&alt) {
+        return Err(format!("Altitude must be between 0 and {MAX_GOTO_ALTITUDE_M} m above home"));
+    }
+    if ground_speed.map_or(false, |speed| !(speed > 0.0 && speed <= MAX_GOTO_GROUND_SPEED_MPS)) {
+        return Err(format!("Ground speed must be between 0 and {MAX_GOTO_GROUND_SPEED_MPS} m/s"));
+    }
+    if !is_within_geofence(geofence, &target)? {
+        return Err("Target is outside the geofence".to_string());
+    }
+    if let Some(ceiling) = geofence_ceiling(geofence)? {
+        if alt > ceiling {
+            return Err(format!("Target altitude {alt} m is above the geofence ceiling of {ceiling} m"));
+        }
+    }
+    Ok(target)
+}
+
+// Mode in which the autopilot accepts MAV_CMD_DO_REPOSITION. PX4 takes it in
+// Hold (AUTO_LOITER), where no setpoint stream is needed, unlike OFFBOARD.
+fn guided_mode(autopilot_type: &str) -> Result<&'static str, String> {
+    match autopilot_type {
+        "ArduPilot" => Ok("GUIDED"),
+        "PX4" => Ok("AUTO_LOITER"),
+        other => Err(format!("Guided flight is not supported for {other} autopilots")),
+    }
+}
+
+// ===== TAKEOFF COMMANDS =====
+
+// Climb to `altitude` metres above home. The vehicle must be armed and, for
+// ArduPilot copters, in GUIDED; the altitude may not exceed the geofence
+// ceiling. Once the vehicle accepts, `takeoff-progress` follows the climb until
+// the target is reached, the climb times out or the vehicle disarms.
+#[tauri::command]
+pub async fn command_takeoff(
+    app_handle: tauri::AppHandle,
+    altitude: f64,
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    verify_connection(&state)?;
+    verify_emergency_stop_clear(&state)?;
+    if !is_vehicle_armed(&state)? {
+        return Err("Vehicle must be armed to take off".to_string());
+    }
+    if !(altitude > 0.0 && altitude <= MAX_GOTO_ALTITUDE_M) {
+        return Err(format!("Takeoff altitude must be between 0 and {MAX_GOTO_ALTITUDE_M} m above home"));
+    }
+    if let Some(ceiling) = geofence_ceiling(&app_handle.state::<GeofenceState>())? {
+        if altitude > ceiling {
+            return Err(format!("Takeoff altitude {altitude} m is above the geofence ceiling of {ceiling} m"));
+        }
+    }
+
+    let info = state.vehicle_info.read()
+        .map_err(|_| "Failed to read vehicle info")?
+        .clone()
+        .ok_or("Vehicle info not available")?;
+    if let Some(required) = takeoff_mode(&info)? {
+        let mode = current_flight_mode(&state)?;
+        if mode != required {
+            return Err(format!("Vehicle is in {mode}; switch to {required} before taking off"));
+        }
+    }
+
+    // PX4 takes the target above mean sea level, ArduPilot above home
+    let target_alt = if info.autopilot_type == "PX4" {
+        let progress = state.vehicle_progress.read()
+            .map_err(|_| "Failed to read vehicle progress")?;
+        let relative = progress.position.as_ref().and_then(|position| position.alt);
+        match (progress.altitude_amsl_m, relative) {
+            (Some(amsl), Some(relative)) => amsl - relative + altitude,
+            _ => return Err("Cannot take off: no position received from the vehicle".to_string()),
+        }
+    } else {
+        altitude
+    };
+    let params = [0.0, 0.0, 0.0, f32::NAN, f32::NAN, f32::NAN, target_alt as f32];
+    send_command_long(&app_handle, &state, MavCmd::MAV_CMD_NAV_TAKEOFF, params).await?;
+
+    let epoch = state.connection_epoch.load(Ordering::SeqCst);
+    tauri::async_runtime::spawn(monitor_takeoff(app_handle, epoch, altitude));
+    Ok(())
+}
+
+// Mode the vehicle must be in to accept a takeoff; None when any mode will do
+// (PX4 switches to its takeoff mode itself)
+fn takeoff_mode(info: &VehicleInfo) -> Result<Option<&'static str>, String> {
+    match (info.autopilot_type.as_str(), info.vehicle_type.as_str()) {
+        (_, "Rover" | "SURFACE_BOAT" | "SUBMARINE") => Err(format!("A {} cannot take off", info.vehicle_type)),
+        ("ArduPilot", vehicle_type) if frame_motor_count(vehicle_type).is_some() => Ok(Some("GUIDED")),
+        _ => Ok(None),
+    }
+}
+
+// NASA JPL Rule 2: bounded by the climb timeout
+async fn monitor_takeoff(app_handle: tauri::AppHandle, epoch: u64, target_alt: f64) {
+    let timeout_s = TAKEOFF_TIMEOUT_BASE_S + target_alt / TAKEOFF_MIN_CLIMB_RATE_MPS;
+    let deadline = Instant::now() + Duration::from_secs_f64(timeout_s);
+    loop {
+        tokio::time::sleep(Duration::from_millis(TAKEOFF_PROGRESS_INTERVAL_MS)).await;
+        let state = app_handle.state::<MavlinkState>();
+        let relative_alt = state.vehicle_progress.read().ok()
+            .and_then(|progress| progress.position.as_ref().and_then(|position| position.alt));
+
+        let stopped = state.connection_epoch.load(Ordering::SeqCst) != epoch
+            || verify_emergency_stop_clear(&state).is_err()
+            || !is_vehicle_armed(&state).unwrap_or(false);
+        let status = if relative_alt.map_or(false, |alt| (target_alt - alt).abs() <= TAKEOFF_ALTITUDE_TOLERANCE_M) {
+            "reached"
+        } else if stopped {
+            "aborted"
+        } else if Instant::now() >= deadline {
+            "timeout"
+        } else {
+            "climbing"
+        };
+
+        let payload = serde_json::json!({
+            "target_alt_m": target_alt,
+            "relative_alt_m": relative_alt,
+            "status": status,
+        });
+        if let Err(e) = app_handle.emit_all("takeoff-progress", payload) {
+            eprintln!("Failed to emit takeoff progress: {e}");
+        }
+        if status != "climbing" {
+            return;
+        }
+    }
+}
+
+// ===== REBOOT COMMANDS =====
+
+// Restart the flight controller and/or companion computer. The vehicle drops
+// the link while restarting, so the connection is closed right after sending
+// instead of waiting for an acknowledgement.
+#[tauri::command]
+pub async fn reboot_vehicle(
+    app_handle: tauri::AppHandle,
+    reboot_autopilot: bool,
+    reboot_companion: bool,
+    safety_confirmed: bool,
+    reconnect_after_reboot: bool,
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    let command = reboot_command(&state, reboot_autopilot, reboot_companion, safety_confirmed)?;
+    let connection_string = state.connection_status.read()
+        .map_err(|_| "Failed to read connection status")?
+        .connection_string
+        .clone()
+        .ok_or("Not connected to drone")?;
+
+    let payload = serde_json::json!({
+        "reboot_autopilot": reboot_autopilot,
+        "reboot_companion": reboot_companion,
+    });
+    if let Err(e) = app_handle.emit_all("vehicle-rebooting", payload) {
+        eprintln!("Failed to emit vehicle reboot: {e}");
+    }
+
+    transmit_command(&app_handle, &state, &MavMessage::COMMAND_LONG(command))?;
+    reset_connection(&state)?;
+
+    if reconnect_after_reboot {
+        tauri::async_runtime::spawn(reconnect_after_reboot_delay(app_handle, connection_string));
+    }
+    Ok(())
+}
+
+// MAV_CMD_PREFLIGHT_REBOOT_SHUTDOWN for a confirmed reboot of a disarmed vehicle
+fn reboot_command(
+    state: &MavlinkState,
+    reboot_autopilot: bool,
+    reboot_companion: bool,
+    safety_confirmed: bool,
+) -> Result<COMMAND_LONG_DATA, String> {
+    if !safety_confirmed {
+        return Err("Reboot requires confirmation that the vehicle is safe to restart".to_string());
+    }
+    if !reboot_autopilot && !reboot_companion {
+        return Err("Select the autopilot, the companion computer or both to reboot".to_string());
+    }
+    verify_connection(state)?;
+    if is_vehicle_armed(state)? {
+        return Err("Cannot reboot while the vehicle is armed".to_string());
+    }
+    ensure_no_active_operation(state, "reboot")?;
+
+    let (target_system, target_component) = vehicle_target(state)?;
+    Ok(COMMAND_LONG_DATA {
+        param1: if reboot_autopilot { 1.0 } else { 0.0 },
+        param2: if reboot_companion { 1.0 } else { 0.0 },
+        param3: 0.0,
+        param4: 0.0,
+        param5: 0.0,
+        param6: 0.0,
+        param7: 0.0,
+        command: MavCmd::MAV_CMD_PREFLIGHT_REBOOT_SHUTDOWN,
+        target_system,
+        target_component,
+        confirmation: 0,
+    })
+}
+
+// Reconnect with the previous connection string once the vehicle has had
+// REBOOT_RECONNECT_DELAY_SECS to restart, retrying a few times. Gives up
+// quietly if the operator connected in the meantime.
+async fn reconnect_after_reboot_delay(app_handle: tauri::AppHandle, connection_string: String) {
+    tokio::time::sleep(Duration::from_secs(REBOOT_RECONNECT_DELAY_SECS)).await;
+    let mut last_error = String::new();
+    for attempt in 1..=REBOOT_RECONNECT_ATTEMPTS {
+        let state = app_handle.state::<MavlinkState>();
+        if state.connection_status.read().map_or(true, |status| status.connected) {
+            return;
+        }
+
+        match connect_drone(app_handle.clone(), connection_string.clone(), state).await {
+            Ok(_) => {
+                if let Err(e) = app_handle.emit_all("vehicle-reconnected", serde_json::json!({ "attempt": attempt })) {
+                    eprintln!("Failed to emit vehicle reconnect: {e}");
+                }
+                return;
+            }
+            Err(e) => last_error = e,
+        }
+        tokio::time::sleep(Duration::from_secs(REBOOT_RECONNECT_INTERVAL_SECS)).await;
+    }
+
+    let payload = serde_json::json!({ "reason": last_error });
+    if let Err(e) = app_handle.emit_all("vehicle-reconnect-failed", payload) {
+        eprintln!("Failed to emit vehicle reconnect failure: {e}");
+    }
+}
+
+// ===== VIBRATION COMMANDS =====
+
+#[tauri::command]
+pub async fn get_vibration_status(
+    state: State<'_, MavlinkState>,
+) -> Result<VibrationData, String> {
+    verify_connection(&state)?;
+    state.vibration_history.lock()
+        .map_err(|_| "Failed to lock vibration history")?
+        .back()
+        .copied()
+        .ok_or_else(|| "No VIBRATION received from the vehicle yet".to_string())
+}
+
+// Reports from the last `seconds`, oldest first, for plotting
+#[tauri::command]
+pub async fn get_vibration_history(
+    seconds: u32,
+    state: State<'_, MavlinkState>,
+) -> Result<Vec<VibrationData>, String> {
+    let since_ms = get_timestamp().saturating_sub(u64::from(seconds) * 1000);
+    let history = state.vibration_history.lock()
+        .map_err(|_| "Failed to lock vibration history")?;
+    Ok(history.iter()
+        .filter(|report| report.timestamp_ms >= since_ms)
+        .copied()
+        .collect())
+}
+
+// ===== ESC COMMANDS =====
+
+// Latest report from each ESC that sends telemetry, by motor ID
+#[tauri::command]
+pub async fn get_esc_telemetry(
+    state: State<'_, MavlinkState>,
+) -> Result<Vec<EscTelemetry>, String> {
+    verify_connection(&state)?;
+    Ok(state.esc_telemetry.read()
+        .map_err(|_| "Failed to read ESC telemetry")?
+        .clone())
+}
+
+// ===== BATTERY COMMANDS =====
+
+// Thresholds for `battery-warning` (remaining or cell voltage) and
+// `battery-critical` (remaining)
+#[tauri::command]
+pub async fn set_battery_thresholds(
+    warn_pct: f32,
+    critical_pct: f32,
+    warn_cell_v: f32,
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    if !(0.0..=100.0).contains(&critical_pct) || !(0.0..=100.0).contains(&warn_pct) {
+        return Err("Battery thresholds must be between 0 and 100%".to_string());
+    }
+    if critical_pct >= warn_pct {
+        return Err(format!("Critical level {critical_pct}% must be below the warning level {warn_pct}%"));
+    }
+    if !(MIN_WARN_CELL_V..=MAX_WARN_CELL_V).contains(&warn_cell_v) {
+        return Err(format!("Cell warning voltage must be between {MIN_WARN_CELL_V} and {MAX_WARN_CELL_V} V"));
+    }
+
+    *state.battery_thresholds.write()
+        .map_err(|_| "Failed to set battery thresholds")? = BatteryThresholds { warn_pct, critical_pct, warn_cell_v };
+    Ok(())
+}
+
+// ===== TELEMETRY COMMANDS =====
+
+// Last cached attitude, for views opened after the stream started
+#[tauri::command]
+pub async fn get_latest_attitude(
+    state: State<'_, MavlinkState>,
+) -> Result<AttitudeData, String> {
+    verify_connection(&state)?;
+    state.attitude.read()
+        .map_err(|_| "Failed to read attitude")?
+        .ok_or_else(|| "No ATTITUDE received from the vehicle yet".to_string())
+}
+
+#[tauri::command]
+pub async fn get_latest_vfr(
+    state: State<'_, MavlinkState>,
+) -> Result<VfrData, String> {
+    verify_connection(&state)?;
+    state.vfr.read()
+        .map_err(|_| "Failed to read VFR telemetry")?
+        .ok_or_else(|| "No VFR_HUD received from the vehicle yet".to_string())
+}
+
+// Latest GPS fix; pre-flight workflows refuse to continue without `has_3d_fix`
+#[tauri::command]
+pub async fn get_gps_status(
+    state: State<'_, MavlinkState>,
+) -> Result<GpsStatus, String> {
+    verify_connection(&state)?;
+    state.gps_status.read()
+        .map_err(|_| "Failed to read GPS status")?
+        .clone()
+        .ok_or_else(|| "No GPS_RAW_INT received from the vehicle yet".to_string())
+}
+
+#[tauri::command]
+pub async fn get_telemetry_rates(
+    state: State<'_, MavlinkState>,
+) -> Result<TelemetryRates, String> {
+    Ok(*state.telemetry_rates.read()
+        .map_err(|_| "Failed to read telemetry rates")?)
+}
+
+// Cap the event rate of each telemetry stream; the latest values stay cached
+// at the full rate
+#[tauri::command]
+pub async fn set_telemetry_rates(
+    rates: TelemetryRates,
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    let all = [rates.attitude_hz, rates.vfr_hz, rates.position_hz];
+    if all.iter().any(|rate| !(1..=MAX_TELEMETRY_RATE_HZ).contains(rate)) {
+        return Err(format!("Telemetry rates must be between 1 and {MAX_TELEMETRY_RATE_HZ} Hz"));
+    }
+    *state.telemetry_rates.write()
+        .map_err(|_| "Failed to set telemetry rates")? = rates;
+    Ok(())
+}
+
+// ===== MAVLINK CONSOLE COMMANDS =====
+
+// Forward the vehicle's shell output as `mavlink-console-output` events
+#[tauri::command]
+pub async fn start_mavlink_console(
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    verify_connection(&state)?;
+    state.console_buffer.lock()
+        .map_err(|_| "Failed to lock console buffer")?
+        .clear();
+    state.console_active.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+// Stop forwarding output and release the shell; a final SERIAL_CONTROL
+// without the exclusive flag hands the device back to the autopilot
+#[tauri::command]
+pub async fn stop_mavlink_console(
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    if !state.console_active.swap(false, Ordering::SeqCst) {
+        return Ok(());
+    }
+    state.console_buffer.lock()
+        .map_err(|_| "Failed to lock console buffer")?
+        .clear();
+    verify_connection(&state)?;
+    send_message(&state, &serial_control_message(&[], SerialControlFlag::empty()))
+}
+
+// Send `data` to the vehicle's shell, split across SERIAL_CONTROL messages
+#[tauri::command]
+pub async fn mavlink_console_write(
+    data: String,
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    write_console(&state, &data)
+}
+
+fn write_console(state: &MavlinkState, data: &str) -> Result<(), String> {
+    verify_connection(state)?;
+    if !state.console_active.load(Ordering::SeqCst) {
+        return Err("MAVLink console is not started".to_string());
+    }
+    if data.len() > MAX_CONSOLE_WRITE_BYTES {
+        return Err(format!("Console input exceeds {MAX_CONSOLE_WRITE_BYTES} bytes"));
+    }
+
+    let flags = SerialControlFlag::SERIAL_CONTROL_FLAG_EXCLUSIVE | SerialControlFlag::SERIAL_CONTROL_FLAG_RESPOND;
+    for chunk in data.as_bytes().chunks(SERIAL_CONTROL_DATA_BYTES) {
+        send_message(state, &serial_control_message(chunk, flags))?;
+    }
+    Ok(())
+}
+
+// ===== RELAY COMMANDS =====
+
+// Forward every frame received from the vehicle to `output_addr` over UDP,
+// e.g. for a second ground station. Relays outlive connections.
+#[tauri::command]
+pub async fn start_mavlink_relay(
+    output_addr: String,
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    let mut targets = state.relay_targets.lock()
+        .map_err(|_| "Failed to lock relay targets")?;
+    if targets.iter().any(|target| target.output_addr() == output_addr) {
+        return Err(format!("Already relaying to {output_addr}"));
+    }
+    if targets.len() >= MAX_RELAY_TARGETS {
+        return Err(format!("At most {MAX_RELAY_TARGETS} relay targets are supported"));
+    }
+
+    targets.push(RelayTarget::start(&output_addr)?);
+    Ok(())
+}
+
+// Stop the relay to `output_addr`, or every relay when none is given
+#[tauri::command]
+pub async fn stop_mavlink_relay(
+    output_addr: Option<String>,
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    let stopped: Vec<RelayTarget> = {
+        let mut targets = state.relay_targets.lock()
+            .map_err(|_| "Failed to lock relay targets")?;
+        match output_addr {
+            Some(output_addr) => {
+                let index = targets.iter()
+                    .position(|target| target.output_addr() == output_addr)
+                    .ok_or_else(|| format!("Not relaying to {output_addr}"))?;
+                vec![targets.remove(index)]
+            }
+            None => std::mem::take(&mut *targets),
+        }
+    };
+
+    // Joining the sender threads happens outside the lock
+    drop(stopped);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_relay_stats(
+    state: State<'_, MavlinkState>,
+) -> Result<Vec<RelayStats>, String> {
+    let targets = state.relay_targets.lock()
+        .map_err(|_| "Failed to lock relay targets")?;
+    Ok(targets.iter().map(RelayTarget::stats).collect())
+}
+
+// ===== MOTOR TEST COMMANDS =====
+
+#[tauri::command]
+pub async fn test_motor(
+    app_handle: tauri::AppHandle,
+    motor_id: u8,
+    throttle: u16,
+    duration_ms: u32,
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    // Verify connection
+    verify_connection(&state)?;
+
+    // Safety checks
+    if motor_id > 8 {
+        return Err("Invalid motor ID (must be 1-8)".to_string());
+    }
+    validate_motor_test(throttle, duration_ms)?;
+
+    claim_motor_test(&state)?;
+    let result = run_motor_test(&app_handle, &state, motor_id, throttle, duration_ms).await;
+    release_motor_test(&state)?;
+
+    result.map(|_| ())
+}
+
+// Test motors 1 to N in board order, N from the frame type. The first failure
+// or an emergency stop aborts the rest; motor_test_active stays set between
+// motors so no single test can start mid-sequence.
+#[tauri::command]
+pub async fn test_all_motors_sequence(
+    app_handle: tauri::AppHandle,
+    throttle: u16,
+    duration_ms_per_motor: u32,
+    state: State<'_, MavlinkState>,
+) -> Result<Vec<MotorTestResult>, String> {
+    verify_connection(&state)?;
+    verify_emergency_stop_clear(&state)?;
+    validate_motor_test(throttle, duration_ms_per_motor)?;
+    if is_vehicle_armed(&state)? {
+        return Err("Cannot test motors while the vehicle is armed".to_string());
+    }
+    let motor_count = connected_motor_count(&state)?;
+
+    claim_motor_test(&state)?;
+    let results = run_motor_sequence(
+        &state,
+        motor_count,
+        |motor_id| run_motor_test(&app_handle, &state, motor_id, throttle, duration_ms_per_motor),
+        |motor_id, status| emit_motor_test_progress(&app_handle, motor_id, status),
+    )
+    .await;
+    release_motor_test(&state)?;
+
+    Ok(results)
+}
+
+// Test motors 1..=motor_count in order. The first failure or an emergency
+// stop ends the running test on the vehicle and aborts the remaining motors.
+async fn run_motor_sequence<F, Fut>(
+    state: &MavlinkState,
+    motor_count: u8,
+    mut test_motor: F,
+    mut progress: impl FnMut(u8, &str),
+) -> Vec<MotorTestResult>
+where
+    F: FnMut(u8) -> Fut,
+    Fut: Future<Output = Result<Option<EscTelemetry>, String>>,
+{
+    let mut results = Vec::with_capacity(motor_count as usize);
+    let mut abort_reason: Option<String> = None;
+    for motor_id in 1..=motor_count {
+        let result = match &abort_reason {
+            Some(reason) => MotorTestResult::aborted(motor_id, reason.clone()),
+            None => {
+                progress(motor_id, "testing");
+                let outcome = test_motor(motor_id).await;
+                motor_test_result(state, motor_id, outcome)
+            }
+        };
+        if let (None, Some(e)) = (&abort_reason, &result.error) {
+            abort_reason = Some(format!("Motor {motor_id}: {e}"));
+            stop_motor_test(state, motor_id);
+        }
+        progress(motor_id, &result.status);
+        results.push(result);
+    }
+    results
+}
+
+#[tauri::command]
+pub async fn emergency_stop(
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    trigger_emergency_stop(&state)
+}
+
+// Latch the stop, then force a disarm without waiting for its COMMAND_ACK
+fn trigger_emergency_stop(state: &MavlinkState) -> Result<(), String> {
+    // This must complete in < 1ms for safety
+    let start = Instant::now();
+
+    // Set emergency stop flag immediately
+    {
+        let mut active = state.emergency_stop.active.write()
+            .map_err(|_| "Critical: Failed to set emergency stop")?;
+        *active = true;
+    }
+
+    // Record activation time
+    {
+        let mut last = state.emergency_stop.last_activation.lock()
+            .map_err(|_| "Failed to record emergency stop time")?;
+        *last = Some(Instant::now());
+    }
+
+    // Clear motor test flag if active
+    {
+        if let Ok(mut motor_test) = state.motor_test_active.write() {
+            *motor_test = false;
+        }
+    }
+
+    // Without a vehicle there is nothing to disarm
+    let disarm = match vehicle_target(state) {
+        Ok((target_system, target_component)) => send_message(state, &force_disarm_message(target_system, target_component)),
+        Err(_) => Ok(()),
+    };
+
+    // Verify completion time
+    let elapsed = start.elapsed();
+    if elapsed.as_micros() > 1000 {
+        eprintln!("WARNING: Emergency stop took {}μs (> 1ms)", elapsed.as_micros());
+    }
+
+    disarm.map_err(|e| format!("Emergency stop latched but the disarm was not sent: {e}"))
+}
+
+// Release the latch so arming, flight and motor test commands are accepted again
+#[tauri::command]
+pub async fn reset_emergency_stop(
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    clear_emergency_stop(&state)
+}
+
+fn clear_emergency_stop(state: &MavlinkState) -> Result<(), String> {
+    let mut active = state.emergency_stop.active.write()
+        .map_err(|_| "Failed to clear emergency stop")?;
+    *active = false;
+    Ok(())
+}
+
+// MAV_CMD_COMPONENT_ARM_DISARM with the magic value that disarms even in flight
+fn force_disarm_message(target_system: u8, target_component: u8) -> MavMessage {
+    MavMessage::COMMAND_LONG(COMMAND_LONG_DATA {
+        param1: 0.0,
+        param2: FORCE_ARM_DISARM_MAGIC,
+        param3: 0.0,
+        param4: 0.0,
+        param5: 0.0,
+        param6: 0.0,
+        param7: 0.0,
+        command: MavCmd::MAV_CMD_COMPONENT_ARM_DISARM,
+        target_system,
+        target_component,
+        confirmation: 0,
+    })
+}
+
+// ===== MOTOR TEST HELPERS =====
+
+fn validate_motor_test(throttle: u16, duration_ms: u32) -> Result<(), String> {
+    if throttle > 100 {
+        return Err("Invalid throttle percentage (must be 0-100)".to_string());
+    }
+    if duration_ms > 5000 {
+        return Err("Test duration too long (max 5 seconds)".to_string());
+    }
+    Ok(())
+}
+
+fn claim_motor_test(state: &MavlinkState) -> Result<(), String> {
+    let mut motor_test = state.motor_test_active.write()
+        .map_err(|_| "Failed to update motor test status")?;
+    if *motor_test {
+        return Err("Motor test already in progress".to_string());
+    }
+    *motor_test = true;
+    Ok(())
+}
+
+fn release_motor_test(state: &MavlinkState) -> Result<(), String> {
+    let mut motor_test = state.motor_test_active.write()
+        .map_err(|_| "Failed to update motor test status")?;
+    *motor_test = false;
+    Ok(())
+}
+
+// Spin one motor for `duration_ms` and return the peak ESC telemetry seen
+// meanwhile. Fails as soon as the emergency stop fires.
+async fn run_motor_test(
+    app_handle: &tauri::AppHandle,
+    state: &MavlinkState,
+    motor_id: u8,
+    throttle: u16,
+    duration_ms: u32,
+) -> Result<Option<EscTelemetry>, String> {
+    verify_emergency_stop_clear(state)?;
+    let params = motor_test_params(motor_id, throttle, duration_ms);
+    send_command_long(app_handle, state, MavCmd::MAV_CMD_DO_MOTOR_TEST, params).await?;
+
+    // NASA JPL Rule 2: the wait is bounded by the test duration
+    let started_ms = get_timestamp();
+    let deadline = Instant::now() + Duration::from_millis(duration_ms as u64);
+    let mut peak: Option<EscTelemetry> = None;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Ok(peak);
+        }
+        tokio::time::sleep(remaining.min(Duration::from_millis(MOTOR_TEST_POLL_INTERVAL_MS))).await;
+        verify_emergency_stop_clear(state)?;
+        if let Some(reading) = esc_reading_since(state, motor_id, started_ms) {
+            peak = Some(peak.map_or(reading, |peak| EscTelemetry {
+                rpm: peak.rpm.max(reading.rpm),
+                current_ca: peak.current_ca.max(reading.current_ca),
+                ..reading
+            }));
+        }
+    }
+}
+
+fn motor_test_params(motor_id: u8, throttle: u16, duration_ms: u32) -> [f32; 7] {
+    [
+        motor_id as f32,
+        MotorTestThrottleType::MOTOR_TEST_THROTTLE_PERCENT as u8 as f32,
+        throttle as f32,
+        duration_ms as f32 / 1000.0,
+        1.0,
+        MotorTestOrder::MOTOR_TEST_ORDER_BOARD as u8 as f32,
+        0.0,
+    ]
+}
+
+// Zero-throttle, zero-length DO_MOTOR_TEST ending the test the vehicle may
+// still be running. Sent without waiting for its COMMAND_ACK.
+fn stop_motor_test(state: &MavlinkState, motor_id: u8) {
+    let sent = vehicle_target(state).and_then(|(target_system, target_component)| {
+        let params = motor_test_params(motor_id, 0, 0);
+        let message = MavMessage::COMMAND_LONG(COMMAND_LONG_DATA {
+            param1: params[0],
+            param2: params[1],
+            param3: params[2],
+            param4: params[3],
+            param5: params[4],
+            param6: params[5],
+            param7: params[6],
+            command: MavCmd::MAV_CMD_DO_MOTOR_TEST,
+            target_system,
+            target_component,
+            confirmation: 0,
+        });
+        send_message(state, &message)
+    });
+    if let Err(e) = sent {
+        eprintln!("Failed to stop motor {motor_id} test: {e}");
+    }
+}
+
+// A failure caused by the emergency stop counts as an abort
+fn motor_test_result(
+    state: &MavlinkState,
+    motor_id: u8,
+    outcome: Result<Option<EscTelemetry>, String>,
+) -> MotorTestResult {
+    match outcome {
+        Ok(peak) => MotorTestResult {
+            motor_id,
+            success: true,
+            status: "passed".to_string(),
+            error: None,
+            measured_rpm: peak.map(|esc| esc.rpm as f32),
+            current_draw_a: peak.map(|esc| f32::from(esc.current_ca) / 100.0),
+        },
+        Err(e) if verify_emergency_stop_clear(state).is_err() => MotorTestResult::aborted(motor_id, e),
+        Err(e) => MotorTestResult {
+            status: "failed".to_string(),
+            ..MotorTestResult::aborted(motor_id, e)
+        },
+    }
+}
+
+fn emit_motor_test_progress(app_handle: &tauri::AppHandle, motor_id: u8, status: &str) {
+    let payload = serde_json::json!({ "motor_id": motor_id, "status": status });
+    if let Err(e) = app_handle.emit_all("motor-test-progress", payload) {
+        eprintln!("Failed to emit motor test progress: {e}");
+    }
+}
+
+// Telemetry of the ESC driving `motor_id` (board order), if it reported since `since_ms`
+fn esc_reading_since(state: &MavlinkState, motor_id: u8, since_ms: u64) -> Option<EscTelemetry> {
+    state.esc_telemetry.read().ok()?
+        .iter()
+        .find(|esc| esc.motor_id == motor_id && esc.timestamp_ms >= since_ms)
+        .copied()
+}
+
+fn connected_motor_count(state: &MavlinkState) -> Result<u8, String> {
+    let info = state.vehicle_info.read()
+        .map_err(|_| "Failed to read vehicle info")?
+        .clone()
+        .ok_or("Vehicle info not available")?;
+    frame_motor_count(&info.vehicle_type)
+        .ok_or_else(|| format!("Motor count unknown for vehicle type {}", info.vehicle_type))
+}
+
+fn frame_motor_count(vehicle_type: &str) -> Option<u8> {
+    match vehicle_type {
+        "Helicopter" => Some(1),
+        "COAXIAL" => Some(2),
+        "TRICOPTER" => Some(3),
+        "Quadcopter" => Some(4),
+        "Hexacopter" => Some(6),
+        "Octocopter" => Some(8),
+        _ => None,
+    }
+}
+
+// ===== CALIBRATION COMMANDS =====
+
+#[tauri::command]
+pub async fn calibrate_accelerometer(
+    app_handle: tauri::AppHandle,
+    state: State<'_, MavlinkState>,
+) -> Result<CalibrationResult, String> {
+    // Verify connection
+    verify_connection(&state)?;
+
+    // Check if already calibrating
+    {
+        let mut calibrating = state.calibration_active.write()
+            .map_err(|_| "Failed to update calibration status")?;
+        if *calibrating {
+            return Err("Calibration already in progress".to_string());
+        }
+        *calibrating = true;
+    }
+
+    let params = [0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+    if let Err(e) = send_command_long(&app_handle, &state, MavCmd::MAV_CMD_PREFLIGHT_CALIBRATION, params).await {
+        set_calibration_active(&state, false);
+        return Err(e);
+    }
+
+    // The operator drives the six orientations via advance/confirm commands
+    let (completion_tx, completion_rx) = oneshot::channel();
+    {
+        let mut session = state.calibration_session.lock()
+            .map_err(|_| "Failed to lock calibration session")?;
+        *session = Some(accelerometer_session(Some(completion_tx)));
+    }
+
+    // Each step has its own timeout; this bounds a session that is never driven
+    let session_timeout = Duration::from_secs(CALIBRATION_STEP_TIMEOUT_SECS * 7);
+    match tokio::time::timeout(session_timeout, completion_rx).await {
+        Ok(Ok(result)) => Ok(result),
+        Ok(Err(_)) => Err("Accelerometer calibration aborted".to_string()),
+        Err(_) => {
+            abort_calibration_session(&state);
+            Err("Accelerometer calibration timed out".to_string())
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn advance_calibration_step(
+    app_handle: tauri::AppHandle,
+    state: State<'_, MavlinkState>,
+) -> Result<CalibrationStep, String> {
+    // Move to the next orientation and arm the confirmation channel
+    let (confirm_rx, mut step) = {
+        let mut guard = state.calibration_session.lock()
+            .map_err(|_| "Failed to lock calibration session")?;
+        let session = guard.as_mut().ok_or("No calibration in progress")?;
+        if session.confirm_tx.is_some() {
+            return Err("Waiting for confirmation of the current step".to_string());
+        }
+        if session.current_step >= session.total_steps {
+            return Err("All calibration steps already completed".to_string());
+        }
+
+        session.current_step += 1;
+        let (confirm_tx, confirm_rx) = oneshot::channel();
+        session.confirm_tx = Some(confirm_tx);
+
+        let step = CalibrationStep {
+            sensor_type: session.sensor_type.clone(),
+            step: session.current_step,
+            total_steps: session.total_steps,
+            instruction: session.step_instructions
+                .get(usize::from(session.current_step) - 1)
+                .cloned()
+                .unwrap_or_default(),
+            completed: false,
+            result: None,
+        };
+        (confirm_rx, step)
+    };
+
+    if let Err(e) = app_handle.emit_all("calibration-step", step.clone()) {
+        eprintln!("Failed to emit calibration step: {e}");
+    }
+
+    // Wait for the operator to confirm the vehicle is in position
+    let timeout = Duration::from_secs(CALIBRATION_STEP_TIMEOUT_SECS);
+    match tokio::time::timeout(timeout, confirm_rx).await {
+        Ok(Ok(())) => {}
+        Ok(Err(_)) => return Err("Calibration session aborted".to_string()),
+        Err(_) => {
+            abort_calibration_session(&state);
+            return Err(format!("Calibration step {} timed out", step.step));
+        }
+    }
+
+    // Tell the vehicle which orientation it is in (steps follow the
+    // ACCELCAL_VEHICLE_POS order), then average what its IMU measures there
+    let since_ms = get_timestamp();
+    let params = [f32::from(step.step), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+    if let Err(e) = send_command_long(&app_handle, &state, MavCmd::MAV_CMD_ACCELCAL_VEHICLE_POS, params).await {
+        abort_calibration_session(&state);
+        return Err(e);
+    }
+    tokio::time::sleep(Duration::from_millis(ACCEL_SAMPLE_WINDOW_MS)).await;
+    let sample = state.imu_samples.lock().ok().and_then(|samples| mean_accel_since(&samples, since_ms));
+    let sample = match sample {
+        Some(sample) => sample,
+        None => {
+            abort_calibration_session(&state);
+            return Err(format!("Too few SCALED_IMU readings for calibration step {}", step.step));
+        }
+    };
+
+    let mut guard = state.calibration_session.lock()
+        .map_err(|_| "Failed to lock calibration session")?;
+    let session = guard.as_mut().ok_or("Calibration session aborted")?;
+    if let Some(result) = session.record_sample(sample) {
+        if let Some(completion_tx) = session.completion_tx.take() {
+            // The caller may have timed out already; the result is still returned here
+            let _ = completion_tx.send(result.clone());
+        }
+        *guard = None;
+        drop(guard);
+        set_calibration_active(&state, false);
+
+        step.completed = true;
+        step.result = Some(result);
+    }
+
+    Ok(step)
+}
+
+#[tauri::command]
+pub async fn confirm_calibration_step(
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    let mut guard = state.calibration_session.lock()
+        .map_err(|_| "Failed to lock calibration session")?;
+    let session = guard.as_mut().ok_or("No calibration in progress")?;
+    let confirm_tx = session.confirm_tx.take()
+        .ok_or("No calibration step is awaiting confirmation")?;
+
+    confirm_tx.send(())
+        .map_err(|_| "Calibration step is no longer waiting".to_string())
+}
+
+// The vehicle finishes gyro calibration before it ACKs and keeps the offsets
+// to itself, so the ACK is the whole outcome
+#[tauri::command]
+pub async fn calibrate_gyroscope(
+    app_handle: tauri::AppHandle,
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    // Verify connection
+    verify_connection(&state)?;
+
+    // Check if already calibrating
+    {
+        let mut calibrating = state.calibration_active.write()
+            .map_err(|_| "Failed to update calibration status")?;
+        if *calibrating {
+            return Err("Calibration already in progress".to_string());
+        }
+        *calibrating = true;
+    }
+
+    let params = [1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+    let result = send_command_long(&app_handle, &state, MavCmd::MAV_CMD_PREFLIGHT_CALIBRATION, params).await;
+    set_calibration_active(&state, false);
+    result
+}
+
+#[tauri::command]
+pub async fn calibrate_magnetometer(
+    app_handle: tauri::AppHandle,
+    full_sphere: bool,
+    state: State<'_, MavlinkState>,
+) -> Result<CalibrationResult, String> {
+    // Verify connection
+    verify_connection(&state)?;
+
+    // Check if already calibrating
+    {
+        let mut calibrating = state.calibration_active.write()
+            .map_err(|_| "Failed to update calibration status")?;
+        if *calibrating {
+            return Err("Calibration already in progress".to_string());
+        }
+        *calibrating = true;
+    }
+
+    // Listen for the reports before the vehicle can start sending them
+    let (completion_tx, completion_rx) = oneshot::channel();
+    *state.mag_cal_session.lock().map_err(|_| "Failed to lock compass calibration")? = Some(MagCalSession {
+        completion_tx: Some(completion_tx),
+        ..MagCalSession::default()
+    });
+
+    // param3 = 1 (simple) or param3 = 2 (full sphere)
+    let params = [0.0, 0.0, if full_sphere { 2.0 } else { 1.0 }, 0.0, 0.0, 0.0, 0.0];
+    if let Err(e) = send_command_long(&app_handle, &state, MavCmd::MAV_CMD_PREFLIGHT_CALIBRATION, params).await {
+        end_mag_cal_session(&state);
+        return Err(e);
+    }
+
+    // The vehicle streams MAG_CAL_PROGRESS while it is rotated, then a
+    // MAG_CAL_REPORT per compass
+    let outcome = tokio::time::timeout(Duration::from_secs(MAG_CAL_TIMEOUT_SECS), completion_rx).await;
+    end_mag_cal_session(&state);
+    let mut result = match outcome {
+        Ok(Ok(result)) => result,
+        Ok(Err(_)) => return Err("Magnetometer calibration aborted".to_string()),
+        Err(_) => {
+            // Best effort: stop the vehicle's calibrator so it does not save late
+            let _ = send_command_long(&app_handle, &state, MavCmd::MAV_CMD_DO_CANCEL_MAG_CAL, [0.0; 7]).await;
+            return Err(format!("Magnetometer calibration timed out after {MAG_CAL_TIMEOUT_SECS} s"));
+        }
+    };
+    let cal_mode = if full_sphere { "full sphere" } else { "simple" };
+    result.message = format!("{} ({cal_mode})", result.message);
+    Ok(result)
+}
+
+// ESC calibration happens on the vehicle after a power cycle, out of reach of
+// MAVLink, so success here only means the vehicle accepted the command;
+// `esc-calibration-complete` says so rather than claiming the ESCs are calibrated
+#[tauri::command]
+pub async fn calibrate_escs(
+    app_handle: tauri::AppHandle,
+    safety_confirmed: bool,
+    state: State<'_, MavlinkState>,
+) -> Result<(), String> {
+    verify_esc_calibration_allowed(&state, safety_confirmed)?;
+
+    // Check if already calibrating
+    {
+        let mut calibrating = state.calibration_active.write()
+            .map_err(|_| "Failed to update calibration status")?;
+        if *calibrating {
+            return Err("Calibration already in progress".to_string());
+        }
+        *calibrating = true;
+    }
+
+    let params = [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0];
+    let result = send_command_long(&app_handle, &state, MavCmd::MAV_CMD_PREFLIGHT_CALIBRATION, params).await;
+    set_calibration_active(&state, false);
+    result?;
+
+    // Guide the operator through the throttle cycling protocol
+    let steps = [
+        "Set RC throttle to maximum and power cycle the vehicle",
+        "Wait for the ESCs to beep, then lower throttle to minimum",
+        "Wait for the ESCs to confirm the low endpoint",
+        "Power cycle the vehicle to save the calibration",
+    ];
+    for (index, instruction) in steps.iter().enumerate() {
+        let payload = serde_json::json!({
+            "step": index + 1,
+            "total_steps": steps.len(),
+            "instruction": instruction
+        });
+        if let Err(e) = app_handle.emit_all("esc-calibration-progress", payload) {
+            eprintln!("Failed to emit ESC calibration progress: {e}");
+        }
+    }
+
+    let payload = serde_json::json!({
+        "accepted": true,
+        "verified": false,
+        "message": "The vehicle accepted ESC calibration; confirm the ESCs' tones, as the result is not reported over MAVLink"
+    });
+    if let Err(e) = app_handle.emit_all("esc-calibration-complete", payload) {
+        eprintln!("Failed to emit ESC calibration completion: {e}");
+    }
+
+    Ok(())
+}
+
+// The propeller confirmation is checked first, so nothing else can let an
+// unconfirmed calibration through
+fn verify_esc_calibration_allowed(state: &MavlinkState, safety_confirmed: bool) -> Result<(), String> {
+    // Safety interlock: ESC calibration spins motors to full throttle
+    if !safety_confirmed {
+        return Err("ESC calibration requires confirmation that all propellers have been removed".to_string());
+    }
+
+    verify_connection(state)?;
+    if is_vehicle_armed(state)? {
+        return Err("Cannot calibrate ESCs while the vehicle is armed".to_string());
+    }
+    let motor_test = state.motor_test_active.read()
+        .map_err(|_| "Failed to read motor test status")?;
+    if *motor_test {
+        return Err("Cannot calibrate ESCs while a motor test is active".to_string());
+    }
+    Ok(())
+}
+
+// ===== LINK MONITORING =====
+
+// Simulated vehicle link: delivers a vehicle heartbeat once per second until the
+// connection epoch changes, then publishes the final status and exits
+async fn run_mock_link(app_handle: tauri::AppHandle, epoch: u64) {
+    loop {
+        tokio::time::sleep(Duration::from_millis(HEARTBEAT_INTERVAL_MS)).await;
+        let state = app_handle.state::<MavlinkState>();
+        let current = state.connection_epoch.load(Ordering::SeqCst) == epoch;
+
+        if current {
+            handle_incoming_message(&app_handle, &state, mock_vehicle_header(&state), &mock_heartbeat(&state));
+            handle_incoming_message(&app_handle, &state, mock_vehicle_header(&state), &mock_sys_status());
+            handle_incoming_message(&app_handle, &state, mock_vehicle_header(&state), &mock_ekf_status_report());
+        } else {
+            update_link_quality(&app_handle, &state);
+        }
+        publish_connection_status(&app_handle, &state);
+
+        if !current {
+            break;
+        }
+    }
+}
+
+// Real vehicle link: hands every message from the transport reader to
+// handle_incoming_message until the connection epoch changes or the transport
+// fails. Every epoch change closes the transport, which ends the wait.
+async fn run_vehicle_link(app_handle: tauri::AppHandle, epoch: u64, mut events: mpsc::UnboundedReceiver<LinkEvent>) {
+    loop {
+        let event = events.recv().await;
+        let state = app_handle.state::<MavlinkState>();
+        if state.connection_epoch.load(Ordering::SeqCst) != epoch {
+            update_link_quality(&app_handle, &state);
+            // The reader may close right after a status went out; the final one must not be throttled
+            if let Ok(mut publisher) = state.status_publisher.lock() {
+                publisher.last_emit_at = None;
+            }
+            publish_connection_status(&app_handle, &state);
+            break;
+        }
+
+        match event {
+            Some(LinkEvent::Message(frame)) => {
+                relay_frame(&state, &frame.raw);
+                record_tlog_frame(&state, &frame.raw);
+                handle_incoming_message(&app_handle, &state, frame.header, &frame.message);
+                // Chunk fields are extensions only the raw frame carries
+                if let MavMessage::STATUSTEXT(report) = &frame.message {
+                    handle_statustext(&app_handle, &state, &frame.header, report, frame.statustext_chunk());
+                }
+            }
+            Some(LinkEvent::Closed(reason)) => lose_connection(&app_handle, &state, &reason),
+            None => lose_connection(&app_handle, &state, "Link reader stopped"),
+        }
+        publish_connection_status(&app_handle, &state);
+    }
+}
+
+// Every HEARTBEAT_CHECK_INTERVAL_MS, decay the link quality with the heartbeat
+// age and emit `mavlink-connection-lost` / `mavlink-connection-restored` as the
+// heartbeats stop and resume. The link stays open in between so that a
+// vehicle briefly out of radio range reconnects by itself.
+async fn run_heartbeat_monitor(app_handle: tauri::AppHandle, epoch: u64) {
+    loop {
+        tokio::time::sleep(Duration::from_millis(HEARTBEAT_CHECK_INTERVAL_MS)).await;
+        let state = app_handle.state::<MavlinkState>();
+        if state.connection_epoch.load(Ordering::SeqCst) != epoch {
+            break;
+        }
+
+        update_link_quality(&app_handle, &state);
+        let expired = heartbeat_expired(&state);
+        if expired && !state.heartbeat_lost.swap(true, Ordering::SeqCst) {
+            let reason = format!("No heartbeat from the vehicle for {} s", HEARTBEAT_TIMEOUT_MS / 1000);
+            emit_connection_lost(&app_handle, &reason);
+        } else if !expired && state.heartbeat_lost.swap(false, Ordering::SeqCst) {
+            let last_heartbeat = connection_snapshot(&state).ok().and_then(|status| status.last_heartbeat);
+            let payload = serde_json::json!({ "last_heartbeat": last_heartbeat });
+            if let Err(e) = app_handle.emit_all("mavlink-connection-restored", payload) {
+                eprintln!("Failed to emit connection restore: {e}");
+            }
+        }
+        publish_connection_status(&app_handle, &state);
+    }
+}
+
+// Open the transport and wait for the vehicle's first heartbeat. Heartbeats
+// from other ground stations on the link do not count.
+async fn open_vehicle_link(connection_string: &str, connect_timeout: Duration) -> Result<OpenedLink, String> {
+    let (events_tx, mut events) = mpsc::unbounded_channel();
+    let link = if is_udp_connection_string(connection_string) {
+        VehicleLink::open_udp(&parse_udp_endpoint(connection_string)?, events_tx)?
+    } else if is_tcp_connection_string(connection_string) {
+        let endpoint = parse_tcp_endpoint(connection_string)?;
+        // Connecting blocks for up to the timeout, so keep it off the async workers
+        tauri::async_runtime::spawn_blocking(move || VehicleLink::open_tcp(&endpoint, connect_timeout, events_tx))
+            .await
+            .map_err(|e| format!("TCP connect task failed: {e}"))??
+    } else {
+        VehicleLink::open_serial(&parse_serial_endpoint(connection_string)?, events_tx)?
+    };
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(FIRST_HEARTBEAT_TIMEOUT_SECS);
+    loop {
+        match tokio::time::timeout_at(deadline, events.recv()).await {
+            Err(_) => {
+                return Err(format!(
+                    "No heartbeat from {} within {FIRST_HEARTBEAT_TIMEOUT_SECS} s; {}",
+                    link.description(), link.silence_hint()
+                ));
+            }
+            Ok(None) => return Err(format!("Reader for {} stopped", link.description())),
+            Ok(Some(LinkEvent::Closed(reason))) => return Err(reason),
+            Ok(Some(LinkEvent::Message(frame))) => {
+                if let MavMessage::HEARTBEAT(heartbeat) = frame.message {
+                    if heartbeat.mavtype != MavType::MAV_TYPE_GCS {
+                        return Ok(OpenedLink { link, events, header: frame.header, heartbeat, raw: frame.raw });
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Queue a received frame for every relay target
+fn relay_frame(state: &MavlinkState, raw: &[u8]) {
+    if let Ok(targets) = state.relay_targets.lock() {
+        for target in targets.iter() {
+            target.forward(raw);
+        }
+    }
+}
+
+// Dropping the link joins its reader thread and closes the transport
+fn close_vehicle_link(state: &MavlinkState) -> Result<(), String> {
+    let link = state.vehicle_link.lock()
+        .map_err(|_| "Failed to lock vehicle link")?
+        .take();
+    drop(link);
+    Ok(())
+}
+
+// Simulated links have no transport; the mock vehicle answers requests instead
+fn is_simulated_link(state: &MavlinkState) -> bool {
+    state.vehicle_link.lock().map_or(false, |link| link.is_none())
+}
+
+// GCS heartbeat once per second until `stop` is set. ArduPilot's GCS failsafe
+// trips when these stop arriving, so repeated send failures drop the connection.
+async fn run_gcs_heartbeat(app_handle: tauri::AppHandle, stop: Arc<AtomicBool>) {
+    let state = app_handle.state::<MavlinkState>();
+    if let Err(e) = send_gcs_heartbeats(&state, &stop).await {
+        fail_connection(&app_handle, &state, &e);
+    }
+}
+
+// Ok once stopped, or the last error after MAX_HEARTBEAT_SEND_FAILURES failed sends in a row
+async fn send_gcs_heartbeats(state: &MavlinkState, stop: &AtomicBool) -> Result<(), String> {
+    let mut consecutive_failures = 0;
+    loop {
+        tokio::time::sleep(Duration::from_millis(HEARTBEAT_INTERVAL_MS)).await;
+        if stop.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        match send_message(state, &gcs_heartbeat()) {
+            Ok(()) => consecutive_failures = 0,
+            Err(e) => {
+                consecutive_failures += 1;
+                eprintln!("Failed to send GCS heartbeat: {e}");
+                if consecutive_failures >= MAX_HEARTBEAT_SEND_FAILURES {
+                    return Err(e);
+                }
+            }
+        }
+    }
+}
+
+fn start_gcs_heartbeat(app_handle: &tauri::AppHandle, state: &MavlinkState) -> Result<(), String> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let previous = state.gcs_heartbeat_stop.lock()
+        .map_err(|_| "Failed to lock heartbeat task")?
+        .replace(stop.clone());
+    if let Some(previous) = previous {
+        previous.store(true, Ordering::SeqCst);
+    }
+    tauri::async_runtime::spawn(run_gcs_heartbeat(app_handle.clone(), stop));
+    Ok(())
+}
+
+fn stop_gcs_heartbeat(state: &MavlinkState) -> Result<(), String> {
+    let stop = state.gcs_heartbeat_stop.lock()
+        .map_err(|_| "Failed to lock heartbeat task")?
+        .take();
+    if let Some(stop) = stop {
+        stop.store(true, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
+// Mark the link down after it stopped accepting writes
+fn fail_connection(app_handle: &tauri::AppHandle, state: &MavlinkState, reason: &str) {
+    mark_link_down(state);
+    let payload = serde_json::json!({ "reason": reason });
+    if let Err(e) = app_handle.emit_all("connection-failed", payload) {
+        eprintln!("Failed to emit connection failure: {e}");
+    }
+}
+
+// Mark the link down after the transport closed; unlike a heartbeat timeout
+// this cannot recover, so the monitor must not report a restore
+fn lose_connection(app_handle: &tauri::AppHandle, state: &MavlinkState, reason: &str) {
+    mark_link_down(state);
+    state.heartbeat_lost.store(true, Ordering::SeqCst);
+    emit_connection_lost(app_handle, reason);
+}
+
+fn emit_connection_lost(app_handle: &tauri::AppHandle, reason: &str) {
+    let payload = serde_json::json!({ "reason": reason });
+    if let Err(e) = app_handle.emit_all("mavlink-connection-lost", payload) {
+        eprintln!("Failed to emit connection loss: {e}");
+    }
+}
+
+fn mark_link_down(state: &MavlinkState) {
+    if let Ok(mut status) = state.connection_status.write() {
+        status.connected = false;
+        status.link_quality = 0.0;
+    }
+    // The link task publishes the new status on its next tick and exits
+    state.connection_epoch.fetch_add(1, Ordering::SeqCst);
+    if let Err(e) = close_vehicle_link(state) {
+        eprintln!("{e}");
+    }
+}
+
+// Entry point for every received message
+fn handle_incoming_message(
+    app_handle: &tauri::AppHandle,
+    state: &MavlinkState,
+    header: MavHeader,
+    message: &MavMessage,
+) {
+    state.messages_received.fetch_add(1, Ordering::Relaxed);
+
+    log_inspector_message(state, message, get_timestamp());
+    if let Ok(mut tracker) = state.link_tracker.lock() {
+        tracker.record_sequence(header.sequence);
+    }
+
+    match message {
+        // Other ground stations sharing the link say nothing about the vehicle
+        MavMessage::HEARTBEAT(heartbeat) if heartbeat.mavtype != MavType::MAV_TYPE_GCS => {
+            record_heartbeat(app_handle, state);
+            let armed = heartbeat.base_mode.contains(MavModeFlag::MAV_MODE_FLAG_SAFETY_ARMED);
+            let flight_mode = flight_mode_name(heartbeat);
+            if let Some(was_armed) = record_armed_state(state, armed, &flight_mode) {
+                update_mission_lock(app_handle, was_armed, armed);
+            }
+
+            let payload = serde_json::json!({
+                "system_id": header.system_id,
+                "flight_mode": flight_mode,
+                "armed": armed,
+            });
+            if let Err(e) = app_handle.emit_all("mavlink-heartbeat", payload) {
+                eprintln!("Failed to emit heartbeat: {e}");
+            }
+        }
+        MavMessage::MISSION_CURRENT(current) => {
+            update_vehicle_progress(state, |progress| progress.current_seq = Some(current.seq));
+        }
+        MavMessage::GLOBAL_POSITION_INT(position) => handle_global_position(app_handle, state, position),
+        MavMessage::VFR_HUD(hud) => handle_vfr_hud(app_handle, state, hud),
+        MavMessage::GPS_RAW_INT(gps) => handle_gps_raw_int(app_handle, state, gps),
+        MavMessage::COMMAND_ACK(ack) => complete_pending_command(state, &header, ack),
+        MavMessage::PARAM_VALUE(value) => handle_param_value(app_handle, state, value),
+        MavMessage::SYS_STATUS(status) => {
+            handle_sys_status(app_handle, state, status);
+            update_battery(app_handle, state, |battery| apply_sys_status_battery(battery, status));
+        }
+        MavMessage::BATTERY_STATUS(status) if status.id == 0 => {
+            update_battery(app_handle, state, |battery| apply_battery_status(battery, status));
+        }
+        MavMessage::EKF_STATUS_REPORT(report) => handle_ekf_status_report(app_handle, state, report),
+        MavMessage::VIBRATION(vibration) => handle_vibration(app_handle, state, vibration),
+        MavMessage::ESC_TELEMETRY_1_TO_4(esc) => {
+            let reports = esc_reports(1, &esc.voltage, &esc.current, &esc.totalcurrent, &esc.rpm, &esc.count, &esc.temperature);
+            handle_esc_telemetry(app_handle, state, reports);
+        }
+        MavMessage::ESC_TELEMETRY_5_TO_8(esc) => {
+            let reports = esc_reports(5, &esc.voltage, &esc.current, &esc.totalcurrent, &esc.rpm, &esc.count, &esc.temperature);
+            handle_esc_telemetry(app_handle, state, reports);
+        }
+        MavMessage::ATTITUDE(attitude) => handle_attitude(app_handle, state, attitude),
+        MavMessage::SERIAL_CONTROL(control) => handle_serial_control(app_handle, state, control),
+        MavMessage::SCALED_IMU(imu) => record_scaled_imu(state, imu),
+        MavMessage::MAG_CAL_PROGRESS(progress) => handle_mag_cal_progress(app_handle, state, progress),
+        MavMessage::MAG_CAL_REPORT(report) => handle_mag_cal_report(app_handle, state, report),
+        MavMessage::MISSION_REQUEST_INT(request) => handle_mission_request(state, &header, request.seq),
+        // Deprecated float request; MISSION_ITEM_INT answers it just as well
+        MavMessage::MISSION_REQUEST(request) => handle_mission_request(state, &header, request.seq),
+        MavMessage::MISSION_ACK(ack) => handle_mission_ack(state, &header, ack),
+        _ => {}
+    }
+}
+
+// Start a parameter download session; fails while another is in progress
+fn begin_parameter_sync(state: &MavlinkState, progress_event: &'static str) -> Result<oneshot::Receiver<u32>, String> {
+    let (done_tx, done_rx) = oneshot::channel();
+    let mut sync = state.parameter_sync.lock()
+        .map_err(|_| "Failed to lock parameter sync")?;
+    if sync.is_some() {
+        return Err("Parameter sync already in progress".to_string());
+    }
+    *sync = Some(ParameterSync {
+        total: None,
+        received: HashSet::new(),
+        done_tx: Some(done_tx),
+        progress_event,
+    });
+    state.parameters_synced.store(false, Ordering::SeqCst);
+    Ok(done_rx)
+}
+
+// True once every parameter has arrived, false when the retry rounds run out
+async fn download_parameters(
+    app_handle: &tauri::AppHandle,
+    state: &MavlinkState,
+    done_rx: &mut oneshot::Receiver<u32>,
+    (target_system, target_component): (u8, u8),
+) -> Result<bool, String> {
+    let request_list = PARAM_REQUEST_LIST_DATA { target_system, target_component };
+    transmit_param_request_list(app_handle, state, request_list.clone())?;
+
+    for round in 0..=MAX_PARAMETER_RETRY_ROUNDS {
+        if round > 0 {
+            match missing_parameter_indices(state)? {
+                Some(missing) if missing.len() <= MAX_PARAMETER_READS_PER_ROUND => {
+                    for index in missing {
+                        send_message(state, &MavMessage::PARAM_REQUEST_READ(PARAM_REQUEST_READ_DATA {
+                            param_index: index as i16,
+                            target_system,
+                            target_component,
+                            param_id: [0; 16],
+                        }))?;
+                    }
+                }
+                _ => transmit_param_request_list(app_handle, state, request_list.clone())?,
+            }
+        }
+        if wait_for_parameters(state, done_rx).await? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// Wait until the download completes (true) or stalls for PARAMETER_DOWNLOAD_IDLE_MS (false)
+async fn wait_for_parameters(state: &MavlinkState, done_rx: &mut oneshot::Receiver<u32>) -> Result<bool, String> {
+    let idle = Duration::from_millis(PARAMETER_DOWNLOAD_IDLE_MS);
+    let mut last_received = parameters_received(state)?;
+    // NASA JPL Rule 2: every extra wait needs a new parameter, of which there are at most u16::MAX
+    for _ in 0..=u16::MAX {
+        match tokio::time::timeout(idle, &mut *done_rx).await {
+            Ok(Ok(_)) => return Ok(true),
+            Ok(Err(_)) => return Err("Parameter download aborted: vehicle disconnected".to_string()),
+            Err(_) => {
+                let received = parameters_received(state)?;
+                if received == last_received {
+                    return Ok(false);
+                }
+                last_received = received;
+            }
+        }
+    }
+    Ok(false)
+}
+
+fn parameters_received(state: &MavlinkState) -> Result<usize, String> {
+    let sync = state.parameter_sync.lock()
+        .map_err(|_| "Failed to lock parameter sync")?;
+    Ok(sync.as_ref().map_or(0, |session| session.received.len()))
+}
+
+// Indices not yet received, or None before the vehicle has reported its count
+fn missing_parameter_indices(state: &MavlinkState) -> Result<Option<Vec<u16>>, String> {
+    let sync = state.parameter_sync.lock()
+        .map_err(|_| "Failed to lock parameter sync")?;
+    Ok(sync.as_ref().and_then(|session| {
+        session.total.map(|total| (0..total).filter(|index| !session.received.contains(index)).collect())
+    }))
+}
+
+// Track mission progress and move the vehicle on the map, emitting `vehicle-position`
+// at the configured rate
+fn handle_global_position(app_handle: &tauri::AppHandle, state: &MavlinkState, position: &GLOBAL_POSITION_INT_DATA) {
+    let coord = Coordinate {
+        lat: position.lat as f64 / 1e7,
+        lng: position.lon as f64 / 1e7,
+        alt: Some(position.relative_alt as f64 / 1000.0),
+    };
+    update_vehicle_progress(state, |progress| {
+        progress.position = Some(coord.clone());
+        progress.altitude_amsl_m = Some(position.alt as f64 / 1000.0);
+    });
+
+    // Velocities are cm/s north and east; hdg is centidegrees, UINT16_MAX when unknown
+    let vx = position.vx as f64 / 100.0;
+    let vy = position.vy as f64 / 100.0;
+    let heading = if position.hdg == u16::MAX {
+        vy.atan2(vx).to_degrees().rem_euclid(360.0)
+    } else {
+        position.hdg as f64 / 100.0
+    };
+    // GLOBAL_POSITION_INT carries no accuracy; estimate it from the last HDOP
+    let hdop = state.gps_status.read().ok().and_then(|gps| gps.as_ref().and_then(|gps| gps.hdop));
+    let gps = GpsData {
+        coordinate: coord,
+        heading,
+        speed: vx.hypot(vy),
+        accuracy: hdop.map_or(0.0, |hdop| f64::from(hdop) * GPS_UERE_M),
+    };
+
+    if let Err(e) = app_handle.state::<MapFeaturesState>().set_gps_position(gps.clone()) {
+        eprintln!("Failed to update map GPS position: {e}");
+    }
+    if !telemetry_due(state, TelemetryStream::Position) {
+        return;
+    }
+    if let Err(e) = app_handle.emit_all("vehicle-position", gps) {
+        eprintln!("Failed to emit vehicle position: {e}");
+    }
+}
+
+// Hand a COMMAND_ACK to the command awaiting it; IN_PROGRESS keeps it pending
+fn complete_pending_command(state: &MavlinkState, header: &MavHeader, ack: &COMMAND_ACK_DATA) {
+    let command_id = ack.command as u16;
+    let result = MavCmdResult::from(ack.result);
+    if result == MavCmdResult::InProgress {
+        return;
+    }
+
+    let mut pending = match state.pending_commands.lock() {
+        Ok(pending) => pending,
+        Err(_) => return,
+    };
+    if !pending.get(&command_id).map_or(false, |cmd| cmd.matches(header, command_id)) {
+        return;
+    }
+    if let Some(command) = pending.remove(&command_id) {
+        // The sender has already given up if the receiver is gone
+        let _ = command.result_tx.send(result);
+    }
+}
+
+// Store a PARAM_VALUE and advance the running parameter sync, if any
+fn handle_param_value(app_handle: &tauri::AppHandle, state: &MavlinkState, value: &PARAM_VALUE_DATA) {
+    if let Some((event, progress)) = record_param_value(state, value) {
+        if let Err(e) = app_handle.emit_all(event, progress) {
+            eprintln!("Failed to emit parameter sync progress: {e}");
+        }
+    }
+}
+
+// The sync progress event to emit, when the value belongs to a running sync
+fn record_param_value(
+    state: &MavlinkState,
+    value: &PARAM_VALUE_DATA,
+) -> Option<(&'static str, ParameterSyncProgress)> {
+    let id = param_id_string(&value.param_id);
+    let param_type = param_type_name(value.param_type).to_string();
+    if let Ok(mut params) = state.parameters.write() {
+        let param = params.entry(id.clone()).or_insert_with(|| Parameter {
+            id,
+            value: value.param_value,
+            param_type: param_type.clone(),
+            description: None,
+            min_value: None,
+            max_value: None,
+            units: None,
+            increment: None,
+            default_value: None,
+            values: Vec::new(),
+            bitmask: Vec::new(),
+        });
+        if param.description.is_none() {
+            if let Some(metadata) = state.parameter_metadata.read().ok().and_then(|defs| defs.get(&param.id).cloned()) {
+                param_metadata::apply_metadata(param, &metadata);
+            }
+        }
+        param.value = value.param_value;
+        param.param_type = param_type;
+    }
+
+    let confirmation = state.pending_param_sets.lock().ok()
+        .and_then(|mut pending| pending.remove(&param_id_string(&value.param_id)));
+    if let Some(echo_tx) = confirmation {
+        // The writer has already given up if the receiver is gone
+        let _ = echo_tx.send(value.param_value);
+    }
+
+    if value.param_index == UNINDEXED_PARAM {
+        return None;
+    }
+    let mut sync = state.parameter_sync.lock().ok()?;
+    let session = sync.as_mut()?;
+    let total = *session.total.get_or_insert(value.param_count);
+    session.received.insert(value.param_index);
+    let received = session.received.len() as u32;
+    let progress = ParameterSyncProgress {
+        received,
+        total: u32::from(total),
+        percent: if total == 0 { 100.0 } else { f64::from(received) * 100.0 / f64::from(total) },
+    };
+    let event = session.progress_event;
+    if progress.received >= progress.total {
+        if let Some(done_tx) = session.done_tx.take() {
+            // The caller has already given up if the receiver is gone
+            let _ = done_tx.send(progress.received);
+        }
+        *sync = None;
+    }
+    Some((event, progress))
+}
+
+// Store the SYS_STATUS sensor bitmasks, emitting `preflight-check` when sensor health changes
+fn handle_sys_status(app_handle: &tauri::AppHandle, state: &MavlinkState, sys_status: &SYS_STATUS_DATA) {
+    let status = PreflightStatus {
+        sensors_present: sys_status.onboard_control_sensors_present.bits(),
+        sensors_enabled: sys_status.onboard_control_sensors_enabled.bits(),
+        sensors_health: sys_status.onboard_control_sensors_health.bits(),
+        timestamp_ms: get_timestamp(),
+    };
+    let previous = match state.preflight_status.write() {
+        Ok(mut stored) => stored.replace(status),
+        Err(_) => return,
+    };
+    if previous.map_or(false, |previous| previous.sensors_health == status.sensors_health) {
+        return;
+    }
+
+    if let Err(e) = app_handle.emit_all("preflight-check", preflight_report(status)) {
+        eprintln!("Failed to emit preflight check: {e}");
+    }
+}
+
+// Store the report and raise `ekf-critical` in constant position mode, otherwise
+// `ekf-warning` for excess variances or no absolute horizontal position; each
+// is emitted when the reasons change rather than on every report
+fn handle_ekf_status_report(app_handle: &tauri::AppHandle, state: &MavlinkState, report: &EKF_STATUS_REPORT_DATA) {
+    let status = EkfStatus {
+        velocity_variance: report.velocity_variance,
+        pos_horiz_variance: report.pos_horiz_variance,
+        pos_vert_variance: report.pos_vert_variance,
+        compass_variance: report.compass_variance,
+        terrain_alt_variance: report.terrain_alt_variance,
+        flags: report.flags.bits(),
+        timestamp_ms: get_timestamp(),
+    };
+    let threshold = match state.ekf_variance_threshold.read() {
+        Ok(threshold) => *threshold,
+        Err(_) => return,
+    };
+    let previous = match state.ekf_status.write() {
+        Ok(mut stored) => stored.replace(status),
+        Err(_) => return,
+    };
+
+    let reasons = ekf_problems(&status, threshold);
+    if reasons.is_empty() || previous.map_or(false, |previous| ekf_problems(&previous, threshold) == reasons) {
+        return;
+    }
+    let event = if ekf_in_const_pos_mode(&status) { "ekf-critical" } else { "ekf-warning" };
+    if let Err(e) = app_handle.emit_all(event, EkfAlert { status, reasons }) {
+        eprintln!("Failed to emit EKF alert: {e}");
+    }
+}
+
+// Buffer shell output and emit every complete UTF-8 character received so
+// far; the oldest bytes are dropped beyond MAX_CONSOLE_BUFFER_BYTES
+fn handle_serial_control(app_handle: &tauri::AppHandle, state: &MavlinkState, control: &SERIAL_CONTROL_DATA) {
+    if control.device != SerialControlDev::SERIAL_CONTROL_DEV_SHELL || !state.console_active.load(Ordering::SeqCst) {
+        return;
+    }
+    let count = usize::from(control.count).min(SERIAL_CONTROL_DATA_BYTES);
+    let data = match state.console_buffer.lock() {
+        Ok(mut buffer) => {
+            buffer.extend_from_slice(&control.data[..count]);
+            if buffer.len() > MAX_CONSOLE_BUFFER_BYTES {
+                let excess = buffer.len() - MAX_CONSOLE_BUFFER_BYTES;
+                buffer.drain(..excess);
+            }
+            take_console_text(&mut buffer)
+        }
+        Err(_) => return,
+    };
+    if data.is_empty() {
+        return;
+    }
+
+    let output = ConsoleOutput { data, timestamp_ms: get_timestamp() };
+    if let Err(e) = app_handle.emit_all("mavlink-console-output", output) {
+        eprintln!("Failed to emit console output: {e}");
+    }
+}
+
+// Deliver single-part texts directly and chunked ones (id != 0) once complete
+fn handle_statustext(
+    app_handle: &tauri::AppHandle,
+    state: &MavlinkState,
+    header: &MavHeader,
+    report: &STATUSTEXT_DATA,
+    (id, chunk_seq): (u16, u8),
+) {
+    let text_len = report.text.iter().position(|&byte| byte == 0).unwrap_or(STATUSTEXT_CHUNK_LEN);
+    let text = &report.text[..text_len];
+    if id == 0 {
+        publish_statustext(app_handle, state, status_text(header.system_id, header.component_id, report.severity, text));
+        return;
+    }
+
+    let key = (header.system_id, header.component_id, id);
+    let complete = match state.statustext_chunks.lock() {
+        Ok(mut chunks) => reassemble_statustext(&mut chunks, key, report.severity, chunk_seq, text),
+        Err(_) => return,
+    };
+    for status in complete {
+        publish_statustext(app_handle, state, status);
+    }
+}
+
+// Add a chunk and return the texts it completes. The final chunk is the one
+// not filling all 50 bytes; texts whose final chunk never arrives are
+// delivered as received, and a missing chunk is marked with "...".
+fn reassemble_statustext(
+    chunks: &mut HashMap<StatusTextKey, PendingStatusText>,
+    key: StatusTextKey,
+    severity: MavSeverity,
+    chunk_seq: u8,
+    text: &[u8],
+) -> Vec<StatusText> {
+    let finish = |key: StatusTextKey, pending: PendingStatusText| status_text(key.0, key.1, pending.severity, &pending.text);
+    let timeout = Duration::from_millis(STATUSTEXT_CHUNK_TIMEOUT_MS);
+    let stale: Vec<StatusTextKey> = chunks.iter()
+        .filter(|(_, pending)| pending.started_at.elapsed() > timeout)
+        .map(|(key, _)| *key)
+        .collect();
+    let mut complete: Vec<StatusText> = stale.into_iter()
+        .filter_map(|key| chunks.remove(&key).map(|pending| finish(key, pending)))
+        .collect();
+
+    let mut pending = match chunks.remove(&key) {
+        // Chunk 0 starts a new text under a reused ID
+        Some(previous) if chunk_seq == 0 => {
+            complete.push(finish(key, previous));
+            None
+        }
+        pending => pending,
+    }
+    .unwrap_or(PendingStatusText { severity, text: Vec::new(), next_chunk_seq: 0, started_at: Instant::now() });
+    if chunk_seq != pending.next_chunk_seq {
+        pending.text.extend_from_slice(b"...");
+    }
+    pending.text.extend_from_slice(text);
+    pending.next_chunk_seq = chunk_seq.wrapping_add(1);
+
+    if text.len() < STATUSTEXT_CHUNK_LEN || chunks.len() >= MAX_PENDING_STATUSTEXTS {
+        complete.push(finish(key, pending));
+    } else {
+        chunks.insert(key, pending);
+    }
+    complete
+}
+
+fn status_text(system_id: u8, component_id: u8, severity: MavSeverity, text: &[u8]) -> StatusText {
+    StatusText {
+        system_id,
+        component_id,
+        severity: severity_name(severity).to_string(),
+        text: String::from_utf8_lossy(text).trim_end().to_string(),
+        timestamp_ms: get_timestamp(),
+    }
+}
+
+// Log and emit `vehicle-statustext`; EMERGENCY, ALERT and CRITICAL also raise `vehicle-alert`
+fn publish_statustext(app_handle: &tauri::AppHandle, state: &MavlinkState, status: StatusText) {
+    if status.text.is_empty() {
+        return;
+    }
+    if let Ok(mut log) = state.statustext_log.lock() {
+        if log.len() >= STATUSTEXT_LOG_CAPACITY {
+            log.pop_front();
+        }
+        log.push_back(status.clone());
+    }
+
+    if matches!(status.severity.as_str(), "emergency" | "alert" | "critical") {
+        if let Err(e) = app_handle.emit_all("vehicle-alert", &status) {
+            eprintln!("Failed to emit vehicle alert: {e}");
+        }
+    }
+    if let Err(e) = app_handle.emit_all("vehicle-statustext", status) {
+        eprintln!("Failed to emit status text: {e}");
+    }
+}
+
+fn severity_name(severity: MavSeverity) -> &'static str {
+    match severity {
+        MavSeverity::MAV_SEVERITY_EMERGENCY => "emergency",
+        MavSeverity::MAV_SEVERITY_ALERT => "alert",
+        MavSeverity::MAV_SEVERITY_CRITICAL => "critical",
+        MavSeverity::MAV_SEVERITY_ERROR => "error",
+        MavSeverity::MAV_SEVERITY_WARNING => "warning",
+        MavSeverity::MAV_SEVERITY_NOTICE => "notice",
+        MavSeverity::MAV_SEVERITY_INFO => "info",
+        MavSeverity::MAV_SEVERITY_DEBUG => "debug",
+    }
+}
+
+// Remove and decode the buffered bytes, keeping a trailing partial UTF-8
+// character for the next message; invalid bytes become U+FFFD
+fn take_console_text(buffer: &mut Vec<u8>) -> String {
+    let complete = match std::str::from_utf8(buffer) {
+        Ok(_) => buffer.len(),
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        Err(_) => buffer.len(),
+    };
+    let text = String::from_utf8_lossy(&buffer[..complete]).into_owned();
+    buffer.drain(..complete);
+    text
+}
+
+// Cache every ATTITUDE but emit `vehicle-attitude` at the configured rate
+fn handle_attitude(app_handle: &tauri::AppHandle, state: &MavlinkState, report: &ATTITUDE_DATA) {
+    let attitude = AttitudeData {
+        roll_deg: report.roll.to_degrees(),
+        pitch_deg: report.pitch.to_degrees(),
+        yaw_deg: report.yaw.to_degrees(),
+        roll_rate_dps: report.rollspeed.to_degrees(),
+        pitch_rate_dps: report.pitchspeed.to_degrees(),
+        yaw_rate_dps: report.yawspeed.to_degrees(),
+        timestamp_ms: get_timestamp(),
+    };
+    if let Ok(mut cached) = state.attitude.write() {
+        *cached = Some(attitude);
+    }
+    if !telemetry_due(state, TelemetryStream::Attitude) {
+        return;
+    }
+
+    if let Err(e) = app_handle.emit_all("vehicle-attitude", attitude) {
+        eprintln!("Failed to emit vehicle attitude: {e}");
+    }
+}
+
+// Cache every VFR_HUD but emit `vehicle-vfr` at the configured rate
+fn handle_vfr_hud(app_handle: &tauri::AppHandle, state: &MavlinkState, hud: &VFR_HUD_DATA) {
+    let vfr = VfrData {
+        airspeed_ms: hud.airspeed,
+        groundspeed_ms: hud.groundspeed,
+        heading_deg: hud.heading,
+        throttle_pct: hud.throttle,
+        alt_m: hud.alt,
+        climb_ms: hud.climb,
+        timestamp_ms: get_timestamp(),
+    };
+    let groundspeed = hud.groundspeed as f64;
+    update_vehicle_progress(state, |progress| progress.groundspeed_ms = Some(groundspeed));
+    if let Ok(mut cached) = state.vfr.write() {
+        *cached = Some(vfr);
+    }
+    if !telemetry_due(state, TelemetryStream::Vfr) {
+        return;
+    }
+
+    if let Err(e) = app_handle.emit_all("vehicle-vfr", vfr) {
+        eprintln!("Failed to emit VFR telemetry: {e}");
+    }
+}
+
+// Cache every GPS_RAW_INT and emit `vehicle-gps-status` at GPS_STATUS_RATE_HZ.
+// DOPs are sent times 100 and the course in centidegrees, UINT16_MAX when unknown.
+fn handle_gps_raw_int(app_handle: &tauri::AppHandle, state: &MavlinkState, gps: &GPS_RAW_INT_DATA) {
+    let scaled = |value: u16| (value != u16::MAX).then(|| value as f32 / 100.0);
+    let status = GpsStatus {
+        fix_type: gps_fix_name(gps.fix_type).to_string(),
+        has_3d_fix: gps.fix_type as u8 >= GpsFixType::GPS_FIX_TYPE_3D_FIX as u8,
+        satellites: (gps.satellites_visible != u8::MAX).then(|| gps.satellites_visible),
+        hdop: scaled(gps.eph),
+        vdop: scaled(gps.epv),
+        ground_course_deg: scaled(gps.cog),
+        timestamp_ms: get_timestamp(),
+    };
+    if let Ok(mut cached) = state.gps_status.write() {
+        *cached = Some(status.clone());
+    }
+    if !telemetry_due(state, TelemetryStream::GpsStatus) {
+        return;
+    }
+
+    if let Err(e) = app_handle.emit_all("vehicle-gps-status", status) {
+        eprintln!("Failed to emit GPS status: {e}");
+    }
+}
+
+fn gps_fix_name(fix_type: GpsFixType) -> &'static str {
+    match fix_type {
+        GpsFixType::GPS_FIX_TYPE_NO_GPS => "no_gps",
+        GpsFixType::GPS_FIX_TYPE_NO_FIX => "no_fix",
+        GpsFixType::GPS_FIX_TYPE_2D_FIX => "2d",
+        GpsFixType::GPS_FIX_TYPE_3D_FIX => "3d",
+        GpsFixType::GPS_FIX_TYPE_DGPS => "dgps",
+        GpsFixType::GPS_FIX_TYPE_RTK_FLOAT => "rtk_float",
+        GpsFixType::GPS_FIX_TYPE_RTK_FIXED => "rtk_fixed",
+        GpsFixType::GPS_FIX_TYPE_STATIC => "static",
+        GpsFixType::GPS_FIX_TYPE_PPP => "ppp",
+    }
+}
+
+// Whether `stream` may emit now under its configured rate; records the emission
+fn telemetry_due(state: &MavlinkState, stream: TelemetryStream) -> bool {
+    let rate_hz = match state.telemetry_rates.read() {
+        Ok(rates) => rates.rate_hz(stream),
+        Err(_) => return false,
+    };
+    let interval = Duration::from_millis(1000 / rate_hz.max(1));
+    let mut last_emit = match state.telemetry_last_emit.lock() {
+        Ok(last_emit) => last_emit,
+        Err(_) => return false,
+    };
+    if last_emit.get(&stream).map_or(false, |at| at.elapsed() < interval) {
+        return false;
+    }
+    last_emit.insert(stream, Instant::now());
+    true
+}
+
+// Merge a battery report into the cached BatteryInfo, emit `vehicle-battery`
+// at most once per BATTERY_EMIT_INTERVAL_MS and raise the threshold alerts
+fn update_battery(app_handle: &tauri::AppHandle, state: &MavlinkState, apply: impl FnOnce(&mut BatteryInfo)) {
+    let battery = match state.battery.write() {
+        Ok(mut cached) => {
+            let battery = cached.get_or_insert_with(|| BatteryInfo {
+                voltage_v: 0.0,
+                current_a: None,
+                consumed_mah: None,
+                remaining_pct: None,
+                cell_voltages: Vec::new(),
+                timestamp_ms: 0,
+            });
+            apply(battery);
+            battery.timestamp_ms = get_timestamp();
+            battery.clone()
+        }
+        Err(_) => return,
+    };
+    let thresholds = match state.battery_thresholds.read() {
+        Ok(thresholds) => *thresholds,
+        Err(_) => return,
+    };
+    let mut monitor = match state.battery_monitor.lock() {
+        Ok(monitor) => monitor,
+        Err(_) => return,
+    };
+
+    let level = battery_level(&battery, &thresholds, monitor.level);
+    if level != monitor.level {
+        let rising = matches!(
+            (monitor.level, level),
+            (BatteryLevel::Normal, _) | (BatteryLevel::Warning, BatteryLevel::Critical)
+        );
+        monitor.level = level;
+        if rising {
+            let event = if level == BatteryLevel::Critical { "battery-critical" } else { "battery-warning" };
+            let alert = BatteryAlert { reason: battery_alert_reason(&battery, &thresholds, level), battery: battery.clone() };
+            if let Err(e) = app_handle.emit_all(event, alert) {
+                eprintln!("Failed to emit battery alert: {e}");
+            }
+        }
+    }
+
+    let due = monitor.last_emit_at
+        .map_or(true, |at| at.elapsed() >= Duration::from_millis(BATTERY_EMIT_INTERVAL_MS));
+    if due {
+        monitor.last_emit_at = Some(Instant::now());
+        if let Err(e) = app_handle.emit_all("vehicle-battery", battery) {
+            eprintln!("Failed to emit vehicle battery: {e}");
+        }
+    }
+}
+
+// SYS_STATUS: voltage in mV (UINT16_MAX unknown), current in cA (-1 unknown),
+// remaining in % (-1 unknown)
+fn apply_sys_status_battery(battery: &mut BatteryInfo, status: &SYS_STATUS_DATA) {
+    if status.voltage_battery != u16::MAX {
+        battery.voltage_v = status.voltage_battery as f32 / 1000.0;
+    }
+    battery.current_a = (status.current_battery >= 0).then(|| status.current_battery as f32 / 100.0);
+    battery.remaining_pct = (status.battery_remaining >= 0).then(|| status.battery_remaining);
+}
+
+// BATTERY_STATUS: unused cells are UINT16_MAX, consumed charge in mAh (-1 unknown)
+fn apply_battery_status(battery: &mut BatteryInfo, status: &BATTERY_STATUS_DATA) {
+    battery.cell_voltages = status.voltages.iter()
+        .take_while(|&&cell| cell != u16::MAX)
+        .map(|&cell| cell as f32 / 1000.0)
+        .collect();
+    if !battery.cell_voltages.is_empty() {
+        battery.voltage_v = battery.cell_voltages.iter().sum();
+    }
+    battery.current_a = (status.current_battery >= 0).then(|| status.current_battery as f32 / 100.0);
+    battery.consumed_mah = (status.current_consumed >= 0).then(|| status.current_consumed);
+    battery.remaining_pct = (status.battery_remaining >= 0).then(|| status.battery_remaining);
+}
+
+// Level for this reading. Entering a level uses its threshold; staying in it
+// until the reading recovers past the hysteresis margin.
+fn battery_level(battery: &BatteryInfo, thresholds: &BatteryThresholds, current: BatteryLevel) -> BatteryLevel {
+    let remaining = battery.remaining_pct.map(f32::from);
+    let min_cell = battery.cell_voltages.iter().copied().reduce(f32::min);
+    let pct_at_or_below = |threshold: f32, margin: f32| remaining.map_or(false, |pct| pct <= threshold + margin);
+    let cell_at_or_below = |margin: f32| min_cell.map_or(false, |cell| cell <= thresholds.warn_cell_v + margin);
+
+    let critical_margin = if current == BatteryLevel::Critical { BATTERY_HYSTERESIS_PCT } else { 0.0 };
+    if pct_at_or_below(thresholds.critical_pct, critical_margin) {
+        return BatteryLevel::Critical;
+    }
+    let staying = current != BatteryLevel::Normal;
+    let (pct_margin, cell_margin) = if staying { (BATTERY_HYSTERESIS_PCT, BATTERY_HYSTERESIS_CELL_V) } else { (0.0, 0.0) };
+    if pct_at_or_below(thresholds.warn_pct, pct_margin) || cell_at_or_below(cell_margin) {
+        return BatteryLevel::Warning;
+    }
+    BatteryLevel::Normal
+}
+
+fn battery_alert_reason(battery: &BatteryInfo, thresholds: &BatteryThresholds, level: BatteryLevel) -> String {
+    let remaining = battery.remaining_pct.map_or_else(|| "unknown".to_string(), |pct| format!("{pct}%"));
+    match level {
+        BatteryLevel::Critical => format!("Battery at {remaining}, at or below {}%", thresholds.critical_pct),
+        _ => format!(
+            "Battery at {remaining} ({:.2} V); warning at {}% or {:.2} V per cell",
+            battery.voltage_v, thresholds.warn_pct, thresholds.warn_cell_v
+        ),
+    }
+}
+
+// Record the report and raise `vibration-warning` when an axis rises past
+// VIBRATION_WARNING_MS2 or an IMU clipping counter increments
+fn handle_vibration(app_handle: &tauri::AppHandle, state: &MavlinkState, report: &VIBRATION_DATA) {
+    if let Some(warning) = record_vibration(state, report) {
+        if let Err(e) = app_handle.emit_all("vibration-warning", warning) {
+            eprintln!("Failed to emit vibration warning: {e}");
+        }
+    }
+}
+
+// Append the report to the history; the warning to raise, if any
+fn record_vibration(state: &MavlinkState, report: &VIBRATION_DATA) -> Option<VibrationWarning> {
+    let vibration = VibrationData {
+        accel_x: report.vibration_x,
+        accel_y: report.vibration_y,
+        accel_z: report.vibration_z,
+        clipping_0: report.clipping_0,
+        clipping_1: report.clipping_1,
+        clipping_2: report.clipping_2,
+        timestamp_ms: get_timestamp(),
+    };
+    let previous = match state.vibration_history.lock() {
+        Ok(mut history) => {
+            let previous = history.back().copied();
+            if history.len() >= VIBRATION_HISTORY_CAPACITY {
+                history.pop_front();
+            }
+            history.push_back(vibration);
+            previous
+        }
+        Err(_) => return None,
+    };
+
+    let reasons = vibration_problems(&vibration, previous.as_ref());
+    if reasons.is_empty() {
+        return None;
+    }
+    Some(VibrationWarning { vibration, reasons })
+}
+
+// Cache each ESC's report, raise `esc-warning` when one crosses a limit and
+// emit `esc-telemetry` at ESC_TELEMETRY_RATE_HZ
+fn handle_esc_telemetry(app_handle: &tauri::AppHandle, state: &MavlinkState, reports: Vec<EscTelemetry>) {
+    let (escs, warnings) = match record_esc_telemetry(state, reports) {
+        Some(recorded) => recorded,
+        None => return,
+    };
+
+    for warning in warnings {
+        if let Err(e) = app_handle.emit_all("esc-warning", warning) {
+            eprintln!("Failed to emit ESC warning: {e}");
+        }
+    }
+    if !telemetry_due(state, TelemetryStream::Esc) {
+        return;
+    }
+    if let Err(e) = app_handle.emit_all("esc-telemetry", escs) {
+        eprintln!("Failed to emit ESC telemetry: {e}");
+    }
+}
+
+// Store each report in motor order, returning every cached ESC and the
+// warnings raised by this batch
+fn record_esc_telemetry(state: &MavlinkState, reports: Vec<EscTelemetry>) -> Option<(Vec<EscTelemetry>, Vec<EscWarning>)> {
+    let mut escs = state.esc_telemetry.write().ok()?;
+    let mut warnings = Vec::new();
+    for report in reports {
+        let previous = escs.iter().position(|esc| esc.motor_id == report.motor_id);
+        let reasons = esc_problems(&report, previous.map(|i| &escs[i]));
+        if !reasons.is_empty() {
+            warnings.push(EscWarning { motor_id: report.motor_id, telemetry: report, reasons });
+        }
+        match previous {
+            Some(i) => escs[i] = report,
+            None => {
+                escs.push(report);
+                escs.sort_by_key(|esc| esc.motor_id);
+            }
+        }
+    }
+    Some((escs.clone(), warnings))
+}
+
+// ESC_TELEMETRY_1_TO_4 and _5_TO_8 each carry four ESCs from `first_motor`.
+// Voltage arrives in cV and temperature in °C; ESCs with a zero packet count
+// send no telemetry.
+fn esc_reports(
+    first_motor: u8,
+    voltage_cv: &[u16; 4],
+    current_ca: &[u16; 4],
+    totalcurrent_mah: &[u16; 4],
+    rpm: &[u16; 4],
+    count: &[u16; 4],
+    temperature_c: &[u8; 4],
+) -> Vec<EscTelemetry> {
+    let timestamp_ms = get_timestamp();
+    (0..4)
+        .filter(|&i| count[i] > 0)
+        .map(|i| EscTelemetry {
+            motor_id: first_motor + i as u8,
+            rpm: u32::from(rpm[i]),
+            voltage_mv: voltage_cv[i].saturating_mul(10),
+            current_ca: current_ca[i],
+            temperature_cdeg: u16::from(temperature_c[i]) * 100,
+            totalcurrent_mah: u32::from(totalcurrent_mah[i]),
+            timestamp_ms,
+        })
+        .collect()
+}
+
+// Like vibration, a limit only counts when it is crossed, so an ESC running
+// hot warns once rather than on every report
+fn esc_problems(esc: &EscTelemetry, previous: Option<&EscTelemetry>) -> Vec<String> {
+    let overheated = |esc: &EscTelemetry| esc.temperature_cdeg > ESC_TEMPERATURE_WARNING_CDEG;
+    let overloaded = |esc: &EscTelemetry| esc.current_ca > ESC_CURRENT_WARNING_CA;
+    let mut problems = Vec::new();
+    if overheated(esc) && !previous.map_or(false, overheated) {
+        problems.push(format!(
+            "Motor {} ESC at {:.0} °C exceeds {} °C",
+            esc.motor_id,
+            f32::from(esc.temperature_cdeg) / 100.0,
+            ESC_TEMPERATURE_WARNING_CDEG / 100
+        ));
+    }
+    if overloaded(esc) && !previous.map_or(false, overloaded) {
+        problems.push(format!(
+            "Motor {} ESC drawing {:.1} A exceeds {} A",
+            esc.motor_id,
+            f32::from(esc.current_ca) / 100.0,
+            ESC_CURRENT_WARNING_CA / 100
+        ));
+    }
+    problems
+}
+
+// Axes only count when they cross the limit, so a vehicle vibrating steadily
+// above it warns once rather than on every report. Clipping counters are
+// cumulative since boot, so the first report only sets the baseline.
+fn vibration_problems(vibration: &VibrationData, previous: Option<&VibrationData>) -> Vec<String> {
+    let axes = [("X", vibration.accel_x), ("Y", vibration.accel_y), ("Z", vibration.accel_z)];
+    let was_above = previous.map_or([false; 3], |p| {
+        [p.accel_x, p.accel_y, p.accel_z].map(|accel| accel > VIBRATION_WARNING_MS2)
+    });
+    let mut problems: Vec<String> = axes.iter()
+        .zip(was_above)
+        .filter(|((_, accel), was_above)| *accel > VIBRATION_WARNING_MS2 && !was_above)
+        .map(|((name, accel), _)| format!("{name} vibration {accel:.1} m/s² exceeds {VIBRATION_WARNING_MS2:.0} m/s²"))
+        .collect();
+
+    if let Some(previous) = previous {
+        let counts = [vibration.clipping_0, vibration.clipping_1, vibration.clipping_2];
+        let before = [previous.clipping_0, previous.clipping_1, previous.clipping_2];
+        // Counters restart with the autopilot, so a decrease is not clipping
+        for (imu, (count, before)) in counts.iter().zip(before).enumerate() {
+            if *count > before {
+                problems.push(format!("IMU {imu} clipped {} times", count - before));
+            }
+        }
+    }
+    problems
+}
+
+fn ekf_in_const_pos_mode(status: &EkfStatus) -> bool {
+    status.flags & EkfStatusFlags::EKF_CONST_POS_MODE.bits() != 0
+}
+
+fn ekf_problems(status: &EkfStatus, threshold: f32) -> Vec<String> {
+    let variances = [
+        ("Velocity", status.velocity_variance),
+        ("Horizontal position", status.pos_horiz_variance),
+        ("Vertical position", status.pos_vert_variance),
+        ("Compass", status.compass_variance),
+        ("Terrain altitude", status.terrain_alt_variance),
+    ];
+    let mut problems: Vec<String> = variances.iter()
+        .filter(|(_, variance)| variance.is_nan() || *variance > threshold)
+        .map(|(name, _)| format!("{name} variance above {threshold:.2}"))
+        .collect();
+    if ekf_in_const_pos_mode(status) {
+        problems.push("EKF is in constant position mode".to_string());
+    }
+    if status.flags & EkfStatusFlags::EKF_PRED_POS_HORIZ_ABS.bits() == 0 {
+        problems.push("No predicted absolute horizontal position".to_string());
+    }
+    problems
+}
+
+// Decode the sensor bitmasks; an enabled sensor without its health bit is failing
+fn preflight_report(status: PreflightStatus) -> PreflightCheckReport {
+    let checks: Vec<SensorCheck> = SYS_STATUS_SENSORS.iter()
+        .filter(|(sensor, _)| status.sensors_present & sensor.bits() != 0)
+        .map(|(sensor, name)| SensorCheck {
+            sensor_name: name.to_string(),
+            present: true,
+            enabled: status.sensors_enabled & sensor.bits() != 0,
+            healthy: status.sensors_health & sensor.bits() != 0,
+        })
+        .collect();
+    let failing_checks = SYS_STATUS_SENSORS.iter()
+        .filter(|(sensor, _)| {
+            status.sensors_enabled & sensor.bits() != 0 && status.sensors_health & sensor.bits() == 0
+        })
+        .map(|(_, name)| name.to_string())
+        .collect();
+
+    PreflightCheckReport { status, checks, failing_checks }
+}
+
+// PARAM_VALUE ids are NUL-padded, without a terminator when all 16 bytes are used
+fn param_id_string(param_id: &[u8; 16]) -> String {
+    let len = param_id.iter().position(|&b| b == 0).unwrap_or(param_id.len());
+    String::from_utf8_lossy(&param_id[..len]).into_owned()
+}
+
+// Parse a .param file: Mission Planner `NAME,VALUE` / `NAME<TAB>VALUE` lines or
+// QGroundControl `SYSID COMPID NAME VALUE TYPE` lines; `#` starts a comment
+fn parse_param_file(contents: &str) -> Result<Vec<(String, f32)>, String> {
+    let mut params = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|field| !field.is_empty())
+            .collect();
+        let (name, value) = match fields.as_slice() {
+            [name, value] => (*name, *value),
+            [_, _, name, value, _] => (*name, *value),
+            _ => return Err(format!("Line {}: expected NAME,VALUE", index + 1)),
+        };
+        if name.len() > MAX_PARAM_ID_LENGTH || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("Line {}: invalid parameter name {name}", index + 1));
+        }
+        let value: f32 = value.parse()
+            .map_err(|_| format!("Line {}: invalid value {value} for {name}", index + 1))?;
+        if !value.is_finite() {
+            return Err(format!("Line {}: invalid value for {name}", index + 1));
+        }
+        if params.len() >= MAX_PARAM_FILE_ENTRIES {
+            return Err(format!("Parameter file has more than {MAX_PARAM_FILE_ENTRIES} parameters"));
+        }
+        params.push((name.to_string(), value));
+    }
+    Ok(params)
+}
+
+fn param_file_diff(state: &MavlinkState, path: &str) -> Result<ParamFileDiff, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let file_params = parse_param_file(&contents)?;
+    let params = state.parameters.read()
+        .map_err(|_| "Failed to read parameters")?;
+
+    let entries: Vec<ParamFileEntry> = file_params.into_iter()
+        .map(|(param_id, file_value)| {
+            let current_value = params.get(&param_id).map(|param| param.value);
+            ParamFileEntry {
+                changed: current_value.map_or(false, |current| !param_values_match(current, file_value)),
+                unknown: current_value.is_none(),
+                param_id,
+                file_value,
+                current_value,
+            }
+        })
+        .collect();
+    Ok(ParamFileDiff {
+        changed_count: entries.iter().filter(|entry| entry.changed).count() as u32,
+        unknown_count: entries.iter().filter(|entry| entry.unknown).count() as u32,
+        entries,
+    })
+}
+
+// ArduPilot groups parameters by the prefix before the first underscore
+fn parameter_group(param_id: &str) -> String {
+    param_id.split('_').next().unwrap_or(param_id).to_uppercase()
+}
+
+// 0 for an id prefix match, 1 elsewhere in the id, 2 in the description;
+// `query` is already uppercased and an empty one matches everything
+fn search_rank(param: &Parameter, query: &str) -> Option<u8> {
+    let id = param.id.to_uppercase();
+    if id.starts_with(query) {
+        Some(0)
+    } else if id.contains(query) {
+        Some(1)
+    } else if param.description.as_ref().map_or(false, |d| d.to_uppercase().contains(query)) {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+// Equal within float precision, as a vehicle echoes or a file rounds them
+fn param_values_match(a: f32, b: f32) -> bool {
+    (a - b).abs() <= f32::EPSILON * a.abs().max(b.abs()).max(1.0)
+}
+
+// `NAME<TAB>VALUE` lines under a `# Vehicle-Id Component-Id` header; the
+// type goes in a trailing comment, which both ground stations ignore
+fn param_file_contents(system_id: u8, component_id: u8, params: &[Parameter]) -> String {
+    let mut contents = format!(
+        "# Onboard parameters for Vehicle {system_id}\n#\n# Vehicle-Id Component-Id\n# {system_id} {component_id}\n#\n"
+    );
+    for param in params {
+        let is_integer = param_type_from_name(&param.param_type).map_or(false, |param_type| {
+            !matches!(param_type, MavParamType::MAV_PARAM_TYPE_REAL32 | MavParamType::MAV_PARAM_TYPE_REAL64)
+        });
+        let value = if is_integer {
+            format!("{}", param.value.round() as i64)
+        } else {
+            format!("{}", param.value)
+        };
+        contents.push_str(&format!("{}\t{}\t# {}\n", param.id, value, param.param_type));
+    }
+    contents
+}
+
+fn param_type_from_name(name: &str) -> Option<MavParamType> {
+    match name {
+        "UINT8" => Some(MavParamType::MAV_PARAM_TYPE_UINT8),
+        "INT8" => Some(MavParamType::MAV_PARAM_TYPE_INT8),
+        "UINT16" => Some(MavParamType::MAV_PARAM_TYPE_UINT16),
+        "INT16" => Some(MavParamType::MAV_PARAM_TYPE_INT16),
+        "UINT32" => Some(MavParamType::MAV_PARAM_TYPE_UINT32),
+        "INT32" => Some(MavParamType::MAV_PARAM_TYPE_INT32),
+        "UINT64" => Some(MavParamType::MAV_PARAM_TYPE_UINT64),
+        "INT64" => Some(MavParamType::MAV_PARAM_TYPE_INT64),
+        "REAL32" => Some(MavParamType::MAV_PARAM_TYPE_REAL32),
+        "REAL64" => Some(MavParamType::MAV_PARAM_TYPE_REAL64),
+        _ => None,
+    }
+}
+
+// ArduPilot carries integer parameters as their numeric value in the float
+// field (C-cast encoding), so integers are rounded and range-checked here
+fn encode_param_value(value: f32, param_type: MavParamType) -> Result<f32, String> {
+    if !value.is_finite() {
+        return Err("Parameter value must be a finite number".to_string());
+    }
+    let (min, max) = match param_type {
+        MavParamType::MAV_PARAM_TYPE_REAL32 | MavParamType::MAV_PARAM_TYPE_REAL64 => return Ok(value),
+        MavParamType::MAV_PARAM_TYPE_UINT8 => (0.0, f64::from(u8::MAX)),
+        MavParamType::MAV_PARAM_TYPE_INT8 => (f64::from(i8::MIN), f64::from(i8::MAX)),
+        MavParamType::MAV_PARAM_TYPE_UINT16 => (0.0, f64::from(u16::MAX)),
+        MavParamType::MAV_PARAM_TYPE_INT16 => (f64::from(i16::MIN), f64::from(i16::MAX)),
+        MavParamType::MAV_PARAM_TYPE_UINT32 | MavParamType::MAV_PARAM_TYPE_UINT64 => (0.0, f64::from(u32::MAX)),
+        MavParamType::MAV_PARAM_TYPE_INT32 | MavParamType::MAV_PARAM_TYPE_INT64 => (f64::from(i32::MIN), f64::from(i32::MAX)),
+    };
+    let rounded = value.round();
+    if !(min..=max).contains(&f64::from(rounded)) {
+        return Err(format!("Value {value} is out of range for a {} parameter", param_type_name(param_type)));
+    }
+    Ok(rounded)
+}
+
+fn param_id_bytes(id: &str) -> [u8; 16] {
+    let mut bytes = [0u8; 16];
+    for (dst, src) in bytes.iter_mut().zip(id.bytes()) {
+        *dst = src;
+    }
+    bytes
+}
+
+fn param_type_name(param_type: MavParamType) -> &'static str {
+    match param_type {
+        MavParamType::MAV_PARAM_TYPE_UINT8 => "UINT8",
+        MavParamType::MAV_PARAM_TYPE_INT8 => "INT8",
+        MavParamType::MAV_PARAM_TYPE_UINT16 => "UINT16",
+        MavParamType::MAV_PARAM_TYPE_INT16 => "INT16",
+        MavParamType::MAV_PARAM_TYPE_UINT32 => "UINT32",
+        MavParamType::MAV_PARAM_TYPE_INT32 => "INT32",
+        MavParamType::MAV_PARAM_TYPE_UINT64 => "UINT64",
+        MavParamType::MAV_PARAM_TYPE_INT64 => "INT64",
+        MavParamType::MAV_PARAM_TYPE_REAL32 => "REAL32",
+        MavParamType::MAV_PARAM_TYPE_REAL64 => "REAL64",
+    }
+}
+
+fn update_vehicle_progress(state: &MavlinkState, update: impl FnOnce(&mut VehicleProgress)) {
+    if let Ok(mut progress) = state.vehicle_progress.write() {
+        update(&mut progress);
+    }
+}
+
+// Store the armed flag and flight mode from a heartbeat, returning the previous armed flag
+fn record_armed_state(state: &MavlinkState, armed: bool, flight_mode: &str) -> Option<bool> {
+    let mut info = state.vehicle_info.write().ok()?;
+    let info = info.as_mut()?;
+    if info.flight_mode != flight_mode {
+        info.flight_mode = flight_mode.to_string();
+    }
+    Some(std::mem::replace(&mut info.armed, armed))
+}
+
+// Arming with an uploaded mission locks it against edits; disarming releases the lock
+fn update_mission_lock(app_handle: &tauri::AppHandle, was_armed: bool, armed: bool) {
+    let app_state = app_handle.state::<AppState>();
+    let result = match (was_armed, armed) {
+        (false, true) => match app_state.vehicle_mission.lock() {
+            Ok(uploaded) if uploaded.is_some() => {
+                crate::set_mission_lock(&app_state, Some(EXECUTING_LOCK_REASON.to_string()))
+            }
+            Ok(_) => Ok(()),
+            Err(_) => Err("Failed to lock vehicle mission".to_string()),
+        },
+        (true, false) => crate::set_mission_lock(&app_state, None),
+        _ => Ok(()),
+    };
+    if let Err(e) = result {
+        eprintln!("Failed to update mission lock: {e}");
+    }
+}
+
+// Record a received HEARTBEAT and rescore the link from arrival jitter and loss
+fn record_heartbeat(app_handle: &tauri::AppHandle, state: &MavlinkState) {
+    let (quality, jitter_ms, packet_loss_ratio) = match state.link_tracker.lock() {
+        Ok(mut tracker) => {
+            tracker.record_heartbeat(Instant::now());
+            (tracker.quality(), tracker.jitter_ms(), tracker.packet_loss_ratio())
+        }
+        Err(_) => return,
+    };
+
+    if let Ok(mut status) = state.connection_status.write() {
+        status.last_heartbeat = Some(get_timestamp());
+        status.link_quality = quality;
+        status.jitter_ms = jitter_ms;
+        status.packet_loss_ratio = packet_loss_ratio;
+    }
+    publish_link_quality(app_handle, state);
+}
+
+// Degrade link quality while heartbeats are overdue
+fn update_link_quality(app_handle: &tauri::AppHandle, state: &MavlinkState) {
+    let (since_last, quality) = match state.link_tracker.lock() {
+        Ok(tracker) => (tracker.heartbeat_arrivals.back().map(|at| at.elapsed()), tracker.quality()),
+        Err(_) => return,
+    };
+
+    if let Ok(mut status) = state.connection_status.write() {
+        status.link_quality = match (status.connected, since_last) {
+            (true, Some(elapsed)) => quality.min(link_quality_for_interval(elapsed)),
+            _ => 0.0,
+        };
+    }
+    publish_link_quality(app_handle, state);
+}
+
+// Emit `link-quality` when the score moved by more than LINK_QUALITY_EVENT_DELTA
+fn publish_link_quality(app_handle: &tauri::AppHandle, state: &MavlinkState) {
+    let status = match connection_snapshot(state) {
+        Ok(status) => status,
+        Err(_) => return,
+    };
+    let mut tracker = match state.link_tracker.lock() {
+        Ok(tracker) => tracker,
+        Err(_) => return,
+    };
+    let changed = tracker.last_emitted_quality
+        .map_or(true, |last| (last - status.link_quality).abs() > LINK_QUALITY_EVENT_DELTA);
+    if !changed {
+        return;
+    }
+
+    let payload = serde_json::json!({
+        "link_quality": status.link_quality,
+        "jitter_ms": status.jitter_ms,
+        "packet_loss_ratio": status.packet_loss_ratio,
+    });
+    if let Err(e) = app_handle.emit_all("link-quality", payload) {
+        eprintln!("Failed to emit link quality: {e}");
+        return;
+    }
+    tracker.last_emitted_quality = Some(status.link_quality);
+}
+
+// Ceiling while heartbeats are overdue: 1.0 at the nominal 1 Hz rate, 0.0 after three misses
+fn link_quality_for_interval(interval: Duration) -> f32 {
+    let expected = HEARTBEAT_INTERVAL_MS as f32;
+    let missed = (interval.as_millis() as f32 / expected - 1.0).max(0.0);
+    (1.0 - missed / MAX_MISSED_HEARTBEATS).clamp(0.0, 1.0)
+}
+
+fn connection_snapshot(state: &MavlinkState) -> Result<ConnectionStatus, String> {
+    let mut status = state.connection_status.read()
+        .map_err(|_| "Failed to read connection status")?
+        .clone();
+    status.messages_received = state.messages_received.load(Ordering::Relaxed);
+    status.messages_sent = state.messages_sent.load(Ordering::Relaxed);
+    Ok(status)
+}
+
+// Emit `connection-status` when it changed meaningfully, at most every 500 ms
+fn publish_connection_status(app_handle: &tauri::AppHandle, state: &MavlinkState) {
+    let status = match connection_snapshot(state) {
+        Ok(status) => status,
+        Err(_) => return,
+    };
+    let mut publisher = match state.status_publisher.lock() {
+        Ok(publisher) => publisher,
+        Err(_) => return,
+    };
+
+    let significant = match &publisher.last_emitted {
+        None => true,
+        Some(last) => {
+            last.connected != status.connected
+                || (last.link_quality - status.link_quality).abs() >= LINK_QUALITY_EMIT_DELTA
+                || status.messages_received.abs_diff(last.messages_received) >= MESSAGE_COUNT_EMIT_DELTA
+        }
+    };
+    let throttled = publisher.last_emit_at
+        .map_or(false, |at| at.elapsed() < Duration::from_millis(STATUS_EMIT_MIN_INTERVAL_MS));
+    if !significant || throttled {
+        return;
+    }
+
+    if let Err(e) = app_handle.emit_all("connection-status", status.clone()) {
+        eprintln!("Failed to emit connection status: {e}");
+        return;
+    }
+    publisher.last_emitted = Some(status);
+    publisher.last_emit_at = Some(Instant::now());
+}
+
+// ===== COMMAND PROTOCOL HELPERS =====
+
+// Send a COMMAND_LONG to the connected vehicle and wait for its COMMAND_ACK
+async fn send_command_long(
+    app_handle: &tauri::AppHandle,
+    state: &MavlinkState,
+    command: MavCmd,
+    params: [f32; 7],
+) -> Result<(), String> {
+    send_command_long_result(app_handle, state, command, params).await?.into_result(command)
+}
+
+// As `send_command_long`, but hands back a refusal for the caller to explain
+async fn send_command_long_result(
+    app_handle: &tauri::AppHandle,
+    state: &MavlinkState,
+    command: MavCmd,
+    params: [f32; 7],
+) -> Result<MavCmdResult, String> {
+    await_command_ack(app_handle, state, command, |target_system, target_component| {
+        MavMessage::COMMAND_LONG(COMMAND_LONG_DATA {
+            param1: params[0],
+            param2: params[1],
+            param3: params[2],
+            param4: params[3],
+            param5: params[4],
+            param6: params[5],
+            param7: params[6],
+            command,
+            target_system,
+            target_component,
+            confirmation: 0,
+        })
+    })
+    .await
+}
+
+// COMMAND_INT, for commands whose position needs integer degrees * 1e7
+async fn send_command_int(
+    app_handle: &tauri::AppHandle,
+    state: &MavlinkState,
+    command: MavCmd,
+    params: [f32; 4],
+    location: &Coordinate,
+) -> Result<(), String> {
+    let result = await_command_ack(app_handle, state, command, |target_system, target_component| {
+        MavMessage::COMMAND_INT(COMMAND_INT_DATA {
+            param1: params[0],
+            param2: params[1],
+            param3: params[2],
+            param4: params[3],
+            x: (location.lat * 1e7).round() as i32,
+            y: (location.lng * 1e7).round() as i32,
+            z: location.alt.unwrap_or(0.0) as f32,
+            command,
+            target_system,
+            target_component,
+            frame: MavFrame::MAV_FRAME_GLOBAL_RELATIVE_ALT_INT,
+            current: 0,
+            autocontinue: 0,
+        })
+    })
+    .await?;
+    result.into_result(command)
+}
+
+// Send the command built for the vehicle's system and component ids, then
+// wait for its COMMAND_ACK
+async fn await_command_ack(
+    app_handle: &tauri::AppHandle,
+    state: &MavlinkState,
+    command: MavCmd,
+    message: impl FnOnce(u8, u8) -> MavMessage,
+) -> Result<MavCmdResult, String> {
+    let (target_system, target_component) = vehicle_target(state)?;
+    let timeout = Duration::from_millis(state.command_timeout_ms.load(Ordering::Relaxed));
+    let (sent_at, result_rx) = register_pending_command(state, command, timeout)?;
+
+    if let Err(e) = transmit_command(app_handle, state, &message(target_system, target_component)) {
+        remove_pending_command(state, command as u16, sent_at);
+        return Err(e);
+    }
+    wait_for_command_ack(state, command, sent_at, result_rx, timeout).await
+}
+
+// Claim the command's ID for an ACK from the connected vehicle
+fn register_pending_command(
+    state: &MavlinkState,
+    command: MavCmd,
+    timeout: Duration,
+) -> Result<(Instant, oneshot::Receiver<MavCmdResult>), String> {
+    let (target_system, target_component) = vehicle_target(state)?;
+    let command_id = command as u16;
+    let sent_at = Instant::now();
+
+    let (result_tx, result_rx) = oneshot::channel();
+    let mut pending = state.pending_commands.lock()
+        .map_err(|_| "Failed to lock pending commands")?;
+    // An entry whose sender was dropped or timed out can be replaced
+    if let Some(existing) = pending.get(&command_id) {
+        if !existing.result_tx.is_closed() && existing.sent_at.elapsed() < timeout {
+            return Err(format!("{command:?} is already awaiting acknowledgement"));
+        }
+    }
+    pending.insert(command_id, PendingCommand {
+        command_id,
+        target_system,
+        target_component,
+        sent_at,
+        result_tx,
+    });
+    Ok((sent_at, result_rx))
+}
+
+async fn wait_for_command_ack(
+    state: &MavlinkState,
+    command: MavCmd,
+    sent_at: Instant,
+    result_rx: oneshot::Receiver<MavCmdResult>,
+    timeout: Duration,
+) -> Result<MavCmdResult, String> {
+    match tokio::time::timeout(timeout, result_rx).await {
+        Ok(Ok(result)) => Ok(result),
+        Ok(Err(_)) => Err(format!("{command:?} aborted: vehicle disconnected")),
+        Err(_) => {
+            remove_pending_command(state, command as u16, sent_at);
+            Err(format!(
+                "{command:?} timed out after {} ms without acknowledgement",
+                timeout.as_millis()
+            ))
+        }
+    }
+}
+
+// MAV_CMD_COMPONENT_ARM_DISARM; a refusal is reported with the STATUSTEXT
+// failures the vehicle sent from just before the command until
+// ARM_FAILURE_COLLECT_MS after the ACK
+async fn send_arm_disarm(
+    app_handle: &tauri::AppHandle,
+    state: &MavlinkState,
+    arm: bool,
+    force: bool,
+) -> Result<ArmDisarmResult, String> {
+    let command = MavCmd::MAV_CMD_COMPONENT_ARM_DISARM;
+    let params = [
+        if arm { 1.0 } else { 0.0 },
+        if force { FORCE_ARM_DISARM_MAGIC } else { 0.0 },
+        0.0, 0.0, 0.0, 0.0, 0.0,
+    ];
+    let sent_ms = get_timestamp();
+    let result = send_command_long_result(app_handle, state, command, params).await?;
+    let error = match result.into_result(command) {
+        Ok(()) => return Ok(ArmDisarmResult { accepted: true, error: None, failures: Vec::new() }),
+        Err(e) => e,
+    };
+
+    tokio::time::sleep(Duration::from_millis(ARM_FAILURE_COLLECT_MS)).await;
+    let failures = state.statustext_log.lock()
+        .map_err(|_| "Failed to lock status text log")?
+        .iter()
+        .filter(|status| status.timestamp_ms >= sent_ms)
+        .filter_map(arm_failure)
+        .collect();
+    Ok(ArmDisarmResult { accepted: false, error: Some(error), failures })
+}
+
+fn arm_failure(status: &StatusText) -> Option<ArmFailure> {
+    let text = status.text.trim();
+    let prefix = ARM_FAILURE_PREFIXES.iter()
+        .find(|prefix| text.len() >= prefix.len() && text[..prefix.len()].eq_ignore_ascii_case(prefix))?;
+    Some(ArmFailure {
+        severity: status.severity.clone(),
+        message: text[prefix.len()..].trim().to_string(),
+        timestamp_ms: status.timestamp_ms,
+    })
+}
+
+// Drop our pending entry unless a newer command with the same ID replaced it
+fn remove_pending_command(state: &MavlinkState, command_id: u16, sent_at: Instant) {
+    if let Ok(mut pending) = state.pending_commands.lock() {
+        if pending.get(&command_id).map_or(false, |cmd| cmd.sent_at == sent_at) {
+            pending.remove(&command_id);
+        }
+    }
+}
+
+// System and component id of the connected vehicle
+fn vehicle_target(state: &MavlinkState) -> Result<(u8, u8), String> {
+    let info = state.vehicle_info.read()
+        .map_err(|_| "Failed to read vehicle info")?;
+    let info = info.as_ref().ok_or("No vehicle connected")?;
+    Ok((info.system_id, info.component_id))
+}
+
+fn transmit_command(
+    app_handle: &tauri::AppHandle,
+    state: &MavlinkState,
+    message: &MavMessage,
+) -> Result<(), String> {
+    send_message(state, message)?;
+    if !is_simulated_link(state) {
+        return Ok(());
+    }
+    let (command_id, param1, param2, param3) = match message {
+        MavMessage::COMMAND_LONG(command) => (command.command, command.param1, command.param2, command.param3),
+        MavMessage::COMMAND_INT(command) => (command.command, command.param1, command.param2, command.param3),
+        _ => return Ok(()),
+    };
+
+    // The simulated vehicle accepts every command
+    if command_id == MavCmd::MAV_CMD_COMPONENT_ARM_DISARM {
+        state.mock_armed.store(param1 == 1.0, Ordering::SeqCst);
+    }
+    if command_id == MavCmd::MAV_CMD_DO_SET_MODE {
+        state.mock_custom_mode.store(param2 as u64, Ordering::SeqCst);
+    }
+    if command_id == MavCmd::MAV_CMD_PREFLIGHT_CALIBRATION && param3 != 0.0 {
+        let epoch = state.connection_epoch.load(Ordering::SeqCst);
+        tauri::async_runtime::spawn(run_mock_mag_cal(app_handle.clone(), epoch));
+    }
+    if command_id == MavCmd::MAV_CMD_ACCELCAL_VEHICLE_POS {
+        let epoch = state.connection_epoch.load(Ordering::SeqCst);
+        tauri::async_runtime::spawn(run_mock_imu(app_handle.clone(), epoch, param1 as u8));
+    }
+    let ack = MavMessage::COMMAND_ACK(COMMAND_ACK_DATA {
+        command: command_id,
+        result: MavResult::MAV_RESULT_ACCEPTED,
+    });
+    handle_incoming_message(app_handle, state, mock_vehicle_header(state), &ack);
+    Ok(())
+}
+
+// Simulated onboard compass calibration: progress while the vehicle is
+// rotated, then the report for its single compass
+async fn run_mock_mag_cal(app_handle: tauri::AppHandle, epoch: u64) {
+    let mut messages: Vec<MavMessage> = (10..=100u8).step_by(10)
+        .map(|completion_pct| MavMessage::MAG_CAL_PROGRESS(MAG_CAL_PROGRESS_DATA {
+            cal_mask: 1,
+            cal_status: if completion_pct <= 50 {
+                MagCalStatus::MAG_CAL_RUNNING_STEP_ONE
+            } else {
+                MagCalStatus::MAG_CAL_RUNNING_STEP_TWO
+            },
+            completion_pct,
+            ..MAG_CAL_PROGRESS_DATA::DEFAULT
+        }))
+        .collect();
+    messages.push(MavMessage::MAG_CAL_REPORT(MAG_CAL_REPORT_DATA {
+        fitness: 8.4,
+        ofs_x: 42.0,
+        ofs_y: -17.5,
+        ofs_z: 63.2,
+        diag_x: 1.012,
+        diag_y: 0.994,
+        diag_z: 1.003,
+        cal_mask: 1,
+        cal_status: MagCalStatus::MAG_CAL_SUCCESS,
+        autosaved: 1,
+        ..MAG_CAL_REPORT_DATA::DEFAULT
+    }));
+
+    for message in messages {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let state = app_handle.state::<MavlinkState>();
+        if state.connection_epoch.load(Ordering::SeqCst) != epoch {
+            return;
+        }
+        handle_incoming_message(&app_handle, &state, mock_vehicle_header(&state), &message);
+    }
+}
+
+// Simulated vehicle held in the commanded calibration position: streams
+// SCALED_IMU at 50 Hz for a little longer than one sample window
+async fn run_mock_imu(app_handle: tauri::AppHandle, epoch: u64, position: u8) {
+    let message = MavMessage::SCALED_IMU(mock_scaled_imu(position));
+    let count = ACCEL_SAMPLE_WINDOW_MS / MOCK_IMU_INTERVAL_MS + 10;
+    for _ in 0..count {
+        tokio::time::sleep(Duration::from_millis(MOCK_IMU_INTERVAL_MS)).await;
+        let state = app_handle.state::<MavlinkState>();
+        if state.connection_epoch.load(Ordering::SeqCst) != epoch {
+            return;
+        }
+        handle_incoming_message(&app_handle, &state, mock_vehicle_header(&state), &message);
+    }
+}
+
+// Gravity as an accelerometer with a small bias and scale error reads it in
+// each ACCELCAL_VEHICLE_POS orientation, in mG
+fn mock_scaled_imu(position: u8) -> SCALED_IMU_DATA {
+    let ideal: [f32; 3] = match position {
+        1 => [0.0, 0.0, -1000.0],
+        2 => [0.0, 1000.0, 0.0],
+        3 => [0.0, -1000.0, 0.0],
+        4 => [1000.0, 0.0, 0.0],
+        5 => [-1000.0, 0.0, 0.0],
+        _ => [0.0, 0.0, 1000.0],
+    };
+    let bias = [1.2, -0.8, 0.3];
+    let scale = [1.001, 0.998, 1.002];
+    let reading = |axis: usize| (ideal[axis] * scale[axis] + bias[axis]).round() as i16;
+    SCALED_IMU_DATA {
+        xacc: reading(0),
+        yacc: reading(1),
+        zacc: reading(2),
+        ..SCALED_IMU_DATA::DEFAULT
+    }
+}
+
+fn transmit_param_set(
+    app_handle: &tauri::AppHandle,
+    state: &MavlinkState,
+    request: PARAM_SET_DATA,
+) -> Result<(), String> {
+    send_message(state, &MavMessage::PARAM_SET(request.clone()))?;
+    if !is_simulated_link(state) {
+        return Ok(());
+    }
+
+    // The simulated vehicle accepts every value and echoes it
+    let message = MavMessage::PARAM_VALUE(PARAM_VALUE_DATA {
+        param_value: request.param_value,
+        param_count: MOCK_VEHICLE_PARAMETERS.len() as u16,
+        param_index: UNINDEXED_PARAM,
+        param_id: request.param_id,
+        param_type: request.param_type,
+    });
+    handle_incoming_message(app_handle, state, mock_vehicle_header(state), &message);
+    Ok(())
+}
+
+fn transmit_param_request_list(
+    app_handle: &tauri::AppHandle,
+    state: &MavlinkState,
+    request: PARAM_REQUEST_LIST_DATA,
+) -> Result<(), String> {
+    send_message(state, &MavMessage::PARAM_REQUEST_LIST(request))?;
+    if !is_simulated_link(state) {
+        return Ok(());
+    }
+
+    // The simulated vehicle streams its parameter table
+    let param_count = MOCK_VEHICLE_PARAMETERS.len() as u16;
+    for (param_index, (id, value, param_type)) in (0u16..).zip(MOCK_VEHICLE_PARAMETERS) {
+        let message = MavMessage::PARAM_VALUE(PARAM_VALUE_DATA {
+            param_value: value,
+            param_count,
+            param_index,
+            param_id: param_id_bytes(id),
+            param_type,
+        });
+        handle_incoming_message(app_handle, state, mock_vehicle_header(state), &message);
+    }
+    Ok(())
+}
+
+fn transmit_mission_count(
+    app_handle: &tauri::AppHandle,
+    state: &MavlinkState,
+    count: &MISSION_COUNT_DATA,
+) -> Result<(), String> {
+    send_message(state, &MavMessage::MISSION_COUNT(count.clone()))?;
+    if !is_simulated_link(state) {
+        return Ok(());
+    }
+
+    // The simulated vehicle requests every item in order, then accepts the mission
+    for seq in 0..count.count {
+        let request = MavMessage::MISSION_REQUEST_INT(MISSION_REQUEST_INT_DATA {
+            seq,
+            target_system: GCS_SYSTEM_ID,
+            target_component: GCS_COMPONENT_ID,
+        });
+        handle_incoming_message(app_handle, state, mock_vehicle_header(state), &request);
+    }
+    let ack = MavMessage::MISSION_ACK(MISSION_ACK_DATA {
+        target_system: GCS_SYSTEM_ID,
+        target_component: GCS_COMPONENT_ID,
+        mavtype: MavMissionResult::MAV_MISSION_ACCEPTED,
+    });
+    handle_incoming_message(app_handle, state, mock_vehicle_header(state), &ack);
+    Ok(())
+}
+
+// Encode an outgoing message and write it to the link, counting it once sent
+fn send_message(state: &MavlinkState, message: &MavMessage) -> Result<(), String> {
+    let header = MavHeader {
+        system_id: GCS_SYSTEM_ID,
+        component_id: GCS_COMPONENT_ID,
+        sequence: state.messages_sent.load(Ordering::Relaxed) as u8,
+    };
+    let mut frame = Vec::new();
+    ::mavlink::write_v2_msg(&mut frame, header, message)
+        .map_err(|e| format!("Failed to encode MAVLink message: {e}"))?;
+
+    // Simulated links have nowhere to write the frame
+    if let Some(link) = state.vehicle_link.lock().map_err(|_| "Failed to lock vehicle link")?.as_ref() {
+        link.write_frame(&frame)?;
+    }
+    state.messages_sent.fetch_add(1, Ordering::Relaxed);
+    Ok(())
+}
+
+// SERIAL_CONTROL carrying up to SERIAL_CONTROL_DATA_BYTES of shell input
+fn serial_control_message(chunk: &[u8], flags: SerialControlFlag) -> MavMessage {
+    let mut data = [0u8; SERIAL_CONTROL_DATA_BYTES];
+    let count = chunk.len().min(SERIAL_CONTROL_DATA_BYTES);
+    data[..count].copy_from_slice(&chunk[..count]);
+    MavMessage::SERIAL_CONTROL(SERIAL_CONTROL_DATA {
+        baudrate: 0,
+        timeout: 0,
+        device: SerialControlDev::SERIAL_CONTROL_DEV_SHELL,
+        flags,
+        count: count as u8,
+        data,
+    })
+}
+
+// HEARTBEAT identifying this application as a ground station
+fn gcs_heartbeat() -> MavMessage {
+    MavMessage::HEARTBEAT(HEARTBEAT_DATA {
+        custom_mode: 0,
+        mavtype: MavType::MAV_TYPE_GCS,
+        autopilot: MavAutopilot::MAV_AUTOPILOT_INVALID,
+        base_mode: MavModeFlag::empty(),
+        system_status: MavState::MAV_STATE_ACTIVE,
+        mavlink_version: 3,
+    })
+}
+
+// Header of a frame from the simulated vehicle, numbered by receive count
+fn mock_vehicle_header(state: &MavlinkState) -> MavHeader {
+    MavHeader {
+        system_id: 1,
+        component_id: 1,
+        sequence: state.messages_received.load(Ordering::Relaxed) as u8,
+    }
+}
+
+// Parameter table of the simulated vehicle
+const MOCK_VEHICLE_PARAMETERS: [(&str, f32, MavParamType); 4] = [
+    ("ARMING_CHECK", 1.0, MavParamType::MAV_PARAM_TYPE_INT32),
+    ("THR_MIN", 130.0, MavParamType::MAV_PARAM_TYPE_INT16),
+    ("ANGLE_MAX", 4500.0, MavParamType::MAV_PARAM_TYPE_INT16),
+    ("BATT_CAPACITY", 5000.0, MavParamType::MAV_PARAM_TYPE_INT32),
+];
+
+// SYS_STATUS of a simulated quadcopter with every sensor healthy
+fn mock_sys_status() -> MavMessage {
+    let sensors = MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_3D_GYRO
+        | MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_3D_ACCEL
+        | MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_3D_MAG
+        | MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_ABSOLUTE_PRESSURE
+        | MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_GPS
+        | MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_RC_RECEIVER
+        | MavSysStatusSensor::MAV_SYS_STATUS_AHRS
+        | MavSysStatusSensor::MAV_SYS_STATUS_SENSOR_BATTERY
+        | MavSysStatusSensor::MAV_SYS_STATUS_PREARM_CHECK;
+    MavMessage::SYS_STATUS(SYS_STATUS_DATA {
+        onboard_control_sensors_present: sensors,
+        onboard_control_sensors_enabled: sensors,
+        onboard_control_sensors_health: sensors,
+        voltage_battery: 12_600,
+        current_battery: 520,
+        battery_remaining: 87,
+        ..SYS_STATUS_DATA::DEFAULT
+    })
+}
+
+// EKF_STATUS_REPORT of a simulated vehicle with a healthy GPS-aided solution
+fn mock_ekf_status_report() -> MavMessage {
+    MavMessage::EKF_STATUS_REPORT(EKF_STATUS_REPORT_DATA {
+        velocity_variance: 0.1,
+        pos_horiz_variance: 0.1,
+        pos_vert_variance: 0.1,
+        compass_variance: 0.1,
+        terrain_alt_variance: 0.0,
+        flags: EkfStatusFlags::EKF_ATTITUDE
+            | EkfStatusFlags::EKF_VELOCITY_HORIZ
+            | EkfStatusFlags::EKF_VELOCITY_VERT
+            | EkfStatusFlags::EKF_POS_HORIZ_REL
+            | EkfStatusFlags::EKF_POS_HORIZ_ABS
+            | EkfStatusFlags::EKF_POS_VERT_ABS
+            | EkfStatusFlags::EKF_PRED_POS_HORIZ_REL
+            | EkfStatusFlags::EKF_PRED_POS_HORIZ_ABS,
+    })
+}
+
+// Vehicle identity from its first heartbeat; firmware and capabilities need
+// AUTOPILOT_VERSION, which is not requested yet
+fn vehicle_info_from_heartbeat(header: &MavHeader, heartbeat: &HEARTBEAT_DATA) -> VehicleInfo {
+    VehicleInfo {
+        system_id: header.system_id,
+        component_id: header.component_id,
+        autopilot_type: autopilot_name(heartbeat.autopilot),
+        vehicle_type: vehicle_type_name(heartbeat.mavtype),
+        firmware_version: "Unknown".to_string(),
+        capabilities: Vec::new(),
+        armed: heartbeat.base_mode.contains(MavModeFlag::MAV_MODE_FLAG_SAFETY_ARMED),
+        flight_mode: flight_mode_name(heartbeat),
+        battery: None,
+    }
+}
+
+fn autopilot_name(autopilot: MavAutopilot) -> String {
+    match autopilot {
+        MavAutopilot::MAV_AUTOPILOT_ARDUPILOTMEGA => "ArduPilot".to_string(),
+        MavAutopilot::MAV_AUTOPILOT_PX4 => "PX4".to_string(),
+        other => format!("{other:?}").trim_start_matches("MAV_AUTOPILOT_").to_string(),
+    }
+}
+
+fn vehicle_type_name(mavtype: MavType) -> String {
+    match mavtype {
+        MavType::MAV_TYPE_QUADROTOR => "Quadcopter".to_string(),
+        MavType::MAV_TYPE_HEXAROTOR => "Hexacopter".to_string(),
+        MavType::MAV_TYPE_OCTOROTOR => "Octocopter".to_string(),
+        MavType::MAV_TYPE_FIXED_WING => "Fixed Wing".to_string(),
+        MavType::MAV_TYPE_HELICOPTER => "Helicopter".to_string(),
+        MavType::MAV_TYPE_GROUND_ROVER => "Rover".to_string(),
+        other => format!("{other:?}").trim_start_matches("MAV_TYPE_").to_string(),
+    }
+}
+
+// Mode names by custom_mode
+type FlightModeTable = &'static [(u32, &'static str)];
+
+// ArduCopter mode names by custom_mode
+const COPTER_FLIGHT_MODES: [(u32, &str); 25] = [
+    (0, "STABILIZE"), (1, "ACRO"), (2, "ALT_HOLD"), (3, "AUTO"), (4, "GUIDED"),
+    (5, "LOITER"), (6, "RTL"), (7, "CIRCLE"), (9, "LAND"), (11, "DRIFT"),
+    (13, "SPORT"), (14, "FLIP"), (15, "AUTOTUNE"), (16, "POSHOLD"), (17, "BRAKE"),
+    (18, "THROW"), (19, "AVOID_ADSB"), (20, "GUIDED_NOGPS"), (21, "SMART_RTL"), (22, "FLOWHOLD"),
+    (23, "FOLLOW"), (24, "ZIGZAG"), (25, "SYSTEMID"), (26, "AUTOROTATE"), (27, "AUTO_RTL"),
+];
+
+// ArduPlane mode names by custom_mode
+const PLANE_FLIGHT_MODES: [(u32, &str); 24] = [
+    (0, "MANUAL"), (1, "CIRCLE"), (2, "STABILIZE"), (3, "TRAINING"), (4, "ACRO"),
+    (5, "FBWA"), (6, "FBWB"), (7, "CRUISE"), (8, "AUTOTUNE"), (10, "AUTO"),
+    (11, "RTL"), (12, "LOITER"), (13, "TAKEOFF"), (14, "AVOID_ADSB"), (15, "GUIDED"),
+    (17, "QSTABILIZE"), (18, "QHOVER"), (19, "QLOITER"), (20, "QLAND"), (21, "QRTL"),
+    (22, "QAUTOTUNE"), (23, "QACRO"), (24, "THERMAL"), (25, "LOITER_ALT_QLAND"),
+];
+
+// ArduRover mode names by custom_mode
+const ROVER_FLIGHT_MODES: [(u32, &str); 13] = [
+    (0, "MANUAL"), (1, "ACRO"), (3, "STEERING"), (4, "HOLD"), (5, "LOITER"),
+    (6, "FOLLOW"), (7, "SIMPLE"), (8, "DOCK"), (9, "CIRCLE"), (10, "AUTO"),
+    (11, "RTL"), (12, "SMART_RTL"), (15, "GUIDED"),
+];
+
+// PX4 packs its main mode into bits 16-23 of custom_mode and the auto
+// sub-mode into bits 24-31
+const fn px4_custom_mode(main_mode: u32, sub_mode: u32) -> u32 {
+    (main_mode << 16) | (sub_mode << 24)
+}
+
+const PX4_FLIGHT_MODES: [(u32, &str); 15] = [
+    (px4_custom_mode(1, 0), "MANUAL"),
+    (px4_custom_mode(2, 0), "ALTCTL"),
+    (px4_custom_mode(3, 0), "POSCTL"),
+    (px4_custom_mode(4, 1), "AUTO_READY"),
+    (px4_custom_mode(4, 2), "AUTO_TAKEOFF"),
+    (px4_custom_mode(4, 3), "AUTO_LOITER"),
+    (px4_custom_mode(4, 4), "AUTO_MISSION"),
+    (px4_custom_mode(4, 5), "AUTO_RTL"),
+    (px4_custom_mode(4, 6), "AUTO_LAND"),
+    (px4_custom_mode(4, 8), "AUTO_FOLLOW_TARGET"),
+    (px4_custom_mode(4, 9), "AUTO_PRECLAND"),
+    (px4_custom_mode(5, 0), "ACRO"),
+    (px4_custom_mode(6, 0), "OFFBOARD"),
+    (px4_custom_mode(7, 0), "STABILIZED"),
+    (px4_custom_mode(8, 0), "RATTITUDE"),
+];
+
+// Mode table for the firmware named by VehicleInfo; None when unknown
+fn flight_mode_table(autopilot_type: &str, vehicle_type: &str) -> Option<FlightModeTable> {
+    match (autopilot_type, vehicle_type) {
+        ("PX4", _) => Some(&PX4_FLIGHT_MODES),
+        ("ArduPilot", "Quadcopter" | "Hexacopter" | "Octocopter" | "Helicopter" | "TRICOPTER" | "COAXIAL") => {
+            Some(&COPTER_FLIGHT_MODES)
+        }
+        ("ArduPilot", "Fixed Wing" | "VTOL_FIXEDROTOR" | "VTOL_TAILSITTER_QUADROTOR") => Some(&PLANE_FLIGHT_MODES),
+        ("ArduPilot", "Rover" | "SURFACE_BOAT") => Some(&ROVER_FLIGHT_MODES),
+        _ => None,
+    }
+}
+
+// Mode names are firmware specific; unknown ones are shown by number
+fn flight_mode_name(heartbeat: &HEARTBEAT_DATA) -> String {
+    let known = flight_mode_table(&autopilot_name(heartbeat.autopilot), &vehicle_type_name(heartbeat.mavtype))
+        .and_then(|table| table.iter().find(|(mode, _)| *mode == heartbeat.custom_mode));
+    match known {
+        Some((_, name)) => name.to_string(),
+        None => format!("MODE {}", heartbeat.custom_mode),
+    }
+}
+
+fn mock_vehicle_info() -> VehicleInfo {
+    VehicleInfo {
+        system_id: 1,
+        component_id: 1,
+        autopilot_type: "ArduPilot".to_string(),
+        vehicle_type: "Quadcopter".to_string(),
+        firmware_version: "4.5.0".to_string(),
+        capabilities: vec![
+            "MISSION".to_string(),
+            "PARAM".to_string(),
+            "FENCE".to_string(),
+            "RALLY".to_string(),
+        ],
+        armed: false,
+        flight_mode: "STABILIZE".to_string(),
+        battery: None,
+    }
+}
+
+// HEARTBEAT as the simulated vehicle would send it
+fn mock_heartbeat(state: &MavlinkState) -> MavMessage {
+    let base_mode = if state.mock_armed.load(Ordering::SeqCst) {
+        MavModeFlag::MAV_MODE_FLAG_SAFETY_ARMED
+    } else {
+        MavModeFlag::empty()
+    };
+    MavMessage::HEARTBEAT(HEARTBEAT_DATA {
+        custom_mode: state.mock_custom_mode.load(Ordering::SeqCst) as u32,
+        mavtype: MavType::MAV_TYPE_QUADROTOR,
+        autopilot: MavAutopilot::MAV_AUTOPILOT_ARDUPILOTMEGA,
+        base_mode,
+        system_status: MavState::MAV_STATE_STANDBY,
+        mavlink_version: 3,
+    })
+}
+
+// ===== MESSAGE INSPECTOR HELPERS =====
+
+// Append to the bounded inspector log and update per-ID statistics
+fn log_inspector_message(state: &MavlinkState, message: &MavMessage, timestamp_ms: u64) {
+    let msg_id = ::mavlink::Message::message_id(message);
+
+    if let Ok(mut log) = state.message_log.lock() {
+        if log.len() >= MESSAGE_LOG_CAPACITY {
+            log.pop_front();
+        }
+        log.push_back(MavlogEntry {
+            msg_id,
+            msg_name: ::mavlink::Message::message_name(message).to_string(),
+            payload_json: message_payload_json(message),
+            timestamp_ms,
+        });
+    }
+
+    if let Ok(mut stats) = state.message_stats.lock() {
+        let entry = stats.entry(msg_id).or_default();
+        if entry.count > 0 {
+            // Running mean over the count - 1 intervals seen so far
+            let interval = timestamp_ms.saturating_sub(entry.last_timestamp_ms) as f64;
+            entry.average_interval_ms += (interval - entry.average_interval_ms) / entry.count as f64;
+        }
+        entry.count += 1;
+        entry.last_timestamp_ms = timestamp_ms;
+    }
+}
+
+// Message fields as JSON, without the enum tag serde adds for the variant
+fn message_payload_json(message: &MavMessage) -> String {
+    match serde_json::to_value(message) {
+        Ok(serde_json::Value::Object(mut fields)) => {
+            fields.remove("type");
+            serde_json::Value::Object(fields).to_string()
+        }
+        Ok(other) => other.to_string(),
+        Err(e) => serde_json::json!({ "error": e.to_string() }).to_string(),
+    }
+}
+
+// ===== TELEMETRY LOG HELPERS =====
+
+fn is_tlog_recording(state: &MavlinkState) -> Result<bool, String> {
+    let writer = state.tlog_writer.lock()
+        .map_err(|_| "Failed to lock telemetry log")?;
+    Ok(writer.is_some())
+}
+
+// Append a received frame as [8-byte big-endian µs timestamp][raw frame]
+fn record_tlog_frame(state: &MavlinkState, frame: &[u8]) {
+    let mut guard = match state.tlog_writer.lock() {
+        Ok(guard) => guard,
+        Err(_) => return,
+    };
+    let writer = match guard.as_mut() {
+        Some(writer) => writer,
+        None => return,
+    };
+
+    let written = writer.write_all(&get_timestamp_us().to_be_bytes())
+        .and_then(|_| writer.write_all(frame));
+    match written {
+        Ok(()) => {
+            state.tlog_bytes_written.fetch_add(8 + frame.len() as u64, Ordering::Relaxed);
+        }
+        Err(e) => eprintln!("Failed to write telemetry log frame: {e}"),
+    }
+}
+
+// Flush and close the log, returning the total bytes written
+fn finish_tlog_recording(state: &MavlinkState) -> Result<u64, String> {
+    let writer = state.tlog_writer.lock()
+        .map_err(|_| "Failed to lock telemetry log")?
+        .take();
+    if let Ok(mut recording) = state.tlog_recording.lock() {
+        *recording = None;
+    }
+
+    if let Some(mut writer) = writer {
+        writer.flush()
+            .map_err(|e| format!("Failed to flush telemetry log: {e}"))?;
+    }
+    Ok(state.tlog_bytes_written.load(Ordering::Relaxed))
+}
+
+// ===== CALIBRATION SESSION HELPERS =====
+
+// Drop the active session (failing any waiters) and release the calibration guard
+fn abort_calibration_session(state: &State<'_, MavlinkState>) {
+    if let Ok(mut session) = state.calibration_session.lock() {
+        *session = None;
+    }
+    set_calibration_active(state, false);
+}
+
+fn set_calibration_active(state: &State<'_, MavlinkState>, active: bool) {
+    if let Ok(mut calibrating) = state.calibration_active.write() {
+        *calibrating = active;
+    }
+}
+
+fn accelerometer_session(completion_tx: Option<oneshot::Sender<CalibrationResult>>) -> CalibrationSession {
+    CalibrationSession {
+        sensor_type: "Accelerometer".to_string(),
+        current_step: 0,
+        total_steps: 6,
+        step_instructions: vec![
+            "Place the vehicle level".to_string(),
+            "Place the vehicle on its left side".to_string(),
+            "Place the vehicle on its right side".to_string(),
+            "Place the vehicle nose down".to_string(),
+            "Place the vehicle nose up".to_string(),
+            "Place the vehicle on its back".to_string(),
+        ],
+        confirm_tx: None,
+        completion_tx,
+        samples: Vec::new(),
+    }
+}
+
+// SCALED_IMU accelerations are in mG
+fn record_scaled_imu(state: &MavlinkState, imu: &SCALED_IMU_DATA) {
+    let ms2_per_mg = STANDARD_GRAVITY / 1000.0;
+    let sample = ImuSample {
+        accel: [imu.xacc, imu.yacc, imu.zacc].map(|mg| f32::from(mg) * ms2_per_mg),
+        timestamp_ms: get_timestamp(),
+    };
+    if let Ok(mut samples) = state.imu_samples.lock() {
+        if samples.len() >= IMU_SAMPLE_CAPACITY {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+}
+
+// Mean accelerometer reading since `since_ms`; None with too few readings to trust
+fn mean_accel_since(samples: &VecDeque<ImuSample>, since_ms: u64) -> Option<[f32; 3]> {
+    let recent: Vec<&ImuSample> = samples.iter().filter(|sample| sample.timestamp_ms >= since_ms).collect();
+    if recent.len() < MIN_ACCEL_SAMPLES {
+        return None;
     }
+    let count = recent.len() as f32;
+    Some([0, 1, 2].map(|axis| recent.iter().map(|sample| sample.accel[axis]).sum::<f32>() / count))
+}
 
-    // TODO: Send immediate DISARM command via MAVLink
-    // TODO: Cut motor PWM signals directly if possible
+// NASA JPL Rule 4: Function under 60 lines
+// Six-position calibration: per-axis offset is the midpoint of the +g/-g
+// readings and scale is their half-span relative to standard gravity
+fn compute_accelerometer_result(samples: &[[f32; 3]]) -> CalibrationResult {
+    let mut offsets = Vec::with_capacity(3);
+    let mut scales = Vec::with_capacity(3);
 
-    // Clear motor test flag if active
-    {
-        if let Ok(mut motor_test) = state.motor_test_active.write() {
-            *motor_test = false;
-        }
+    for axis in 0..3 {
+        let max = samples.iter().map(|s| s[axis]).fold(f32::MIN, f32::max);
+        let min = samples.iter().map(|s| s[axis]).fold(f32::MAX, f32::min);
+        offsets.push((max + min) / 2.0);
+        scales.push((max - min) / (2.0 * STANDARD_GRAVITY));
     }
 
-    // Verify completion time
-    let elapsed = start.elapsed();
-    if elapsed.as_micros() > 1000 {
-        eprintln!("WARNING: Emergency stop took {}μs (> 1ms)", elapsed.as_micros());
+    // Fitness: how closely corrected samples match a unit gravity vector
+    let mean_error = samples.iter()
+        .map(|s| {
+            let corrected: f32 = (0..3)
+                .map(|axis| ((s[axis] - offsets[axis]) / scales[axis]).powi(2))
+                .sum::<f32>()
+                .sqrt();
+            (corrected - STANDARD_GRAVITY).abs() / STANDARD_GRAVITY
+        })
+        .sum::<f32>() / samples.len().max(1) as f32;
+    let fitness = (1.0 - mean_error).clamp(0.0, 1.0);
+
+    CalibrationResult {
+        success: samples.len() == 6,
+        sensor_type: "Accelerometer".to_string(),
+        offsets,
+        scales,
+        fitness,
+        message: "Accelerometer calibration successful".to_string(),
     }
+}
 
-    Ok(())
+fn end_mag_cal_session(state: &State<'_, MavlinkState>) {
+    if let Ok(mut session) = state.mag_cal_session.lock() {
+        *session = None;
+    }
+    set_calibration_active(state, false);
 }
 
-// ===== CALIBRATION COMMANDS =====
+// The primary (lowest numbered) compass supplies offsets and scales; every
+// compass must succeed, and the worst fitness is reported
+fn mag_cal_result(reports: &[MAG_CAL_REPORT_DATA]) -> CalibrationResult {
+    let primary = reports.iter().min_by_key(|report| report.compass_id);
+    let failures: Vec<String> = reports.iter()
+        .filter(|report| report.cal_status != MagCalStatus::MAG_CAL_SUCCESS)
+        .map(|report| format!("compass {}: {:?}", report.compass_id, report.cal_status))
+        .collect();
+    let success = primary.is_some() && failures.is_empty();
 
-#[tauri::command]
-pub async fn calibrate_accelerometer(
-    state: State<'_, MavlinkState>,
-) -> Result<CalibrationResult, String> {
-    // Verify connection
-    verify_connection(&state)?;
+    CalibrationResult {
+        success,
+        sensor_type: "Magnetometer".to_string(),
+        offsets: primary.map_or_else(Vec::new, |report| vec![report.ofs_x, report.ofs_y, report.ofs_z]),
+        scales: primary.map_or_else(Vec::new, |report| vec![report.diag_x, report.diag_y, report.diag_z]),
+        fitness: reports.iter().map(|report| report.fitness).fold(0.0, f32::max),
+        message: if success {
+            "Magnetometer calibration successful".to_string()
+        } else {
+            format!("Magnetometer calibration failed: {}", failures.join(", "))
+        },
+    }
+}
 
-    // Check if already calibrating
-    {
-        let mut calibrating = state.calibration_active.write()
-            .map_err(|_| "Failed to update calibration status")?;
-        if *calibrating {
-            return Err("Calibration already in progress".to_string());
+// ===== CALIBRATION MESSAGE HANDLERS =====
+
+// Forward MAG_CAL_PROGRESS to the frontend and note which compasses are running
+fn handle_mag_cal_progress(app_handle: &tauri::AppHandle, state: &MavlinkState, data: &MAG_CAL_PROGRESS_DATA) {
+    if let Ok(mut session) = state.mag_cal_session.lock() {
+        if let Some(session) = session.as_mut() {
+            session.record_progress(data);
         }
-        *calibrating = true;
     }
+    emit_mag_cal_progress(app_handle, data, 0.0);
+}
 
-    // TODO: Implement actual accelerometer calibration
-    // This would involve:
-    // 1. Send MAV_CMD_PREFLIGHT_CALIBRATION with accel flag
-    // 2. Guide user through 6 orientations
-    // 3. Collect samples for each orientation
-    // 4. Calculate offsets and scales
-    // 5. Write calibration to vehicle
+fn emit_mag_cal_progress(app_handle: &tauri::AppHandle, data: &MAG_CAL_PROGRESS_DATA, fitness: f32) {
+    let step = match data.cal_status {
+        MagCalStatus::MAG_CAL_RUNNING_STEP_ONE => 1,
+        MagCalStatus::MAG_CAL_RUNNING_STEP_TWO => 2,
+        MagCalStatus::MAG_CAL_SUCCESS => 3,
+        _ => 0,
+    };
 
-    // Mock calibration process
-    tokio::time::sleep(Duration::from_secs(2)).await;
+    let progress = CalibrationProgress {
+        step,
+        completion_pct: f32::from(data.completion_pct),
+        fitness,
+    };
+    if let Err(e) = app_handle.emit_all("calibration-progress", progress) {
+        eprintln!("Failed to emit calibration progress: {e}");
+    }
+}
 
-    let result = CalibrationResult {
-        success: true,
-        sensor_type: "Accelerometer".to_string(),
-        offsets: vec![0.012, -0.008, 0.003],
-        scales: vec![1.001, 0.998, 1.002],
-        fitness: 0.98,
-        message: "Accelerometer calibration successful".to_string(),
+// Record a compass's MAG_CAL_REPORT; the last one due resolves the session
+fn handle_mag_cal_report(app_handle: &tauri::AppHandle, state: &MavlinkState, data: &MAG_CAL_REPORT_DATA) {
+    // Final progress update carries the fitness reported by the vehicle
+    let progress = MAG_CAL_PROGRESS_DATA {
+        compass_id: data.compass_id,
+        cal_mask: data.cal_mask,
+        cal_status: data.cal_status,
+        completion_pct: 100,
+        ..MAG_CAL_PROGRESS_DATA::DEFAULT
     };
+    emit_mag_cal_progress(app_handle, &progress, data.fitness);
 
-    // Clear calibration flag
-    {
-        let mut calibrating = state.calibration_active.write()
-            .map_err(|_| "Failed to update calibration status")?;
-        *calibrating = false;
+    let mut guard = match state.mag_cal_session.lock() {
+        Ok(guard) => guard,
+        Err(_) => return,
+    };
+    let result = match guard.as_mut().and_then(|session| session.record_report(data)) {
+        Some(result) => result,
+        None => return,
+    };
+    if let Some(completion_tx) = guard.as_mut().and_then(|session| session.completion_tx.take()) {
+        // The caller may have timed out already
+        let _ = completion_tx.send(result);
     }
-
-    Ok(result)
 }
 
-#[tauri::command]
-pub async fn calibrate_gyroscope(
-    state: State<'_, MavlinkState>,
-) -> Result<CalibrationResult, String> {
-    // Verify connection
-    verify_connection(&state)?;
+// ===== MISSION UPLOAD HELPERS =====
 
-    // Check if already calibrating
-    {
-        let mut calibrating = state.calibration_active.write()
-            .map_err(|_| "Failed to update calibration status")?;
-        if *calibrating {
-            return Err("Calibration already in progress".to_string());
+// Send MISSION_COUNT and serve the vehicle's item requests until it ACKs. The
+// wait restarts with every request, so long missions are not cut short.
+async fn await_mission_upload(
+    app_handle: &tauri::AppHandle,
+    state: &MavlinkState,
+    count: &MISSION_COUNT_DATA,
+    events: &mut mpsc::UnboundedReceiver<MissionUploadEvent>,
+) -> Result<(), String> {
+    let idle = Duration::from_millis(MISSION_UPLOAD_IDLE_MS);
+    let mut count_retries = 0;
+    let mut requested_any = false;
+    transmit_mission_count(app_handle, state, count)?;
+
+    loop {
+        match tokio::time::timeout(idle, events.recv()).await {
+            Ok(Some(MissionUploadEvent::Requested)) => requested_any = true,
+            Ok(Some(MissionUploadEvent::Acknowledged { result, complete })) => {
+                return mission_upload_outcome(result, complete);
+            }
+            Ok(None) => return Err("Mission upload aborted".to_string()),
+            Err(_) if !requested_any && count_retries < MISSION_COUNT_RETRIES => {
+                count_retries += 1;
+                transmit_mission_count(app_handle, state, count)?;
+            }
+            Err(_) => {
+                return Err(format!(
+                    "Mission upload timed out: no request or acknowledgement from the vehicle within {MISSION_UPLOAD_IDLE_MS} ms"
+                ));
+            }
         }
-        *calibrating = true;
     }
+}
 
-    // TODO: Implement actual gyroscope calibration
-    // This would involve:
-    // 1. Send MAV_CMD_PREFLIGHT_CALIBRATION with gyro flag
-    // 2. Ensure vehicle is stationary
-    // 3. Collect samples over time
-    // 4. Calculate zero-rate offsets
-    // 5. Write calibration to vehicle
-
-    // Mock calibration process
-    tokio::time::sleep(Duration::from_secs(1)).await;
+fn mission_upload_outcome(result: MavMissionResult, complete: bool) -> Result<(), String> {
+    match result {
+        MavMissionResult::MAV_MISSION_ACCEPTED if complete => Ok(()),
+        MavMissionResult::MAV_MISSION_ACCEPTED => {
+            Err("Vehicle accepted the mission before requesting every item".to_string())
+        }
+        rejected => Err(format!("Vehicle rejected the mission: {rejected:?}")),
+    }
+}
 
-    let result = CalibrationResult {
-        success: true,
-        sensor_type: "Gyroscope".to_string(),
-        offsets: vec![-0.002, 0.001, -0.003],
-        scales: vec![1.0, 1.0, 1.0],
-        fitness: 0.99,
-        message: "Gyroscope calibration successful".to_string(),
+// Answer a MISSION_REQUEST(_INT) from the vehicle with the requested item
+fn handle_mission_request(state: &MavlinkState, header: &MavHeader, seq: u16) {
+    let mut guard = match state.mission_upload.lock() {
+        Ok(guard) => guard,
+        Err(_) => return,
+    };
+    let session = match guard.as_mut() {
+        Some(session) if session.is_from_target(header) => session,
+        _ => return,
+    };
+    let index = usize::from(seq);
+    let item = match session.items.get(index) {
+        Some(item) => item,
+        None => {
+            eprintln!("Vehicle requested mission item {seq} of {}", session.items.len());
+            return;
+        }
     };
 
-    // Clear calibration flag
-    {
-        let mut calibrating = state.calibration_active.write()
-            .map_err(|_| "Failed to update calibration status")?;
-        *calibrating = false;
+    if let Err(e) = send_message(state, item) {
+        eprintln!("Failed to send mission item {seq}: {e}");
+        return;
     }
+    session.requested[index] = true;
+    let _ = session.event_tx.send(MissionUploadEvent::Requested);
+}
 
-    Ok(result)
+fn handle_mission_ack(state: &MavlinkState, header: &MavHeader, ack: &MISSION_ACK_DATA) {
+    if let Ok(upload) = state.mission_upload.lock() {
+        if let Some(session) = upload.as_ref().filter(|session| session.is_from_target(header)) {
+            let complete = session.requested.iter().all(|requested| *requested);
+            let _ = session.event_tx.send(MissionUploadEvent::Acknowledged { result: ack.mavtype, complete });
+        }
+    }
 }
 
 // ===== HELPER FUNCTIONS =====
 
-fn verify_connection(state: &State<'_, MavlinkState>) -> Result<(), String> {
+// Arming commands stay blocked while the emergency stop is latched
+fn verify_emergency_stop_clear(state: &MavlinkState) -> Result<(), String> {
+    let active = *state.emergency_stop.active.read()
+        .map_err(|_| "Failed to read emergency stop")?;
+    if active {
+        return Err("Emergency stop is latched".to_string());
+    }
+    Ok(())
+}
+
+// Whether a connected vehicle currently reports itself armed
+pub fn is_vehicle_armed(state: &MavlinkState) -> Result<bool, String> {
+    let info = state.vehicle_info.read()
+        .map_err(|_| "Failed to read vehicle info")?;
+    Ok(info.as_ref().map(|i| i.armed).unwrap_or(false))
+}
+
+// Mission progress of a connected vehicle, or None while disconnected
+pub fn vehicle_progress(state: &MavlinkState) -> Result<Option<VehicleProgress>, String> {
+    let connected = state.connection_status.read()
+        .map_err(|_| "Failed to read connection status")?
+        .connected;
+    if !connected {
+        return Ok(None);
+    }
+    let progress = state.vehicle_progress.read()
+        .map_err(|_| "Failed to read vehicle progress")?;
+    Ok(Some(progress.clone()))
+}
+
+fn verify_connection(state: &MavlinkState) -> Result<(), String> {
     let status = state.connection_status.read()
         .map_err(|_| "Failed to read connection status")?;
     
@@ -469,10 +5737,9 @@ fn verify_connection(state: &State<'_, MavlinkState>) -> Result<(), String> {
         return Err("Not connected to drone".to_string());
     }
 
-    // Check heartbeat timeout (5 seconds)
     if let Some(last_hb) = status.last_heartbeat {
         let now = get_timestamp();
-        if now - last_hb > 5000 {
+        if now - last_hb > HEARTBEAT_TIMEOUT_MS {
             return Err("Connection lost (heartbeat timeout)".to_string());
         }
     }
@@ -480,17 +5747,62 @@ fn verify_connection(state: &State<'_, MavlinkState>) -> Result<(), String> {
     Ok(())
 }
 
+// A connected vehicle whose heartbeats stopped for HEARTBEAT_TIMEOUT_MS
+fn heartbeat_expired(state: &MavlinkState) -> bool {
+    state.connection_status.read().map_or(false, |status| {
+        status.connected
+            && status.last_heartbeat
+                .map_or(false, |last| get_timestamp().saturating_sub(last) > HEARTBEAT_TIMEOUT_MS)
+    })
+}
+
+// RALLY_POINT carries altitudes as whole meters
+fn rally_altitude(altitude_m: f64, id: u8) -> Result<i16, String> {
+    let meters = altitude_m.round();
+    if !(meters >= f64::from(i16::MIN) && meters <= f64::from(i16::MAX)) {
+        return Err(format!("Rally point {id} altitude {altitude_m} m is out of range"));
+    }
+    Ok(meters as i16)
+}
+
+// Positions are sent as degrees * 1e7; other commands carry params 5-6 unscaled
+fn mission_item_int(seq: usize, planned: &PlannedCommand, target: (u8, u8)) -> Result<MavMessage, String> {
+    let seq = u16::try_from(seq).map_err(|_| "Mission has too many items to upload".to_string())?;
+    Ok(MavMessage::MISSION_ITEM_INT(MISSION_ITEM_INT_DATA {
+        param1: planned.params[0],
+        param2: planned.params[1],
+        param3: planned.params[2],
+        param4: planned.params[3],
+        x: if planned.positioned { (planned.lat * 1e7).round() as i32 } else { planned.lat as i32 },
+        y: if planned.positioned { (planned.lng * 1e7).round() as i32 } else { planned.lng as i32 },
+        z: planned.alt as f32,
+        seq,
+        command: planned.command,
+        target_system: target.0,
+        target_component: target.1,
+        frame: MavFrame::MAV_FRAME_GLOBAL_RELATIVE_ALT_INT,
+        current: u8::from(seq == 0),
+        autocontinue: 1,
+    }))
+}
+
 fn validate_connection_string(conn_str: &str) -> bool {
     // Validate connection string formats:
     // - Serial: /dev/ttyUSB0:57600
     // - UDP: udp://127.0.0.1:14550
+    // - UDP client: udpout://192.168.1.10:14550
     // - TCP: tcp://127.0.0.1:5760
+    // - Simulated vehicle: sim://vehicle
     
-    if conn_str.starts_with("udp://") || conn_str.starts_with("tcp://") {
+    if conn_str.starts_with(SIMULATED_CONNECTION_PREFIX) {
+        return true;
+    }
+
+    if is_tcp_connection_string(conn_str) || is_udp_connection_string(conn_str) {
         return conn_str.contains(':') && conn_str.len() > 10;
     }
     
-    if conn_str.starts_with("/dev/") || conn_str.starts_with("COM") {
+    if is_serial_connection_string(conn_str) {
         return conn_str.contains(':');
     }
     
@@ -504,6 +5816,13 @@ fn get_timestamp() -> u64 {
         .unwrap_or(0)
 }
 
+fn get_timestamp_us() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_micros() as u64)
+        .unwrap_or(0)
+}
+
 fn load_default_parameters(state: &State<'_, MavlinkState>) -> Result<(), String> {
     let mut params = state.parameters.write()
         .map_err(|_| "Failed to update parameters")?;
@@ -517,6 +5836,10 @@ fn load_default_parameters(state: &State<'_, MavlinkState>) -> Result<(), String
         min_value: Some(0.0),
         max_value: Some(65535.0),
         units: None,
+        increment: None,
+        default_value: Some(1.0),
+        values: Vec::new(),
+        bitmask: Vec::new(),
     });
 
     params.insert("THR_MIN".to_string(), Parameter {
@@ -527,6 +5850,10 @@ fn load_default_parameters(state: &State<'_, MavlinkState>) -> Result<(), String
         min_value: Some(0.0),
         max_value: Some(1000.0),
         units: Some("PWM".to_string()),
+        increment: None,
+        default_value: Some(130.0),
+        values: Vec::new(),
+        bitmask: Vec::new(),
     });
 
     params.insert("ANGLE_MAX".to_string(), Parameter {
@@ -537,6 +5864,10 @@ fn load_default_parameters(state: &State<'_, MavlinkState>) -> Result<(), String
         min_value: Some(1000.0),
         max_value: Some(8000.0),
         units: Some("centidegrees".to_string()),
+        increment: None,
+        default_value: Some(3000.0),
+        values: Vec::new(),
+        bitmask: Vec::new(),
     });
 
     params.insert("BATT_CAPACITY".to_string(), Parameter {
@@ -547,6 +5878,10 @@ fn load_default_parameters(state: &State<'_, MavlinkState>) -> Result<(), String
         min_value: Some(0.0),
         max_value: Some(50000.0),
         units: Some("mAh".to_string()),
+        increment: None,
+        default_value: Some(3300.0),
+        values: Vec::new(),
+        bitmask: Vec::new(),
     });
 
     Ok(())
@@ -556,4 +5891,696 @@ fn load_default_parameters(state: &State<'_, MavlinkState>) -> Result<(), String
 
 pub fn init() -> MavlinkState {
     MavlinkState::new()
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mag_cal_report(compass_id: u8, ofs: [f32; 3], diag: [f32; 3], fitness: f32, cal_status: MagCalStatus) -> MAG_CAL_REPORT_DATA {
+        MAG_CAL_REPORT_DATA {
+            fitness,
+            ofs_x: ofs[0],
+            ofs_y: ofs[1],
+            ofs_z: ofs[2],
+            diag_x: diag[0],
+            diag_y: diag[1],
+            diag_z: diag[2],
+            compass_id,
+            cal_mask: 0b11,
+            cal_status,
+            autosaved: 1,
+            ..MAG_CAL_REPORT_DATA::DEFAULT
+        }
+    }
+
+    #[test]
+    fn mag_cal_resolves_once_every_compass_reports() {
+        let mut session = MagCalSession::default();
+        for (compass_id, completion_pct) in [(0, 25), (1, 30), (0, 80), (1, 90)] {
+            session.record_progress(&MAG_CAL_PROGRESS_DATA {
+                compass_id,
+                cal_mask: 0b11,
+                cal_status: MagCalStatus::MAG_CAL_RUNNING_STEP_TWO,
+                completion_pct,
+                ..MAG_CAL_PROGRESS_DATA::DEFAULT
+            });
+        }
+
+        let second = mag_cal_report(1, [5.0, 6.0, 7.0], [1.1, 1.1, 1.1], 12.0, MagCalStatus::MAG_CAL_SUCCESS);
+        assert!(session.record_report(&second).is_none());
+        let primary = mag_cal_report(0, [42.0, -17.5, 63.2], [1.012, 0.994, 1.003], 8.4, MagCalStatus::MAG_CAL_SUCCESS);
+        let result = session.record_report(&primary).expect("both compasses reported");
+
+        assert!(result.success);
+        assert_eq!(result.sensor_type, "Magnetometer");
+        assert_eq!(result.offsets, vec![42.0, -17.5, 63.2]);
+        assert_eq!(result.scales, vec![1.012, 0.994, 1.003]);
+        assert_eq!(result.fitness, 12.0);
+    }
+
+    #[test]
+    fn mag_cal_fails_when_any_compass_fails() {
+        let mut session = MagCalSession::default();
+        let failed = mag_cal_report(1, [0.0; 3], [1.0; 3], 90.0, MagCalStatus::MAG_CAL_FAILED);
+        assert!(session.record_report(&failed).is_none());
+        let primary = mag_cal_report(0, [1.0, 2.0, 3.0], [1.0; 3], 5.0, MagCalStatus::MAG_CAL_SUCCESS);
+        let result = session.record_report(&primary).unwrap();
+        assert!(!result.success);
+        assert!(result.message.contains("compass 1"), "{}", result.message);
+    }
+
+    // A connected, disarmed vehicle with no motor test running
+    fn ready_vehicle_state() -> MavlinkState {
+        let state = MavlinkState::new();
+        state.connection_status.write().unwrap().connected = true;
+        *state.vehicle_info.write().unwrap() = Some(mock_vehicle_info());
+        state
+    }
+
+    #[test]
+    fn esc_calibration_requires_propeller_confirmation() {
+        let state = ready_vehicle_state();
+        assert!(verify_esc_calibration_allowed(&state, true).is_ok());
+
+        let error = verify_esc_calibration_allowed(&state, false).unwrap_err();
+        assert!(error.contains("propellers"), "{error}");
+    }
+
+    #[test]
+    fn accelerometer_session_accumulates_six_orientations() {
+        let state = MavlinkState::new();
+        let mut session = accelerometer_session(None);
+        let mut result = None;
+        for position in 1..=6u8 {
+            session.current_step = position;
+            // Steps run within the same millisecond here, unlike on a vehicle
+            state.imu_samples.lock().unwrap().clear();
+            let since_ms = get_timestamp();
+            for _ in 0..MIN_ACCEL_SAMPLES {
+                record_scaled_imu(&state, &mock_scaled_imu(position));
+            }
+            let sample = mean_accel_since(&state.imu_samples.lock().unwrap(), since_ms).unwrap();
+            result = session.record_sample(sample);
+            assert_eq!(result.is_some(), position == 6);
+        }
+
+        let result = result.unwrap();
+        assert_eq!(session.samples.len(), 6);
+        assert!(result.success);
+        // mock_scaled_imu's bias of (1.2, -0.8, 0.3) mG and scale errors
+        let ms2_per_mg = STANDARD_GRAVITY / 1000.0;
+        for (offset, bias_mg) in result.offsets.iter().zip([1.2, -0.8, 0.3]) {
+            assert!((offset - bias_mg * ms2_per_mg).abs() < ms2_per_mg, "offset {offset}");
+        }
+        for (scale, expected) in result.scales.iter().zip([1.001, 0.998, 1.002]) {
+            assert!((scale - expected).abs() < 0.001, "scale {scale}");
+        }
+    }
+
+    #[test]
+    fn accelerometer_step_needs_enough_readings() {
+        let state = MavlinkState::new();
+        let since_ms = get_timestamp();
+        record_scaled_imu(&state, &mock_scaled_imu(1));
+        assert!(mean_accel_since(&state.imu_samples.lock().unwrap(), since_ms).is_none());
+    }
+
+    #[test]
+    fn tlog_records_received_bytes_verbatim() {
+        let path = std::env::temp_dir().join(format!("olympus-tlog-{}.tlog", std::process::id()));
+        let state = MavlinkState::new();
+        *state.tlog_writer.lock().unwrap() = Some(BufWriter::new(File::create(&path).unwrap()));
+
+        // A MAVLink 1 frame proves the bytes are stored as received, not re-encoded as v2
+        let header = MavHeader { system_id: 1, component_id: 1, sequence: 7 };
+        let mut v1_frame = Vec::new();
+        ::mavlink::write_v1_msg(&mut v1_frame, header, &MavMessage::HEARTBEAT(HEARTBEAT_DATA::DEFAULT)).unwrap();
+        let mut v2_frame = Vec::new();
+        ::mavlink::write_v2_msg(&mut v2_frame, header, &MavMessage::SCALED_IMU(mock_scaled_imu(1))).unwrap();
+        let before_us = get_timestamp_us();
+        record_tlog_frame(&state, &v1_frame);
+        record_tlog_frame(&state, &v2_frame);
+
+        let written = finish_tlog_recording(&state).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(written, bytes.len() as u64);
+
+        let mut cursor = &bytes[..];
+        let mut last_us = before_us;
+        for expected in [&v1_frame, &v2_frame] {
+            let mut timestamp = [0u8; 8];
+            timestamp.copy_from_slice(&cursor[..8]);
+            let timestamp_us = u64::from_be_bytes(timestamp);
+            assert!(timestamp_us >= last_us);
+            last_us = timestamp_us;
+            assert_eq!(&cursor[8..8 + expected.len()], expected.as_slice());
+            cursor = &cursor[8 + expected.len()..];
+        }
+        assert!(cursor.is_empty());
+    }
+
+    #[test]
+    fn inspector_statistics_track_fifty_heartbeats() {
+        let state = MavlinkState::new();
+        let heartbeat = MavMessage::HEARTBEAT(HEARTBEAT_DATA {
+            custom_mode: 4,
+            mavtype: MavType::MAV_TYPE_QUADROTOR,
+            ..HEARTBEAT_DATA::DEFAULT
+        });
+        for i in 0..50u64 {
+            log_inspector_message(&state, &heartbeat, 10_000 + i * 1000);
+        }
+
+        let stats = state.message_stats.lock().unwrap();
+        let heartbeat_stats = &stats[&0];
+        assert_eq!(heartbeat_stats.count, 50);
+        assert_eq!(heartbeat_stats.last_timestamp_ms, 59_000);
+        assert!((heartbeat_stats.average_interval_ms - 1000.0).abs() < 1e-9);
+
+        let log = state.message_log.lock().unwrap();
+        assert_eq!(log.len(), 50);
+        let entry = log.back().unwrap();
+        assert_eq!(entry.msg_name, "HEARTBEAT");
+        let payload: serde_json::Value = serde_json::from_str(&entry.payload_json).unwrap();
+        assert_eq!(payload["custom_mode"], 4);
+        assert!(payload.get("type").is_none());
+    }
+
+    fn planned_waypoint(lat: f64, lng: f64) -> PlannedCommand {
+        PlannedCommand {
+            command: MavCmd::MAV_CMD_NAV_WAYPOINT,
+            params: [0.0; 4],
+            lat,
+            lng,
+            alt: 50.0,
+            positioned: true,
+        }
+    }
+
+    #[test]
+    fn mission_items_round_positions_and_address_the_vehicle() {
+        // 40.7127753 * 1e7 and 139.6917064 * 1e7 land just below the integer
+        match mission_item_int(3, &planned_waypoint(40.712_775_3, 139.691_706_4), (7, 42)).unwrap() {
+            MavMessage::MISSION_ITEM_INT(item) => {
+                assert_eq!(item.x, 407_127_753);
+                assert_eq!(item.y, 1_396_917_064);
+                assert_eq!(item.seq, 3);
+                assert_eq!((item.target_system, item.target_component), (7, 42));
+            }
+            other => panic!("unexpected message {other:?}"),
+        }
+    }
+
+    #[test]
+    fn mission_upload_serves_every_request_before_the_ack() {
+        let state = ready_vehicle_state();
+        let items: Vec<MavMessage> = (0..3)
+            .map(|seq| mission_item_int(seq, &planned_waypoint(37.0, -122.0), (1, 1)).unwrap())
+            .collect();
+        let (event_tx, mut events) = mpsc::unbounded_channel();
+        *state.mission_upload.lock().unwrap() = Some(MissionUploadSession::new((1, 1), items, event_tx));
+        let vehicle = mock_vehicle_header(&state);
+        let accepted = MISSION_ACK_DATA {
+            target_system: GCS_SYSTEM_ID,
+            target_component: GCS_COMPONENT_ID,
+            mavtype: MavMissionResult::MAV_MISSION_ACCEPTED,
+        };
+
+        // Requests from another system are not ours to answer
+        handle_mission_request(&state, &MavHeader { system_id: 9, ..vehicle }, 0);
+        assert!(events.try_recv().is_err());
+
+        handle_mission_request(&state, &vehicle, 0);
+        handle_mission_request(&state, &vehicle, 1);
+        handle_mission_ack(&state, &vehicle, &accepted);
+        assert_eq!(events.try_recv().unwrap(), MissionUploadEvent::Requested);
+        assert_eq!(events.try_recv().unwrap(), MissionUploadEvent::Requested);
+        let early = events.try_recv().unwrap();
+        assert_eq!(early, MissionUploadEvent::Acknowledged { result: MavMissionResult::MAV_MISSION_ACCEPTED, complete: false });
+
+        handle_mission_request(&state, &vehicle, 2);
+        handle_mission_ack(&state, &vehicle, &accepted);
+        assert_eq!(events.try_recv().unwrap(), MissionUploadEvent::Requested);
+        match events.try_recv().unwrap() {
+            MissionUploadEvent::Acknowledged { result, complete } => {
+                assert!(complete);
+                assert!(mission_upload_outcome(result, complete).is_ok());
+            }
+            other => panic!("unexpected event {other:?}"),
+        }
+        assert_eq!(state.messages_sent.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn mission_upload_fails_unless_fully_accepted() {
+        assert!(mission_upload_outcome(MavMissionResult::MAV_MISSION_ACCEPTED, false).is_err());
+        assert!(mission_upload_outcome(MavMissionResult::MAV_MISSION_NO_SPACE, true).is_err());
+    }
+
+    // Ten heartbeats with the given gaps (ms) after the first, each carrying its sequence number
+    fn tracker_with_heartbeats(gaps_ms: &[u64], sequences: &[u8]) -> LinkQualityTracker {
+        let mut tracker = LinkQualityTracker::default();
+        let mut at = Instant::now();
+        for (i, &sequence) in sequences.iter().enumerate() {
+            if i > 0 {
+                at += Duration::from_millis(gaps_ms[i - 1]);
+            }
+            tracker.record_sequence(sequence);
+            tracker.record_heartbeat(at);
+        }
+        tracker
+    }
+
+    #[test]
+    fn link_quality_degrades_with_jitter_and_loss() {
+        let in_order: Vec<u8> = (0..10).collect();
+        let steady = tracker_with_heartbeats(&[1000; 9], &in_order);
+        assert!(steady.jitter_ms() < 1.0);
+        assert!((steady.quality() - 1.0).abs() < 0.01);
+
+        // Alternating 700/1300 ms arrivals: 300 ms of jitter costs 0.6 * 0.6
+        let jittery = tracker_with_heartbeats(&[700, 1300, 700, 1300, 700, 1300, 700, 1300, 700], &in_order);
+        assert!((jittery.jitter_ms() - 300.0).abs() < 20.0);
+        assert!((jittery.quality() - 0.64).abs() < 0.03);
+
+        // Two gaps of 2500 ms among 1000 ms heartbeats push jitter past the 500 ms cap
+        let gapped = tracker_with_heartbeats(&[1000, 2500, 1000, 1000, 2500, 1000, 1000, 1000, 1000], &in_order);
+        assert!(gapped.jitter_ms() > 500.0);
+        assert!((gapped.quality() - 0.4).abs() < 0.01);
+
+        // Every other sequence number missing is 50% loss on a steady link
+        let even: Vec<u8> = (0..10).map(|i| i * 2).collect();
+        let lossy = tracker_with_heartbeats(&[1000; 9], &even);
+        assert!((lossy.packet_loss_ratio() - 0.5).abs() < 1e-6);
+        assert!((lossy.quality() - 0.8).abs() < 0.01);
+        assert!(steady.quality() > jittery.quality() && jittery.quality() > gapped.quality());
+    }
+
+    #[tokio::test]
+    async fn denied_command_ack_fails_the_command() {
+        let state = ready_vehicle_state();
+        let command = MavCmd::MAV_CMD_PREFLIGHT_CALIBRATION;
+        let timeout = Duration::from_secs(1);
+        let (sent_at, result_rx) = register_pending_command(&state, command, timeout).unwrap();
+        assert!(register_pending_command(&state, command, timeout).is_err());
+
+        let ack = COMMAND_ACK_DATA { command, result: MavResult::MAV_RESULT_DENIED };
+        complete_pending_command(&state, &mock_vehicle_header(&state), &ack);
+        let result = wait_for_command_ack(&state, command, sent_at, result_rx, timeout).await.unwrap();
+        assert_eq!(
+            result.into_result(command),
+            Err("MAV_CMD_PREFLIGHT_CALIBRATION denied by the vehicle".to_string())
+        );
+        assert!(state.pending_commands.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn parameter_sync_collects_a_four_parameter_vehicle() {
+        let state = ready_vehicle_state();
+        let done_rx = begin_parameter_sync(&state, "parameter-sync-progress").unwrap();
+        assert!(!state.parameters_synced.load(Ordering::SeqCst));
+
+        // Out of order, with index 1 sent twice
+        let vehicle = [("SYSID_THISMAV", 1.0), ("ARMING_CHECK", 1.0), ("WPNAV_SPEED", 500.0), ("RTL_ALT", 1500.0)];
+        for index in [2u16, 1, 1, 0, 3] {
+            let (id, value) = vehicle[usize::from(index)];
+            let (_, progress) = record_param_value(&state, &PARAM_VALUE_DATA {
+                param_value: value,
+                param_count: 4,
+                param_index: index,
+                param_id: param_id_bytes(id),
+                param_type: MavParamType::MAV_PARAM_TYPE_REAL32,
+            }).unwrap();
+            assert_eq!(progress.total, 4);
+        }
+
+        let count = await_parameter_sync(&state, done_rx, Duration::from_secs(1)).await.unwrap();
+        assert_eq!(count, 4);
+        assert!(state.parameters_synced.load(Ordering::SeqCst));
+        assert!(state.parameter_sync.lock().unwrap().is_none());
+        let params = state.parameters.read().unwrap();
+        for (id, value) in vehicle {
+            assert_eq!(params[id].value, value, "{id}");
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn gcs_heartbeat_sends_once_per_second() {
+        let state = Arc::new(ready_vehicle_state());
+        let stop = Arc::new(AtomicBool::new(false));
+        let task = {
+            let (state, stop) = (state.clone(), stop.clone());
+            tokio::spawn(async move { send_gcs_heartbeats(&state, &stop).await })
+        };
+
+        tokio::time::sleep(Duration::from_millis(10_100)).await;
+        stop.store(true, Ordering::SeqCst);
+        assert_eq!(state.messages_sent.load(Ordering::Relaxed), 10);
+
+        // The task exits at its next tick without sending again
+        assert_eq!(task.await.unwrap(), Ok(()));
+        assert_eq!(state.messages_sent.load(Ordering::Relaxed), 10);
+    }
+
+    #[test]
+    fn preflight_report_names_unhealthy_enabled_sensors() {
+        // Gyro, accel, mag, baro and GPS enabled; optical flow present but disabled.
+        // Only gyro, accel and baro report healthy.
+        let report = preflight_report(PreflightStatus {
+            sensors_present: 0x6F,
+            sensors_enabled: 0x2F,
+            sensors_health: 0x0B,
+            timestamp_ms: 0,
+        });
+        assert_eq!(report.failing_checks, ["Magnetometer", "GPS"]);
+
+        let names: Vec<&str> = report.checks.iter().map(|check| check.sensor_name.as_str()).collect();
+        assert_eq!(names, ["Gyroscope", "Accelerometer", "Magnetometer", "Barometer", "GPS", "Optical flow"]);
+        let flow = &report.checks[5];
+        assert!(flow.present && !flow.enabled && !flow.healthy);
+    }
+
+    // Link the state to a loopback socket standing in for the vehicle
+    fn loopback_vehicle(state: &MavlinkState) -> std::net::UdpSocket {
+        let vehicle = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        vehicle.set_read_timeout(Some(Duration::from_secs(2))).unwrap();
+        let endpoint = parse_udp_endpoint(&format!("udpout://{}", vehicle.local_addr().unwrap())).unwrap();
+        let (events_tx, _events_rx) = mpsc::unbounded_channel();
+        *state.vehicle_link.lock().unwrap() = Some(VehicleLink::open_udp(&endpoint, events_tx).unwrap());
+        vehicle
+    }
+
+    fn receive_message(vehicle: &std::net::UdpSocket) -> MavMessage {
+        let mut buffer = [0u8; 512];
+        let len = vehicle.recv(&mut buffer).unwrap();
+        let mut frame = std::io::Cursor::new(&buffer[..len]);
+        ::mavlink::read_v2_msg::<MavMessage, _>(&mut frame).unwrap().1
+    }
+
+    #[test]
+    fn rally_points_upload_in_order_over_the_link() {
+        let state = ready_vehicle_state();
+        let vehicle = loopback_vehicle(&state);
+
+        let home = Coordinate { lat: 37.7749, lng: -122.4194, alt: None };
+        let points: Vec<crate::map_features::RallyPoint> = [(2u8, 0.01), (5, 0.02), (9, 0.03)].iter()
+            .map(|&(id, offset)| crate::map_features::RallyPoint {
+                id,
+                coordinate: Coordinate { lat: home.lat + offset, lng: home.lng, alt: None },
+                break_altitude_m: 60.0,
+                land_altitude_m: 20.0,
+                flags: 0,
+            })
+            .collect();
+        assert_eq!(send_rally_points(&state, &points, &home).unwrap(), 3);
+
+        for (idx, point) in points.iter().enumerate() {
+            match receive_message(&vehicle) {
+                MavMessage::RALLY_POINT(rally) => {
+                    assert_eq!((usize::from(rally.idx), rally.count), (idx, 3));
+                    assert_eq!(rally.lat, (point.coordinate.lat * 1e7).round() as i32);
+                    assert_eq!((rally.alt, rally.break_alt), (20, 60));
+                    assert_eq!((rally.target_system, rally.target_component), (1, 1));
+                }
+                other => panic!("expected RALLY_POINT, got {other:?}"),
+            }
+        }
+        close_vehicle_link(&state).unwrap();
+    }
+
+    #[test]
+    fn arming_is_refused_in_ekf_const_pos_mode() {
+        let state = ready_vehicle_state();
+        let all_healthy = PreflightStatus { sensors_present: 0x2F, sensors_enabled: 0x2F, sensors_health: 0x2F, timestamp_ms: 0 };
+        *state.preflight_status.write().unwrap() = Some(all_healthy);
+        let ekf = |flags: EkfStatusFlags| EkfStatus {
+            velocity_variance: 0.1,
+            pos_horiz_variance: 0.1,
+            pos_vert_variance: 0.1,
+            compass_variance: 0.1,
+            terrain_alt_variance: 0.0,
+            flags: flags.bits(),
+            timestamp_ms: 0,
+        };
+
+        *state.ekf_status.write().unwrap() = Some(ekf(EkfStatusFlags::EKF_PRED_POS_HORIZ_ABS));
+        assert_eq!(verify_ready_to_arm(&state), Ok(()));
+
+        *state.ekf_status.write().unwrap() =
+            Some(ekf(EkfStatusFlags::EKF_PRED_POS_HORIZ_ABS | EkfStatusFlags::EKF_CONST_POS_MODE));
+        let error = verify_ready_to_arm(&state).unwrap_err();
+        assert!(error.contains("EKF"), "{error}");
+    }
+
+    #[test]
+    fn vibration_warns_on_crossing_the_threshold_only() {
+        let state = ready_vehicle_state();
+        let report = |accel: f32, clipping: u32| VIBRATION_DATA {
+            vibration_x: accel,
+            vibration_y: 5.0,
+            vibration_z: 10.0,
+            clipping_0: clipping,
+            ..VIBRATION_DATA::DEFAULT
+        };
+
+        // Rising X vibration warns once at 32 m/s², then stays quiet while above
+        let warned: Vec<bool> = [10.0, 20.0, 29.0, 32.0, 40.0, 50.0].iter()
+            .map(|&accel| record_vibration(&state, &report(accel, 0)).is_some())
+            .collect();
+        assert_eq!(warned, [false, false, false, true, false, false]);
+
+        // Dropping below and crossing again warns again
+        assert!(record_vibration(&state, &report(25.0, 0)).is_none());
+        let warning = record_vibration(&state, &report(31.0, 0)).unwrap();
+        assert_eq!(warning.reasons, ["X vibration 31.0 m/s² exceeds 30 m/s²"]);
+
+        // A clipping increment warns even below the threshold
+        let warning = record_vibration(&state, &report(12.0, 3)).unwrap();
+        assert_eq!(warning.reasons, ["IMU 0 clipped 3 times"]);
+        assert_eq!(state.vibration_history.lock().unwrap().len(), 9);
+    }
+
+    #[test]
+    fn console_input_goes_out_as_shell_serial_control() {
+        let state = ready_vehicle_state();
+        let vehicle = loopback_vehicle(&state);
+        assert!(write_console(&state, "ver all\n").is_err());
+        state.console_active.store(true, Ordering::SeqCst);
+
+        // 80 bytes of input need two SERIAL_CONTROL messages
+        let input = format!("ver all\n{}", "x".repeat(72));
+        write_console(&state, &input).unwrap();
+        let mut sent = Vec::new();
+        for expected_count in [70u8, 10] {
+            match receive_message(&vehicle) {
+                MavMessage::SERIAL_CONTROL(control) => {
+                    assert_eq!(control.device, SerialControlDev::SERIAL_CONTROL_DEV_SHELL);
+                    assert_eq!(
+                        control.flags,
+                        SerialControlFlag::SERIAL_CONTROL_FLAG_EXCLUSIVE | SerialControlFlag::SERIAL_CONTROL_FLAG_RESPOND
+                    );
+                    assert_eq!(control.count, expected_count);
+                    sent.extend_from_slice(&control.data[..usize::from(control.count)]);
+                }
+                other => panic!("expected SERIAL_CONTROL, got {other:?}"),
+            }
+        }
+        assert_eq!(sent, input.as_bytes());
+        close_vehicle_link(&state).unwrap();
+    }
+
+    #[test]
+    fn reboot_needs_a_disarmed_vehicle() {
+        let state = ready_vehicle_state();
+        state.vehicle_info.write().unwrap().as_mut().unwrap().armed = true;
+        assert_eq!(
+            reboot_command(&state, true, false, true).unwrap_err(),
+            "Cannot reboot while the vehicle is armed"
+        );
+
+        state.vehicle_info.write().unwrap().as_mut().unwrap().armed = false;
+        assert!(reboot_command(&state, true, false, false).is_err());
+        assert!(reboot_command(&state, false, false, true).is_err());
+        let autopilot = reboot_command(&state, true, false, true).unwrap();
+        assert_eq!(autopilot.command, MavCmd::MAV_CMD_PREFLIGHT_REBOOT_SHUTDOWN);
+        assert_eq!((autopilot.param1, autopilot.param2), (1.0, 0.0));
+        assert_eq!((autopilot.target_system, autopilot.target_component), (1, 1));
+        let both = reboot_command(&state, true, true, true).unwrap();
+        assert_eq!((both.param1, both.param2), (1.0, 1.0));
+    }
+
+    #[test]
+    fn emergency_stop_forces_a_disarm_and_resets_explicitly() {
+        let state = ready_vehicle_state();
+        state.vehicle_info.write().unwrap().as_mut().unwrap().armed = true;
+        *state.motor_test_active.write().unwrap() = true;
+        let vehicle = loopback_vehicle(&state);
+
+        trigger_emergency_stop(&state).unwrap();
+        assert!(*state.emergency_stop.active.read().unwrap());
+        assert!(!*state.motor_test_active.read().unwrap());
+        match receive_message(&vehicle) {
+            MavMessage::COMMAND_LONG(command) => {
+                assert_eq!(command.command, MavCmd::MAV_CMD_COMPONENT_ARM_DISARM);
+                assert_eq!(command.param1, 0.0);
+                assert_eq!(command.param2, FORCE_ARM_DISARM_MAGIC);
+                assert_eq!((command.target_system, command.target_component), (1, 1));
+            }
+            other => panic!("expected COMMAND_LONG, got {other:?}"),
+        }
+
+        // Disarming is still allowed while latched; arming is not
+        assert!(verify_ready_to_disarm(&state).is_ok());
+        assert_eq!(verify_ready_to_arm(&state).unwrap_err(), "Emergency stop is latched");
+
+        clear_emergency_stop(&state).unwrap();
+        assert!(!*state.emergency_stop.active.read().unwrap());
+        assert_eq!(verify_ready_to_arm(&state).unwrap_err(), "Vehicle is already armed");
+
+        // With no vehicle there is nothing to send, but the stop still latches
+        let disconnected = MavlinkState::new();
+        trigger_emergency_stop(&disconnected).unwrap();
+        assert!(*disconnected.emergency_stop.active.read().unwrap());
+    }
+
+    #[tokio::test]
+    async fn emergency_stop_mid_sequence_aborts_the_remaining_motors() {
+        let state = ready_vehicle_state();
+        let vehicle = loopback_vehicle(&state);
+        claim_motor_test(&state).unwrap();
+
+        let mut statuses = Vec::new();
+        let results = run_motor_sequence(
+            &state,
+            4,
+            |motor_id| {
+                let state = &state;
+                async move {
+                    if motor_id == 2 {
+                        trigger_emergency_stop(state)?;
+                        verify_emergency_stop_clear(state)?;
+                    }
+                    Ok(None)
+                }
+            },
+            |motor_id, status| statuses.push((motor_id, status.to_string())),
+        )
+        .await;
+
+        assert_eq!(results.len(), 4);
+        assert!(results[0].success);
+        for result in &results[1..] {
+            assert!(!result.success, "motor {} reported success", result.motor_id);
+            assert_eq!(result.status, "aborted");
+        }
+        assert_eq!(statuses.last().unwrap(), &(4, "aborted".to_string()));
+
+        // The E-stop's forced disarm, then a zero-throttle test of motor 2
+        let mut commands = Vec::new();
+        for _ in 0..2 {
+            match receive_message(&vehicle) {
+                MavMessage::COMMAND_LONG(command) => commands.push(command),
+                other => panic!("expected COMMAND_LONG, got {other:?}"),
+            }
+        }
+        assert_eq!(commands[0].command, MavCmd::MAV_CMD_COMPONENT_ARM_DISARM);
+        assert_eq!(commands[1].command, MavCmd::MAV_CMD_DO_MOTOR_TEST);
+        assert_eq!((commands[1].param1, commands[1].param3, commands[1].param4), (2.0, 0.0, 0.0));
+    }
+
+    #[tokio::test]
+    async fn failed_motor_stops_its_test_and_aborts_the_rest() {
+        let state = ready_vehicle_state();
+        let vehicle = loopback_vehicle(&state);
+
+        let results = run_motor_sequence(
+            &state,
+            3,
+            |motor_id| async move {
+                if motor_id == 1 {
+                    Err("MAV_CMD_DO_MOTOR_TEST denied by the vehicle".to_string())
+                } else {
+                    Ok(None)
+                }
+            },
+            |_, _| {},
+        )
+        .await;
+
+        assert_eq!(results[0].status, "failed");
+        assert!(results.iter().all(|result| !result.success));
+        assert_eq!(results[2].error.as_deref(), Some("Motor 1: MAV_CMD_DO_MOTOR_TEST denied by the vehicle"));
+        match receive_message(&vehicle) {
+            MavMessage::COMMAND_LONG(command) => {
+                assert_eq!(command.command, MavCmd::MAV_CMD_DO_MOTOR_TEST);
+                assert_eq!((command.param1, command.param3), (1.0, 0.0));
+            }
+            other => panic!("expected COMMAND_LONG, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn decodes_esc_telemetry_1_to_4_payload() {
+        // voltage cV, current cA, totalcurrent mAh, rpm and count as u16[4],
+        // then temperature °C as u8[4]
+        let payload: [u8; 44] = [
+            0x54, 0x06, 0x4f, 0x06, 0x4a, 0x06, 0x45, 0x06, // 1620, 1615, 1610, 1605
+            0xe2, 0x04, 0x14, 0x05, 0xfb, 0x04, 0xd8, 0x04, // 1250, 1300, 1275, 1240
+            0x78, 0x00, 0x7a, 0x00, 0x79, 0x00, 0x77, 0x00, // 120, 122, 121, 119
+            0x50, 0x14, 0x82, 0x14, 0x3c, 0x14, 0xb4, 0x14, // 5200, 5250, 5180, 5300
+            0x0a, 0x00, 0x0b, 0x00, 0x0c, 0x00, 0x0d, 0x00, // 10, 11, 12, 13
+            0x2d, 0x2f, 0x2e, 0x55, // 45, 47, 46, 85
+        ];
+        let message = <MavMessage as ::mavlink::Message>::parse(::mavlink::MavlinkVersion::V2, 11030, &payload).unwrap();
+        let esc = match message {
+            MavMessage::ESC_TELEMETRY_1_TO_4(esc) => esc,
+            other => panic!("expected ESC_TELEMETRY_1_TO_4, got {other:?}"),
+        };
+        let reports = esc_reports(1, &esc.voltage, &esc.current, &esc.totalcurrent, &esc.rpm, &esc.count, &esc.temperature);
+
+        let decoded: Vec<(u8, u32, u16, u16, u16, u32)> = reports.iter()
+            .map(|esc| (esc.motor_id, esc.rpm, esc.voltage_mv, esc.current_ca, esc.temperature_cdeg, esc.totalcurrent_mah))
+            .collect();
+        assert_eq!(decoded, [
+            (1, 5200, 16_200, 1250, 4500, 120),
+            (2, 5250, 16_150, 1300, 4700, 122),
+            (3, 5180, 16_100, 1275, 4600, 121),
+            (4, 5300, 16_050, 1240, 8500, 119),
+        ]);
+
+        // Motor 4 at 85 °C warns once; a repeat report does not warn again
+        let state = MavlinkState::new();
+        let (escs, warnings) = record_esc_telemetry(&state, reports.clone()).unwrap();
+        assert_eq!(escs.len(), 4);
+        let warned: Vec<u8> = warnings.iter().map(|warning| warning.motor_id).collect();
+        assert_eq!(warned, [4]);
+        assert!(record_esc_telemetry(&state, reports).unwrap().1.is_empty());
+        assert_eq!(esc_reading_since(&state, 2, 0).map(|esc| esc.rpm), Some(5250));
+    }
+
+    #[test]
+    fn goto_target_respects_the_geofence_ceiling() {
+        let geofence = GeofenceState::new();
+        assert!(goto_target(&geofence, 37.0, -122.0, 150.0, Some(5.0)).is_ok());
+
+        crate::geofence::store_geofence_ceiling(&geofence, Some(120.0)).unwrap();
+        assert!(goto_target(&geofence, 37.0, -122.0, 120.0, None).is_ok());
+        assert_eq!(
+            goto_target(&geofence, 37.0, -122.0, 150.0, None).unwrap_err(),
+            "Target altitude 150 m is above the geofence ceiling of 120 m"
+        );
+        assert!(goto_target(&geofence, 37.0, -122.0, 50.0, Some(0.0)).is_err());
+        assert!(goto_target(&geofence, 91.0, -122.0, 50.0, None).is_err());
+    }
+
+    #[test]
+    fn px4_repositions_in_hold_rather_than_offboard() {
+        assert_eq!(guided_mode("ArduPilot").unwrap(), "GUIDED");
+        assert_eq!(guided_mode("PX4").unwrap(), "AUTO_LOITER");
+        assert!(guided_mode("Generic").is_err());
+        // Hold must be a mode the PX4 table can switch to
+        assert!(PX4_FLIGHT_MODES.iter().any(|(_, name)| *name == "AUTO_LOITER"));
+    }
+}
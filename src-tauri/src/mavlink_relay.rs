@@ -0,0 +1,160 @@
+// MAVLink relay: forwards received frames verbatim to other ground stations over UDP
+// NASA JPL Power of 10 compliant implementation
+
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self as std_mpsc, SyncSender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+// ===== TYPE DEFINITIONS =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayStats {
+    pub forwarded_count: u64,
+    pub dropped_count: u64,
+    pub output_addr: String,
+}
+
+// One relay output. Frames are queued for a sender thread so a slow or full
+// socket never holds up the receive loop; dropping the target drains the
+// queue and joins the thread.
+pub struct RelayTarget {
+    output_addr: String,
+    queue: Option<SyncSender<Vec<u8>>>,
+    forwarded: Arc<AtomicU64>,
+    dropped: Arc<AtomicU64>,
+    sender: Option<JoinHandle<()>>,
+}
+
+// ===== CONSTANTS =====
+
+pub const MAX_RELAY_TARGETS: usize = 4;
+// Frames waiting for the sender thread; beyond this they are dropped
+const RELAY_QUEUE_CAPACITY: usize = 256;
+
+// ===== RELAY TARGET =====
+
+impl RelayTarget {
+    // Resolve "host:port" and start forwarding from an ephemeral local port
+    pub fn start(output_addr: &str) -> Result<Self, String> {
+        let destination = output_addr.to_socket_addrs()
+            .map_err(|e| format!("Invalid relay address {output_addr}: {e}"))?
+            .next()
+            .ok_or_else(|| format!("Relay address {output_addr} did not resolve"))?;
+        let local: SocketAddr = if destination.is_ipv4() {
+            (Ipv4Addr::UNSPECIFIED, 0).into()
+        } else {
+            (Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        let socket = UdpSocket::bind(local)
+            .map_err(|e| format!("Failed to open a relay socket for {output_addr}: {e}"))?;
+        // A full send buffer must drop the frame rather than stall the sender
+        socket.set_nonblocking(true)
+            .map_err(|e| format!("Failed to configure the relay socket for {output_addr}: {e}"))?;
+
+        let (queue, frames) = std_mpsc::sync_channel::<Vec<u8>>(RELAY_QUEUE_CAPACITY);
+        let forwarded = Arc::new(AtomicU64::new(0));
+        let dropped = Arc::new(AtomicU64::new(0));
+        let sender_forwarded = forwarded.clone();
+        let sender_dropped = dropped.clone();
+        let sender = std::thread::Builder::new()
+            .name("mavlink-relay".to_string())
+            .spawn(move || {
+                for frame in frames {
+                    match socket.send_to(&frame, destination) {
+                        Ok(_) => sender_forwarded.fetch_add(1, Ordering::Relaxed),
+                        Err(e) if e.kind() == io::ErrorKind::WouldBlock => sender_dropped.fetch_add(1, Ordering::Relaxed),
+                        Err(e) => {
+                            eprintln!("Failed to relay frame to {destination}: {e}");
+                            sender_dropped.fetch_add(1, Ordering::Relaxed)
+                        }
+                    };
+                }
+            })
+            .map_err(|e| format!("Failed to start the relay to {output_addr}: {e}"))?;
+
+        Ok(Self {
+            output_addr: output_addr.to_string(),
+            queue: Some(queue),
+            forwarded,
+            dropped,
+            sender: Some(sender),
+        })
+    }
+
+    pub fn output_addr(&self) -> &str {
+        &self.output_addr
+    }
+
+    // Queue a copy of the frame; never blocks, a full queue drops it
+    pub fn forward(&self, frame: &[u8]) {
+        let queued = self.queue.as_ref().map_or(false, |queue| queue.try_send(frame.to_vec()).is_ok());
+        if !queued {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn stats(&self) -> RelayStats {
+        RelayStats {
+            forwarded_count: self.forwarded.load(Ordering::Relaxed),
+            dropped_count: self.dropped.load(Ordering::Relaxed),
+            output_addr: self.output_addr.clone(),
+        }
+    }
+}
+
+impl Drop for RelayTarget {
+    // Closing the queue ends the sender loop once it has sent what is queued
+    fn drop(&mut self) {
+        self.queue.take();
+        if let Some(sender) = self.sender.take() {
+            if sender.join().is_err() {
+                eprintln!("Relay to {} panicked", self.output_addr);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::mavlink::ardupilotmega::{MavMessage, HEARTBEAT_DATA};
+    use ::mavlink::MavHeader;
+    use std::time::Duration;
+
+    #[test]
+    fn loopback_receiver_gets_identical_frames() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver.set_read_timeout(Some(Duration::from_secs(2))).unwrap();
+        let target = RelayTarget::start(&receiver.local_addr().unwrap().to_string()).unwrap();
+
+        let frames: Vec<Vec<u8>> = (0u8..20)
+            .map(|sequence| {
+                let header = MavHeader { system_id: 1, component_id: 1, sequence };
+                let heartbeat = HEARTBEAT_DATA { custom_mode: u32::from(sequence), ..HEARTBEAT_DATA::DEFAULT };
+                let mut frame = Vec::new();
+                ::mavlink::write_v2_msg(&mut frame, header, &MavMessage::HEARTBEAT(heartbeat)).unwrap();
+                frame
+            })
+            .collect();
+        for frame in &frames {
+            target.forward(frame);
+        }
+
+        let mut buffer = [0u8; 512];
+        for frame in &frames {
+            let len = receiver.recv(&mut buffer).unwrap();
+            assert_eq!(&buffer[..len], frame.as_slice());
+        }
+        // The counter moves just after the send, so it can trail the receiver briefly
+        let deadline = std::time::Instant::now() + Duration::from_secs(1);
+        while target.stats().forwarded_count < 20 && std::time::Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        let stats = target.stats();
+        assert_eq!((stats.forwarded_count, stats.dropped_count), (20, 0));
+    }
+}
@@ -0,0 +1,524 @@
+// MAVLink transports: serial ports, UDP and TCP sockets with a background reader
+// NASA JPL Power of 10 compliant implementation
+
+use ::mavlink::ardupilotmega::MavMessage;
+use ::mavlink::MavHeader;
+use serial::SerialPort;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc as std_mpsc, Arc};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+// ===== TYPE DEFINITIONS =====
+
+// Port and baud rate of a "PORT:BAUD" connection string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerialEndpoint {
+    pub port: String,
+    pub baud_rate: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UdpMode {
+    // udp://: bind the address and reply to whoever sends first
+    Listen,
+    // udpout://: send to the address from an ephemeral port
+    Client,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UdpEndpoint {
+    pub mode: UdpMode,
+    pub address: SocketAddr,
+}
+
+// Address of a "tcp://host:port" connection string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TcpEndpoint {
+    pub host_port: String,
+    pub addresses: Vec<SocketAddr>,
+}
+
+// A decoded message with the exact bytes it arrived in
+pub struct ReceivedFrame {
+    pub header: MavHeader,
+    pub message: MavMessage,
+    pub raw: Vec<u8>,
+}
+
+// Delivered by the reader thread, in arrival order
+pub enum LinkEvent {
+    Message(Box<ReceivedFrame>),
+    // The transport failed and the reader has exited
+    Closed(String),
+}
+
+// An open transport. Only the reader thread touches the port or socket:
+// outgoing frames are queued and written between reads. Dropping the link
+// stops and joins the reader, which closes the transport.
+pub struct VehicleLink {
+    // Human-readable endpoint for error messages
+    description: String,
+    // What to check when the vehicle stays silent
+    silence_hint: &'static str,
+    outgoing: std_mpsc::Sender<Vec<u8>>,
+    stop: Arc<AtomicBool>,
+    reader: Option<JoinHandle<()>>,
+}
+
+const SUPPORTED_BAUD_RATES: [usize; 9] = [9600, 19200, 38400, 57600, 111_100, 115_200, 230_400, 460_800, 921_600];
+// Bounds how long a queued frame and a stop request wait on an idle link
+const READ_TIMEOUT: Duration = Duration::from_millis(50);
+const MAX_DATAGRAM_BYTES: usize = 65_535;
+const STREAM_READ_CHUNK_BYTES: usize = 4096;
+
+// Frame layout: v1 has 6 header and 2 checksum bytes; v2 has 10 header and
+// 2 checksum bytes plus a 13-byte signature when signed
+const V1_FRAME_OVERHEAD: usize = 8;
+const V2_FRAME_OVERHEAD: usize = 12;
+const V2_SIGNATURE_LEN: usize = 13;
+const MAVLINK_IFLAG_SIGNED: u8 = 0x01;
+const V2_HEADER_LEN: usize = 10;
+// STATUSTEXT id (u16) and chunk_seq extensions follow the severity and 50 text bytes
+const STATUSTEXT_ID_OFFSET: usize = 51;
+const STATUSTEXT_CHUNK_SEQ_OFFSET: usize = 53;
+
+// Splits a byte stream into MAVLink 1 and 2 frames
+#[derive(Default)]
+pub struct FrameParser {
+    buffer: Vec<u8>,
+}
+
+// ===== CONNECTION STRINGS =====
+
+// Serial ports are given as a device path or a Windows COM name
+pub fn is_serial_connection_string(connection_string: &str) -> bool {
+    connection_string.starts_with("/dev/") || connection_string.starts_with("COM")
+}
+
+pub fn is_udp_connection_string(connection_string: &str) -> bool {
+    connection_string.starts_with("udp://") || connection_string.starts_with("udpout://")
+}
+
+pub fn is_tcp_connection_string(connection_string: &str) -> bool {
+    connection_string.starts_with("tcp://")
+}
+
+pub fn parse_serial_endpoint(connection_string: &str) -> Result<SerialEndpoint, String> {
+    let (port, baud) = connection_string.rsplit_once(':')
+        .ok_or_else(|| format!("Serial connection {connection_string} needs a baud rate, e.g. /dev/ttyUSB0:57600"))?;
+    if port.is_empty() {
+        return Err(format!("Serial connection {connection_string} is missing the port"));
+    }
+    let baud_rate = baud.parse::<usize>()
+        .map_err(|_| format!("Invalid baud rate {baud} in {connection_string}"))?;
+    if !SUPPORTED_BAUD_RATES.contains(&baud_rate) {
+        return Err(format!("Unsupported baud rate {baud_rate}; use one of {SUPPORTED_BAUD_RATES:?}"));
+    }
+
+    Ok(SerialEndpoint {
+        port: port.to_string(),
+        baud_rate,
+    })
+}
+
+pub fn parse_udp_endpoint(connection_string: &str) -> Result<UdpEndpoint, String> {
+    let (mode, host_port) = if let Some(rest) = connection_string.strip_prefix("udpout://") {
+        (UdpMode::Client, rest)
+    } else if let Some(rest) = connection_string.strip_prefix("udp://") {
+        (UdpMode::Listen, rest)
+    } else {
+        return Err(format!("{connection_string} is not a udp:// or udpout:// address"));
+    };
+    let address = host_port.to_socket_addrs()
+        .map_err(|e| format!("Invalid UDP address {host_port}: {e}"))?
+        .next()
+        .ok_or_else(|| format!("UDP address {host_port} did not resolve"))?;
+
+    Ok(UdpEndpoint { mode, address })
+}
+
+pub fn parse_tcp_endpoint(connection_string: &str) -> Result<TcpEndpoint, String> {
+    let host_port = connection_string.strip_prefix("tcp://")
+        .ok_or_else(|| format!("{connection_string} is not a tcp:// address"))?;
+    let addresses: Vec<SocketAddr> = host_port.to_socket_addrs()
+        .map_err(|e| format!("Invalid TCP address {host_port}: {e}"))?
+        .collect();
+    if addresses.is_empty() {
+        return Err(format!("TCP address {host_port} did not resolve"));
+    }
+
+    Ok(TcpEndpoint {
+        host_port: host_port.to_string(),
+        addresses,
+    })
+}
+
+// ===== VEHICLE LINK =====
+
+impl VehicleLink {
+    // Open the serial port (8N1, no flow control) and start the reader
+    pub fn open_serial(endpoint: &SerialEndpoint, events: mpsc::UnboundedSender<LinkEvent>) -> Result<Self, String> {
+        let mut port = serial::open(&endpoint.port)
+            .map_err(|e| describe_open_error(&endpoint.port, &e))?;
+        let settings = serial::PortSettings {
+            baud_rate: serial::BaudRate::from_speed(endpoint.baud_rate),
+            char_size: serial::Bits8,
+            parity: serial::ParityNone,
+            stop_bits: serial::Stop1,
+            flow_control: serial::FlowNone,
+        };
+        port.configure(&settings)
+            .map_err(|e| format!("Failed to configure {} at {} baud: {e}", endpoint.port, endpoint.baud_rate))?;
+        port.set_timeout(READ_TIMEOUT)
+            .map_err(|e| format!("Failed to set the read timeout on {}: {e}", endpoint.port))?;
+
+        let description = format!("{} at {} baud", endpoint.port, endpoint.baud_rate);
+        let (outgoing, outgoing_rx) = std_mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let reader_stop = stop.clone();
+        let reader_description = description.clone();
+        let reader = spawn_reader("mavlink-serial-reader", move || {
+            run_stream_reader(port, &outgoing_rx, &reader_stop, &reader_description, events)
+        })?;
+
+        Ok(Self {
+            description,
+            silence_hint: "check the baud rate and that the autopilot is powered",
+            outgoing,
+            stop,
+            reader: Some(reader),
+        })
+    }
+
+    // Bind the socket and start the reader; a listening link cannot send
+    // until the vehicle's first datagram reveals its address
+    pub fn open_udp(endpoint: &UdpEndpoint, events: mpsc::UnboundedSender<LinkEvent>) -> Result<Self, String> {
+        let (bind_address, peer, description, silence_hint) = match endpoint.mode {
+            UdpMode::Listen => (
+                endpoint.address,
+                None,
+                format!("UDP port {}", endpoint.address),
+                "check that the vehicle is sending to this port",
+            ),
+            UdpMode::Client => {
+                let unspecified: SocketAddr = if endpoint.address.is_ipv4() {
+                    ([0, 0, 0, 0], 0).into()
+                } else {
+                    ([0u16; 8], 0).into()
+                };
+                (
+                    unspecified,
+                    Some(endpoint.address),
+                    format!("UDP peer {}", endpoint.address),
+                    "check that the vehicle is listening on that address",
+                )
+            }
+        };
+        let socket = UdpSocket::bind(bind_address)
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::AddrInUse => format!("UDP port {bind_address} is already in use by another program"),
+                io::ErrorKind::PermissionDenied => format!("Permission denied binding UDP port {bind_address}"),
+                _ => format!("Failed to bind UDP port {bind_address}: {e}"),
+            })?;
+        socket.set_read_timeout(Some(READ_TIMEOUT))
+            .map_err(|e| format!("Failed to set the read timeout on {description}: {e}"))?;
+
+        let (outgoing, outgoing_rx) = std_mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let reader_stop = stop.clone();
+        let reader_description = description.clone();
+        let reader = spawn_reader("mavlink-udp-reader", move || {
+            run_udp_reader(socket, peer, &outgoing_rx, &reader_stop, &reader_description, events)
+        })?;
+
+        Ok(Self {
+            description,
+            silence_hint,
+            outgoing,
+            stop,
+            reader: Some(reader),
+        })
+    }
+
+    // Connect to the first resolved address that accepts within `timeout`
+    // and start the reader
+    pub fn open_tcp(
+        endpoint: &TcpEndpoint,
+        timeout: Duration,
+        events: mpsc::UnboundedSender<LinkEvent>,
+    ) -> Result<Self, String> {
+        let mut last_error = None;
+        let mut connected = None;
+        for address in &endpoint.addresses {
+            match TcpStream::connect_timeout(address, timeout) {
+                Ok(stream) => {
+                    connected = Some(stream);
+                    break;
+                }
+                Err(e) => last_error = Some(describe_connect_error(address, timeout, &e)),
+            }
+        }
+        let stream = connected
+            .ok_or_else(|| last_error.unwrap_or_else(|| format!("TCP address {} did not resolve", endpoint.host_port)))?;
+        // Frames are small and latency matters more than packing
+        stream.set_nodelay(true)
+            .and_then(|_| stream.set_read_timeout(Some(READ_TIMEOUT)))
+            .map_err(|e| format!("Failed to configure the TCP connection to {}: {e}", endpoint.host_port))?;
+
+        let description = format!("TCP {}", endpoint.host_port);
+        let (outgoing, outgoing_rx) = std_mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let reader_stop = stop.clone();
+        let reader_description = description.clone();
+        let reader = spawn_reader("mavlink-tcp-reader", move || {
+            run_stream_reader(stream, &outgoing_rx, &reader_stop, &reader_description, events)
+        })?;
+
+        Ok(Self {
+            description,
+            silence_hint: "check that the address serves MAVLink",
+            outgoing,
+            stop,
+            reader: Some(reader),
+        })
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn silence_hint(&self) -> &'static str {
+        self.silence_hint
+    }
+
+    // Queue an encoded frame; fails once the reader has exited
+    pub fn write_frame(&self, frame: &[u8]) -> Result<(), String> {
+        self.outgoing.send(frame.to_vec())
+            .map_err(|_| format!("Link to {} is closed", self.description))
+    }
+}
+
+impl Drop for VehicleLink {
+    // The reader notices the stop flag within one read timeout
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(reader) = self.reader.take() {
+            if reader.join().is_err() {
+                eprintln!("Reader for {} panicked", self.description);
+            }
+        }
+    }
+}
+
+fn spawn_reader<F>(name: &str, reader: F) -> Result<JoinHandle<()>, String>
+where
+    F: FnOnce() + Send + 'static,
+{
+    std::thread::Builder::new()
+        .name(name.to_string())
+        .spawn(reader)
+        .map_err(|e| format!("Failed to start the link reader: {e}"))
+}
+
+// ===== STREAM READER =====
+
+// Read a serial port or TCP stream until stopped or it closes, writing queued
+// frames between reads
+fn run_stream_reader<S: Read + Write>(
+    mut stream: S,
+    outgoing: &std_mpsc::Receiver<Vec<u8>>,
+    stop: &AtomicBool,
+    description: &str,
+    events: mpsc::UnboundedSender<LinkEvent>,
+) {
+    let mut parser = FrameParser::default();
+    let mut chunk = [0u8; STREAM_READ_CHUNK_BYTES];
+    while !stop.load(Ordering::SeqCst) {
+        let written = outgoing.try_iter().try_for_each(|frame| stream.write_all(&frame));
+        let received = written.and_then(|_| stream.read(&mut chunk));
+        let len = match received {
+            Ok(0) => {
+                let _ = events.send(LinkEvent::Closed(format!("{description} closed the connection")));
+                break;
+            }
+            Ok(len) => len,
+            Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => continue,
+            Err(e) => {
+                let _ = events.send(LinkEvent::Closed(format!("Link to {description} failed: {e}")));
+                break;
+            }
+        };
+
+        parser.push(&chunk[..len]);
+        while let Some(frame) = parser.next_message() {
+            if events.send(LinkEvent::Message(Box::new(frame))).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+// The serial crate reports missing, inaccessible and busy devices alike, so
+// probe the path to tell the operator which it is
+fn describe_open_error(port: &str, error: &serial::Error) -> String {
+    match error.kind() {
+        serial::ErrorKind::NoDevice => match std::fs::OpenOptions::new().read(true).write(true).open(port) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                format!("Serial port {port} does not exist; check that the autopilot is plugged in")
+            }
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+                format!("Permission denied opening {port}; add your user to the dialout group")
+            }
+            _ => format!("Serial port {port} is busy; close any other ground station using it"),
+        },
+        serial::ErrorKind::InvalidInput => format!("Invalid serial port name {port}"),
+        serial::ErrorKind::Io(_) => format!("Failed to open {port}: {error}"),
+    }
+}
+
+// ===== UDP READER =====
+
+// Receive datagrams until stopped or the socket fails, sending queued frames to
+// the peer between reads. A listening link adopts the sender of the first
+// datagram as its peer; frames queued before then are dropped.
+fn run_udp_reader(
+    socket: UdpSocket,
+    mut peer: Option<SocketAddr>,
+    outgoing: &std_mpsc::Receiver<Vec<u8>>,
+    stop: &AtomicBool,
+    description: &str,
+    events: mpsc::UnboundedSender<LinkEvent>,
+) {
+    let mut datagram = vec![0u8; MAX_DATAGRAM_BYTES];
+    while !stop.load(Ordering::SeqCst) {
+        while let Ok(frame) = outgoing.try_recv() {
+            if let Some(peer) = peer {
+                if let Err(e) = socket.send_to(&frame, peer) {
+                    eprintln!("Failed to send to {peer}: {e}");
+                }
+            }
+        }
+
+        let (len, sender) = match socket.recv_from(&mut datagram) {
+            Ok(received) => received,
+            Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => continue,
+            // An ICMP port unreachable from a client-mode peer that is not up yet
+            Err(e) if e.kind() == io::ErrorKind::ConnectionReset => continue,
+            Err(e) => {
+                let _ = events.send(LinkEvent::Closed(format!("UDP link on {description} failed: {e}")));
+                break;
+            }
+        };
+        if peer.is_none() {
+            peer = Some(sender);
+        }
+        for frame in parse_datagram(&datagram[..len]) {
+            if events.send(LinkEvent::Message(Box::new(frame))).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+// Every MAVLink 1 or 2 frame in one datagram; frames never span datagrams,
+// so a truncated frame at the end is dropped
+fn parse_datagram(datagram: &[u8]) -> Vec<ReceivedFrame> {
+    let mut parser = FrameParser::default();
+    parser.push(datagram);
+    std::iter::from_fn(|| parser.next_message()).collect()
+}
+
+fn describe_connect_error(address: &SocketAddr, timeout: Duration, error: &io::Error) -> String {
+    match error.kind() {
+        io::ErrorKind::ConnectionRefused => {
+            format!("Connection refused by {address}; check that the vehicle or SITL is listening")
+        }
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => {
+            format!("Timed out connecting to {address} after {} ms", timeout.as_millis())
+        }
+        _ => format!("Failed to connect to {address}: {error}"),
+    }
+}
+
+// ===== FRAME PARSER =====
+
+impl ReceivedFrame {
+    // STATUSTEXT (id, chunk_seq) from the MAVLink 2 extension fields, which the
+    // decoded message leaves out. MAVLink 1 frames and single-part texts give id 0.
+    pub fn statustext_chunk(&self) -> (u16, u8) {
+        let id = u16::from_le_bytes([
+            self.payload_byte(STATUSTEXT_ID_OFFSET),
+            self.payload_byte(STATUSTEXT_ID_OFFSET + 1),
+        ]);
+        (id, self.payload_byte(STATUSTEXT_CHUNK_SEQ_OFFSET))
+    }
+
+    // Senders truncate trailing zero bytes of a MAVLink 2 payload, so bytes
+    // past the received length read as zero
+    fn payload_byte(&self, offset: usize) -> u8 {
+        if self.raw.first() != Some(&::mavlink::MAV_STX_V2) {
+            return 0;
+        }
+        let payload_len = usize::from(self.raw.get(1).copied().unwrap_or(0));
+        if offset >= payload_len {
+            return 0;
+        }
+        self.raw.get(V2_HEADER_LEN + offset).copied().unwrap_or(0)
+    }
+}
+
+impl FrameParser {
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    // Next complete frame with a valid checksum. Bytes before a start marker
+    // are skipped, and a frame that fails to decode gives up only its marker
+    // so parsing resynchronizes on the next one.
+    pub fn next_message(&mut self) -> Option<ReceivedFrame> {
+        loop {
+            let start = self.buffer.iter()
+                .position(|&byte| byte == ::mavlink::MAV_STX || byte == ::mavlink::MAV_STX_V2);
+            match start {
+                Some(start) => drop(self.buffer.drain(..start)),
+                None => {
+                    self.buffer.clear();
+                    return None;
+                }
+            }
+
+            let frame_len = frame_length(&self.buffer)?;
+            if self.buffer.len() < frame_len {
+                return None;
+            }
+            let mut frame = io::Cursor::new(&self.buffer[..frame_len]);
+            let decoded = if self.buffer[0] == ::mavlink::MAV_STX {
+                ::mavlink::read_v1_msg::<MavMessage, _>(&mut frame)
+            } else {
+                ::mavlink::read_v2_msg::<MavMessage, _>(&mut frame)
+            };
+            match decoded {
+                Ok((header, message)) => {
+                    let raw = self.buffer.drain(..frame_len).collect();
+                    return Some(ReceivedFrame { header, message, raw });
+                }
+                Err(_) => drop(self.buffer.drain(..1)),
+            }
+        }
+    }
+}
+
+// Total length of the frame starting at `buffer[0]`, once its header is in
+fn frame_length(buffer: &[u8]) -> Option<usize> {
+    let payload_len = usize::from(*buffer.get(1)?);
+    if buffer[0] == ::mavlink::MAV_STX {
+        return Some(V1_FRAME_OVERHEAD + payload_len);
+    }
+    let incompat_flags = *buffer.get(2)?;
+    let signature = if incompat_flags & MAVLINK_IFLAG_SIGNED != 0 { V2_SIGNATURE_LEN } else { 0 };
+    Some(V2_FRAME_OVERHEAD + payload_len + signature)
+}
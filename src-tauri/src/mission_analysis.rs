@@ -0,0 +1,1155 @@
+// Mission analysis backend
+// NASA JPL Power of 10 compliant implementation
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+
+use crate::geo::{catmull_rom_points, distance_m, haversine_distance};
+use crate::map_features::Coordinate;
+use crate::mavlink::{vehicle_progress, MavlinkState, VehicleProgress};
+use crate::mission_io::item_sequence_numbers;
+use crate::performance::advisory_warnings;
+use crate::{AppState, CameraParams, CameraTriggerType, LoiterType, MissionItem};
+
+// ===== TYPE DEFINITIONS =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionDiff {
+    pub added: Vec<MissionItem>,
+    pub removed: Vec<MissionItem>,
+    pub modified: Vec<ItemDiff>,
+    pub unchanged_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemDiff {
+    pub id_a: String,
+    pub id_b: String,
+    pub index_a: usize,
+    pub index_b: usize,
+    pub matched_by: String,
+    pub changed_fields: Vec<String>,
+    pub horizontal_delta_m: f64,
+    pub alt_delta_m: f64,
+    pub speed_delta: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionStats {
+    pub total_distance_km: f64,
+    pub estimated_time_min: f64,
+    pub max_altitude_m: f64,
+    pub altitude_gain_m: f64,
+    pub waypoint_count: u32,
+    pub battery_warning: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaypointEta {
+    pub item_id: String,
+    pub distance_from_start_m: f64,
+    // Unix milliseconds
+    pub eta_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionEta {
+    // True when estimates start from the vehicle's live position instead of the mission start
+    pub anchored_to_vehicle: bool,
+    pub start_time_ms: u64,
+    pub waypoints: Vec<WaypointEta>,
+}
+
+// Live vehicle state the ETA is measured from
+struct EtaAnchor {
+    position: Coordinate,
+    groundspeed_ms: Option<f64>,
+    // Index of the uploaded item the vehicle is heading for
+    next_index: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegPhotoCoverage {
+    pub from_item_id: String,
+    pub to_item_id: String,
+    pub altitude_m: f64,
+    pub gsd_cm: f64,
+    pub trigger_distance_m: Option<f64>,
+    pub photo_count: u32,
+    pub forward_overlap_pct: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhotoCoverageEstimate {
+    pub photo_count: u32,
+    pub overlap_met: bool,
+    pub legs: Vec<LegPhotoCoverage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SurveyPhotoStats {
+    pub estimated_photo_count: u32,
+    // Ground swept by the camera footprint along triggered legs
+    pub coverage_area_km2: f64,
+    // Sensor resolution needed to reach the requested GSD at the highest triggered leg
+    pub required_image_width_px: u32,
+}
+
+// Flight-path leg flown while a distance or time camera trigger is active
+struct TriggeredLeg<'a> {
+    from: &'a MissionItem,
+    to: &'a MissionItem,
+    length_m: f64,
+    altitude_m: f64,
+    // Along-track photo spacing; None while triggering is off
+    spacing_m: Option<f64>,
+}
+
+impl TriggeredLeg<'_> {
+    fn photo_count(&self) -> u32 {
+        self.spacing_m.map_or(0, |d| (self.length_m / d).floor() as u32)
+    }
+}
+
+// Broken structural rule, e.g. "takeoff_first"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructureViolation {
+    pub rule: String,
+    pub message: String,
+    pub item_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionStructureReport {
+    // In strict mode violations are errors and edits introducing them are rejected
+    pub strict: bool,
+    pub violations: Vec<StructureViolation>,
+}
+
+// Rule broken by a mission item; `item_id` is empty for mission-level problems
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationError {
+    pub item_id: String,
+    pub rule: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationWarning {
+    pub item_id: String,
+    pub rule: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionValidationReport {
+    // False when any error is present; warnings do not block upload
+    pub valid: bool,
+    pub errors: Vec<ValidationError>,
+    pub warnings: Vec<ValidationWarning>,
+}
+
+// Used for legs whose destination item does not specify a speed
+pub const DEFAULT_CRUISE_SPEED_MS: f64 = 10.0;
+const DEFAULT_MAX_FLIGHT_TIME_MIN: f64 = 20.0;
+// Time lost decelerating into and accelerating out of each intermediate waypoint
+const DEFAULT_CORNER_PENALTY_S: f64 = 2.0;
+// NASA JPL Rule 2: bound DO_JUMP unrolling
+const MAX_EXPANDED_ITEMS: usize = 100_000;
+// Segments approximating each leg into a spline waypoint
+const SPLINE_LEG_SAMPLES: usize = 16;
+
+const DEFAULT_MAX_LEG_KM: f64 = 50.0;
+const DEFAULT_MAX_ROI_DISTANCE_M: f64 = 1000.0;
+
+// Differences below these tolerances are treated as equal
+const HORIZONTAL_TOLERANCE_M: f64 = 0.01;
+const VALUE_TOLERANCE: f64 = 1e-6;
+
+// ===== MISSION DIFF =====
+
+#[tauri::command]
+pub async fn diff_missions(
+    a: Vec<MissionItem>,
+    b: Vec<MissionItem>,
+) -> Result<MissionDiff, String> {
+    Ok(compute_mission_diff(&a, &b))
+}
+
+#[tauri::command]
+pub async fn diff_with_vehicle_mission(
+    state: State<'_, AppState>,
+) -> Result<MissionDiff, String> {
+    let local = state.mission_items.lock()
+        .map_err(|_| "Failed to lock state")?
+        .clone();
+    let vehicle = state.vehicle_mission.lock()
+        .map_err(|_| "Failed to lock vehicle mission")?
+        .clone()
+        .ok_or("No mission has been downloaded from the vehicle")?;
+
+    Ok(compute_mission_diff(&local, &vehicle))
+}
+
+// ===== MISSION STATISTICS =====
+
+#[tauri::command]
+pub async fn calculate_mission_stats(
+    cruise_speed_ms: Option<f64>,
+    max_flight_time_min: Option<f64>,
+    state: State<'_, AppState>,
+) -> Result<MissionStats, String> {
+    let cruise_speed_ms = cruise_speed_ms.unwrap_or(DEFAULT_CRUISE_SPEED_MS);
+    let max_flight_time_min = max_flight_time_min.unwrap_or(DEFAULT_MAX_FLIGHT_TIME_MIN);
+    if !(cruise_speed_ms.is_finite() && cruise_speed_ms > 0.0) {
+        return Err("Cruise speed must be a positive number".to_string());
+    }
+
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let sequence = expand_jumps(&items, None)?;
+    Ok(compute_mission_stats(&sequence, cruise_speed_ms, max_flight_time_min))
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+// Items in execution order with DO_JUMP repeats unrolled (jump items themselves
+// are dropped). Forever jumps (repeat 0) run `forever_repeats` times, or fail if None.
+pub fn expand_jumps(
+    items: &[MissionItem],
+    forever_repeats: Option<u32>,
+) -> Result<Vec<&MissionItem>, String> {
+    let mut repeats_left: HashMap<usize, u32> = HashMap::new();
+    let mut sequence = Vec::new();
+    let mut index = 0;
+
+    while index < items.len() {
+        if sequence.len() >= MAX_EXPANDED_ITEMS {
+            return Err("Mission repeats expand to too many items".to_string());
+        }
+        let item = &items[index];
+        if !item.is_jump() {
+            sequence.push(item);
+            index += 1;
+            continue;
+        }
+
+        let target = item.params.target_item_id.as_deref().unwrap_or_default();
+        let target_index = items[..index].iter()
+            .position(|i| i.id == target)
+            .ok_or_else(|| format!("Jump {} targets missing or later item {}", item.id, target))?;
+        let repeats = match item.params.repeat_count.unwrap_or(0) {
+            0 => forever_repeats
+                .ok_or_else(|| format!("Jump {} repeats forever; statistics are unbounded", item.id))?,
+            n => n,
+        };
+
+        let left = repeats_left.entry(index).or_insert(repeats);
+        if *left > 0 {
+            *left -= 1;
+            index = target_index;
+        } else {
+            index += 1;
+        }
+    }
+    Ok(sequence)
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+// Each leg is flown at the speed of the item it arrives at; loiters and delays add their hold time
+fn compute_mission_stats(
+    items: &[&MissionItem],
+    cruise_speed_ms: f64,
+    max_flight_time_min: f64,
+) -> MissionStats {
+    let positioned: Vec<&MissionItem> = items.iter()
+        .copied()
+        .filter(|item| item.is_on_flight_path())
+        .collect();
+
+    let mut total_distance_km = 0.0;
+    let mut flight_time_s = 0.0;
+    let mut altitude_gain_m = 0.0;
+    for (index, pair) in positioned.windows(2).enumerate() {
+        let leg_km = track_length_km(&leg_track(&positioned, index + 1));
+        let speed_ms = pair[1].params.speed
+            .filter(|speed| *speed > 0.0)
+            .unwrap_or(cruise_speed_ms);
+        total_distance_km += leg_km;
+        flight_time_s += leg_km * 1000.0 / speed_ms;
+        altitude_gain_m += (pair[1].params.alt - pair[0].params.alt).max(0.0);
+    }
+    flight_time_s += items.iter()
+        .map(|item| loiter_duration_s(item, cruise_speed_ms) + item.delay_duration_s())
+        .sum::<f64>();
+
+    let estimated_time_min = flight_time_s / 60.0;
+    MissionStats {
+        total_distance_km,
+        estimated_time_min,
+        max_altitude_m: positioned.iter()
+            .map(|item| item.params.alt)
+            .fold(0.0, f64::max),
+        altitude_gain_m,
+        waypoint_count: positioned.len() as u32,
+        battery_warning: estimated_time_min > max_flight_time_min,
+    }
+}
+
+// Ground track of the leg from `path[to_index - 1]` to `path[to_index]`. A leg into
+// a spline waypoint follows a Catmull-Rom curve through the neighboring items; at
+// either end of the path the missing neighbor is the leg endpoint itself.
+pub fn leg_track(path: &[&MissionItem], to_index: usize) -> Vec<Coordinate> {
+    let (from, to) = (path[to_index - 1].location(), path[to_index].location());
+    if !path[to_index].params.spline {
+        return vec![from, to];
+    }
+
+    let before = match to_index.checked_sub(2) {
+        Some(index) => path[index].location(),
+        None => from.clone(),
+    };
+    let after = path.get(to_index + 1).map_or_else(|| to.clone(), |item| item.location());
+    catmull_rom_points(&before, &from, &to, &after, SPLINE_LEG_SAMPLES)
+}
+
+pub fn track_length_km(track: &[Coordinate]) -> f64 {
+    track.windows(2).map(|pair| haversine_distance(&pair[0], &pair[1])).sum()
+}
+
+// Time spent holding at a loiter item; unlimited loiters are excluded since they end
+// only when the operator moves the vehicle on
+fn loiter_duration_s(item: &MissionItem, cruise_speed_ms: f64) -> f64 {
+    let loiter = match &item.loiter_params {
+        Some(loiter) if item.is_loiter() => loiter,
+        _ => return 0.0,
+    };
+    match loiter.loiter_type {
+        LoiterType::Unlimited => 0.0,
+        LoiterType::Time => loiter.duration_s.unwrap_or(0.0),
+        LoiterType::Turns => {
+            let speed_ms = item.params.speed
+                .filter(|speed| *speed > 0.0)
+                .unwrap_or(cruise_speed_ms);
+            let circumference_m = 2.0 * std::f64::consts::PI * loiter.radius_m;
+            f64::from(loiter.turns.unwrap_or(0.0)) * circumference_m / speed_ms
+        }
+    }
+}
+
+// ===== MISSION ETA =====
+
+// NASA JPL Rule 4: Function under 60 lines
+// Arrival time at every flight-path item. With `anchor_to_vehicle` and a connected
+// vehicle reporting MISSION_CURRENT, estimates start at the vehicle's position and
+// groundspeed and cover the uploaded mission from its current item onward;
+// otherwise they start at the first item of the active mission.
+#[tauri::command]
+pub async fn get_mission_eta(
+    start_time: Option<u64>,
+    cruise_speed_ms: Option<f64>,
+    corner_penalty_s: Option<f64>,
+    anchor_to_vehicle: Option<bool>,
+    state: State<'_, AppState>,
+    mavlink_state: State<'_, MavlinkState>,
+) -> Result<MissionEta, String> {
+    let cruise_speed_ms = cruise_speed_ms.unwrap_or(DEFAULT_CRUISE_SPEED_MS);
+    let corner_penalty_s = corner_penalty_s.unwrap_or(DEFAULT_CORNER_PENALTY_S);
+    if !(cruise_speed_ms.is_finite() && cruise_speed_ms > 0.0) {
+        return Err("Cruise speed must be a positive number".to_string());
+    }
+    if !(corner_penalty_s.is_finite() && corner_penalty_s >= 0.0) {
+        return Err("Corner penalty must be a non-negative number".to_string());
+    }
+
+    let anchored = if anchor_to_vehicle.unwrap_or(false) {
+        vehicle_anchor(&state, &mavlink_state)?
+    } else {
+        None
+    };
+    let (items, anchor, start_time_ms) = match anchored {
+        Some((items, anchor)) => (items, Some(anchor), current_time_ms()),
+        None => {
+            let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?.clone();
+            (items, None, start_time.unwrap_or_else(current_time_ms))
+        }
+    };
+
+    let mut sequence = expand_jumps(&items, None)?;
+    if let Some(anchor) = anchor.as_ref() {
+        // Drop what the vehicle has already flown (first pass through any repeats)
+        let ahead: HashSet<&str> = items[anchor.next_index..].iter().map(|i| i.id.as_str()).collect();
+        let first = sequence.iter().position(|item| ahead.contains(item.id.as_str()));
+        sequence.drain(..first.unwrap_or(sequence.len()));
+    }
+    Ok(MissionEta {
+        anchored_to_vehicle: anchor.is_some(),
+        start_time_ms,
+        waypoints: waypoint_etas(&sequence, anchor.as_ref(), start_time_ms, cruise_speed_ms, corner_penalty_s),
+    })
+}
+
+// Uploaded mission and the vehicle's place in it, if a connected vehicle reports progress
+fn vehicle_anchor(
+    state: &AppState,
+    mavlink_state: &MavlinkState,
+) -> Result<Option<(Vec<MissionItem>, EtaAnchor)>, String> {
+    let (current_seq, position, groundspeed_ms) = match vehicle_progress(mavlink_state)? {
+        Some(VehicleProgress { current_seq: Some(seq), position: Some(position), groundspeed_ms, .. }) => {
+            (seq as usize, position, groundspeed_ms)
+        }
+        _ => return Ok(None),
+    };
+    let uploaded = state.vehicle_mission.lock()
+        .map_err(|_| "Failed to lock vehicle mission")?
+        .clone();
+    let items = match uploaded {
+        Some(items) => items,
+        None => return Ok(None),
+    };
+
+    // Prefix commands (speed changes, camera settings) share the index of their item
+    let next = item_sequence_numbers(&items).iter().position(|seq| *seq >= current_seq);
+    Ok(next.map(|next_index| (items, EtaAnchor { position, groundspeed_ms, next_index })))
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+// Legs are flown at the arriving item's speed (the first leg from the vehicle at
+// its groundspeed), each intermediate waypoint costs `corner_penalty_s`, and
+// loiters hold for their duration before the next leg
+fn waypoint_etas(
+    sequence: &[&MissionItem],
+    anchor: Option<&EtaAnchor>,
+    start_time_ms: u64,
+    cruise_speed_ms: f64,
+    corner_penalty_s: f64,
+) -> Vec<WaypointEta> {
+    let mut previous = anchor.map(|a| a.position.clone());
+    let mut first_leg_speed = anchor.and_then(|a| a.groundspeed_ms).filter(|speed| *speed > 0.0);
+    let mut legs_flown = 0;
+    let mut distance_m = 0.0;
+    let mut elapsed_s = 0.0;
+    let mut etas = Vec::new();
+
+    for item in sequence {
+        if item.is_on_flight_path() {
+            let location = item.location();
+            if let Some(from) = previous.as_ref() {
+                let leg_m = haversine_distance(from, &location) * 1000.0;
+                let speed_ms = first_leg_speed.take()
+                    .or(item.params.speed.filter(|speed| *speed > 0.0))
+                    .unwrap_or(cruise_speed_ms);
+                if legs_flown > 0 {
+                    elapsed_s += corner_penalty_s;
+                }
+                distance_m += leg_m;
+                elapsed_s += leg_m / speed_ms;
+                legs_flown += 1;
+            }
+            etas.push(WaypointEta {
+                item_id: item.id.clone(),
+                distance_from_start_m: distance_m,
+                eta_ms: start_time_ms + (elapsed_s * 1000.0).round() as u64,
+            });
+            previous = Some(location);
+        }
+        elapsed_s += loiter_duration_s(item, cruise_speed_ms);
+    }
+    etas
+}
+
+fn current_time_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// ===== MISSION STRUCTURE =====
+
+#[tauri::command]
+pub async fn check_mission_structure(
+    state: State<'_, AppState>,
+) -> Result<MissionStructureReport, String> {
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let strict = *state.strict_structure.lock().map_err(|_| "Failed to lock strict mode")?;
+
+    Ok(MissionStructureReport {
+        strict,
+        violations: structure_violations(&items),
+    })
+}
+
+#[tauri::command]
+pub async fn set_strict_mission_structure(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    *state.strict_structure.lock().map_err(|_| "Failed to lock strict mode")? = enabled;
+    Ok(())
+}
+
+// In strict mode, reject an edit that breaks a rule the mission currently satisfies
+pub fn enforce_structure(
+    state: &AppState,
+    before: &[MissionItem],
+    after: &[MissionItem],
+) -> Result<(), String> {
+    if !*state.strict_structure.lock().map_err(|_| "Failed to lock strict mode")? {
+        return Ok(());
+    }
+
+    let existing: Vec<String> = structure_violations(before).into_iter().map(|v| v.rule).collect();
+    match structure_violations(after).into_iter().find(|v| !existing.contains(&v.rule)) {
+        Some(violation) => Err(format!("Rejected by rule {}: {}", violation.rule, violation.message)),
+        None => Ok(()),
+    }
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+// Exactly one takeoff, first; at most one land/RTL, last
+pub fn structure_violations(items: &[MissionItem]) -> Vec<StructureViolation> {
+    let mut violations = Vec::new();
+    let mut violate = |rule: &str, message: String, item: Option<&MissionItem>| {
+        violations.push(StructureViolation {
+            rule: rule.to_string(),
+            message,
+            item_id: item.map(|i| i.id.clone()),
+        });
+    };
+
+    let takeoffs: Vec<&MissionItem> = items.iter().filter(|i| i.is_takeoff()).collect();
+    match (takeoffs.first(), items.first()) {
+        (None, _) => violate("takeoff_first", "Mission has no takeoff".to_string(), None),
+        (Some(takeoff), Some(first)) if takeoff.id != first.id => violate(
+            "takeoff_first",
+            "Takeoff must be the first item".to_string(),
+            Some(takeoff),
+        ),
+        _ => {}
+    }
+    for extra in takeoffs.iter().skip(1) {
+        violate("single_takeoff", "Mission has more than one takeoff".to_string(), Some(extra));
+    }
+
+    let terminals: Vec<(usize, &MissionItem)> = items.iter()
+        .enumerate()
+        .filter(|(_, i)| i.is_terminal())
+        .collect();
+    for (_, extra) in terminals.iter().skip(1) {
+        violate("single_terminal", "Mission has more than one land/RTL".to_string(), Some(extra));
+    }
+    if let Some((index, terminal)) = terminals.first() {
+        if let Some(after) = items.get(index + 1) {
+            violate(
+                "terminal_last",
+                format!("No items may follow {} ({})", terminal.name, terminal.item_type),
+                Some(after),
+            );
+        }
+    }
+
+    violations
+}
+
+// ===== MISSION VALIDATION =====
+
+#[tauri::command]
+pub async fn validate_mission(
+    max_leg_km: Option<f64>,
+    max_roi_distance_m: Option<f64>,
+    state: State<'_, AppState>,
+) -> Result<MissionValidationReport, String> {
+    let max_leg_km = max_leg_km.unwrap_or(DEFAULT_MAX_LEG_KM);
+    let max_roi_distance_m = max_roi_distance_m.unwrap_or(DEFAULT_MAX_ROI_DISTANCE_M);
+    if !(max_leg_km.is_finite() && max_leg_km > 0.0) {
+        return Err("Maximum leg distance must be positive".to_string());
+    }
+    if !(max_roi_distance_m.is_finite() && max_roi_distance_m > 0.0) {
+        return Err("Maximum ROI distance must be positive".to_string());
+    }
+
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let strict = *state.strict_structure.lock().map_err(|_| "Failed to lock strict mode")?;
+    let mut report = mission_validation_report(&items, strict, max_leg_km, max_roi_distance_m);
+    if let Some(estimates) = state.performance_advisory.lock().map_err(|_| "Failed to lock performance advisory")?.as_ref() {
+        report.warnings.extend(advisory_warnings(estimates));
+    }
+    Ok(report)
+}
+
+// Report with the default limits, as checked before uploading to a vehicle
+pub fn validate_for_upload(state: &AppState) -> Result<MissionValidationReport, String> {
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let strict = *state.strict_structure.lock().map_err(|_| "Failed to lock strict mode")?;
+    Ok(mission_validation_report(&items, strict, DEFAULT_MAX_LEG_KM, DEFAULT_MAX_ROI_DISTANCE_M))
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+pub fn mission_validation_report(
+    items: &[MissionItem],
+    strict: bool,
+    max_leg_km: f64,
+    max_roi_distance_m: f64,
+) -> MissionValidationReport {
+    let mut errors = item_errors(items, max_leg_km);
+    let mut warnings = Vec::new();
+
+    if items.len() > crate::MAX_MISSION_ITEMS {
+        errors.push(ValidationError {
+            item_id: String::new(),
+            rule: "max_items".to_string(),
+            message: format!("Mission has more than {} items", crate::MAX_MISSION_ITEMS),
+        });
+    }
+
+    match items.first() {
+        Some(first) if first.is_takeoff() => {}
+        first => errors.push(ValidationError {
+            item_id: first.map(|i| i.id.clone()).unwrap_or_default(),
+            rule: "takeoff_first".to_string(),
+            message: "First item must be a takeoff".to_string(),
+        }),
+    }
+    if !items.last().map(MissionItem::is_terminal).unwrap_or(false) {
+        warnings.push(ValidationWarning {
+            item_id: items.last().map(|i| i.id.clone()).unwrap_or_default(),
+            rule: "ends_with_land_or_rtl".to_string(),
+            message: "Mission does not end with a land or RTL".to_string(),
+        });
+    }
+
+    // takeoff_first is always an error and already reported above
+    for violation in structure_violations(items).into_iter().filter(|v| v.rule != "takeoff_first") {
+        let item_id = violation.item_id.unwrap_or_default();
+        if strict {
+            errors.push(ValidationError { item_id, rule: violation.rule, message: violation.message });
+        } else {
+            warnings.push(ValidationWarning { item_id, rule: violation.rule, message: violation.message });
+        }
+    }
+    warnings.extend(roi_distance_warnings(items, max_roi_distance_m));
+    warnings.extend(grounded_delay_warnings(items));
+    warnings.extend(untimed_loiter_warnings(items));
+
+    MissionValidationReport {
+        valid: errors.is_empty(),
+        errors,
+        warnings,
+    }
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+// Per-item rules: parameters, ids, jump targets, splines, altitude, speed and leg length
+fn item_errors(items: &[MissionItem], max_leg_km: f64) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    let mut error = |item: &MissionItem, rule: &str, message: String| {
+        errors.push(ValidationError { item_id: item.id.clone(), rule: rule.to_string(), message });
+    };
+
+    let mut seen_ids: HashMap<&str, usize> = HashMap::new();
+    let mut previous: Option<&MissionItem> = None;
+    for (index, item) in items.iter().enumerate() {
+        let occurrences = seen_ids.entry(item.id.as_str()).or_insert(0);
+        *occurrences += 1;
+        if *occurrences == 2 {
+            error(item, "unique_id", format!("Item id {} is used more than once", item.id));
+        }
+        if let Err(message) = item.validate_params() {
+            error(item, "item_params", message);
+        }
+        if let Err(message) = item.validate_jump_target(&items[..index]) {
+            error(item, "jump_target", message);
+        }
+        if let Some(speed) = item.params.speed {
+            if !(speed.is_finite() && speed > 0.0) {
+                error(item, "positive_speed", format!("{} has a non-positive speed", item.name));
+            }
+        }
+        // A spline needs a leg in and a leg out to shape the curve
+        if item.params.spline && (index == 0 || index + 1 == items.len()) {
+            error(item, "spline_endpoint", format!("{} cannot be a spline as the first or last item", item.name));
+        }
+        if !item.is_on_flight_path() {
+            continue;
+        }
+
+        if item.item_type != "land" && !(item.params.alt.is_finite() && item.params.alt > 0.0) {
+            error(item, "positive_altitude", format!("{} must be above 0 m", item.name));
+        }
+        if let Some(from) = previous {
+            let leg_km = haversine_distance(&from.location(), &item.location());
+            if leg_km > max_leg_km {
+                error(
+                    item,
+                    "max_leg_distance",
+                    format!("Leg to {} is {:.1} km (max {} km)", item.name, leg_km, max_leg_km),
+                );
+            }
+        }
+        previous = Some(item);
+    }
+
+    errors
+}
+
+// An ROI applies until the next ROI or ROI cancel; warn when it is far from the items it covers
+fn roi_distance_warnings(items: &[MissionItem], max_roi_distance_m: f64) -> Vec<ValidationWarning> {
+    let mut warnings = Vec::new();
+    for (index, roi) in items.iter().enumerate().filter(|(_, i)| i.is_roi()) {
+        let target = roi.location();
+        let farthest_m = items[index + 1..].iter()
+            .take_while(|i| !i.is_roi() && i.item_type != "roi_cancel")
+            .filter(|i| i.is_on_flight_path())
+            .map(|i| distance_m(&target, &i.location()))
+            .fold(0.0, f64::max);
+        if farthest_m > max_roi_distance_m {
+            warnings.push(ValidationWarning {
+                item_id: roi.id.clone(),
+                rule: "roi_distance".to_string(),
+                message: format!(
+                    "{} is {:.0} m from the legs it covers (max {} m)",
+                    roi.name, farthest_m, max_roi_distance_m
+                ),
+            });
+        }
+    }
+    warnings
+}
+
+// Delays before takeoff or after landing hold a vehicle that is on the ground
+fn grounded_delay_warnings(items: &[MissionItem]) -> Vec<ValidationWarning> {
+    let takeoff = items.iter().position(MissionItem::is_takeoff);
+    let land = items.iter().position(|item| item.item_type == "land");
+    items.iter()
+        .enumerate()
+        .filter(|(index, item)| {
+            item.is_delay()
+                && (takeoff.map_or(false, |t| *index < t) || land.map_or(false, |l| *index > l))
+        })
+        .map(|(_, item)| ValidationWarning {
+            item_id: item.id.clone(),
+            rule: "delay_on_ground".to_string(),
+            message: format!("{} has no effect before takeoff or after landing", item.name),
+        })
+        .collect()
+}
+
+// A time loiter without a duration never finishes, like an unlimited loiter
+fn untimed_loiter_warnings(items: &[MissionItem]) -> Vec<ValidationWarning> {
+    items.iter()
+        .filter(|item| item.is_loiter())
+        .filter(|item| {
+            item.loiter_params.as_ref()
+                .map_or(false, |l| l.loiter_type == LoiterType::Time && l.duration_s.is_none())
+        })
+        .map(|item| ValidationWarning {
+            item_id: item.id.clone(),
+            rule: "loiter_duration".to_string(),
+            message: format!("{} is a time loiter without a duration", item.name),
+        })
+        .collect()
+}
+
+// ===== PHOTO COVERAGE =====
+
+// Photos taken by camera_trigger distance spacing along the mission legs. The
+// sensor width is assumed to lie along track, so it sets the forward footprint.
+#[tauri::command]
+pub async fn estimate_photo_coverage(
+    sensor_width_mm: f64,
+    focal_length_mm: f64,
+    image_width_px: u32,
+    overlap_pct: f64,
+    state: State<'_, AppState>,
+) -> Result<PhotoCoverageEstimate, String> {
+    if !(sensor_width_mm > 0.0 && focal_length_mm > 0.0 && image_width_px > 0) {
+        return Err("Camera dimensions must be positive".to_string());
+    }
+    if !(0.0..100.0).contains(&overlap_pct) {
+        return Err("Overlap must be between 0 and 100%".to_string());
+    }
+
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let legs = photo_legs(&items, sensor_width_mm, focal_length_mm, image_width_px);
+
+    Ok(PhotoCoverageEstimate {
+        photo_count: legs.iter().map(|leg| leg.photo_count).sum(),
+        overlap_met: legs.iter()
+            .filter_map(|leg| leg.forward_overlap_pct)
+            .all(|overlap| overlap >= overlap_pct),
+        legs,
+    })
+}
+
+// Photos from the mission's camera triggers: distance/time spacing along the legs plus one
+// per manual trigger. Each photo's footprint width is sensor width * altitude / focal length.
+#[tauri::command]
+pub async fn calculate_survey_photos(
+    mission_id: String,
+    sensor_width_mm: f64,
+    focal_length_mm: f64,
+    gsd_cm: f64,
+    state: State<'_, AppState>,
+) -> Result<SurveyPhotoStats, String> {
+    let camera_valid = sensor_width_mm.is_finite() && sensor_width_mm > 0.0
+        && focal_length_mm.is_finite() && focal_length_mm > 0.0;
+    if !(camera_valid && gsd_cm.is_finite() && gsd_cm > 0.0) {
+        return Err("Camera dimensions and GSD must be positive".to_string());
+    }
+
+    let items = crate::mission_items_by_id(&state, &mission_id)?;
+    Ok(survey_photo_stats(&items, sensor_width_mm, focal_length_mm, gsd_cm))
+}
+
+fn survey_photo_stats(
+    items: &[MissionItem],
+    sensor_width_mm: f64,
+    focal_length_mm: f64,
+    gsd_cm: f64,
+) -> SurveyPhotoStats {
+    let legs: Vec<TriggeredLeg> = triggered_legs(items)
+        .into_iter()
+        .filter(|leg| leg.spacing_m.is_some())
+        .collect();
+    let footprint_m = |leg: &TriggeredLeg| sensor_width_mm * leg.altitude_m.max(0.0) / focal_length_mm;
+
+    let manual_shots = items.iter()
+        .filter_map(|item| item.camera_params.as_ref())
+        .filter(|camera| camera.trigger_type == CameraTriggerType::Manual)
+        .count() as u32;
+    let max_footprint_m = legs.iter().map(footprint_m).fold(0.0, f64::max);
+
+    SurveyPhotoStats {
+        estimated_photo_count: legs.iter().map(TriggeredLeg::photo_count).sum::<u32>() + manual_shots,
+        coverage_area_km2: legs.iter()
+            .map(|leg| leg.length_m * footprint_m(leg))
+            .sum::<f64>() / 1_000_000.0,
+        required_image_width_px: (max_footprint_m * 100.0 / gsd_cm).ceil() as u32,
+    }
+}
+
+fn photo_legs(
+    items: &[MissionItem],
+    sensor_width_mm: f64,
+    focal_length_mm: f64,
+    image_width_px: u32,
+) -> Vec<LegPhotoCoverage> {
+    triggered_legs(items)
+        .into_iter()
+        .map(|leg| {
+            let footprint_m = sensor_width_mm * leg.altitude_m / focal_length_mm;
+            LegPhotoCoverage {
+                from_item_id: leg.from.id.clone(),
+                to_item_id: leg.to.id.clone(),
+                altitude_m: leg.altitude_m,
+                gsd_cm: footprint_m * 100.0 / image_width_px as f64,
+                trigger_distance_m: leg.spacing_m,
+                photo_count: leg.photo_count(),
+                forward_overlap_pct: leg.spacing_m
+                    .filter(|_| footprint_m > 0.0)
+                    .map(|d| ((1.0 - d / footprint_m) * 100.0).max(0.0)),
+            }
+        })
+        .collect()
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+fn triggered_legs(items: &[MissionItem]) -> Vec<TriggeredLeg<'_>> {
+    let mut legs = Vec::new();
+    let mut trigger: Option<&CameraParams> = None;
+    let mut previous: Option<&MissionItem> = None;
+
+    for item in items {
+        // Camera triggers have no position; distance and time triggers change spacing
+        // for the legs that follow, manual shots leave it unchanged
+        if let Some(camera) = item.camera_params.as_ref().filter(|_| item.is_camera_trigger()) {
+            if camera.trigger_type != CameraTriggerType::Manual {
+                trigger = Some(camera);
+            }
+            continue;
+        }
+        if !item.is_on_flight_path() {
+            continue;
+        }
+
+        if let Some(from) = previous {
+            let speed_ms = item.params.speed
+                .filter(|speed| *speed > 0.0)
+                .unwrap_or(DEFAULT_CRUISE_SPEED_MS);
+            let spacing_m = trigger.and_then(|camera| match camera.trigger_type {
+                CameraTriggerType::Distance => camera.distance_m,
+                CameraTriggerType::Time => camera.interval_s.map(|interval| interval * speed_ms),
+                CameraTriggerType::Manual => None,
+            });
+            legs.push(TriggeredLeg {
+                from,
+                to: item,
+                length_m: distance_m(&from.location(), &item.location()),
+                altitude_m: (from.params.alt + item.params.alt) / 2.0,
+                spacing_m: spacing_m.filter(|spacing| *spacing > 0.0),
+            });
+        }
+        previous = Some(item);
+    }
+    legs
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+fn compute_mission_diff(a: &[MissionItem], b: &[MissionItem]) -> MissionDiff {
+    let mut matched_a = vec![false; a.len()];
+    let mut matched_b = vec![false; b.len()];
+    let mut pairs: Vec<(usize, usize, &str)> = Vec::new();
+
+    // Match by ID first
+    for (i, item) in a.iter().enumerate() {
+        if let Some(j) = b.iter().position(|other| other.id == item.id) {
+            if !matched_b[j] {
+                matched_a[i] = true;
+                matched_b[j] = true;
+                pairs.push((i, j, "id"));
+            }
+        }
+    }
+
+    // Fall back to positional matching for items whose IDs don't align
+    for i in 0..a.len().min(b.len()) {
+        if !matched_a[i] && !matched_b[i] {
+            matched_a[i] = true;
+            matched_b[i] = true;
+            pairs.push((i, i, "position"));
+        }
+    }
+    pairs.sort_by_key(|&(i, _, _)| i);
+
+    let mut modified = Vec::new();
+    let mut unchanged_count = 0;
+    for (i, j, matched_by) in pairs {
+        match diff_items(&a[i], &b[j], i, j, matched_by) {
+            Some(diff) => modified.push(diff),
+            None => unchanged_count += 1,
+        }
+    }
+
+    MissionDiff {
+        added: collect_unmatched(b, &matched_b),
+        removed: collect_unmatched(a, &matched_a),
+        modified,
+        unchanged_count,
+    }
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+fn diff_items(
+    a: &MissionItem,
+    b: &MissionItem,
+    index_a: usize,
+    index_b: usize,
+    matched_by: &str,
+) -> Option<ItemDiff> {
+    let mut changed_fields = Vec::new();
+
+    if a.item_type != b.item_type {
+        changed_fields.push("type".to_string());
+    }
+    if a.name != b.name {
+        changed_fields.push("name".to_string());
+    }
+    if a.params.action != b.params.action {
+        changed_fields.push("action".to_string());
+    }
+    if a.position.is_some() != b.position.is_some() {
+        changed_fields.push("position".to_string());
+    }
+
+    let horizontal_delta_m = distance_m(&a.location(), &b.location());
+    if horizontal_delta_m > HORIZONTAL_TOLERANCE_M {
+        changed_fields.push("lat_lng".to_string());
+    }
+
+    let alt_delta_m = b.params.alt - a.params.alt;
+    if alt_delta_m.abs() > VALUE_TOLERANCE {
+        changed_fields.push("alt".to_string());
+    }
+
+    let speed_delta = match (a.params.speed, b.params.speed) {
+        (Some(speed_a), Some(speed_b)) => Some(speed_b - speed_a),
+        _ => None,
+    };
+    let speed_changed = match speed_delta {
+        Some(delta) => delta.abs() > VALUE_TOLERANCE,
+        None => a.params.speed.is_some() != b.params.speed.is_some(),
+    };
+    if speed_changed {
+        changed_fields.push("speed".to_string());
+    }
+
+    if changed_fields.is_empty() {
+        return None;
+    }
+
+    Some(ItemDiff {
+        id_a: a.id.clone(),
+        id_b: b.id.clone(),
+        index_a,
+        index_b,
+        matched_by: matched_by.to_string(),
+        changed_fields,
+        horizontal_delta_m,
+        alt_delta_m,
+        speed_delta,
+    })
+}
+
+fn collect_unmatched(items: &[MissionItem], matched: &[bool]) -> Vec<MissionItem> {
+    items.iter()
+        .zip(matched)
+        .filter(|(_, &is_matched)| !is_matched)
+        .map(|(item, _)| item.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_mission_stats_match_hand_haversine() {
+        let items = crate::initialize_mission_data();
+        let sequence = expand_jumps(&items, None).unwrap();
+        let stats = compute_mission_stats(&sequence, DEFAULT_CRUISE_SPEED_MS, DEFAULT_MAX_FLIGHT_TIME_MIN);
+
+        // (37.7749, -122.4194) to (37.7849, -122.4094) on a 6371 km sphere
+        let expected_km = 1.417_325;
+        assert!((stats.total_distance_km - expected_km).abs() < 0.001);
+        // The leg is flown at the waypoint's 10 m/s
+        assert!((stats.estimated_time_min - expected_km * 1000.0 / 10.0 / 60.0).abs() < 0.01);
+        assert_eq!(stats.waypoint_count, 2);
+        assert_eq!(stats.max_altitude_m, 150.0);
+        assert_eq!(stats.altitude_gain_m, 50.0);
+        assert!(!stats.battery_warning);
+    }
+
+    // Default takeoff and waypoint followed by a landing at the waypoint
+    fn valid_mission() -> Vec<MissionItem> {
+        let mut items = crate::initialize_mission_data();
+        let land = MissionItem {
+            id: "mission-3".to_string(),
+            item_type: "land".to_string(),
+            name: "Land".to_string(),
+            ..items[1].clone()
+        };
+        items.push(land);
+        items
+    }
+
+    fn single_error_rule(items: &[MissionItem]) -> String {
+        let report = mission_validation_report(items, false, DEFAULT_MAX_LEG_KM, DEFAULT_MAX_ROI_DISTANCE_M);
+        assert!(!report.valid);
+        assert_eq!(report.errors.len(), 1, "{:?}", report.errors);
+        report.errors[0].rule.clone()
+    }
+
+    #[test]
+    fn valid_mission_has_no_findings() {
+        let report = mission_validation_report(&valid_mission(), false, DEFAULT_MAX_LEG_KM, DEFAULT_MAX_ROI_DISTANCE_M);
+        assert!(report.valid);
+        assert!(report.errors.is_empty());
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn each_broken_rule_yields_one_error() {
+        let mut items = valid_mission();
+        items.remove(0);
+        assert_eq!(single_error_rule(&items), "takeoff_first");
+
+        let mut items = valid_mission();
+        items[1].params.lat += 1.0;
+        items[2].params.lat += 1.0;
+        assert_eq!(single_error_rule(&items), "max_leg_distance");
+
+        let mut items = valid_mission();
+        items[1].params.alt = 0.0;
+        assert_eq!(single_error_rule(&items), "positive_altitude");
+
+        let mut items = valid_mission();
+        items[1].params.speed = Some(-2.0);
+        assert_eq!(single_error_rule(&items), "positive_speed");
+
+        let mut items = valid_mission();
+        items[2].id = items[1].id.clone();
+        assert_eq!(single_error_rule(&items), "unique_id");
+    }
+
+    #[test]
+    fn missing_landing_is_only_a_warning() {
+        let mut items = valid_mission();
+        items.pop();
+        let report = mission_validation_report(&items, false, DEFAULT_MAX_LEG_KM, DEFAULT_MAX_ROI_DISTANCE_M);
+        assert!(report.valid);
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].rule, "ends_with_land_or_rtl");
+    }
+
+    #[test]
+    fn time_loiter_without_duration_is_a_warning() {
+        let mut items = valid_mission();
+        let waypoint = items[1].clone();
+        let loiter = |id: &str, duration_s: Option<f64>| MissionItem {
+            id: id.to_string(),
+            item_type: "loiter_time".to_string(),
+            loiter_params: Some(crate::LoiterParams {
+                loiter_type: LoiterType::Time,
+                radius_m: 50.0,
+                duration_s,
+                turns: None,
+                clockwise: true,
+            }),
+            ..waypoint.clone()
+        };
+        items.insert(2, loiter("untimed", None));
+        items.insert(3, loiter("timed", Some(30.0)));
+
+        let report = mission_validation_report(&items, false, DEFAULT_MAX_LEG_KM, DEFAULT_MAX_ROI_DISTANCE_M);
+        assert!(report.valid);
+        assert_eq!(report.warnings.len(), 1, "{:?}", report.warnings);
+        assert_eq!(report.warnings[0].rule, "loiter_duration");
+        assert_eq!(report.warnings[0].item_id, "untimed");
+    }
+
+    #[test]
+    fn survey_photos_for_a_straight_kilometre() {
+        let start = &crate::initialize_mission_data()[0];
+        let end = crate::geo::offset_coordinate(&start.location(), 1000.0, 0.0);
+        let at = |id: &str, item_type: &str, lat: f64, lng: f64| MissionItem {
+            id: id.to_string(),
+            item_type: item_type.to_string(),
+            params: crate::WaypointParams { lat, lng, alt: 50.0, ..start.params.clone() },
+            position: Some(crate::Position { lat, lng, alt: 50.0 }),
+            ..start.clone()
+        };
+        let trigger = MissionItem {
+            position: None,
+            camera_params: Some(CameraParams {
+                trigger_type: CameraTriggerType::Distance,
+                distance_m: Some(30.0),
+                interval_s: None,
+                shutter_ms: None,
+                aperture: None,
+                iso: None,
+            }),
+            ..at("camera", "camera_trigger", 0.0, 0.0)
+        };
+        let items = vec![
+            at("start", "takeoff", start.params.lat, start.params.lng),
+            trigger,
+            at("end", "waypoint", end.lat, end.lng),
+        ];
+
+        // 13.2 mm sensor behind an 8.8 mm lens sees 75 m of ground from 50 m
+        let stats = survey_photo_stats(&items, 13.2, 8.8, 2.0);
+        assert_eq!(stats.estimated_photo_count, 33);
+        assert!((stats.coverage_area_km2 - 0.075).abs() < 1e-6);
+        assert_eq!(stats.required_image_width_px, 3750);
+    }
+}
@@ -0,0 +1,894 @@
+// Mission editing backend
+// NASA JPL Power of 10 compliant implementation
+
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, State};
+
+use crate::geo::{
+    bearing_deg, distance_m, is_valid_coordinate, local_offset_m, offset_coordinate, rotate_offset,
+    turn_angle_deg,
+};
+use crate::geofence::{geofence_ceiling, GeofenceState};
+use crate::map_features::Coordinate;
+use crate::{generate_mission_item_id, AppState, MissionItem, Position, WaypointParams};
+
+// Old and new name of an item changed by `renumber_waypoints`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenamedItem {
+    pub item_id: String,
+    pub old_name: String,
+    pub new_name: String,
+}
+
+// Prefix of waypoint names generated by the mission planner
+const DEFAULT_WAYPOINT_PREFIX: &str = "Waypoint";
+// Altitude range accepted by bulk altitude edits; a geofence ceiling lowers the top
+const MIN_BULK_ALTITUDE_M: f64 = 0.0;
+const MAX_BULK_ALTITUDE_M: f64 = 10_000.0;
+
+// ===== ORDERING =====
+
+#[tauri::command]
+pub async fn reverse_mission(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<MissionItem>, String> {
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+
+    // A leading takeoff and trailing land/RTL keep their places
+    let start = usize::from(items.first().map_or(false, MissionItem::is_takeoff));
+    let end = match items.last() {
+        Some(last) if last.is_terminal() && items.len() > start => items.len() - 1,
+        _ => items.len(),
+    };
+    crate::mission_history::record_snapshot(&state, &items, "Reverse mission")?;
+    items[start..end].reverse();
+
+    renumber_default_names(&mut items, DEFAULT_WAYPOINT_PREFIX);
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+
+    Ok(items.clone())
+}
+
+// ===== BULK OPERATIONS =====
+
+// Each bulk command is recorded as a single undo step
+
+#[tauri::command]
+pub async fn bulk_delete_mission_items(
+    app_handle: tauri::AppHandle,
+    ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<u32, String> {
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let count = delete_items(&state, &mut items, &ids)?;
+    if count > 0 {
+        crate::notify_mission_changed(&app_handle, &state, &items)?;
+    }
+    Ok(count)
+}
+
+fn delete_items(state: &AppState, items: &mut Vec<MissionItem>, ids: &[String]) -> Result<u32, String> {
+    let count = items.iter().filter(|item| ids.contains(&item.id)).count();
+    if count == 0 {
+        return Ok(0);
+    }
+
+    let remaining: Vec<MissionItem> = items.iter().filter(|item| !ids.contains(&item.id)).cloned().collect();
+    crate::mission_analysis::enforce_structure(state, items, &remaining)?;
+    crate::mission_history::record_snapshot(state, items, "Delete mission items")?;
+    *items = remaining;
+    Ok(count as u32)
+}
+
+// Whether the items were cleared, as one undo step
+fn clear_items(state: &AppState, items: &mut Vec<MissionItem>, confirm: bool) -> Result<bool, String> {
+    if !confirm || items.is_empty() {
+        return Ok(false);
+    }
+    crate::mission_history::record_snapshot(state, items, "Clear mission")?;
+    items.clear();
+    Ok(true)
+}
+
+// Remove every item, but only with `confirm`; without it, report how many
+// items would go and change nothing. Starting over is always allowed, so the
+// structure rules are not enforced here.
+#[tauri::command]
+pub async fn clear_mission(
+    app_handle: tauri::AppHandle,
+    confirm: bool,
+    state: State<'_, AppState>,
+) -> Result<u32, String> {
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let count = items.len() as u32;
+    if !clear_items(&state, &mut items, confirm)? {
+        return Ok(count);
+    }
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+    app_handle
+        .emit_all("mission-cleared", serde_json::json!({ "count": count }))
+        .map_err(|e| format!("Failed to emit mission clear: {e}"))?;
+
+    Ok(count)
+}
+
+// Fails without changes, listing the offending ids, if any result leaves the
+// range from 0 m to the ceiling
+#[tauri::command]
+pub async fn bulk_update_altitude(
+    app_handle: tauri::AppHandle,
+    ids: Vec<String>,
+    delta_m: f64,
+    state: State<'_, AppState>,
+) -> Result<u32, String> {
+    if !delta_m.is_finite() {
+        return Err("Altitude offset must be a finite number".to_string());
+    }
+
+    let ceiling_m = altitude_ceiling_m(&app_handle)?;
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let out_of_range = altitude_violations(&items, &ids, delta_m, ceiling_m);
+    if !out_of_range.is_empty() {
+        return Err(altitude_range_error(ceiling_m, &out_of_range));
+    }
+
+    let count = items.iter().filter(|item| ids.contains(&item.id)).count();
+    if count == 0 {
+        return Ok(0);
+    }
+    crate::mission_history::record_snapshot(&state, &items, "Change altitude")?;
+    for item in items.iter_mut().filter(|item| ids.contains(&item.id)) {
+        offset_altitude(item, delta_m);
+    }
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+
+    Ok(count as u32)
+}
+
+#[tauri::command]
+pub async fn bulk_update_speed(
+    app_handle: tauri::AppHandle,
+    ids: Vec<String>,
+    speed_mps: f64,
+    state: State<'_, AppState>,
+) -> Result<u32, String> {
+    if !(speed_mps.is_finite() && speed_mps > 0.0) {
+        return Err("Speed must be a positive number".to_string());
+    }
+
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let count = items.iter().filter(|item| ids.contains(&item.id)).count();
+    if count == 0 {
+        return Ok(0);
+    }
+
+    crate::mission_history::record_snapshot(&state, &items, "Change speed")?;
+    for item in items.iter_mut().filter(|item| ids.contains(&item.id)) {
+        item.params.speed = Some(speed_mps);
+    }
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+
+    Ok(count as u32)
+}
+
+// Offset (`alt_delta`) or set (`alt_absolute`) altitude and/or set speed on many
+// items at once. Everything is validated first; on failure nothing changes and
+// the error lists the offending ids.
+#[tauri::command]
+pub async fn adjust_mission_items(
+    app_handle: tauri::AppHandle,
+    item_ids: Vec<String>,
+    alt_delta: Option<f64>,
+    alt_absolute: Option<f64>,
+    speed: Option<f64>,
+    state: State<'_, AppState>,
+) -> Result<u32, String> {
+    if alt_delta.is_some() && alt_absolute.is_some() {
+        return Err("Specify either an altitude offset or an absolute altitude, not both".to_string());
+    }
+    if alt_delta.is_none() && alt_absolute.is_none() && speed.is_none() {
+        return Err("No adjustment specified".to_string());
+    }
+    if alt_delta.or(alt_absolute).map_or(false, |alt| !alt.is_finite()) {
+        return Err("Altitude must be a finite number".to_string());
+    }
+    if let Some(speed_mps) = speed {
+        if !(speed_mps.is_finite() && speed_mps > 0.0) {
+            return Err("Speed must be a positive number".to_string());
+        }
+    }
+
+    let ceiling_m = altitude_ceiling_m(&app_handle)?;
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let missing: Vec<&str> = item_ids.iter()
+        .filter(|id| !items.iter().any(|item| &item.id == *id))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("Mission items not found: {}", missing.join(", ")));
+    }
+
+    let out_of_range = match (alt_delta, alt_absolute) {
+        (Some(delta_m), _) => altitude_violations(&items, &item_ids, delta_m, ceiling_m),
+        (_, Some(alt)) if !altitude_in_range(alt, ceiling_m) => item_ids.iter().map(String::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if !out_of_range.is_empty() {
+        return Err(altitude_range_error(ceiling_m, &out_of_range));
+    }
+
+    let count = items.iter().filter(|item| item_ids.contains(&item.id)).count();
+    if count == 0 {
+        return Ok(0);
+    }
+    crate::mission_history::record_snapshot(&state, &items, "Adjust mission items")?;
+    for item in items.iter_mut().filter(|item| item_ids.contains(&item.id)) {
+        match (alt_delta, alt_absolute) {
+            (Some(delta_m), _) => offset_altitude(item, delta_m),
+            (_, Some(alt)) => set_altitude(item, alt),
+            _ => {}
+        }
+        if speed.is_some() {
+            item.params.speed = speed;
+        }
+    }
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+
+    Ok(count as u32)
+}
+
+// ===== DUPLICATE / COPY / PASTE =====
+
+// Clone an item just after itself, shifted by the given degrees to avoid overlap
+#[tauri::command]
+pub async fn duplicate_mission_item(
+    app_handle: tauri::AppHandle,
+    item_id: String,
+    offset_lat: f64,
+    offset_lng: f64,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let index = items.iter().position(|item| item.id == item_id)
+        .ok_or_else(|| format!("Mission item {} not found", item_id))?;
+
+    let mut copy = items[index].clone();
+    copy.id = generate_mission_item_id();
+    copy.params.lat += offset_lat;
+    copy.params.lng += offset_lng;
+    if let Some(position) = copy.position.as_mut() {
+        position.lat += offset_lat;
+        position.lng += offset_lng;
+    }
+    if !is_valid_coordinate(&copy.location()) {
+        return Err("Offset moves the duplicate outside valid coordinates".to_string());
+    }
+
+    crate::mission_history::record_snapshot(&state, &items, "Duplicate mission item")?;
+    let copy_id = copy.id.clone();
+    items.insert(index + 1, copy);
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+
+    Ok(copy_id)
+}
+
+// Snapshot items (in mission order) so later edits don't affect what gets pasted
+#[tauri::command]
+pub async fn copy_mission_items(
+    ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    copy_items(&state, &items, &ids)
+}
+
+fn copy_items(state: &AppState, items: &[MissionItem], ids: &[String]) -> Result<(), String> {
+    let copied: Vec<MissionItem> = items.iter()
+        .filter(|item| ids.contains(&item.id))
+        .cloned()
+        .collect();
+    if copied.is_empty() {
+        return Err("None of the selected items exist".to_string());
+    }
+
+    *state.clipboard.lock().map_err(|_| "Failed to lock clipboard")? = Some(copied);
+    Ok(())
+}
+
+// Insert the clipboard with fresh ids after `insert_after_id` (or at the end)
+#[tauri::command]
+pub async fn paste_mission_items(
+    app_handle: tauri::AppHandle,
+    insert_after_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let new_ids = paste_items(&state, &mut items, insert_after_id.as_deref())?;
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+
+    Ok(new_ids)
+}
+
+fn paste_items(
+    state: &AppState,
+    items: &mut Vec<MissionItem>,
+    insert_after_id: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let mut pasted = state.clipboard.lock()
+        .map_err(|_| "Failed to lock clipboard")?
+        .clone()
+        .ok_or("Clipboard is empty")?;
+
+    let insert_at = match insert_after_id {
+        Some(id) => items.iter().position(|item| item.id == id)
+            .ok_or_else(|| format!("Mission item {} not found", id))? + 1,
+        None => items.len(),
+    };
+    for item in pasted.iter_mut() {
+        item.id = generate_mission_item_id();
+    }
+    let new_ids: Vec<String> = pasted.iter().map(|item| item.id.clone()).collect();
+
+    crate::mission_history::record_snapshot(state, items, "Paste mission items")?;
+    items.splice(insert_at..insert_at, pasted);
+    Ok(new_ids)
+}
+
+// ===== TRANSLATION =====
+
+#[tauri::command]
+pub async fn translate_mission(
+    app_handle: tauri::AppHandle,
+    delta_north_m: f64,
+    delta_east_m: f64,
+    delta_alt_m: f64,
+    state: State<'_, AppState>,
+) -> Result<Vec<MissionItem>, String> {
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+
+    let translated = translate_items(&items, delta_north_m, delta_east_m, delta_alt_m)?;
+    crate::mission_history::record_snapshot(&state, &items, "Translate mission")?;
+    *items = translated;
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+
+    Ok(items.clone())
+}
+
+#[tauri::command]
+pub async fn move_mission_to(
+    app_handle: tauri::AppHandle,
+    anchor_item_id: String,
+    new_position: Coordinate,
+    state: State<'_, AppState>,
+) -> Result<Vec<MissionItem>, String> {
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+
+    let anchor = items.iter()
+        .find(|item| item.id == anchor_item_id)
+        .ok_or_else(|| format!("Mission item {} not found", anchor_item_id))?
+        .location();
+    let (delta_north_m, delta_east_m) = local_offset_m(&anchor, &new_position);
+    let delta_alt_m = match (new_position.alt, anchor.alt) {
+        (Some(target), Some(current)) => target - current,
+        _ => 0.0,
+    };
+
+    let translated = translate_items(&items, delta_north_m, delta_east_m, delta_alt_m)?;
+    crate::mission_history::record_snapshot(&state, &items, "Move mission")?;
+    *items = translated;
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+
+    Ok(items.clone())
+}
+
+// Shift every item; fails without modifying anything if a result is out of range
+fn translate_items(
+    items: &[MissionItem],
+    delta_north_m: f64,
+    delta_east_m: f64,
+    delta_alt_m: f64,
+) -> Result<Vec<MissionItem>, String> {
+    if !(delta_north_m.is_finite() && delta_east_m.is_finite() && delta_alt_m.is_finite()) {
+        return Err("Translation offsets must be finite numbers".to_string());
+    }
+
+    let mut translated = items.to_vec();
+    for item in translated.iter_mut() {
+        let moved = offset_coordinate(&item.location(), delta_north_m, delta_east_m);
+        if !is_valid_coordinate(&moved) {
+            return Err(format!("Translation moves item {} outside valid coordinates", item.id));
+        }
+        item.params.lat = moved.lat;
+        item.params.lng = moved.lng;
+        item.params.alt += delta_alt_m;
+
+        if let Some(position) = item.position.as_mut() {
+            let origin = Coordinate { lat: position.lat, lng: position.lng, alt: None };
+            let moved = offset_coordinate(&origin, delta_north_m, delta_east_m);
+            if !is_valid_coordinate(&moved) {
+                return Err(format!("Translation moves item {} outside valid coordinates", item.id));
+            }
+            *position = Position {
+                lat: moved.lat,
+                lng: moved.lng,
+                alt: position.alt + delta_alt_m,
+            };
+        }
+    }
+
+    Ok(translated)
+}
+
+// ===== TRANSFORMATION =====
+
+#[tauri::command]
+pub async fn transform_mission(
+    app_handle: tauri::AppHandle,
+    rotate_deg: f64,
+    scale: f64,
+    pivot: Option<Coordinate>,
+    state: State<'_, AppState>,
+) -> Result<Vec<MissionItem>, String> {
+    if !rotate_deg.is_finite() {
+        return Err("Rotation must be a finite number".to_string());
+    }
+    if !(scale.is_finite() && scale > 0.0) {
+        return Err("Scale must be a positive number".to_string());
+    }
+
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let pivot = match pivot {
+        Some(pivot) => pivot,
+        None => positioned_centroid(&items)
+            .ok_or("Mission has no positioned items to transform")?,
+    };
+
+    // Rotate/scale a copy so an out-of-range point leaves the mission untouched
+    let mut transformed = items.clone();
+    for item in transformed.iter_mut().filter(|item| item.position.is_some()) {
+        let (north_m, east_m) = local_offset_m(&pivot, &item.location());
+        let (north_m, east_m) = rotate_offset(north_m * scale, east_m * scale, rotate_deg);
+        let moved = offset_coordinate(&pivot, north_m, east_m);
+        if !is_valid_coordinate(&moved) {
+            return Err(format!("Transform moves item {} outside valid coordinates", item.id));
+        }
+
+        item.params.lat = moved.lat;
+        item.params.lng = moved.lng;
+        if let Some(position) = item.position.as_mut() {
+            position.lat = moved.lat;
+            position.lng = moved.lng;
+        }
+    }
+
+    crate::mission_history::record_snapshot(&state, &items, "Transform mission")?;
+    *items = transformed;
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+
+    Ok(items.clone())
+}
+
+// Mean location of positioned items, averaged on the local tangent plane
+fn positioned_centroid(items: &[MissionItem]) -> Option<Coordinate> {
+    let positioned: Vec<Coordinate> = items.iter()
+        .filter(|item| item.is_on_flight_path())
+        .map(MissionItem::location)
+        .collect();
+    let origin = positioned.first()?;
+
+    let (sum_north, sum_east) = positioned.iter()
+        .map(|coord| local_offset_m(origin, coord))
+        .fold((0.0, 0.0), |(n, e), (dn, de)| (n + dn, e + de));
+    let count = positioned.len() as f64;
+    Some(offset_coordinate(origin, sum_north / count, sum_east / count))
+}
+
+// ===== DEDUPLICATION =====
+
+// Merge runs of consecutive same-type items within `tolerance_m` horizontally and
+// `alt_delta_m` vertically into their first item (altitude averaged). Returns the
+// removed ids; with `dry_run` the mission is left untouched.
+#[tauri::command]
+pub async fn dedupe_waypoints(
+    app_handle: tauri::AppHandle,
+    tolerance_m: f64,
+    alt_delta_m: f64,
+    dry_run: bool,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    if !(tolerance_m >= 0.0 && alt_delta_m >= 0.0) {
+        return Err("Tolerances must be non-negative".to_string());
+    }
+
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let (deduped, removed) = merge_near_duplicates(&items, tolerance_m, alt_delta_m);
+
+    if !dry_run && !removed.is_empty() {
+        crate::mission_history::record_snapshot(&state, &items, "Merge duplicate waypoints")?;
+        *items = deduped;
+        crate::notify_mission_changed(&app_handle, &state, &items)?;
+    }
+    Ok(removed)
+}
+
+fn merge_near_duplicates(
+    items: &[MissionItem],
+    tolerance_m: f64,
+    alt_delta_m: f64,
+) -> (Vec<MissionItem>, Vec<String>) {
+    let mut kept: Vec<MissionItem> = Vec::with_capacity(items.len());
+    let mut removed = Vec::new();
+    // Altitudes of the current run, averaged into its first item
+    let mut run_alts: Vec<f64> = Vec::new();
+
+    for item in items {
+        let duplicate = kept.last().map_or(false, |first| {
+            first.is_on_flight_path()
+                && item.is_on_flight_path()
+                && first.item_type == item.item_type
+                && distance_m(&first.location(), &item.location()) <= tolerance_m
+                && (first.params.alt - item.params.alt).abs() <= alt_delta_m
+        });
+
+        if duplicate {
+            removed.push(item.id.clone());
+            run_alts.push(item.params.alt);
+            if let Some(first) = kept.last_mut() {
+                set_altitude(first, run_alts.iter().sum::<f64>() / run_alts.len() as f64);
+            }
+        } else {
+            run_alts.clear();
+            run_alts.push(item.params.alt);
+            kept.push(item.clone());
+        }
+    }
+
+    (kept, removed)
+}
+
+// Top of the bulk altitude range: the geofence ceiling when one is configured
+fn altitude_ceiling_m(app_handle: &tauri::AppHandle) -> Result<f64, String> {
+    let ceiling_m = geofence_ceiling(&app_handle.state::<GeofenceState>())?;
+    Ok(ceiling_m.map_or(MAX_BULK_ALTITUDE_M, |ceiling_m| ceiling_m.min(MAX_BULK_ALTITUDE_M)))
+}
+
+fn altitude_in_range(alt: f64, ceiling_m: f64) -> bool {
+    (MIN_BULK_ALTITUDE_M..=ceiling_m).contains(&alt)
+}
+
+// Ids of the items among `ids` whose params or position altitude would leave
+// the range from 0 m to `ceiling_m` after an offset of `delta_m`
+fn altitude_violations<'a>(items: &'a [MissionItem], ids: &[String], delta_m: f64, ceiling_m: f64) -> Vec<&'a str> {
+    items.iter()
+        .filter(|item| ids.contains(&item.id))
+        .filter(|item| {
+            !altitude_in_range(item.params.alt + delta_m, ceiling_m)
+                || item.position.as_ref().map_or(false, |position| !altitude_in_range(position.alt + delta_m, ceiling_m))
+        })
+        .map(|item| item.id.as_str())
+        .collect()
+}
+
+fn altitude_range_error(ceiling_m: f64, ids: &[&str]) -> String {
+    format!("Altitude would leave {MIN_BULK_ALTITUDE_M}-{ceiling_m} m for items: {}", ids.join(", "))
+}
+
+// Shift both altitudes, which need not match, as `translate_items` does
+fn offset_altitude(item: &mut MissionItem, delta_m: f64) {
+    item.params.alt += delta_m;
+    if let Some(position) = item.position.as_mut() {
+        position.alt += delta_m;
+    }
+}
+
+fn set_altitude(item: &mut MissionItem, alt: f64) {
+    item.params.alt = alt;
+    if let Some(position) = item.position.as_mut() {
+        position.alt = alt;
+    }
+}
+
+// ===== REGION OF INTEREST =====
+
+// Point the camera at `roi` from `start_item_id` through `end_item_id` by
+// inserting an ROI item before the range and an ROI cancel after it
+#[tauri::command]
+pub async fn set_roi_for_range(
+    app_handle: tauri::AppHandle,
+    start_item_id: String,
+    end_item_id: String,
+    roi: Coordinate,
+    state: State<'_, AppState>,
+) -> Result<Vec<MissionItem>, String> {
+    if !is_valid_coordinate(&roi) {
+        return Err("ROI must be a valid coordinate".to_string());
+    }
+
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let start = items.iter().position(|item| item.id == start_item_id)
+        .ok_or_else(|| format!("Mission item {} not found", start_item_id))?;
+    let end = items.iter().position(|item| item.id == end_item_id)
+        .ok_or_else(|| format!("Mission item {} not found", end_item_id))?;
+    if end < start {
+        return Err("ROI range end must not come before its start".to_string());
+    }
+
+    crate::mission_history::record_snapshot(&state, &items, "Set region of interest")?;
+    // Insert the cancel first so `start` stays valid
+    items.insert(end + 1, roi_item("roi_cancel", "Cancel ROI", None));
+    items.insert(start, roi_item("roi", "ROI", Some(&roi)));
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+
+    Ok(items.clone())
+}
+
+fn roi_item(item_type: &str, name: &str, target: Option<&Coordinate>) -> MissionItem {
+    let (lat, lng, alt) = target.map_or((0.0, 0.0, 0.0), |t| (t.lat, t.lng, t.alt.unwrap_or(0.0)));
+    MissionItem {
+        id: generate_mission_item_id(),
+        item_type: item_type.to_string(),
+        name: name.to_string(),
+        params: WaypointParams {
+            lat,
+            lng,
+            alt,
+            speed: None,
+            action: None,
+            target_item_id: None,
+            repeat_count: None,
+            spline: false,
+            delay_s: None,
+        },
+        position: target.map(|_| Position { lat, lng, alt }),
+        loiter_params: None,
+        camera_params: None,
+        notes: None,
+        tags: Vec::new(),
+    }
+}
+
+// ===== SPEED ASSIGNMENT =====
+
+// Assign `slow` to short legs and legs ending in a sharp turn, `cruise` elsewhere.
+// An item's speed applies to the leg arriving at it. Returns the items that changed.
+#[tauri::command]
+pub async fn auto_assign_speeds(
+    app_handle: tauri::AppHandle,
+    cruise: f64,
+    slow: f64,
+    slow_threshold_m: f64,
+    corner_angle_deg: f64,
+    state: State<'_, AppState>,
+) -> Result<Vec<MissionItem>, String> {
+    if !(cruise > 0.0 && slow > 0.0) {
+        return Err("Speeds must be positive".to_string());
+    }
+    if slow > cruise {
+        return Err("Slow speed cannot exceed cruise speed".to_string());
+    }
+    if !(slow_threshold_m >= 0.0 && (0.0..=180.0).contains(&corner_angle_deg)) {
+        return Err("Threshold must be non-negative and corner angle within 0-180°".to_string());
+    }
+
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    // Items off the flight path (camera triggers, ROIs) have no leg of their own
+    let positioned: Vec<usize> = items.iter()
+        .enumerate()
+        .filter(|(_, item)| item.is_on_flight_path())
+        .map(|(index, _)| index)
+        .collect();
+    let locations: Vec<Coordinate> = positioned.iter().map(|&i| items[i].location()).collect();
+
+    let original = items.clone();
+    let mut changed = Vec::new();
+    for (index, &item_index) in positioned.iter().enumerate() {
+        let item = &mut items[item_index];
+        let short_leg = index > 0
+            && distance_m(&locations[index - 1], &locations[index]) < slow_threshold_m;
+        let sharp_turn = index > 0 && index + 1 < locations.len() && {
+            let bearing_in = bearing_deg(&locations[index - 1], &locations[index]);
+            let bearing_out = bearing_deg(&locations[index], &locations[index + 1]);
+            turn_angle_deg(bearing_in, bearing_out) >= corner_angle_deg
+        };
+
+        let speed = if short_leg || sharp_turn { slow } else { cruise };
+        if item.params.speed != Some(speed) {
+            item.params.speed = Some(speed);
+            changed.push(item.clone());
+        }
+    }
+
+    if !changed.is_empty() {
+        crate::mission_history::record_snapshot(&state, &original, "Assign speeds")?;
+        crate::notify_mission_changed(&app_handle, &state, &items)?;
+    }
+    Ok(changed)
+}
+
+// ===== NAMING =====
+
+// Rename items to "<prefix> <n>" in mission order, counting from `start_at`.
+// Only auto-generated names ("Waypoint 3" or "<prefix> 3") are touched unless
+// `force` is set, which also renames custom-named waypoints. Returns the renames.
+#[tauri::command]
+pub async fn renumber_waypoints(
+    app_handle: tauri::AppHandle,
+    prefix: String,
+    start_at: u32,
+    force: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<RenamedItem>, String> {
+    let prefix = prefix.trim();
+    if prefix.is_empty() {
+        return Err("Waypoint name prefix cannot be empty".to_string());
+    }
+    let force = force.unwrap_or(false);
+
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let original = items.clone();
+    let renamed = renumber_names(&mut items, prefix, start_at, |item| {
+        auto_name_number(&item.name, prefix).is_some()
+            || auto_name_number(&item.name, DEFAULT_WAYPOINT_PREFIX).is_some()
+            || (force && item.item_type == "waypoint")
+    });
+
+    if !renamed.is_empty() {
+        crate::mission_history::record_snapshot(&state, &original, "Renumber waypoints")?;
+        crate::notify_mission_changed(&app_handle, &state, &items)?;
+    }
+    Ok(renamed)
+}
+
+// Number of an auto-generated name such as "Waypoint 3"
+fn auto_name_number(name: &str, prefix: &str) -> Option<u32> {
+    name.strip_prefix(prefix)?
+        .strip_prefix(' ')?
+        .parse()
+        .ok()
+}
+
+// Renumber auto-generated names sequentially in mission order
+fn renumber_default_names(items: &mut [MissionItem], prefix: &str) {
+    renumber_names(items, prefix, 1, |item| auto_name_number(&item.name, prefix).is_some());
+}
+
+// Name the items selected by `rename` "<prefix> <n>" in order, reporting actual changes
+fn renumber_names(
+    items: &mut [MissionItem],
+    prefix: &str,
+    start_at: u32,
+    rename: impl Fn(&MissionItem) -> bool,
+) -> Vec<RenamedItem> {
+    let mut renamed = Vec::new();
+    for (number, item) in (u64::from(start_at)..).zip(items.iter_mut().filter(|item| rename(item))) {
+        let new_name = format!("{prefix} {number}");
+        if item.name != new_name {
+            let old_name = std::mem::replace(&mut item.name, new_name.clone());
+            renamed.push(RenamedItem { item_id: item.id.clone(), old_name, new_name });
+        }
+    }
+    renamed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn waypoints(count: usize) -> Vec<MissionItem> {
+        (0..count)
+            .map(|i| roi_item("waypoint", &format!("Waypoint {}", i + 1), Some(&Coordinate {
+                lat: 37.0 + i as f64 * 0.001,
+                lng: -122.0,
+                alt: Some(50.0 + i as f64),
+            })))
+            .collect()
+    }
+
+    fn item_ids(items: &[MissionItem]) -> Vec<String> {
+        items.iter().map(|item| item.id.clone()).collect()
+    }
+
+    #[test]
+    fn bulk_delete_is_one_undo_step() {
+        let state = AppState::default();
+        let original = waypoints(10);
+        let mut items = original.clone();
+        let doomed: Vec<String> = [1, 3, 4, 7, 9].iter().map(|&i| original[i].id.clone()).collect();
+
+        assert_eq!(delete_items(&state, &mut items, &doomed).unwrap(), 5);
+        let kept: Vec<&MissionItem> = [0, 2, 5, 6, 8].iter().map(|&i| &original[i]).collect();
+        assert_eq!(items.len(), 5);
+        for (item, expected) in items.iter().zip(kept) {
+            assert_eq!(item.id, expected.id);
+            assert_eq!(item.name, expected.name);
+            assert_eq!(item.params.lat, expected.params.lat);
+            assert_eq!(item.params.alt, expected.params.alt);
+        }
+
+        crate::mission_history::apply_undo(&state, &mut items).unwrap();
+        assert_eq!(item_ids(&items), item_ids(&original));
+        assert!(crate::mission_history::apply_undo(&state, &mut items).is_err());
+    }
+
+    #[test]
+    fn paste_uses_values_from_copy_time() {
+        let state = AppState::default();
+        let mut items = waypoints(3);
+        let copied = vec![items[0].id.clone(), items[2].id.clone()];
+        copy_items(&state, &items, &copied).unwrap();
+
+        for item in items.iter_mut() {
+            set_altitude(item, 400.0);
+            item.name = "Edited".to_string();
+        }
+        let last_id = items[2].id.clone();
+        let pasted_ids = paste_items(&state, &mut items, Some(&last_id)).unwrap();
+
+        assert_eq!(items.len(), 5);
+        assert_eq!(item_ids(&items[3..]), pasted_ids);
+        assert!(pasted_ids.iter().all(|id| !copied.contains(id)));
+        assert_eq!(items[3].name, "Waypoint 1");
+        assert_eq!(items[3].params.alt, 50.0);
+        assert_eq!(items[4].name, "Waypoint 3");
+        assert_eq!(items[4].params.alt, 52.0);
+        assert_eq!(items[4].position.as_ref().unwrap().alt, 52.0);
+    }
+
+    #[test]
+    fn clear_needs_confirmation_and_undoes() {
+        let state = AppState::default();
+        let original = waypoints(4);
+        let mut items = original.clone();
+
+        assert!(!clear_items(&state, &mut items, false).unwrap());
+        assert_eq!(item_ids(&items), item_ids(&original));
+
+        assert!(clear_items(&state, &mut items, true).unwrap());
+        assert!(items.is_empty());
+        crate::mission_history::apply_undo(&state, &mut items).unwrap();
+        assert_eq!(item_ids(&items), item_ids(&original));
+    }
+
+    #[test]
+    fn bulk_offset_shifts_each_altitude_separately() {
+        let mut items = waypoints(3);
+        // Position altitude above home no longer matches the params altitude
+        items[1].position.as_mut().unwrap().alt = 120.0;
+        let ids = item_ids(&items);
+
+        assert!(altitude_violations(&items, &ids, 20.0, MAX_BULK_ALTITUDE_M).is_empty());
+        for item in items.iter_mut() {
+            offset_altitude(item, 20.0);
+        }
+        assert_eq!(items[1].params.alt, 71.0);
+        assert_eq!(items[1].position.as_ref().unwrap().alt, 140.0);
+        assert_eq!(items[2].position.as_ref().unwrap().alt, 72.0);
+
+        // Only the position altitude of item 1 would drop below zero
+        items[1].position.as_mut().unwrap().alt = 10.0;
+        assert_eq!(altitude_violations(&items, &ids, -30.0, MAX_BULK_ALTITUDE_M), [ids[1].as_str()]);
+        assert_eq!(altitude_violations(&items, &ids, -75.0, MAX_BULK_ALTITUDE_M).len(), 3);
+        assert!(altitude_violations(&items, &ids[..1], -30.0, MAX_BULK_ALTITUDE_M).is_empty());
+    }
+
+    #[test]
+    fn altitude_violations_use_the_configured_ceiling() {
+        // Altitudes 50, 51 and 52 m
+        let items = waypoints(3);
+        let ids = item_ids(&items);
+
+        assert!(altitude_violations(&items, &ids, 48.0, 100.0).is_empty());
+        assert_eq!(altitude_violations(&items, &ids, 49.0, 100.0), [ids[2].as_str()]);
+        assert_eq!(altitude_violations(&items, &ids, 50.0, 100.0).len(), 2);
+        assert!(altitude_violations(&items, &ids, 50.0, MAX_BULK_ALTITUDE_M).is_empty());
+
+        assert!(altitude_in_range(100.0, 100.0));
+        assert!(!altitude_in_range(100.5, 100.0));
+        assert!(!altitude_in_range(-0.1, 100.0));
+        assert_eq!(
+            altitude_range_error(100.0, &[ids[2].as_str()]),
+            format!("Altitude would leave 0-100 m for items: {}", ids[2])
+        );
+    }
+}
@@ -0,0 +1,173 @@
+// Mission undo/redo history
+// NASA JPL Power of 10 compliant implementation
+
+use std::collections::VecDeque;
+use tauri::State;
+
+use crate::{AppState, MissionItem};
+
+// ===== TYPE DEFINITIONS =====
+
+// Copy of the active mission taken before an edit
+#[derive(Debug, Clone)]
+pub struct MissionSnapshot {
+    pub items: Vec<MissionItem>,
+    pub description: String,
+}
+
+// Bounded undo history; the oldest snapshot is dropped once full
+#[derive(Debug)]
+pub struct UndoStack {
+    capacity: usize,
+    undo: VecDeque<MissionSnapshot>,
+    redo: VecDeque<MissionSnapshot>,
+}
+
+const DEFAULT_UNDO_CAPACITY: usize = 50;
+
+impl UndoStack {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            undo: VecDeque::with_capacity(capacity),
+            redo: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    // Record the state before a new edit; any redo history is discarded
+    fn push(&mut self, snapshot: MissionSnapshot) {
+        self.redo.clear();
+        push_bounded(&mut self.undo, snapshot, self.capacity);
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+impl Default for UndoStack {
+    fn default() -> Self {
+        Self::new(DEFAULT_UNDO_CAPACITY)
+    }
+}
+
+// ===== UNDO / REDO COMMANDS =====
+
+#[tauri::command]
+pub async fn undo_mission(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<MissionItem>, String> {
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    apply_undo(&state, &mut items)?;
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+    Ok(items.clone())
+}
+
+#[tauri::command]
+pub async fn redo_mission(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<MissionItem>, String> {
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    apply_redo(&state, &mut items)?;
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+    Ok(items.clone())
+}
+
+// ===== HELPERS =====
+
+// Snapshot the active mission before a mutation. Callers hold the
+// `mission_items` lock, so the snapshot matches what they are about to change.
+// Every edit passes through here first, so this also enforces the mission lock
+// and bumps the mission revision.
+pub fn record_snapshot(
+    state: &AppState,
+    items: &[MissionItem],
+    description: &str,
+) -> Result<(), String> {
+    crate::ensure_mission_unlocked(state)?;
+    let mut history = state.undo_stack.lock().map_err(|_| "Failed to lock undo history")?;
+    history.push(MissionSnapshot {
+        items: items.to_vec(),
+        description: description.to_string(),
+    });
+    drop(history);
+    crate::bump_mission_revision(state)?;
+    Ok(())
+}
+
+// Restore the last snapshot into `items`, keeping the replaced items for redo
+pub fn apply_undo(state: &AppState, items: &mut Vec<MissionItem>) -> Result<(), String> {
+    crate::ensure_mission_unlocked(state)?;
+    let mut history = state.undo_stack.lock().map_err(|_| "Failed to lock undo history")?;
+
+    let previous = history.undo.pop_back().ok_or("Nothing to undo")?;
+    let current = MissionSnapshot {
+        items: std::mem::replace(items, previous.items),
+        description: previous.description,
+    };
+    let capacity = history.capacity;
+    push_bounded(&mut history.redo, current, capacity);
+    drop(history);
+    crate::bump_mission_revision(state)?;
+    Ok(())
+}
+
+fn apply_redo(state: &AppState, items: &mut Vec<MissionItem>) -> Result<(), String> {
+    crate::ensure_mission_unlocked(state)?;
+    let mut history = state.undo_stack.lock().map_err(|_| "Failed to lock undo history")?;
+
+    let next = history.redo.pop_back().ok_or("Nothing to redo")?;
+    let current = MissionSnapshot {
+        items: std::mem::replace(items, next.items),
+        description: next.description,
+    };
+    let capacity = history.capacity;
+    push_bounded(&mut history.undo, current, capacity);
+    drop(history);
+    crate::bump_mission_revision(state)?;
+    Ok(())
+}
+
+fn push_bounded(stack: &mut VecDeque<MissionSnapshot>, snapshot: MissionSnapshot, capacity: usize) {
+    if stack.len() >= capacity {
+        stack.pop_front();
+    }
+    stack.push_back(snapshot);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item_ids(items: &[MissionItem]) -> Vec<String> {
+        items.iter().map(|item| item.id.clone()).collect()
+    }
+
+    #[test]
+    fn undo_and_redo_walk_five_additions() {
+        let state = AppState::default();
+        let template = crate::initialize_mission_data().remove(1);
+        let mut items = Vec::new();
+        for i in 0..5 {
+            record_snapshot(&state, &items, "Add mission item").unwrap();
+            items.push(MissionItem { id: format!("wp-{}", i), ..template.clone() });
+        }
+
+        for _ in 0..3 {
+            apply_undo(&state, &mut items).unwrap();
+        }
+        assert_eq!(item_ids(&items), ["wp-0", "wp-1"]);
+
+        apply_redo(&state, &mut items).unwrap();
+        assert_eq!(item_ids(&items), ["wp-0", "wp-1", "wp-2"]);
+
+        // A new edit after undoing discards what was left to redo
+        record_snapshot(&state, &items, "Add mission item").unwrap();
+        items.push(MissionItem { id: "wp-new".to_string(), ..template });
+        assert!(apply_redo(&state, &mut items).is_err());
+        assert_eq!(*state.mission_revision.lock().unwrap(), 10);
+    }
+}
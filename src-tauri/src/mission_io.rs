@@ -0,0 +1,1196 @@
+// Mission import/export backend
+// NASA JPL Power of 10 compliant implementation
+
+use ::mavlink::ardupilotmega::MavCmd;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tauri::State;
+
+use crate::geo::is_valid_coordinate;
+use crate::map_features::Coordinate;
+use crate::{
+    generate_mission_item_id, AppState, CameraParams, CameraTriggerType, LoiterParams, LoiterType,
+    MissionItem, Position, WaypointParams, MISSION_ITEM_TYPES,
+};
+
+// ===== GEOJSON =====
+
+// RFC 7946 FeatureCollection: one Point per item plus the flight path
+#[tauri::command]
+pub async fn export_mission_geojson(
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    serde_json::to_string_pretty(&mission_feature_collection(&items))
+        .map_err(|e| format!("Failed to serialize GeoJSON: {}", e))
+}
+
+// Replace the active mission with the Point features of a FeatureCollection
+#[tauri::command]
+pub async fn import_mission_geojson(
+    app_handle: tauri::AppHandle,
+    geojson: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<MissionItem>, String> {
+    let imported = geojson_to_items(&geojson)?;
+
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    crate::mission_history::record_snapshot(&state, &items, "Import GeoJSON")?;
+    *items = imported;
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+
+    Ok(items.clone())
+}
+
+fn geojson_to_items(geojson: &str) -> Result<Vec<MissionItem>, String> {
+    let document: Value = serde_json::from_str(geojson)
+        .map_err(|e| format!("Invalid GeoJSON: {}", e))?;
+    if document["type"] != "FeatureCollection" {
+        return Err("GeoJSON must be a FeatureCollection".to_string());
+    }
+    let features = document["features"].as_array()
+        .ok_or("FeatureCollection has no features array")?;
+
+    // LineString features are synthesized flight paths and carry no items
+    let imported = features.iter()
+        .filter(|feature| feature["geometry"]["type"] == "Point")
+        .enumerate()
+        .map(|(index, feature)| feature_to_item(index, feature))
+        .collect::<Result<Vec<MissionItem>, String>>()?;
+    if imported.is_empty() {
+        return Err("GeoJSON contains no Point features".to_string());
+    }
+    Ok(imported)
+}
+
+// Build an item from a Point feature; IDs are always freshly generated
+fn feature_to_item(index: usize, feature: &Value) -> Result<MissionItem, String> {
+    let coordinates = feature["geometry"]["coordinates"].as_array()
+        .filter(|c| c.len() == 2 || c.len() == 3)
+        .ok_or_else(|| format!("Point feature {} must have 2 or 3 coordinates", index + 1))?;
+    let values = coordinates.iter()
+        .map(Value::as_f64)
+        .collect::<Option<Vec<f64>>>()
+        .ok_or_else(|| format!("Point feature {} has non-numeric coordinates", index + 1))?;
+    let (lng, lat) = (values[0], values[1]);
+    let alt = values.get(2).copied().unwrap_or(0.0);
+    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lng) {
+        return Err(format!("Point feature {} is outside valid coordinates", index + 1));
+    }
+
+    let properties = &feature["properties"];
+    let item_type = match properties["type"].as_str() {
+        Some(t) if MISSION_ITEM_TYPES.contains(&t) => t.to_string(),
+        other => {
+            eprintln!(
+                "Warning: GeoJSON feature {} has unknown type {:?}, importing as waypoint",
+                index + 1,
+                other
+            );
+            "waypoint".to_string()
+        }
+    };
+
+    Ok(MissionItem {
+        id: generate_mission_item_id(),
+        item_type,
+        name: properties["name"].as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("Waypoint {}", index + 1)),
+        params: WaypointParams {
+            lat,
+            lng,
+            alt,
+            speed: properties["speed"].as_f64(),
+            action: properties["action"].as_str().map(str::to_string),
+            target_item_id: None,
+            repeat_count: None,
+            spline: false,
+            delay_s: None,
+        },
+        position: Some(Position { lat, lng, alt }),
+        loiter_params: None,
+        camera_params: None,
+        notes: properties["notes"].as_str().map(str::to_string),
+        tags: properties["tags"].as_array()
+            .map(|tags| tags.iter().filter_map(Value::as_str).map(str::to_string).collect())
+            .unwrap_or_default(),
+    })
+}
+
+fn mission_feature_collection(items: &[MissionItem]) -> Value {
+    let mut features: Vec<Value> = items.iter().map(item_feature).collect();
+    let positioned: Vec<&MissionItem> = items.iter()
+        .filter(|item| item.is_on_flight_path())
+        .collect();
+    if positioned.len() > 2 {
+        features.push(flight_path_feature(&positioned));
+    }
+
+    json!({
+        "type": "FeatureCollection",
+        "features": features,
+    })
+}
+
+// GeoJSON positions are [longitude, latitude, altitude]
+fn geojson_position(item: &MissionItem) -> Value {
+    json!([item.params.lng, item.params.lat, item.params.alt])
+}
+
+// Items without a position (e.g. camera triggers) get a null geometry
+fn item_feature(item: &MissionItem) -> Value {
+    let geometry = match item.position {
+        Some(_) => json!({
+            "type": "Point",
+            "coordinates": geojson_position(item),
+        }),
+        None => Value::Null,
+    };
+    json!({
+        "type": "Feature",
+        "geometry": geometry,
+        "properties": {
+            "id": item.id,
+            "type": item.item_type,
+            "name": item.name,
+            "speed": item.params.speed,
+            "action": item.params.action,
+            "notes": item.notes,
+            "tags": item.tags,
+        },
+    })
+}
+
+fn flight_path_feature(items: &[&MissionItem]) -> Value {
+    let coordinates: Vec<Value> = items.iter().map(|item| geojson_position(item)).collect();
+    json!({
+        "type": "Feature",
+        "geometry": {
+            "type": "LineString",
+            "coordinates": coordinates,
+        },
+        "properties": {
+            "type": "flight_path",
+        },
+    })
+}
+
+// ===== CSV =====
+
+// Column by header name (case-insensitive) or zero-based index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CsvColumn {
+    Index(usize),
+    Header(String),
+}
+
+// Unmapped columns are detected from the header row
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CsvColumnMapping {
+    pub lat: Option<CsvColumn>,
+    pub lng: Option<CsvColumn>,
+    pub alt: Option<CsvColumn>,
+    pub name: Option<CsvColumn>,
+    pub speed: Option<CsvColumn>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvRowError {
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvImportResult {
+    pub imported: Vec<MissionItem>,
+    pub errors: Vec<CsvRowError>,
+}
+
+// Resolved zero-based column indices
+struct CsvColumns {
+    lat: usize,
+    lng: usize,
+    alt: Option<usize>,
+    name: Option<usize>,
+    speed: Option<usize>,
+}
+
+// NASA JPL Rule 2: bound the number of rows parsed
+const MAX_CSV_ROWS: usize = 10_000;
+
+const LAT_HEADERS: [&str; 3] = ["lat", "latitude", "y"];
+const LNG_HEADERS: [&str; 5] = ["lng", "lon", "long", "longitude", "x"];
+const ALT_HEADERS: [&str; 3] = ["alt", "altitude", "height"];
+const NAME_HEADERS: [&str; 3] = ["name", "label", "title"];
+const SPEED_HEADERS: [&str; 2] = ["speed", "velocity"];
+
+// Append waypoints from a CSV file; bad rows are reported rather than aborting the import
+#[tauri::command]
+pub async fn import_mission_csv(
+    app_handle: tauri::AppHandle,
+    path: String,
+    mapping: CsvColumnMapping,
+    state: State<'_, AppState>,
+) -> Result<CsvImportResult, String> {
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let mut rows = contents.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (first_line, first_row) = rows.next().ok_or("CSV file is empty")?;
+    let first_fields = split_csv_line(first_row);
+    // A header row is one with any non-numeric field
+    let header = first_fields.iter()
+        .any(|field| field.parse::<f64>().is_err())
+        .then(|| first_fields.clone());
+    let columns = resolve_csv_columns(&mapping, header.as_deref())?;
+
+    let data_rows: Vec<(usize, String)> = header.is_none()
+        .then(|| (first_line, first_row.to_string()))
+        .into_iter()
+        .chain(rows.map(|(line, row)| (line, row.to_string())))
+        .take(MAX_CSV_ROWS)
+        .collect();
+    if data_rows.is_empty() {
+        return Err("CSV file contains no data rows".to_string());
+    }
+
+    let mut result = CsvImportResult { imported: Vec::new(), errors: Vec::new() };
+    for (line, row) in &data_rows {
+        match csv_row_to_item(&split_csv_line(row), &columns, result.imported.len() + 1) {
+            Ok(item) => result.imported.push(item),
+            Err(message) => result.errors.push(CsvRowError { line: *line, message }),
+        }
+    }
+    if result.imported.is_empty() {
+        return Ok(result);
+    }
+
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    crate::mission_history::record_snapshot(&state, &items, "Import CSV")?;
+    // Keep an existing takeoff first and a trailing land/RTL last
+    let insert_at = match items.last() {
+        Some(last) if last.is_terminal() && items.iter().any(MissionItem::is_takeoff) => items.len() - 1,
+        _ => items.len(),
+    };
+    items.splice(insert_at..insert_at, result.imported.iter().cloned());
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+
+    Ok(result)
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+fn resolve_csv_columns(
+    mapping: &CsvColumnMapping,
+    header: Option<&[String]>,
+) -> Result<CsvColumns, String> {
+    let resolve = |column: &Option<CsvColumn>, aliases: &[&str]| -> Result<Option<usize>, String> {
+        match (column, header) {
+            (Some(CsvColumn::Index(index)), _) => Ok(Some(*index)),
+            (Some(CsvColumn::Header(name)), Some(header)) => header_index(header, &[name.as_str()])
+                .map(Some)
+                .ok_or_else(|| format!("CSV has no column named {}", name)),
+            (Some(CsvColumn::Header(name)), None) => {
+                Err(format!("CSV has no header row to find column {}", name))
+            }
+            (None, Some(header)) => Ok(header_index(header, aliases)),
+            (None, None) => Ok(None),
+        }
+    };
+
+    // Without a header row, unmapped columns default to lat, lng, alt order
+    let positional = |index: usize| if header.is_none() { Some(index) } else { None };
+    let lat = resolve(&mapping.lat, &LAT_HEADERS)?
+        .or_else(|| positional(0))
+        .ok_or("Could not find a latitude column")?;
+    let lng = resolve(&mapping.lng, &LNG_HEADERS)?
+        .or_else(|| positional(1))
+        .ok_or("Could not find a longitude column")?;
+    let alt = resolve(&mapping.alt, &ALT_HEADERS)?.or_else(|| positional(2));
+
+    Ok(CsvColumns {
+        lat,
+        lng,
+        alt,
+        name: resolve(&mapping.name, &NAME_HEADERS)?,
+        speed: resolve(&mapping.speed, &SPEED_HEADERS)?,
+    })
+}
+
+// Headers match case-insensitively, ignoring a unit suffix such as "alt (m)"
+fn header_index(header: &[String], names: &[&str]) -> Option<usize> {
+    header.iter().position(|field| {
+        let normalized = field.split('(').next().unwrap_or("").trim().to_lowercase();
+        names.iter().any(|name| normalized == name.to_lowercase())
+    })
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+fn csv_row_to_item(fields: &[String], columns: &CsvColumns, number: usize) -> Result<MissionItem, String> {
+    let cell = |index: usize| fields.get(index).map(String::as_str).filter(|f| !f.is_empty());
+    let number_at = |index: usize, label: &str| -> Result<Option<f64>, String> {
+        match cell(index) {
+            None => Ok(None),
+            Some(text) => text.parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .map(Some)
+                .ok_or_else(|| format!("Invalid {} {:?}", label, text)),
+        }
+    };
+
+    let lat = number_at(columns.lat, "latitude")?.ok_or("Missing latitude")?;
+    let lng = number_at(columns.lng, "longitude")?.ok_or("Missing longitude")?;
+    if !is_valid_coordinate(&Coordinate { lat, lng, alt: None }) {
+        return Err(format!("Coordinate {}, {} is out of range", lat, lng));
+    }
+    let alt = match columns.alt {
+        Some(index) => number_at(index, "altitude")?.unwrap_or(0.0),
+        None => 0.0,
+    };
+    let speed = match columns.speed {
+        Some(index) => number_at(index, "speed")?,
+        None => None,
+    };
+    if speed.map(|s| s <= 0.0).unwrap_or(false) {
+        return Err("Speed must be positive".to_string());
+    }
+    let name = columns.name
+        .and_then(cell)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("Waypoint {}", number));
+
+    Ok(MissionItem {
+        id: generate_mission_item_id(),
+        item_type: "waypoint".to_string(),
+        name,
+        params: WaypointParams {
+            lat,
+            lng,
+            alt,
+            speed,
+            action: None,
+            target_item_id: None,
+            repeat_count: None,
+            spline: false,
+            delay_s: None,
+        },
+        position: Some(Position { lat, lng, alt }),
+        loiter_params: None,
+        camera_params: None,
+        notes: None,
+        tags: Vec::new(),
+    })
+}
+
+// Split one CSV line, honouring double-quoted fields with "" escapes
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => in_quotes = !in_quotes,
+            (',', false) => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+// ===== MAV_CMD MAPPING =====
+
+// MAVLink mission command for an item with params 1-4 (params 5-7 are lat/lng/alt)
+pub struct MissionCommand {
+    pub command: MavCmd,
+    pub params: [f32; 4],
+    // Params 5-7 for commands that carry values there instead of a position
+    pub extra_params: Option<[f64; 3]>,
+}
+
+// Used through `plan_commands` by the plan exporter and vehicle uploader. `sequence_of` maps item
+// ids to mission sequence numbers so DO_JUMP can address its target.
+pub fn mission_command(
+    item: &MissionItem,
+    sequence_of: &HashMap<&str, usize>,
+) -> Result<MissionCommand, String> {
+    let p = &item.params;
+    let (command, params) = match item.item_type.as_str() {
+        "takeoff" => (MavCmd::MAV_CMD_NAV_TAKEOFF, [0.0; 4]),
+        "land" => (MavCmd::MAV_CMD_NAV_LAND, [0.0; 4]),
+        "rtl" => (MavCmd::MAV_CMD_NAV_RETURN_TO_LAUNCH, [0.0; 4]),
+        "roi" => (MavCmd::MAV_CMD_DO_SET_ROI_LOCATION, [0.0; 4]),
+        "jump" => {
+            let target = p.target_item_id.as_deref().unwrap_or_default();
+            let sequence = sequence_of.get(target)
+                .ok_or_else(|| format!("Jump target {} no longer exists", target))?;
+            // MAVLink encodes "repeat forever" as -1
+            let repeat = match p.repeat_count.unwrap_or(0) {
+                0 => -1.0,
+                n => n as f32,
+            };
+            (MavCmd::MAV_CMD_DO_JUMP, [*sequence as f32, repeat, 0.0, 0.0])
+        }
+        "roi_cancel" => (MavCmd::MAV_CMD_DO_SET_ROI_NONE, [0.0; 4]),
+        // Params 2-4 set a UTC time of day instead; -1 leaves them unused
+        "delay" => (MavCmd::MAV_CMD_NAV_DELAY, [p.delay_s.unwrap_or(0.0) as f32, -1.0, -1.0, -1.0]),
+        "camera_trigger" => {
+            let camera = item.camera_params.as_ref()
+                .ok_or_else(|| format!("{} has no camera parameters", item.name))?;
+            return Ok(camera_command(camera));
+        }
+        _ if item.is_loiter() => loiter_command(item.loiter_params.as_ref()),
+        _ if p.spline => (MavCmd::MAV_CMD_NAV_SPLINE_WAYPOINT, [0.0; 4]),
+        _ => (MavCmd::MAV_CMD_NAV_WAYPOINT, [0.0; 4]),
+    };
+    Ok(MissionCommand { command, params, extra_params: None })
+}
+
+fn camera_command(camera: &CameraParams) -> MissionCommand {
+    let (command, params, extra_params) = match camera.trigger_type {
+        CameraTriggerType::Distance => {
+            // Distance 0 stops triggering; param3 = 1 fires once immediately when enabling
+            let distance = camera.distance_m.unwrap_or(0.0) as f32;
+            let trigger_now = f32::from(u8::from(distance > 0.0));
+            (MavCmd::MAV_CMD_DO_SET_CAM_TRIGG_DIST, [distance, 0.0, trigger_now, 0.0], None)
+        }
+        CameraTriggerType::Time => {
+            // Camera id 0 (all cameras), unlimited image count
+            let interval = camera.interval_s.unwrap_or(0.0) as f32;
+            (MavCmd::MAV_CMD_IMAGE_START_CAPTURE, [0.0, interval, 0.0, 0.0], None)
+        }
+        // Param 5 = 1 takes a single photo
+        CameraTriggerType::Manual => (MavCmd::MAV_CMD_DO_DIGICAM_CONTROL, [0.0; 4], Some([1.0, 0.0, 0.0])),
+    };
+    MissionCommand { command, params, extra_params }
+}
+
+// DO_DIGICAM_CONFIGURE ahead of a camera trigger with exposure settings; unset values are 0.
+// MAVLink gives shutter speed as the denominator of 1/x seconds.
+fn camera_configure_command(camera: &CameraParams) -> PlannedCommand {
+    let shutter = camera.shutter_ms.map_or(0.0, |ms| 1000.0 / f32::from(ms));
+    let aperture = camera.aperture.unwrap_or(0.0);
+    let iso = camera.iso.map_or(0.0, |iso| iso as f32);
+    PlannedCommand {
+        command: MavCmd::MAV_CMD_DO_DIGICAM_CONFIGURE,
+        params: [0.0, shutter, aperture, iso],
+        lat: 0.0,
+        lng: 0.0,
+        alt: 0.0,
+        positioned: false,
+    }
+}
+
+// Radius 0 lets the autopilot use its default; a negative radius loiters counter-clockwise
+fn loiter_command(loiter: Option<&LoiterParams>) -> (MavCmd, [f32; 4]) {
+    let loiter = match loiter {
+        Some(loiter) => loiter,
+        None => return (MavCmd::MAV_CMD_NAV_LOITER_UNLIM, [0.0; 4]),
+    };
+    let radius = if loiter.clockwise { loiter.radius_m } else { -loiter.radius_m } as f32;
+    match loiter.loiter_type {
+        LoiterType::Unlimited => (MavCmd::MAV_CMD_NAV_LOITER_UNLIM, [0.0, 0.0, radius, 0.0]),
+        LoiterType::Time => {
+            let duration = loiter.duration_s.unwrap_or(0.0) as f32;
+            (MavCmd::MAV_CMD_NAV_LOITER_TIME, [duration, 0.0, radius, 0.0])
+        }
+        LoiterType::Turns => {
+            let turns = loiter.turns.unwrap_or(0.0);
+            (MavCmd::MAV_CMD_NAV_LOITER_TURNS, [turns, 0.0, radius, 0.0])
+        }
+    }
+}
+
+// One MAVLink mission command in upload/export order
+pub struct PlannedCommand {
+    pub command: MavCmd,
+    pub params: [f32; 4],
+    pub lat: f64,
+    pub lng: f64,
+    pub alt: f64,
+    // False when lat/lng/alt hold raw params 5-7 rather than a position
+    pub positioned: bool,
+}
+
+// The mission as MAVLink commands: speed changes become DO_CHANGE_SPEED entries
+// ahead of the item they apply to, camera exposure settings become DO_DIGICAM_CONFIGURE
+// entries ahead of their trigger, and DO_JUMP targets are resolved to 1-based
+// positions in the returned list
+pub fn plan_commands(items: &[MissionItem]) -> Result<Vec<PlannedCommand>, String> {
+    let prefixes = command_prefixes(items);
+    let sequence_of: HashMap<&str, usize> = items.iter()
+        .map(|item| item.id.as_str())
+        .zip(sequence_numbers(&prefixes))
+        .collect();
+
+    let mut planned = Vec::with_capacity(items.len() + prefixes.iter().map(Vec::len).sum::<usize>());
+    for (item, prefix) in items.iter().zip(prefixes) {
+        planned.extend(prefix);
+        let MissionCommand { command, params, extra_params } = mission_command(item, &sequence_of)?;
+        let ([lat, lng, alt], positioned) = match extra_params {
+            Some(extra) => (extra, false),
+            None => ([item.params.lat, item.params.lng, item.params.alt], item.position.is_some()),
+        };
+        planned.push(PlannedCommand { command, params, lat, lng, alt, positioned });
+    }
+    Ok(planned)
+}
+
+// Uploaded sequence number of each item's own command (seq 0 is home)
+pub fn item_sequence_numbers(items: &[MissionItem]) -> Vec<usize> {
+    sequence_numbers(&command_prefixes(items))
+}
+
+fn sequence_numbers(prefixes: &[Vec<PlannedCommand>]) -> Vec<usize> {
+    let mut next = 1;
+    prefixes.iter()
+        .map(|prefix| {
+            let seq = next + prefix.len();
+            next = seq + 1;
+            seq
+        })
+        .collect()
+}
+
+// Commands emitted ahead of each item: speed changes and camera exposure settings
+fn command_prefixes(items: &[MissionItem]) -> Vec<Vec<PlannedCommand>> {
+    let mut prefixes = Vec::with_capacity(items.len());
+    let mut current_speed: Option<f64> = None;
+    for item in items {
+        let mut prefix = Vec::new();
+        if item.params.speed.is_some() && item.params.speed != current_speed {
+            current_speed = item.params.speed;
+            prefix.push(PlannedCommand {
+                command: MavCmd::MAV_CMD_DO_CHANGE_SPEED,
+                params: [1.0, current_speed.unwrap_or(0.0) as f32, -1.0, 0.0],
+                lat: 0.0,
+                lng: 0.0,
+                alt: 0.0,
+                positioned: false,
+            });
+        }
+        if let Some(camera) = item.camera_params.as_ref().filter(|c| c.has_exposure_settings()) {
+            prefix.push(camera_configure_command(camera));
+        }
+        prefixes.push(prefix);
+    }
+    prefixes
+}
+
+// ===== QGC PLAN =====
+
+// QGroundControl plan constants
+const QGC_FIRMWARE_ARDUPILOT: u8 = 3;
+const QGC_VEHICLE_MULTIROTOR: u8 = 2;
+const MAV_FRAME_GLOBAL_RELATIVE_ALT: u8 = 3;
+
+// MAV_CMD ids as they appear in plan files
+const QGC_NAV_WAYPOINT: u64 = MavCmd::MAV_CMD_NAV_WAYPOINT as u64;
+const QGC_NAV_SPLINE_WAYPOINT: u64 = MavCmd::MAV_CMD_NAV_SPLINE_WAYPOINT as u64;
+const QGC_NAV_LOITER_UNLIM: u64 = MavCmd::MAV_CMD_NAV_LOITER_UNLIM as u64;
+const QGC_NAV_LOITER_TURNS: u64 = MavCmd::MAV_CMD_NAV_LOITER_TURNS as u64;
+const QGC_NAV_LOITER_TIME: u64 = MavCmd::MAV_CMD_NAV_LOITER_TIME as u64;
+const QGC_NAV_RETURN_TO_LAUNCH: u64 = MavCmd::MAV_CMD_NAV_RETURN_TO_LAUNCH as u64;
+const QGC_NAV_LAND: u64 = MavCmd::MAV_CMD_NAV_LAND as u64;
+const QGC_NAV_TAKEOFF: u64 = MavCmd::MAV_CMD_NAV_TAKEOFF as u64;
+const QGC_NAV_DELAY: u64 = MavCmd::MAV_CMD_NAV_DELAY as u64;
+const QGC_CONDITION_DELAY: u64 = MavCmd::MAV_CMD_CONDITION_DELAY as u64;
+const QGC_DO_JUMP: u64 = MavCmd::MAV_CMD_DO_JUMP as u64;
+const QGC_DO_CHANGE_SPEED: u64 = MavCmd::MAV_CMD_DO_CHANGE_SPEED as u64;
+const QGC_DO_SET_ROI_LOCATION: u64 = MavCmd::MAV_CMD_DO_SET_ROI_LOCATION as u64;
+const QGC_DO_SET_ROI_NONE: u64 = MavCmd::MAV_CMD_DO_SET_ROI_NONE as u64;
+const QGC_DO_SET_CAM_TRIGG_DIST: u64 = MavCmd::MAV_CMD_DO_SET_CAM_TRIGG_DIST as u64;
+const QGC_DO_DIGICAM_CONFIGURE: u64 = MavCmd::MAV_CMD_DO_DIGICAM_CONFIGURE as u64;
+const QGC_DO_DIGICAM_CONTROL: u64 = MavCmd::MAV_CMD_DO_DIGICAM_CONTROL as u64;
+const QGC_IMAGE_START_CAPTURE: u64 = MavCmd::MAV_CMD_IMAGE_START_CAPTURE as u64;
+
+// QGroundControl .plan document for the active mission
+#[tauri::command]
+pub async fn export_mission_qgc_plan(
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    serde_json::to_string_pretty(&qgc_plan(&items)?)
+        .map_err(|e| format!("Failed to serialize plan: {}", e))
+}
+
+fn qgc_plan(items: &[MissionItem]) -> Result<Value, String> {
+    let home = items.first().ok_or("Cannot export an empty mission")?;
+
+    // QGC doJumpIds are the same 1-based positions `plan_commands` resolves jumps to
+    let plan_items: Vec<Value> = plan_commands(items)?
+        .iter()
+        .enumerate()
+        .map(|(index, planned)| {
+            let p = planned.params;
+            qgc_simple_item(
+                index + 1,
+                planned.command,
+                json!([p[0], p[1], p[2], p[3], planned.lat, planned.lng, planned.alt]),
+            )
+        })
+        .collect();
+
+    Ok(json!({
+        "fileType": "Plan",
+        "version": 1,
+        "groundStation": "Olympus",
+        "mission": {
+            "version": 2,
+            "firmwareType": QGC_FIRMWARE_ARDUPILOT,
+            "vehicleType": QGC_VEHICLE_MULTIROTOR,
+            "plannedHomePosition": [home.params.lat, home.params.lng, home.params.alt],
+            "items": plan_items,
+        },
+        "geoFence": { "circles": [], "polygons": [], "version": 2 },
+        "rallyPoints": { "points": [], "version": 2 },
+    }))
+}
+
+// Replace the active mission with the SimpleItems of a QGroundControl .plan document
+#[tauri::command]
+pub async fn import_mission_qgc_plan(
+    app_handle: tauri::AppHandle,
+    plan: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<MissionItem>, String> {
+    let imported = qgc_plan_to_items(&plan)?;
+
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    crate::mission_history::record_snapshot(&state, &items, "Import QGC plan")?;
+    *items = imported;
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+
+    Ok(items.clone())
+}
+
+fn qgc_plan_to_items(plan: &str) -> Result<Vec<MissionItem>, String> {
+    let document: Value = serde_json::from_str(plan)
+        .map_err(|e| format!("Invalid plan file: {}", e))?;
+    if document["fileType"] != "Plan" {
+        return Err("Not a QGroundControl plan file".to_string());
+    }
+    let plan_items = document["mission"]["items"].as_array()
+        .ok_or("Plan has no mission items")?;
+
+    let mut imported: Vec<MissionItem> = Vec::new();
+    let mut id_for_jump_id: HashMap<u64, String> = HashMap::new();
+    let mut current_speed: Option<f64> = None;
+    let mut exposure: Option<[f64; 7]> = None;
+    for (index, plan_item) in plan_items.iter().enumerate() {
+        if plan_item["type"] != "SimpleItem" {
+            eprintln!("Warning: plan item {} is not a SimpleItem, skipping", index + 1);
+            continue;
+        }
+        let command = plan_item["command"].as_u64()
+            .ok_or_else(|| format!("Plan item {} has no command", index + 1))?;
+        let params = qgc_params(plan_item)
+            .ok_or_else(|| format!("Plan item {} must have 7 params", index + 1))?;
+        // DO_CHANGE_SPEED persists on the vehicle until the next change
+        if command == QGC_DO_CHANGE_SPEED {
+            current_speed = Some(params[1]).filter(|speed| *speed > 0.0);
+            continue;
+        }
+
+        // Exposure settings apply to the camera trigger that follows them
+        if command == QGC_DO_DIGICAM_CONFIGURE {
+            exposure = Some(params);
+            continue;
+        }
+
+        let converted = qgc_item_to_mission_item(command, &params, &id_for_jump_id, current_speed, exposure)?;
+        let mut item = match converted {
+            Some(item) => item,
+            None => {
+                eprintln!("Warning: plan item {} has unsupported command {}, skipping", index + 1, command);
+                continue;
+            }
+        };
+        if item.is_camera_trigger() {
+            exposure = None;
+        }
+        if item.item_type == "waypoint" {
+            let number = imported.iter().filter(|i| i.item_type == "waypoint").count() + 1;
+            item.name = format!("Waypoint {}", number);
+        }
+        item.validate_params().map_err(|e| format!("Plan item {}: {}", index + 1, e))?;
+        let jump_id = plan_item["doJumpId"].as_u64().unwrap_or(index as u64 + 1);
+        id_for_jump_id.insert(jump_id, item.id.clone());
+        imported.push(item);
+    }
+    if imported.is_empty() {
+        return Err("Plan contains no supported mission items".to_string());
+    }
+    Ok(imported)
+}
+
+// QGC writes NaN params as null
+fn qgc_params(plan_item: &Value) -> Option<[f64; 7]> {
+    let values = plan_item["params"].as_array().filter(|p| p.len() == 7)?;
+    let mut params = [0.0; 7];
+    for (param, value) in params.iter_mut().zip(values) {
+        *param = value.as_f64().unwrap_or(0.0);
+    }
+    Some(params)
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+// Inverse of `mission_command`; None for commands the planner has no item type for
+fn qgc_item_to_mission_item(
+    command: u64,
+    params: &[f64; 7],
+    id_for_jump_id: &HashMap<u64, String>,
+    speed: Option<f64>,
+    exposure: Option<[f64; 7]>,
+) -> Result<Option<MissionItem>, String> {
+    let mut jump = None;
+    let (item_type, name, positioned) = match command {
+        QGC_NAV_TAKEOFF => ("takeoff", "Takeoff", true),
+        QGC_NAV_WAYPOINT | QGC_NAV_SPLINE_WAYPOINT => ("waypoint", "Waypoint", true),
+        QGC_NAV_LAND => ("land", "Land", true),
+        QGC_NAV_RETURN_TO_LAUNCH => ("rtl", "RTL", true),
+        QGC_DO_SET_ROI_LOCATION => ("roi", "ROI", true),
+        QGC_DO_SET_ROI_NONE => ("roi_cancel", "Cancel ROI", false),
+        QGC_NAV_LOITER_UNLIM | QGC_NAV_LOITER_TIME | QGC_NAV_LOITER_TURNS => ("loiter", "Loiter", true),
+        QGC_DO_JUMP => {
+            jump = Some(qgc_jump(params, id_for_jump_id)?);
+            ("jump", "Jump", false)
+        }
+        // A NAV_DELAY until a time of day has param 1 = -1 and is not supported
+        QGC_NAV_DELAY | QGC_CONDITION_DELAY if params[0] > 0.0 => ("delay", "Delay", false),
+        QGC_DO_SET_CAM_TRIGG_DIST | QGC_IMAGE_START_CAPTURE | QGC_DO_DIGICAM_CONTROL => {
+            ("camera_trigger", "Camera Trigger", false)
+        }
+        _ => return Ok(None),
+    };
+
+    let (lat, lng, alt) = if positioned { (params[4], params[5], params[6]) } else { (0.0, 0.0, 0.0) };
+    Ok(Some(MissionItem {
+        id: generate_mission_item_id(),
+        item_type: item_type.to_string(),
+        name: name.to_string(),
+        params: WaypointParams {
+            lat,
+            lng,
+            alt,
+            speed: speed.filter(|_| positioned && item_type != "roi"),
+            action: None,
+            target_item_id: jump.as_ref().map(|(target, _)| target.clone()),
+            repeat_count: jump.map(|(_, repeat)| repeat),
+            spline: command == QGC_NAV_SPLINE_WAYPOINT,
+            delay_s: Some(params[0]).filter(|_| item_type == "delay"),
+        },
+        position: Some(Position { lat, lng, alt }).filter(|_| positioned),
+        loiter_params: qgc_loiter_params(command, params),
+        camera_params: (item_type == "camera_trigger").then(|| qgc_camera_params(command, params, exposure)),
+        notes: None,
+        tags: Vec::new(),
+    }))
+}
+
+// DO_JUMP target item id and repeat count
+fn qgc_jump(params: &[f64; 7], id_for_jump_id: &HashMap<u64, String>) -> Result<(String, u32), String> {
+    let jump_id = params[0] as u64;
+    let target = id_for_jump_id.get(&jump_id)
+        .ok_or_else(|| format!("DO_JUMP target {} is not an earlier item", jump_id))?;
+    // MAVLink encodes "repeat forever" as -1
+    Ok((target.clone(), if params[1] < 0.0 { 0 } else { params[1] as u32 }))
+}
+
+// Inverse of `camera_command` and `camera_configure_command`
+fn qgc_camera_params(command: u64, params: &[f64; 7], exposure: Option<[f64; 7]>) -> CameraParams {
+    let (trigger_type, distance_m, interval_s) = match command {
+        QGC_DO_SET_CAM_TRIGG_DIST => (CameraTriggerType::Distance, Some(params[0].max(0.0)), None),
+        QGC_IMAGE_START_CAPTURE => (CameraTriggerType::Time, None, Some(params[1])),
+        _ => (CameraTriggerType::Manual, None, None),
+    };
+    let setting = |index: usize| exposure.map(|e| e[index]).filter(|value| *value > 0.0);
+    CameraParams {
+        trigger_type,
+        distance_m,
+        interval_s,
+        // Shutter speed is given as the denominator of 1/x seconds
+        shutter_ms: setting(1).map(|x| (1000.0 / x).round().clamp(1.0, f64::from(u16::MAX)) as u16),
+        aperture: setting(2).map(|f| f as f32),
+        iso: setting(3).map(|iso| iso as u32),
+    }
+}
+
+// None for non-loiter commands. Radius 0 (autopilot default) on an unlimited
+// loiter maps back to no loiter parameters
+fn qgc_loiter_params(command: u64, params: &[f64; 7]) -> Option<LoiterParams> {
+    let radius = params[2];
+    let (loiter_type, duration_s, turns) = match command {
+        QGC_NAV_LOITER_UNLIM if radius != 0.0 => (LoiterType::Unlimited, None, None),
+        QGC_NAV_LOITER_TIME => (LoiterType::Time, Some(params[0]), None),
+        QGC_NAV_LOITER_TURNS => (LoiterType::Turns, None, Some(params[0] as f32)),
+        _ => return None,
+    };
+    Some(LoiterParams {
+        loiter_type,
+        // The planner has no "autopilot default" radius for timed loiters; use the minimum
+        radius_m: radius.abs().max(crate::MIN_LOITER_RADIUS_M),
+        duration_s,
+        turns,
+        clockwise: radius >= 0.0,
+    })
+}
+
+fn qgc_simple_item(jump_id: usize, command: MavCmd, params: Value) -> Value {
+    json!({
+        "type": "SimpleItem",
+        "autoContinue": true,
+        "command": command as u32,
+        "doJumpId": jump_id,
+        "frame": MAV_FRAME_GLOBAL_RELATIVE_ALT,
+        "params": params,
+    })
+}
+
+// ===== KML / GPX =====
+
+// Items have no altitude frame yet; mission altitudes are relative to home
+const KML_ALTITUDE_MODE: &str = "relativeToGround";
+
+// KML document: the flight path as a LineString plus one Placemark per item
+#[tauri::command]
+pub async fn export_mission_kml(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let positioned: Vec<&MissionItem> = items.iter()
+        .filter(|item| item.is_on_flight_path())
+        .collect();
+    if positioned.is_empty() {
+        return Err("Mission has no positioned items to export".to_string());
+    }
+
+    write_export(&path, &mission_kml(&positioned))?;
+    Ok(positioned.len())
+}
+
+// GPX 1.1 document with the flight path as a single route
+#[tauri::command]
+pub async fn export_mission_gpx(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    let positioned: Vec<&MissionItem> = items.iter()
+        .filter(|item| item.is_on_flight_path())
+        .collect();
+    if positioned.is_empty() {
+        return Err("Mission has no positioned items to export".to_string());
+    }
+
+    write_export(&path, &mission_gpx(&positioned))?;
+    Ok(positioned.len())
+}
+
+fn mission_kml(items: &[&MissionItem]) -> String {
+    // KML coordinates are longitude,latitude,altitude
+    let coordinates: Vec<String> = items.iter()
+        .map(|item| format!("{},{},{}", item.params.lng, item.params.lat, item.params.alt))
+        .collect();
+
+    let mut kml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    kml.push_str("<kml xmlns=\"http://www.opengis.net/kml/2.2\">\n<Document>\n");
+    kml.push_str("<name>Olympus mission</name>\n");
+    kml.push_str("<Placemark>\n<name>Flight path</name>\n<LineString>\n");
+    kml.push_str(&format!("<altitudeMode>{}</altitudeMode>\n", KML_ALTITUDE_MODE));
+    kml.push_str(&format!("<coordinates>{}</coordinates>\n", coordinates.join(" ")));
+    kml.push_str("</LineString>\n</Placemark>\n");
+    for (item, coordinate) in items.iter().zip(&coordinates) {
+        kml.push_str("<Placemark>\n");
+        kml.push_str(&format!("<name>{}</name>\n", escape_xml(&item.name)));
+        kml.push_str(&format!(
+            "<description>{} at {} m</description>\n",
+            escape_xml(&item.item_type),
+            item.params.alt
+        ));
+        kml.push_str(&format!(
+            "<Point>\n<altitudeMode>{}</altitudeMode>\n<coordinates>{}</coordinates>\n</Point>\n",
+            KML_ALTITUDE_MODE, coordinate
+        ));
+        kml.push_str("</Placemark>\n");
+    }
+    kml.push_str("</Document>\n</kml>\n");
+    kml
+}
+
+fn mission_gpx(items: &[&MissionItem]) -> String {
+    let mut gpx = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    gpx.push_str("<gpx version=\"1.1\" creator=\"Olympus\" xmlns=\"http://www.topografix.com/GPX/1/1\">\n");
+    gpx.push_str("<rte>\n<name>Olympus mission</name>\n");
+    for item in items {
+        gpx.push_str(&format!(
+            "<rtept lat=\"{}\" lon=\"{}\">\n<ele>{}</ele>\n<name>{}</name>\n<type>{}</type>\n</rtept>\n",
+            item.params.lat,
+            item.params.lng,
+            item.params.alt,
+            escape_xml(&item.name),
+            escape_xml(&item.item_type)
+        ));
+    }
+    gpx.push_str("</rte>\n</gpx>\n");
+    gpx
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn write_export(path: &str, contents: &str) -> Result<(), String> {
+    std::fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn waypoint(index: usize, lat: f64, lng: f64, alt: f64) -> MissionItem {
+        MissionItem {
+            id: format!("wp-{}", index),
+            item_type: "waypoint".to_string(),
+            name: format!("Waypoint {}", index + 1),
+            params: WaypointParams {
+                lat,
+                lng,
+                alt,
+                speed: Some(12.5),
+                action: Some("photo".to_string()),
+                target_item_id: None,
+                repeat_count: None,
+                spline: false,
+                delay_s: None,
+            },
+            position: Some(Position { lat, lng, alt }),
+            loiter_params: None,
+            camera_params: None,
+            notes: None,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn geojson_round_trips_coordinates() {
+        let items = vec![
+            waypoint(0, 37.774_929_123_456, -122.419_415_987_654, 50.25),
+            waypoint(1, -33.868_820_111_111, 151.209_295_222_222, 120.0),
+            waypoint(2, 51.507_350_333_333, -0.127_758_444_444, 35.125),
+        ];
+        let text = serde_json::to_string_pretty(&mission_feature_collection(&items)).unwrap();
+        let document: Value = serde_json::from_str(&text).unwrap();
+        let features = document["features"].as_array().unwrap();
+        assert_eq!(features.len(), 4);
+
+        let points: Vec<&Value> = features.iter()
+            .filter(|feature| feature["geometry"]["type"] == "Point")
+            .collect();
+        for (index, (feature, original)) in points.iter().zip(&items).enumerate() {
+            let imported = feature_to_item(index, feature).unwrap();
+            assert!((imported.params.lat - original.params.lat).abs() < 1e-9);
+            assert!((imported.params.lng - original.params.lng).abs() < 1e-9);
+            assert!((imported.params.alt - original.params.alt).abs() < 1e-9);
+            assert_eq!(feature["properties"]["id"], original.id.as_str());
+            assert_eq!(imported.params.speed, Some(12.5));
+            assert_eq!(imported.params.action.as_deref(), Some("photo"));
+        }
+
+        let path = features[3]["geometry"]["coordinates"].as_array().unwrap();
+        for (position, original) in path.iter().zip(&items) {
+            assert!((position[0].as_f64().unwrap() - original.params.lng).abs() < 1e-9);
+            assert!((position[1].as_f64().unwrap() - original.params.lat).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn geojson_import_restores_default_mission() {
+        let original = crate::initialize_mission_data();
+        let exported = serde_json::to_string(&mission_feature_collection(&original)).unwrap();
+        let imported = geojson_to_items(&exported).unwrap();
+
+        assert_eq!(imported.len(), original.len());
+        for (imported, original) in imported.iter().zip(&original) {
+            assert_ne!(imported.id, original.id);
+            assert_eq!(imported.item_type, original.item_type);
+            assert_eq!(imported.name, original.name);
+            assert_eq!(imported.params.lat, original.params.lat);
+            assert_eq!(imported.params.lng, original.params.lng);
+            assert_eq!(imported.params.alt, original.params.alt);
+            assert_eq!(imported.params.speed, original.params.speed);
+            assert_eq!(imported.params.action, original.params.action);
+            assert!(imported.position.is_some());
+        }
+    }
+
+    #[test]
+    fn geojson_import_defaults_altitude_and_type() {
+        let geojson = json!({
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "geometry": { "type": "Point", "coordinates": [-122.4, 37.7] },
+                    "properties": { "type": "hover_dance" },
+                },
+                {
+                    "type": "Feature",
+                    "geometry": { "type": "LineString", "coordinates": [[-122.4, 37.7], [-122.5, 37.8]] },
+                    "properties": { "type": "flight_path" },
+                },
+            ],
+        });
+        let imported = geojson_to_items(&geojson.to_string()).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].item_type, "waypoint");
+        assert_eq!(imported[0].params.alt, 0.0);
+    }
+
+    // Element names in document order; panics unless every tag is closed in order
+    fn balanced_elements(xml: &str) -> Vec<String> {
+        let mut open: Vec<String> = Vec::new();
+        let mut elements = Vec::new();
+        for tag in xml.split('<').skip(1).map(|rest| rest.split('>').next().unwrap()) {
+            if tag.starts_with('?') {
+                continue;
+            }
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(open.pop().as_deref(), Some(name), "unbalanced </{}>", name);
+                continue;
+            }
+            let name = tag.split_whitespace().next().unwrap().to_string();
+            elements.push(name.clone());
+            if !tag.ends_with('/') {
+                open.push(name);
+            }
+        }
+        assert!(open.is_empty(), "unclosed elements {:?}", open);
+        elements
+    }
+
+    fn element_texts<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+        let (start, end) = (format!("<{}>", name), format!("</{}>", name));
+        xml.split(start.as_str())
+            .skip(1)
+            .map(|rest| rest.split(end.as_str()).next().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn kml_and_gpx_exports_are_well_formed() {
+        let mut items = [waypoint(0, 37.7749, -122.4194, 100.0), waypoint(1, 37.7849, -122.4094, 150.0)];
+        items[1].name = "Tower <north> & \"mast\"".to_string();
+        let positioned: Vec<&MissionItem> = items.iter().collect();
+
+        let kml = mission_kml(&positioned);
+        let elements = balanced_elements(&kml);
+        assert_eq!(elements.iter().filter(|name| *name == "Placemark").count(), 3);
+        assert_eq!(element_texts(&kml, "altitudeMode"), vec!["relativeToGround"; 3]);
+        assert_eq!(
+            element_texts(&kml, "coordinates")[0],
+            "-122.4194,37.7749,100 -122.4094,37.7849,150"
+        );
+        assert!(element_texts(&kml, "name").contains(&"Tower &lt;north&gt; &amp; &quot;mast&quot;"));
+        assert_eq!(element_texts(&kml, "description")[1], "waypoint at 150 m");
+
+        let gpx = mission_gpx(&positioned);
+        let elements = balanced_elements(&gpx);
+        assert_eq!(elements.iter().filter(|name| *name == "rtept").count(), 2);
+        assert!(gpx.contains("<rtept lat=\"37.7849\" lon=\"-122.4094\">"));
+        assert_eq!(element_texts(&gpx, "ele"), vec!["100", "150"]);
+    }
+
+    fn loiter(index: usize, loiter_type: LoiterType, duration_s: Option<f64>, turns: Option<f32>, clockwise: bool) -> MissionItem {
+        MissionItem {
+            item_type: "loiter".to_string(),
+            name: "Loiter".to_string(),
+            loiter_params: Some(LoiterParams {
+                loiter_type,
+                radius_m: 40.0 + index as f64,
+                duration_s,
+                turns,
+                clockwise,
+            }),
+            ..waypoint(index, 37.78 + index as f64 * 0.001, -122.41, 80.0)
+        }
+    }
+
+    #[test]
+    fn qgc_plan_round_trips_loiter_types() {
+        let mut takeoff = waypoint(0, 37.7749, -122.4194, 30.0);
+        takeoff.item_type = "takeoff".to_string();
+        takeoff.name = "Takeoff".to_string();
+        let items = vec![
+            takeoff,
+            loiter(1, LoiterType::Unlimited, None, None, true),
+            loiter(2, LoiterType::Time, Some(45.0), None, false),
+            loiter(3, LoiterType::Turns, None, Some(2.5), true),
+        ];
+        let plan = qgc_plan(&items).unwrap();
+        let commands: Vec<u64> = plan["mission"]["items"].as_array().unwrap().iter()
+            .map(|item| item["command"].as_u64().unwrap())
+            .filter(|command| *command != QGC_DO_CHANGE_SPEED)
+            .collect();
+        assert_eq!(commands, [QGC_NAV_TAKEOFF, QGC_NAV_LOITER_UNLIM, QGC_NAV_LOITER_TIME, QGC_NAV_LOITER_TURNS]);
+        assert_eq!(commands[1..], [17, 19, 18]);
+
+        let imported = qgc_plan_to_items(&plan.to_string()).unwrap();
+        assert_eq!(imported.len(), items.len());
+        for (imported, original) in imported.iter().zip(&items).skip(1) {
+            let (got, expected) = (imported.loiter_params.as_ref().unwrap(), original.loiter_params.as_ref().unwrap());
+            assert_eq!(imported.item_type, "loiter");
+            assert_eq!(got.loiter_type, expected.loiter_type);
+            assert_eq!(got.radius_m, expected.radius_m);
+            assert_eq!(got.duration_s, expected.duration_s);
+            assert_eq!(got.turns, expected.turns);
+            assert_eq!(got.clockwise, expected.clockwise);
+            assert!((imported.params.lat - original.params.lat).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn qgc_items_only_carry_their_own_parameters() {
+        let ids: HashMap<u64, String> = [(1, "mission-1".to_string())].into_iter().collect();
+        let import = |command: u64, params: [f64; 7]| {
+            qgc_item_to_mission_item(command, &params, &ids, None, None).unwrap().unwrap()
+        };
+
+        let waypoint = import(QGC_NAV_WAYPOINT, [5.0, 0.0, 30.0, 0.0, 37.78, -122.41, 80.0]);
+        assert!(waypoint.loiter_params.is_none());
+        assert!(waypoint.camera_params.is_none());
+        assert_eq!(waypoint.params.delay_s, None);
+
+        let loiter = import(QGC_NAV_LOITER_TURNS, [3.0, 0.0, -60.0, 0.0, 37.78, -122.41, 80.0]);
+        let loiter_params = loiter.loiter_params.unwrap();
+        assert_eq!(loiter_params.turns, Some(3.0));
+        assert!(!loiter_params.clockwise);
+        assert!(import(QGC_NAV_LOITER_UNLIM, [0.0, 0.0, 0.0, 0.0, 37.78, -122.41, 80.0]).loiter_params.is_none());
+
+        let jump = import(QGC_DO_JUMP, [1.0, -1.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(jump.params.target_item_id.as_deref(), Some("mission-1"));
+        assert_eq!(jump.params.repeat_count, Some(0));
+        assert!(qgc_item_to_mission_item(QGC_DO_JUMP, &[2.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0], &ids, None, None).is_err());
+
+        let delay = import(QGC_CONDITION_DELAY, [12.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(delay.params.delay_s, Some(12.0));
+        assert!(delay.position.is_none());
+        assert!(import(QGC_DO_SET_CAM_TRIGG_DIST, [25.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]).camera_params.is_some());
+    }
+}
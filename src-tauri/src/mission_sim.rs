@@ -0,0 +1,266 @@
+// Mission dry-run simulation backend
+// NASA JPL Power of 10 compliant implementation
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{Manager, State};
+
+use crate::geo::{bearing_deg, distance_m, interpolate};
+use crate::map_features::Coordinate;
+use crate::mission_analysis::{expand_jumps, leg_track, DEFAULT_CRUISE_SPEED_MS};
+use crate::mavlink::{is_vehicle_armed, MavlinkState};
+use crate::{AppState, MissionItem};
+
+// ===== TYPE DEFINITIONS =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedPosition {
+    pub lat: f64,
+    pub lng: f64,
+    pub alt: f64,
+    pub heading: f64,
+    pub elapsed_s: f64,
+    pub active_item_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationSummary {
+    pub completed: bool,
+    pub elapsed_s: f64,
+}
+
+// One flight segment between consecutive mission items; the track has more than
+// two points when the leg curves into a spline waypoint
+struct SimLeg {
+    track: Vec<Coordinate>,
+    to_item_id: String,
+    // Delay items between the endpoints, held at the start of the track
+    hold_s: f64,
+    horizontal_s: f64,
+    vertical_s: f64,
+}
+
+impl SimLeg {
+    // Horizontal and vertical motion run concurrently after the hold
+    fn duration_s(&self) -> f64 {
+        self.hold_s + self.horizontal_s.max(self.vertical_s)
+    }
+
+    // Position and heading `t` seconds into the leg
+    fn position_at(&self, t: f64) -> (Coordinate, f64) {
+        let t = t - self.hold_s;
+        let horizontal = progress(t, self.horizontal_s);
+        let vertical = progress(t, self.vertical_s);
+        let (from, to, fraction) = track_segment_at(&self.track, horizontal);
+        let mut point = interpolate(from, to, fraction);
+
+        let first_alt = self.track[0].alt.unwrap_or(0.0);
+        let last_alt = self.track[self.track.len() - 1].alt.unwrap_or(0.0);
+        point.alt = Some(first_alt + (last_alt - first_alt) * vertical);
+        (point, bearing_deg(from, to))
+    }
+}
+
+const DEFAULT_CLIMB_RATE_MS: f64 = 2.5;
+const DEFAULT_DESCENT_RATE_MS: f64 = 1.5;
+const MAX_UPDATE_HZ: f64 = 50.0;
+// Passes flown for a repeat-forever jump; the simulation can be cancelled at any time
+const FOREVER_JUMP_PASSES: u32 = 100;
+// NASA JPL Rule 2: bound the simulation loop (one day at the lowest useful rate)
+const MAX_SIMULATION_TICKS: u64 = 86_400 * 50;
+
+// ===== STATE MANAGEMENT =====
+
+pub struct MissionSimState {
+    running: AtomicBool,
+    cancel_requested: AtomicBool,
+}
+
+impl MissionSimState {
+    pub fn new() -> Self {
+        Self {
+            running: AtomicBool::new(false),
+            cancel_requested: AtomicBool::new(false),
+        }
+    }
+}
+
+// ===== SIMULATION COMMANDS =====
+
+#[tauri::command]
+pub async fn simulate_mission(
+    app_handle: tauri::AppHandle,
+    update_hz: f64,
+    climb_rate_ms: Option<f64>,
+    descent_rate_ms: Option<f64>,
+    state: State<'_, AppState>,
+    sim_state: State<'_, MissionSimState>,
+    mavlink_state: State<'_, MavlinkState>,
+) -> Result<SimulationSummary, String> {
+    if !(update_hz > 0.0 && update_hz <= MAX_UPDATE_HZ) {
+        return Err(format!("Update rate must be between 0 and {} Hz", MAX_UPDATE_HZ));
+    }
+    let climb_rate = climb_rate_ms.unwrap_or(DEFAULT_CLIMB_RATE_MS);
+    let descent_rate = descent_rate_ms.unwrap_or(DEFAULT_DESCENT_RATE_MS);
+    if !(climb_rate > 0.0 && descent_rate > 0.0) {
+        return Err("Climb and descent rates must be positive".to_string());
+    }
+    if is_vehicle_armed(&mavlink_state)? {
+        return Err("Cannot simulate a mission while the vehicle is armed".to_string());
+    }
+
+    let items = state.mission_items.lock().map_err(|_| "Failed to lock state")?.clone();
+    let sequence = expand_jumps(&items, Some(FOREVER_JUMP_PASSES))?;
+    let legs = build_legs(&sequence, climb_rate, descent_rate);
+    if legs.is_empty() {
+        return Err("Mission needs at least two items to simulate".to_string());
+    }
+
+    if sim_state.running.swap(true, Ordering::SeqCst) {
+        return Err("Mission simulation already running".to_string());
+    }
+    sim_state.cancel_requested.store(false, Ordering::SeqCst);
+
+    let summary = run_simulation(&app_handle, &legs, update_hz, &sim_state).await;
+    sim_state.running.store(false, Ordering::SeqCst);
+
+    Ok(summary)
+}
+
+#[tauri::command]
+pub async fn cancel_mission_simulation(
+    sim_state: State<'_, MissionSimState>,
+) -> Result<(), String> {
+    if !sim_state.running.load(Ordering::SeqCst) {
+        return Err("No mission simulation is running".to_string());
+    }
+    sim_state.cancel_requested.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+// ===== SIMULATION ENGINE =====
+
+// Step the virtual vehicle in real time, emitting `mission-sim-position` each tick
+async fn run_simulation(
+    app_handle: &tauri::AppHandle,
+    legs: &[SimLeg],
+    update_hz: f64,
+    sim_state: &MissionSimState,
+) -> SimulationSummary {
+    let dt = 1.0 / update_hz;
+    let total_s: f64 = legs.iter().map(SimLeg::duration_s).sum();
+    let mut interval = tokio::time::interval(Duration::from_secs_f64(dt));
+
+    for tick in 0..MAX_SIMULATION_TICKS {
+        interval.tick().await;
+        if sim_state.cancel_requested.load(Ordering::SeqCst) {
+            return SimulationSummary { completed: false, elapsed_s: tick as f64 * dt };
+        }
+
+        let elapsed_s = (tick as f64 * dt).min(total_s);
+        if let Err(e) = app_handle.emit_all("mission-sim-position", position_at(legs, elapsed_s)) {
+            eprintln!("Failed to emit simulated position: {e}");
+        }
+        if elapsed_s >= total_s {
+            return SimulationSummary { completed: true, elapsed_s };
+        }
+    }
+
+    SimulationSummary { completed: false, elapsed_s: MAX_SIMULATION_TICKS as f64 * dt }
+}
+
+fn build_legs(items: &[&MissionItem], climb_rate: f64, descent_rate: f64) -> Vec<SimLeg> {
+    let positioned: Vec<&MissionItem> = items.iter()
+        .copied()
+        .filter(|item| item.is_on_flight_path())
+        .collect();
+
+    // Hold time of the delays flown before reaching each positioned item
+    let mut hold_s = vec![0.0; positioned.len()];
+    let mut reached = 0;
+    for item in items {
+        if item.is_on_flight_path() {
+            reached += 1;
+        } else if reached > 0 && reached < positioned.len() {
+            hold_s[reached] += item.delay_duration_s();
+        }
+    }
+
+    positioned.windows(2)
+        .enumerate()
+        .map(|(index, pair)| {
+            let track = leg_track(&positioned, index + 1);
+            let length_m: f64 = track.windows(2).map(|segment| distance_m(&segment[0], &segment[1])).sum();
+            let speed = pair[1].params.speed
+                .filter(|speed| *speed > 0.0)
+                .unwrap_or(DEFAULT_CRUISE_SPEED_MS);
+            let climb_m = pair[1].params.alt - pair[0].params.alt;
+            let vertical_rate = if climb_m >= 0.0 { climb_rate } else { descent_rate };
+
+            SimLeg {
+                track,
+                to_item_id: pair[1].id.clone(),
+                hold_s: hold_s[index + 1],
+                horizontal_s: length_m / speed,
+                vertical_s: climb_m.abs() / vertical_rate,
+            }
+        })
+        .collect()
+}
+
+// Vehicle state `elapsed_s` seconds into the mission
+fn position_at(legs: &[SimLeg], elapsed_s: f64) -> SimulatedPosition {
+    let mut leg_start = 0.0;
+    let mut current = &legs[legs.len() - 1];
+    for leg in legs {
+        if elapsed_s < leg_start + leg.duration_s() {
+            current = leg;
+            break;
+        }
+        leg_start += leg.duration_s();
+    }
+    if elapsed_s >= leg_start + current.duration_s() {
+        // Past the final leg: hold at its end
+        leg_start = elapsed_s - current.duration_s();
+    }
+
+    let (point, heading) = current.position_at(elapsed_s - leg_start);
+    SimulatedPosition {
+        lat: point.lat,
+        lng: point.lng,
+        alt: point.alt.unwrap_or(0.0),
+        heading,
+        elapsed_s,
+        active_item_id: current.to_item_id.clone(),
+    }
+}
+
+// Track segment containing the point `fraction` (0..=1) of the way along the
+// track by distance, and how far along that segment the point lies
+fn track_segment_at(track: &[Coordinate], fraction: f64) -> (&Coordinate, &Coordinate, f64) {
+    let lengths: Vec<f64> = track.windows(2).map(|pair| distance_m(&pair[0], &pair[1])).collect();
+    let mut remaining_m = fraction * lengths.iter().sum::<f64>();
+    for (index, length_m) in lengths.iter().enumerate() {
+        if remaining_m <= *length_m || index + 1 == lengths.len() {
+            return (&track[index], &track[index + 1], progress(remaining_m, *length_m));
+        }
+        remaining_m -= length_m;
+    }
+    (&track[0], &track[track.len() - 1], fraction)
+}
+
+// Fraction of a motion of length `total_s` completed after `t` seconds
+fn progress(t: f64, total_s: f64) -> f64 {
+    if total_s <= 0.0 {
+        1.0
+    } else {
+        (t / total_s).clamp(0.0, 1.0)
+    }
+}
+
+// ===== MODULE REGISTRATION =====
+
+pub fn init() -> MissionSimState {
+    MissionSimState::new()
+}
@@ -0,0 +1,391 @@
+// Survey grid and search pattern mission generation backend
+// NASA JPL Power of 10 compliant implementation
+
+use crate::geo::{is_valid_coordinate, local_offset_m, offset_coordinate, rotate_offset};
+use crate::map_features::Coordinate;
+use crate::{generate_mission_item_id, MissionItem, Position, WaypointParams};
+
+// ===== TYPE DEFINITIONS =====
+
+// Point in the survey's local frame, meters north/east of the boundary centroid
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LocalPoint {
+    north: f64,
+    east: f64,
+}
+
+// Ground width covered by one track when no camera field of view is given
+const DEFAULT_TRACK_WIDTH_M: f64 = 50.0;
+// NASA JPL Rule 2: bound the number of generated tracks
+const MAX_SURVEY_TRACKS: usize = 1000;
+const MAX_BOUNDARY_VERTICES: usize = 1000;
+// Rounding slack so a width of exactly N spacings does not get an extra track
+const TRACK_FIT_TOLERANCE_M: f64 = 1e-3;
+// Spiral chords may bulge at most this fraction of the track spacing off the arc
+const SPIRAL_MAX_DEVIATION_RATIO: f64 = 0.25;
+// Keeps the first turns near the center from collapsing into a few points
+const SPIRAL_MAX_STEP_RAD: f64 = std::f64::consts::FRAC_PI_2;
+
+// ===== SURVEY COMMANDS =====
+
+// Lawnmower survey of `boundary`: takeoff at the centroid, parallel tracks along
+// `angle_deg` (compass heading), then RTL. Track spacing is the camera footprint
+// (or 50 m) reduced by `overlap_pct`. The active mission is not modified.
+#[tauri::command]
+pub async fn generate_survey_grid(
+    boundary: Vec<Coordinate>,
+    altitude_m: f64,
+    overlap_pct: f64,
+    angle_deg: f64,
+    speed_mps: f64,
+    camera_fov_deg: Option<f64>,
+) -> Result<Vec<MissionItem>, String> {
+    if !(3..=MAX_BOUNDARY_VERTICES).contains(&boundary.len()) {
+        return Err(format!("Survey boundary needs 3 to {} vertices", MAX_BOUNDARY_VERTICES));
+    }
+    if !boundary.iter().all(is_valid_coordinate) {
+        return Err("Survey boundary contains invalid coordinates".to_string());
+    }
+    if !(altitude_m.is_finite() && altitude_m > 0.0 && speed_mps.is_finite() && speed_mps > 0.0) {
+        return Err("Survey altitude and speed must be positive".to_string());
+    }
+    if !(0.0..=100.0).contains(&overlap_pct) || !angle_deg.is_finite() {
+        return Err("Overlap must be between 0 and 100% and the angle finite".to_string());
+    }
+    let track_width_m = match camera_fov_deg {
+        Some(fov) if fov.is_finite() && fov > 0.0 && fov < 180.0 => {
+            2.0 * altitude_m * (fov / 2.0).to_radians().tan()
+        }
+        Some(_) => return Err("Camera field of view must be between 0 and 180 degrees".to_string()),
+        None => DEFAULT_TRACK_WIDTH_M,
+    };
+    let spacing_m = track_width_m * (1.0 - overlap_pct / 100.0);
+
+    let origin = boundary_centroid(&boundary);
+    let polygon: Vec<LocalPoint> = boundary.iter()
+        .map(|coord| {
+            // Rotate so tracks run due north in the local frame
+            let (north, east) = local_offset_m(&origin, coord);
+            let (north, east) = rotate_offset(north, east, -angle_deg);
+            LocalPoint { north, east }
+        })
+        .collect();
+    if !is_convex(&polygon) {
+        return Err("Survey boundary must be a convex polygon".to_string());
+    }
+
+    let tracks = survey_tracks(&polygon, spacing_m)?;
+    Ok(survey_items(&origin, &tracks, angle_deg, altitude_m, speed_mps))
+}
+
+// Expanding Archimedean spiral around `center` for search and rescue: takeoff at
+// the center, waypoints from `initial_radius_m` out to `max_radius_m` with
+// `track_spacing_m` between turns, then RTL. The active mission is not modified.
+#[tauri::command]
+pub async fn generate_spiral_search(
+    center: Coordinate,
+    initial_radius_m: f64,
+    max_radius_m: f64,
+    track_spacing_m: f64,
+    altitude_m: f64,
+    speed_mps: f64,
+) -> Result<Vec<MissionItem>, String> {
+    if !is_valid_coordinate(&center) {
+        return Err("Spiral center is not a valid coordinate".to_string());
+    }
+    if !(altitude_m.is_finite() && altitude_m > 0.0 && speed_mps.is_finite() && speed_mps > 0.0) {
+        return Err("Spiral altitude and speed must be positive".to_string());
+    }
+    if !(track_spacing_m.is_finite() && track_spacing_m > 0.0) {
+        return Err("Track spacing must be positive".to_string());
+    }
+    if !(initial_radius_m.is_finite() && initial_radius_m >= 0.0 && max_radius_m.is_finite()
+        && max_radius_m > initial_radius_m)
+    {
+        return Err("Spiral radii must satisfy 0 <= initial < max".to_string());
+    }
+
+    let points = spiral_points(initial_radius_m, max_radius_m, track_spacing_m)?;
+    let mut items = Vec::with_capacity(points.len() + 2);
+    items.push(survey_item("takeoff", "Takeoff".to_string(), &center, altitude_m, None));
+    for point in points {
+        let coord = offset_coordinate(&center, point.north, point.east);
+        let name = format!("Waypoint {}", items.len());
+        items.push(survey_item("waypoint", name, &coord, altitude_m, Some(speed_mps)));
+    }
+    items.push(survey_item("rtl", "RTL".to_string(), &center, altitude_m, None));
+    Ok(items)
+}
+
+// ===== SPIRAL GEOMETRY =====
+
+// NASA JPL Rule 4: Function under 60 lines
+// Samples r = initial + spacing * theta / 2pi clockwise from north. Each step is the
+// smallest of a quarter turn, the angle whose chord stays within a quarter spacing of
+// the arc, and the angle whose arc is one track spacing long. The last point lies
+// exactly on `max_radius_m`. Fails if the mission would exceed MAX_MISSION_ITEMS.
+fn spiral_points(initial_radius_m: f64, max_radius_m: f64, spacing_m: f64) -> Result<Vec<LocalPoint>, String> {
+    // Takeoff and RTL take two of the mission's item slots
+    let max_points = crate::MAX_MISSION_ITEMS - 2;
+    let growth_per_rad = spacing_m / std::f64::consts::TAU;
+    let end_theta = (max_radius_m - initial_radius_m) / growth_per_rad;
+    let max_deviation_m = spacing_m * SPIRAL_MAX_DEVIATION_RATIO;
+
+    let mut points = Vec::new();
+    let mut theta: f64 = 0.0;
+    loop {
+        if points.len() >= max_points {
+            return Err(format!(
+                "Spiral search would exceed the {} item mission limit",
+                crate::MAX_MISSION_ITEMS
+            ));
+        }
+        let radius = initial_radius_m + growth_per_rad * theta;
+        points.push(LocalPoint { north: radius * theta.cos(), east: radius * theta.sin() });
+        if theta >= end_theta {
+            break;
+        }
+
+        // Sagitta r(1 - cos(step / 2)) <= max deviation
+        let deviation_step = 2.0 * (1.0 - max_deviation_m / radius).max(-1.0).acos();
+        // Arc length ~ sqrt(r^2 + b^2) * step <= spacing
+        let length_step = spacing_m / radius.hypot(growth_per_rad);
+        let step = deviation_step.min(length_step).min(SPIRAL_MAX_STEP_RAD);
+        theta = (theta + step).min(end_theta);
+    }
+    Ok(points)
+}
+
+// ===== GRID GEOMETRY =====
+
+// NASA JPL Rule 4: Function under 60 lines
+// Parallel north-running tracks across the rotated bounding box, clipped to the polygon.
+// Alternate tracks are reversed so the vehicle flies a lawnmower pattern. Zero spacing
+// (100% overlap) yields a single track through the middle of the box.
+fn survey_tracks(polygon: &[LocalPoint], spacing_m: f64) -> Result<Vec<(LocalPoint, LocalPoint)>, String> {
+    let (min_north, max_north) = extent(polygon.iter().map(|p| p.north));
+    let (min_east, max_east) = extent(polygon.iter().map(|p| p.east));
+    let width_m = max_east - min_east;
+
+    let offsets: Vec<f64> = if spacing_m <= 0.0 || width_m <= spacing_m {
+        vec![min_east + width_m / 2.0]
+    } else {
+        let count = ((width_m - TRACK_FIT_TOLERANCE_M) / spacing_m).ceil() as usize;
+        if count > MAX_SURVEY_TRACKS {
+            return Err(format!("Survey would need more than {} tracks", MAX_SURVEY_TRACKS));
+        }
+        // Center the tracks in the box
+        let first = min_east + (width_m - (count - 1) as f64 * spacing_m) / 2.0;
+        (0..count).map(|i| first + i as f64 * spacing_m).collect()
+    };
+
+    let mut tracks = Vec::with_capacity(offsets.len());
+    for east in offsets {
+        let line = [
+            LocalPoint { north: min_north - 1.0, east },
+            LocalPoint { north: max_north + 1.0, east },
+        ];
+        let clipped = sutherland_hodgman(&line, polygon);
+        if clipped.is_empty() {
+            continue;
+        }
+        let (south, north) = extent(clipped.iter().map(|p| p.north));
+        let (start, end) = (LocalPoint { north: south, east }, LocalPoint { north, east });
+        if tracks.len() % 2 == 0 {
+            tracks.push((start, end));
+        } else {
+            tracks.push((end, start));
+        }
+    }
+    if tracks.is_empty() {
+        return Err("Survey boundary is too small for a track".to_string());
+    }
+    Ok(tracks)
+}
+
+// Clip `subject` against each edge of the convex `clip` polygon in turn
+fn sutherland_hodgman(subject: &[LocalPoint], clip: &[LocalPoint]) -> Vec<LocalPoint> {
+    // Interior is on the left of each edge for counter-clockwise polygons
+    let orientation = signed_area(clip).signum();
+    let mut output = subject.to_vec();
+
+    for (index, &edge_start) in clip.iter().enumerate() {
+        let edge_end = clip[(index + 1) % clip.len()];
+        let inside = |p: &LocalPoint| cross(edge_start, edge_end, *p) * orientation >= 0.0;
+        let input = std::mem::take(&mut output);
+        for (i, &current) in input.iter().enumerate() {
+            let previous = input[(i + input.len() - 1) % input.len()];
+            match (inside(&previous), inside(&current)) {
+                (true, true) => output.push(current),
+                (true, false) => output.push(intersection(previous, current, edge_start, edge_end)),
+                (false, true) => {
+                    output.push(intersection(previous, current, edge_start, edge_end));
+                    output.push(current);
+                }
+                (false, false) => {}
+            }
+        }
+        if output.is_empty() {
+            break;
+        }
+    }
+    output
+}
+
+// Where segment a-b crosses the infinite line through c-d
+fn intersection(a: LocalPoint, b: LocalPoint, c: LocalPoint, d: LocalPoint) -> LocalPoint {
+    let (side_a, side_b) = (cross(c, d, a), cross(c, d, b));
+    let t = side_a / (side_a - side_b);
+    LocalPoint {
+        north: a.north + (b.north - a.north) * t,
+        east: a.east + (b.east - a.east) * t,
+    }
+}
+
+// Z component of (b - a) x (p - a), with east as x and north as y
+fn cross(a: LocalPoint, b: LocalPoint, p: LocalPoint) -> f64 {
+    (b.east - a.east) * (p.north - a.north) - (b.north - a.north) * (p.east - a.east)
+}
+
+fn signed_area(polygon: &[LocalPoint]) -> f64 {
+    let origin = LocalPoint { north: 0.0, east: 0.0 };
+    (0..polygon.len())
+        .map(|i| cross(origin, polygon[i], polygon[(i + 1) % polygon.len()]))
+        .sum::<f64>() / 2.0
+}
+
+// Every turn goes the same way (collinear vertices allowed)
+fn is_convex(polygon: &[LocalPoint]) -> bool {
+    let len = polygon.len();
+    let turns: Vec<f64> = (0..len)
+        .map(|i| cross(polygon[i], polygon[(i + 1) % len], polygon[(i + 2) % len]))
+        .filter(|turn| turn.abs() > 1e-9)
+        .collect();
+    !turns.is_empty() && (turns.iter().all(|t| *t > 0.0) || turns.iter().all(|t| *t < 0.0))
+}
+
+fn extent(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| (min.min(v), max.max(v)))
+}
+
+// Vertex average in the local frame of the first vertex
+fn boundary_centroid(boundary: &[Coordinate]) -> Coordinate {
+    let origin = &boundary[0];
+    let (sum_north, sum_east) = boundary.iter()
+        .map(|coord| local_offset_m(origin, coord))
+        .fold((0.0, 0.0), |(n, e), (dn, de)| (n + dn, e + de));
+    let count = boundary.len() as f64;
+    let mut centroid = offset_coordinate(origin, sum_north / count, sum_east / count);
+    centroid.alt = None;
+    centroid
+}
+
+// ===== ITEM CONSTRUCTION =====
+
+fn survey_items(
+    origin: &Coordinate,
+    tracks: &[(LocalPoint, LocalPoint)],
+    angle_deg: f64,
+    altitude_m: f64,
+    speed_mps: f64,
+) -> Vec<MissionItem> {
+    let mut items = Vec::with_capacity(tracks.len() * 2 + 2);
+    items.push(survey_item("takeoff", "Takeoff".to_string(), origin, altitude_m, None));
+    for point in tracks.iter().flat_map(|(start, end)| [*start, *end]) {
+        // Undo the track rotation to get back to geographic north/east
+        let (north, east) = rotate_offset(point.north, point.east, angle_deg);
+        let coord = offset_coordinate(origin, north, east);
+        let name = format!("Waypoint {}", items.len());
+        items.push(survey_item("waypoint", name, &coord, altitude_m, Some(speed_mps)));
+    }
+    items.push(survey_item("rtl", "RTL".to_string(), origin, altitude_m, None));
+    items
+}
+
+fn survey_item(
+    item_type: &str,
+    name: String,
+    coord: &Coordinate,
+    alt: f64,
+    speed: Option<f64>,
+) -> MissionItem {
+    let (lat, lng) = (coord.lat, coord.lng);
+    MissionItem {
+        id: generate_mission_item_id(),
+        item_type: item_type.to_string(),
+        name,
+        params: WaypointParams {
+            lat,
+            lng,
+            alt,
+            speed,
+            action: None,
+            target_item_id: None,
+            repeat_count: None,
+            spline: false,
+            delay_s: None,
+        },
+        position: Some(Position { lat, lng, alt }),
+        loiter_params: None,
+        camera_params: None,
+        notes: None,
+        tags: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Square with 1 km sides centred on `center`, counter-clockwise from the south-west
+    fn square_km(center: &Coordinate) -> Vec<Coordinate> {
+        [(-500.0, -500.0), (-500.0, 500.0), (500.0, 500.0), (500.0, -500.0)]
+            .iter()
+            .map(|&(north, east)| offset_coordinate(center, north, east))
+            .collect()
+    }
+
+    fn track_count(items: &[MissionItem]) -> usize {
+        assert_eq!(items.first().unwrap().item_type, "takeoff");
+        assert_eq!(items.last().unwrap().item_type, "rtl");
+        (items.len() - 2) / 2
+    }
+
+    #[tokio::test]
+    async fn survey_grid_track_count_follows_overlap() {
+        let center = Coordinate { lat: 37.7749, lng: -122.4194, alt: None };
+        let boundary = square_km(&center);
+
+        let single = generate_survey_grid(boundary.clone(), 60.0, 100.0, 0.0, 8.0, None).await.unwrap();
+        assert_eq!(track_count(&single), 1);
+
+        // 50% overlap of the default 50 m track width leaves 25 m between tracks
+        let half = generate_survey_grid(boundary, 60.0, 50.0, 0.0, 8.0, None).await.unwrap();
+        assert_eq!(track_count(&half), 40);
+        let first_east = local_offset_m(&center, &half[1].location()).1;
+        let second_east = local_offset_m(&center, &half[3].location()).1;
+        assert!((second_east - first_east - 25.0).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn spiral_search_reaches_max_radius_in_short_steps() {
+        let center = Coordinate { lat: -33.8688, lng: 151.2093, alt: None };
+        let spacing_m = 40.0;
+        let items = generate_spiral_search(center.clone(), 0.0, 600.0, spacing_m, 80.0, 10.0).await.unwrap();
+        assert_eq!(items.first().unwrap().item_type, "takeoff");
+        assert_eq!(items.last().unwrap().item_type, "rtl");
+
+        let waypoints: Vec<Coordinate> = items[1..items.len() - 1].iter().map(MissionItem::location).collect();
+        let final_radius_m = crate::geo::distance_m(&center, waypoints.last().unwrap());
+        assert!((final_radius_m - 600.0).abs() <= spacing_m);
+        let legs: Vec<f64> = waypoints.windows(2).map(|pair| crate::geo::distance_m(&pair[0], &pair[1])).collect();
+        assert!(legs.iter().all(|leg| *leg <= 1.5 * spacing_m));
+
+        // Waypoint legs add up to the Archimedean arc length (b/2)(t sqrt(1 + t^2) + asinh t)
+        let b = spacing_m / std::f64::consts::TAU;
+        let t = 600.0 / b;
+        let arc_m = b / 2.0 * (t * (1.0 + t * t).sqrt() + t.asinh());
+        let flown_m: f64 = legs.iter().sum();
+        assert!((flown_m - arc_m).abs() / arc_m < 0.01);
+    }
+}
@@ -0,0 +1,199 @@
+// Mission template backend
+// NASA JPL Power of 10 compliant implementation
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use crate::geo::{distance_m, local_offset_m, offset_coordinate, rotate_offset};
+use crate::map_features::Coordinate;
+use crate::{AppState, MissionItem, Position};
+
+// ===== TYPE DEFINITIONS =====
+
+// Mission stored relative to its first positioned item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionTemplate {
+    pub name: String,
+    pub items: Vec<TemplateItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateItem {
+    pub item: MissionItem,
+    // Offset from the template origin; None for items without a map position
+    pub offset: Option<TemplateOffset>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct TemplateOffset {
+    pub north_m: f64,
+    pub east_m: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionTemplateSummary {
+    pub name: String,
+    pub item_count: usize,
+    pub total_distance_km: f64,
+}
+
+const TEMPLATES_FILE: &str = "mission_templates.json";
+
+// ===== TEMPLATE COMMANDS =====
+
+#[tauri::command]
+pub async fn save_mission_template(
+    app_handle: tauri::AppHandle,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<MissionTemplateSummary, String> {
+    let name = crate::validate_mission_name(&name)?;
+    let items = state.mission_items.lock()
+        .map_err(|_| "Failed to lock state")?
+        .clone();
+
+    let template = build_template(name, &items)?;
+    let summary = summarize_template(&template);
+
+    let path = templates_path(&app_handle)?;
+    let mut templates = read_templates(&path)?;
+    templates.insert(template.name.clone(), template);
+    write_templates(&path, &templates)?;
+
+    Ok(summary)
+}
+
+#[tauri::command]
+pub async fn apply_mission_template(
+    app_handle: tauri::AppHandle,
+    name: String,
+    anchor: Coordinate,
+    heading_deg: f64,
+    state: State<'_, AppState>,
+) -> Result<Vec<MissionItem>, String> {
+    if !heading_deg.is_finite() {
+        return Err("Heading must be a finite number of degrees".to_string());
+    }
+
+    let templates = read_templates(&templates_path(&app_handle)?)?;
+    let template = templates.get(&name)
+        .ok_or_else(|| format!("Mission template {name} not found"))?;
+    let new_items = instantiate_template(template, &anchor, heading_deg);
+
+    // Replace the active mission with the instantiated template
+    let mut items = state.mission_items.lock().map_err(|_| "Failed to lock state")?;
+    crate::mission_history::record_snapshot(&state, &items, "Apply mission template")?;
+    *items = new_items;
+    crate::notify_mission_changed(&app_handle, &state, &items)?;
+
+    Ok(items.clone())
+}
+
+#[tauri::command]
+pub async fn list_mission_templates(
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<MissionTemplateSummary>, String> {
+    let templates = read_templates(&templates_path(&app_handle)?)?;
+    Ok(templates.values().map(summarize_template).collect())
+}
+
+// ===== TEMPLATE CONVERSION =====
+
+// NASA JPL Rule 4: Function under 60 lines
+fn build_template(name: String, items: &[MissionItem]) -> Result<MissionTemplate, String> {
+    let origin = items.iter()
+        .find(|item| item.position.is_some())
+        .map(MissionItem::location)
+        .ok_or("Cannot save a template from a mission without positioned items")?;
+
+    let template_items = items.iter()
+        .map(|item| {
+            let offset = item.position.as_ref().map(|_| {
+                let (north_m, east_m) = local_offset_m(&origin, &item.location());
+                TemplateOffset { north_m, east_m }
+            });
+            TemplateItem {
+                item: item.clone(),
+                offset,
+            }
+        })
+        .collect();
+
+    Ok(MissionTemplate {
+        name,
+        items: template_items,
+    })
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+fn instantiate_template(
+    template: &MissionTemplate,
+    anchor: &Coordinate,
+    heading_deg: f64,
+) -> Vec<MissionItem> {
+    template.items.iter()
+        .map(|template_item| {
+            let mut item = template_item.item.clone();
+            item.id = crate::generate_mission_item_id();
+
+            if let Some(offset) = template_item.offset {
+                let (north_m, east_m) = rotate_offset(offset.north_m, offset.east_m, heading_deg);
+                let target = offset_coordinate(anchor, north_m, east_m);
+                item.params.lat = target.lat;
+                item.params.lng = target.lng;
+                item.position = Some(Position {
+                    lat: target.lat,
+                    lng: target.lng,
+                    alt: item.params.alt,
+                });
+            }
+            item
+        })
+        .collect()
+}
+
+fn summarize_template(template: &MissionTemplate) -> MissionTemplateSummary {
+    let positioned: Vec<Coordinate> = template.items.iter()
+        .filter(|t| t.offset.is_some())
+        .map(|t| t.item.location())
+        .collect();
+    let total_distance_m: f64 = positioned.windows(2)
+        .map(|leg| distance_m(&leg[0], &leg[1]))
+        .sum();
+
+    MissionTemplateSummary {
+        name: template.name.clone(),
+        item_count: template.items.len(),
+        total_distance_km: total_distance_m / 1000.0,
+    }
+}
+
+// ===== PERSISTENCE =====
+
+fn templates_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app_handle.path_resolver()
+        .app_data_dir()
+        .ok_or("App data directory unavailable")?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create app data directory: {e}"))?;
+    Ok(dir.join(TEMPLATES_FILE))
+}
+
+fn read_templates(path: &Path) -> Result<BTreeMap<String, MissionTemplate>, String> {
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read mission templates: {e}"))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse mission templates: {e}"))
+}
+
+fn write_templates(path: &Path, templates: &BTreeMap<String, MissionTemplate>) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(templates)
+        .map_err(|e| format!("Failed to serialize mission templates: {e}"))?;
+    std::fs::write(path, contents)
+        .map_err(|e| format!("Failed to write mission templates: {e}"))
+}
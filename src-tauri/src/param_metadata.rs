@@ -0,0 +1,214 @@
+// ArduPilot parameter definition metadata (apm.pdef.json)
+// NASA JPL Power of 10 compliant implementation
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::mavlink::{Parameter, VehicleInfo};
+
+// ===== TYPE DEFINITIONS =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamValueLabel {
+    pub value: f64,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamBitLabel {
+    pub bit: u8,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterMetadata {
+    pub param_id: String,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub units: Option<String>,
+    pub min_value: Option<f32>,
+    pub max_value: Option<f32>,
+    pub increment: Option<f32>,
+    pub default_value: Option<f32>,
+    // Enumerated values, for parameters edited through a dropdown
+    pub values: Vec<ParamValueLabel>,
+    // Bit names, for parameters edited as a set of checkboxes
+    pub bitmask: Vec<ParamBitLabel>,
+    pub reboot_required: bool,
+    pub read_only: bool,
+}
+
+pub type ParameterDefinitions = HashMap<String, ParameterMetadata>;
+
+// ===== CONSTANTS =====
+
+const METADATA_URL_BASE: &str = "https://autotest.ardupilot.org/Parameters";
+const METADATA_CACHE_DIR: &str = "param_metadata";
+const METADATA_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+// Firmware names as published under METADATA_URL_BASE
+const FIRMWARES: [&str; 6] = ["ArduCopter", "ArduPlane", "Rover", "ArduSub", "AntennaTracker", "Blimp"];
+// NASA JPL Rule 2: bound the parsed definitions
+const MAX_DEFINITIONS: usize = 10_000;
+const MAX_BITMASK_BIT: u8 = 63;
+
+// ===== FIRMWARE SELECTION =====
+
+// ArduPilot firmware that publishes metadata for a vehicle
+pub fn firmware_for_vehicle(info: &VehicleInfo) -> Result<&'static str, String> {
+    if info.autopilot_type != "ArduPilot" {
+        return Err(format!("Parameter metadata is not available for {} autopilots", info.autopilot_type));
+    }
+    let firmware = match info.vehicle_type.as_str() {
+        "Fixed Wing" | "VTOL_FIXEDROTOR" | "VTOL_TAILSITTER_QUADROTOR" => "ArduPlane",
+        "Rover" | "SURFACE_BOAT" => "Rover",
+        "SUBMARINE" => "ArduSub",
+        "ANTENNA_TRACKER" => "AntennaTracker",
+        "AIRSHIP" => "Blimp",
+        _ => "ArduCopter",
+    };
+    Ok(firmware)
+}
+
+pub fn validate_firmware(firmware: &str) -> Result<&'static str, String> {
+    FIRMWARES.iter()
+        .find(|name| name.eq_ignore_ascii_case(firmware))
+        .copied()
+        .ok_or_else(|| format!("Unknown firmware '{}'; expected one of {}", firmware, FIRMWARES.join(", ")))
+}
+
+// ===== LOADING =====
+
+// Cached copy first; the download is kept for offline use
+pub async fn load_definitions(app_handle: &tauri::AppHandle, firmware: &'static str) -> Result<ParameterDefinitions, String> {
+    let path = cache_path(app_handle, firmware)?;
+    if let Ok(data) = std::fs::read(&path) {
+        match parse_pdef_json(&data) {
+            Ok(definitions) => return Ok(definitions),
+            Err(e) => eprintln!("Ignoring cached metadata {}: {e}", path.display()),
+        }
+    }
+
+    let data = download_pdef(firmware).await?;
+    let definitions = parse_pdef_json(&data)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    std::fs::write(&path, &data).map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    Ok(definitions)
+}
+
+async fn download_pdef(firmware: &str) -> Result<Vec<u8>, String> {
+    let url = format!("{METADATA_URL_BASE}/{firmware}/apm.pdef.json");
+    let client = reqwest::Client::builder()
+        .timeout(METADATA_DOWNLOAD_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
+    let response = client.get(&url).send().await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Failed to download {url}: {e}"))?;
+    let bytes = response.bytes().await.map_err(|e| format!("Failed to read {url}: {e}"))?;
+    Ok(bytes.to_vec())
+}
+
+fn cache_path(app_handle: &tauri::AppHandle, firmware: &str) -> Result<PathBuf, String> {
+    let dir = app_handle.path_resolver()
+        .app_data_dir()
+        .ok_or("App data directory unavailable")?;
+    Ok(dir.join(METADATA_CACHE_DIR).join(format!("{firmware}.json")))
+}
+
+// ===== PARSING =====
+
+// apm.pdef.json groups parameters by prefix: {"ARMING_": {"ARMING_CHECK": {...}}}
+pub fn parse_pdef_json(data: &[u8]) -> Result<ParameterDefinitions, String> {
+    let root: Value = serde_json::from_slice(data)
+        .map_err(|e| format!("Invalid parameter metadata: {e}"))?;
+    let groups = root.as_object().ok_or("Parameter metadata must be a JSON object")?;
+
+    let mut definitions = HashMap::new();
+    for group in groups.values().filter_map(Value::as_object) {
+        for (name, fields) in group {
+            let fields = match fields.as_object() {
+                Some(fields) if fields.contains_key("Description") || fields.contains_key("DisplayName") => fields,
+                _ => continue,
+            };
+            if definitions.len() >= MAX_DEFINITIONS {
+                return Err(format!("Parameter metadata has more than {MAX_DEFINITIONS} definitions"));
+            }
+            definitions.insert(name.clone(), metadata_from_fields(name, fields));
+        }
+    }
+    if definitions.is_empty() {
+        return Err("Parameter metadata contains no definitions".to_string());
+    }
+    Ok(definitions)
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+fn metadata_from_fields(name: &str, fields: &serde_json::Map<String, Value>) -> ParameterMetadata {
+    let text = |key: &str| fields.get(key).and_then(Value::as_str).map(|s| s.trim().to_string());
+    let flag = |key: &str| text(key).map_or(false, |s| s.eq_ignore_ascii_case("true"));
+    let range = fields.get("Range");
+    let range_bound = |key: &str| range.and_then(|r| r.get(key)).and_then(number).map(|n| n as f32);
+
+    let mut values: Vec<ParamValueLabel> = labelled(fields.get("Values"))
+        .filter_map(|(key, label)| key.trim().parse().ok().map(|value| ParamValueLabel { value, label }))
+        .collect();
+    values.sort_by(|a, b| a.value.partial_cmp(&b.value).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut bitmask: Vec<ParamBitLabel> = labelled(fields.get("Bitmask"))
+        .filter_map(|(key, label)| key.trim().parse().ok().map(|bit| ParamBitLabel { bit, label }))
+        .filter(|bit| bit.bit <= MAX_BITMASK_BIT)
+        .collect();
+    bitmask.sort_by_key(|bit| bit.bit);
+
+    ParameterMetadata {
+        param_id: name.to_string(),
+        display_name: text("DisplayName"),
+        description: text("Description"),
+        units: text("Units"),
+        min_value: range_bound("low"),
+        max_value: range_bound("high"),
+        increment: fields.get("Increment").and_then(number).map(|n| n as f32),
+        default_value: fields.get("Default").and_then(number).map(|n| n as f32),
+        values,
+        bitmask,
+        reboot_required: flag("RebootRequired"),
+        read_only: flag("ReadOnly"),
+    }
+}
+
+// {"0": "Disabled", "1": "Enabled"} as (key, label) pairs
+fn labelled(field: Option<&Value>) -> impl Iterator<Item = (&String, String)> {
+    field.and_then(Value::as_object)
+        .into_iter()
+        .flat_map(|map| map.iter())
+        .filter_map(|(key, label)| label.as_str().map(|label| (key, label.trim().to_string())))
+}
+
+// Metadata stores numbers as strings, but accept either
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+    .filter(|n: &f64| n.is_finite())
+}
+
+// ===== MERGING =====
+
+pub fn apply_metadata(param: &mut Parameter, metadata: &ParameterMetadata) {
+    param.description = metadata.description.clone().or_else(|| param.description.take());
+    param.min_value = metadata.min_value.or(param.min_value);
+    param.max_value = metadata.max_value.or(param.max_value);
+    param.units = metadata.units.clone().or_else(|| param.units.take());
+    param.increment = metadata.increment.or(param.increment);
+    param.default_value = metadata.default_value.or(param.default_value);
+    param.values = metadata.values.clone();
+    param.bitmask = metadata.bitmask.clone();
+}
@@ -0,0 +1,193 @@
+// Flight performance planning backend
+// NASA JPL Power of 10 compliant implementation
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::mission_analysis::ValidationWarning;
+use crate::AppState;
+
+// ===== TYPE DEFINITIONS =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceEstimates {
+    pub hover_thrust_pct: f64,
+    pub max_airspeed_mps: f64,
+    pub endurance_reduction_pct: f64,
+}
+
+// ===== CONSTANTS =====
+
+const ISA_SEA_LEVEL_TEMP_C: f64 = 15.0;
+// Standard lapse rate, degrees C per 1000 ft
+const ISA_LAPSE_RATE_C_PER_1000FT: f64 = 1.98;
+// Feet of density altitude per degree C of deviation from ISA
+const DENSITY_ALTITUDE_FT_PER_C: f64 = 118.8;
+const MIN_PRESSURE_ALTITUDE_FT: f64 = -2000.0;
+const MAX_PRESSURE_ALTITUDE_FT: f64 = 60000.0;
+const MIN_TEMPERATURE_C: f64 = -90.0;
+const MAX_TEMPERATURE_C: f64 = 60.0;
+const MAX_TAKEOFF_WEIGHT_KG: f64 = 150.0;
+
+// Linear models, referenced to a standard day at sea level. Air density
+// falls roughly 3% per 1000 ft, so rotors need that much more thrust to hover
+// and batteries drain that much faster.
+const SEA_LEVEL_HOVER_THRUST_PCT: f64 = 40.0;
+const HOVER_THRUST_PCT_PER_KG: f64 = 1.0;
+const DENSITY_LOSS_PCT_PER_1000FT: f64 = 3.0;
+const SEA_LEVEL_MAX_AIRSPEED_MPS: f64 = 20.0;
+const AIRSPEED_LOSS_PCT_PER_1000FT: f64 = 2.0;
+
+// Advisory thresholds for mission validation
+const HOVER_THRUST_WARN_PCT: f64 = 70.0;
+const ENDURANCE_REDUCTION_WARN_PCT: f64 = 10.0;
+
+// ===== DENSITY ALTITUDE =====
+
+// Standard atmosphere temperature at a pressure altitude
+pub fn calculate_isa_temperature(altitude_ft: f64) -> f64 {
+    ISA_SEA_LEVEL_TEMP_C - ISA_LAPSE_RATE_C_PER_1000FT * altitude_ft / 1000.0
+}
+
+// Density altitude = pressure altitude + 118.8 x (OAT - ISA temperature)
+pub fn density_altitude_ft(pressure_altitude_ft: f64, temperature_c: f64) -> Result<f64, String> {
+    if !pressure_altitude_ft.is_finite()
+        || !(MIN_PRESSURE_ALTITUDE_FT..=MAX_PRESSURE_ALTITUDE_FT).contains(&pressure_altitude_ft)
+    {
+        return Err(format!(
+            "Pressure altitude must be between {MIN_PRESSURE_ALTITUDE_FT} and {MAX_PRESSURE_ALTITUDE_FT} ft"
+        ));
+    }
+    if !temperature_c.is_finite() || !(MIN_TEMPERATURE_C..=MAX_TEMPERATURE_C).contains(&temperature_c) {
+        return Err(format!("Temperature must be between {MIN_TEMPERATURE_C} and {MAX_TEMPERATURE_C} °C"));
+    }
+    let isa_temperature_c = calculate_isa_temperature(pressure_altitude_ft);
+    Ok(pressure_altitude_ft + DENSITY_ALTITUDE_FT_PER_C * (temperature_c - isa_temperature_c))
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+pub fn performance_estimates(density_altitude_ft: f64, max_takeoff_weight_kg: f64) -> Result<PerformanceEstimates, String> {
+    if !density_altitude_ft.is_finite() {
+        return Err("Density altitude must be a finite number".to_string());
+    }
+    if !(max_takeoff_weight_kg.is_finite() && max_takeoff_weight_kg > 0.0 && max_takeoff_weight_kg <= MAX_TAKEOFF_WEIGHT_KG) {
+        return Err(format!("Maximum takeoff weight must be between 0 and {MAX_TAKEOFF_WEIGHT_KG} kg"));
+    }
+
+    // Below sea-level density is treated as sea level; the models only degrade
+    let thousands_ft = density_altitude_ft.max(0.0) / 1000.0;
+    let sea_level_hover_pct = SEA_LEVEL_HOVER_THRUST_PCT + HOVER_THRUST_PCT_PER_KG * max_takeoff_weight_kg;
+    let density_loss = DENSITY_LOSS_PCT_PER_1000FT * thousands_ft / 100.0;
+    let airspeed_loss = AIRSPEED_LOSS_PCT_PER_1000FT * thousands_ft / 100.0;
+
+    Ok(PerformanceEstimates {
+        hover_thrust_pct: sea_level_hover_pct * (1.0 + density_loss),
+        max_airspeed_mps: (SEA_LEVEL_MAX_AIRSPEED_MPS * (1.0 - airspeed_loss)).max(0.0),
+        endurance_reduction_pct: (density_loss * 100.0).min(100.0),
+    })
+}
+
+// Warnings for the stored advisory, included in mission validation
+pub fn advisory_warnings(estimates: &PerformanceEstimates) -> Vec<ValidationWarning> {
+    let mut warnings = Vec::new();
+    if estimates.hover_thrust_pct >= 100.0 {
+        warnings.push(ValidationWarning {
+            item_id: String::new(),
+            rule: "density_altitude_hover".to_string(),
+            message: format!(
+                "Estimated hover thrust is {:.0}%; the vehicle cannot hover at this density altitude",
+                estimates.hover_thrust_pct
+            ),
+        });
+    } else if estimates.hover_thrust_pct > HOVER_THRUST_WARN_PCT {
+        warnings.push(ValidationWarning {
+            item_id: String::new(),
+            rule: "density_altitude_hover".to_string(),
+            message: format!(
+                "Estimated hover thrust is {:.0}%, leaving little margin for climb and wind",
+                estimates.hover_thrust_pct
+            ),
+        });
+    }
+    if estimates.endurance_reduction_pct > ENDURANCE_REDUCTION_WARN_PCT {
+        warnings.push(ValidationWarning {
+            item_id: String::new(),
+            rule: "density_altitude_endurance".to_string(),
+            message: format!(
+                "Density altitude reduces endurance by about {:.0}%",
+                estimates.endurance_reduction_pct
+            ),
+        });
+    }
+    warnings
+}
+
+// ===== PERFORMANCE COMMANDS =====
+
+#[tauri::command]
+pub async fn calculate_density_altitude(pressure_altitude_ft: f64, temperature_c: f64) -> Result<f64, String> {
+    density_altitude_ft(pressure_altitude_ft, temperature_c)
+}
+
+#[tauri::command]
+pub async fn get_isa_temperature(altitude_ft: f64) -> Result<f64, String> {
+    if !altitude_ft.is_finite() {
+        return Err("Altitude must be a finite number".to_string());
+    }
+    Ok(calculate_isa_temperature(altitude_ft))
+}
+
+// Estimates are kept as the planning advisory reported by `validate_mission`
+#[tauri::command]
+pub async fn get_performance_estimates(
+    density_altitude_ft: f64,
+    max_takeoff_weight_kg: f64,
+    state: State<'_, AppState>,
+) -> Result<PerformanceEstimates, String> {
+    let estimates = performance_estimates(density_altitude_ft, max_takeoff_weight_kg)?;
+    *state.performance_advisory.lock().map_err(|_| "Failed to lock performance advisory")? = Some(estimates.clone());
+    Ok(estimates)
+}
+
+#[tauri::command]
+pub async fn clear_performance_advisory(state: State<'_, AppState>) -> Result<(), String> {
+    *state.performance_advisory.lock().map_err(|_| "Failed to lock performance advisory")? = None;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_day_at_sea_level_is_zero_density_altitude() {
+        assert_eq!(calculate_isa_temperature(0.0), ISA_SEA_LEVEL_TEMP_C);
+        let density_altitude = density_altitude_ft(0.0, ISA_SEA_LEVEL_TEMP_C).unwrap();
+        assert!(density_altitude.abs() < 1e-9, "got {density_altitude}");
+    }
+
+    #[test]
+    fn hot_day_at_5000_ft_follows_isa_formula() {
+        // ISA at 5000 ft is 15 - 1.98 x 5 = 5.1 °C, so 30 °C is 24.9 °C above standard
+        let isa_temperature = calculate_isa_temperature(5000.0);
+        assert!((isa_temperature - 5.1).abs() < 1e-9, "got {isa_temperature}");
+
+        let density_altitude = density_altitude_ft(5000.0, 30.0).unwrap();
+        assert!((density_altitude - 7958.12).abs() < 1e-6, "got {density_altitude}");
+
+        assert!(density_altitude_ft(70000.0, 15.0).is_err());
+        assert!(density_altitude_ft(5000.0, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn high_density_altitude_raises_advisory_warnings() {
+        let sea_level = performance_estimates(0.0, 10.0).unwrap();
+        assert!(advisory_warnings(&sea_level).is_empty());
+
+        let high = performance_estimates(7958.12, 10.0).unwrap();
+        assert!(high.hover_thrust_pct > sea_level.hover_thrust_pct);
+        assert!(high.max_airspeed_mps < sea_level.max_airspeed_mps);
+        let rules: Vec<String> = advisory_warnings(&high).into_iter().map(|w| w.rule).collect();
+        assert!(rules.iter().any(|rule| rule == "density_altitude_endurance"), "got {rules:?}");
+    }
+}
@@ -0,0 +1,686 @@
+// SDR signal analysis backend
+// NASA JPL Power of 10 compliant implementation
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{Manager, State};
+
+use crate::{AppState, SdrConfig};
+
+// ===== TYPE DEFINITIONS =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalPeak {
+    pub frequency_hz: f64,
+    pub magnitude_dbm: f64,
+    // Width at 3 dB below the peak
+    pub bandwidth_hz: f64,
+    // Above the median of the FFT line
+    pub snr_db: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalAnnotation {
+    pub frequency_hz: f64,
+    pub label: String,
+    pub color: String,
+    pub updated_ms: u64,
+}
+
+// Strongest magnitude seen while dwelling on each scan frequency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanResult {
+    pub frequencies: Vec<f64>,
+    pub peak_magnitudes: Vec<f64>,
+    pub scan_start_ms: u64,
+    pub scan_end_ms: u64,
+}
+
+// Saved tuning the operator can return to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SdrBookmark {
+    pub id: String,
+    pub label: String,
+    pub frequency_hz: f64,
+    pub sample_rate_hz: f64,
+    pub gain_db: f64,
+}
+
+// ===== STATE MANAGEMENT =====
+
+pub struct SdrState {
+    // Operator labels keyed by frequency rounded to the nearest Hz; kept for the session
+    known_signals: Mutex<HashMap<u64, SignalAnnotation>>,
+    // Stop flag of the running frequency scan
+    scan_stop: Mutex<Option<Arc<AtomicBool>>>,
+    // In insertion order; mirrored to BOOKMARKS_FILE
+    bookmarks: Mutex<Vec<SdrBookmark>>,
+}
+
+impl SdrState {
+    pub fn new() -> Self {
+        Self {
+            known_signals: Mutex::new(HashMap::new()),
+            scan_stop: Mutex::new(None),
+            bookmarks: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+// ===== CONSTANTS =====
+
+const PEAK_BANDWIDTH_DROP_DB: f64 = 3.0;
+const MAX_SIGNAL_LABEL_LENGTH: usize = 100;
+// NASA JPL Rule 2: bound the annotation table
+const MAX_SIGNAL_ANNOTATIONS: usize = 1000;
+// Each dwell must span at least one FFT frame
+const MIN_SCAN_DWELL_MS: u32 = crate::SDR_FFT_INTERVAL_MS as u32;
+const MAX_SCAN_DWELL_MS: u32 = 10_000;
+const MAX_SCAN_STEPS: usize = 10_000;
+const BOOKMARKS_FILE: &str = "sdr_bookmarks.json";
+const MAX_BOOKMARKS: usize = 500;
+const MAX_BOOKMARK_ID_LENGTH: usize = 64;
+
+// ===== PEAK DETECTION =====
+
+// Peaks of the most recent FFT line, strongest kept where two are closer
+// than `min_separation_hz`, in order of frequency
+#[tauri::command]
+pub fn detect_signal_peaks(
+    threshold_dbm: f64,
+    min_separation_hz: f64,
+    state: State<AppState>,
+) -> Result<Vec<SignalPeak>, String> {
+    if !threshold_dbm.is_finite() {
+        return Err("Threshold must be a finite number".to_string());
+    }
+    if !(min_separation_hz.is_finite() && min_separation_hz >= 0.0) {
+        return Err("Minimum separation must not be negative".to_string());
+    }
+    latest_peaks(&state, threshold_dbm, min_separation_hz)
+}
+
+fn latest_peaks(state: &AppState, threshold_dbm: f64, min_separation_hz: f64) -> Result<Vec<SignalPeak>, String> {
+    let config = *state.sdr_config.read().map_err(|_| "Failed to lock SDR config")?;
+    let magnitudes = state.waterfall.lock()
+        .map_err(|_| "Failed to lock waterfall")?
+        .lines
+        .back()
+        .cloned()
+        .ok_or("No FFT data received yet")?;
+
+    let start_hz = config.center_frequency_hz - config.sample_rate_hz / 2.0;
+    let bin_hz = config.sample_rate_hz / magnitudes.len() as f64;
+    Ok(find_peaks(&magnitudes, start_hz, bin_hz, threshold_dbm, min_separation_hz))
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+fn find_peaks(
+    magnitudes: &[f64],
+    start_hz: f64,
+    bin_hz: f64,
+    threshold_dbm: f64,
+    min_separation_hz: f64,
+) -> Vec<SignalPeak> {
+    let noise_floor = median(magnitudes);
+    let last = magnitudes.len().saturating_sub(1);
+    // Local maxima; the first bin of a plateau stands for it
+    let mut candidates: Vec<usize> = (0..magnitudes.len())
+        .filter(|&i| {
+            let m = magnitudes[i];
+            m >= threshold_dbm
+                && (i == 0 || m > magnitudes[i - 1])
+                && (i == last || m >= magnitudes[i + 1])
+        })
+        .collect();
+    candidates.sort_by(|&a, &b| {
+        magnitudes[b].partial_cmp(&magnitudes[a]).unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut peaks: Vec<SignalPeak> = Vec::new();
+    for bin in candidates {
+        let frequency_hz = start_hz + bin as f64 * bin_hz;
+        if peaks.iter().any(|p| (p.frequency_hz - frequency_hz).abs() < min_separation_hz) {
+            continue;
+        }
+        peaks.push(SignalPeak {
+            frequency_hz,
+            magnitude_dbm: magnitudes[bin],
+            bandwidth_hz: half_power_width_bins(magnitudes, bin) * bin_hz,
+            snr_db: magnitudes[bin] - noise_floor,
+        });
+    }
+    peaks.sort_by(|a, b| a.frequency_hz.partial_cmp(&b.frequency_hz).unwrap_or(std::cmp::Ordering::Equal));
+    peaks
+}
+
+// Width in bins between the points 3 dB below the peak, interpolated between
+// bins; a side that never drops that far extends to the edge of the line
+fn half_power_width_bins(magnitudes: &[f64], peak: usize) -> f64 {
+    let cutoff = magnitudes[peak] - PEAK_BANDWIDTH_DROP_DB;
+    let crossing = |inside: usize, outside: usize| {
+        let (a, b) = (magnitudes[inside], magnitudes[outside]);
+        let fraction = if a > b { (a - cutoff) / (a - b) } else { 0.0 };
+        inside as f64 + fraction * (outside as f64 - inside as f64)
+    };
+
+    let mut left = peak as f64;
+    for (i, &m) in magnitudes.iter().enumerate().take(peak).rev() {
+        if m < cutoff {
+            left = crossing(i + 1, i);
+            break;
+        }
+        left = i as f64;
+    }
+    let mut right = peak as f64;
+    for (i, &m) in magnitudes.iter().enumerate().skip(peak + 1) {
+        if m < cutoff {
+            right = crossing(i - 1, i);
+            break;
+        }
+        right = i as f64;
+    }
+    right - left
+}
+
+fn median(values: &[f64]) -> f64 {
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+// ===== SIGNAL ANNOTATIONS =====
+
+// Label a frequency; annotating the same Hz again replaces the label
+#[tauri::command]
+pub fn annotate_signal(
+    frequency_hz: f64,
+    label: String,
+    color: String,
+    sdr: State<SdrState>,
+) -> Result<(), String> {
+    if !(frequency_hz.is_finite() && frequency_hz > 0.0) {
+        return Err("Frequency must be positive".to_string());
+    }
+    let label = label.trim().to_string();
+    if label.is_empty() || label.chars().count() > MAX_SIGNAL_LABEL_LENGTH {
+        return Err(format!("Label must be 1-{MAX_SIGNAL_LABEL_LENGTH} characters"));
+    }
+    if !is_hex_color(&color) {
+        return Err(format!("Invalid color {color}; expected #RRGGBB"));
+    }
+
+    let key = frequency_hz.round() as u64;
+    let mut known = sdr.known_signals.lock().map_err(|_| "Failed to lock signal annotations")?;
+    if !known.contains_key(&key) && known.len() >= MAX_SIGNAL_ANNOTATIONS {
+        return Err(format!("At most {MAX_SIGNAL_ANNOTATIONS} signals can be annotated"));
+    }
+    known.insert(key, SignalAnnotation {
+        frequency_hz,
+        label,
+        color,
+        updated_ms: current_time_ms(),
+    });
+    Ok(())
+}
+
+// Annotations in order of frequency
+#[tauri::command]
+pub fn get_signal_annotations(sdr: State<SdrState>) -> Result<Vec<SignalAnnotation>, String> {
+    let known = sdr.known_signals.lock().map_err(|_| "Failed to lock signal annotations")?;
+    let mut annotations: Vec<SignalAnnotation> = known.values().cloned().collect();
+    annotations.sort_by(|a, b| a.frequency_hz.partial_cmp(&b.frequency_hz).unwrap_or(std::cmp::Ordering::Equal));
+    Ok(annotations)
+}
+
+fn is_hex_color(color: &str) -> bool {
+    color.len() == 7
+        && color.starts_with('#')
+        && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn current_time_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// ===== FREQUENCY SCAN =====
+
+// Retune through [start_hz, stop_hz) in `step_hz` steps, dwelling `dwell_ms`
+// at each; emits `sdr-scan-progress` per step and `sdr-scan-complete` once the
+// sweep finishes. The center frequency is restored afterwards.
+#[tauri::command]
+pub fn start_frequency_scan(
+    app_handle: tauri::AppHandle,
+    start_hz: f64,
+    stop_hz: f64,
+    step_hz: f64,
+    dwell_ms: u32,
+    sdr: State<SdrState>,
+) -> Result<(), String> {
+    if !(start_hz.is_finite() && start_hz > 0.0 && stop_hz.is_finite() && stop_hz > start_hz) {
+        return Err("Scan range must be positive with the stop above the start".to_string());
+    }
+    if !(step_hz.is_finite() && step_hz > 0.0) {
+        return Err("Scan step must be positive".to_string());
+    }
+    if !(MIN_SCAN_DWELL_MS..=MAX_SCAN_DWELL_MS).contains(&dwell_ms) {
+        return Err(format!("Dwell must be between {MIN_SCAN_DWELL_MS} and {MAX_SCAN_DWELL_MS} ms"));
+    }
+    let frequencies = scan_frequencies(start_hz, stop_hz, step_hz)?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let mut scan_stop = sdr.scan_stop.lock().map_err(|_| "Failed to lock frequency scan")?;
+        if scan_stop.is_some() {
+            return Err("Frequency scan already running".to_string());
+        }
+        *scan_stop = Some(stop.clone());
+    }
+    tauri::async_runtime::spawn(run_frequency_scan(app_handle, frequencies, Duration::from_millis(u64::from(dwell_ms)), stop));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_frequency_scan(sdr: State<SdrState>) -> Result<(), String> {
+    let stop = sdr.scan_stop.lock()
+        .map_err(|_| "Failed to lock frequency scan")?
+        .take()
+        .ok_or("No frequency scan running")?;
+    stop.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+// Frequencies of each step, from `start_hz` up to but not including `stop_hz`
+fn scan_frequencies(start_hz: f64, stop_hz: f64, step_hz: f64) -> Result<Vec<f64>, String> {
+    // Tolerance keeps an exact multiple of the step from adding a step at the stop frequency
+    let steps = ((stop_hz - start_hz) / step_hz - 1e-9).ceil() as usize;
+    if steps > MAX_SCAN_STEPS {
+        return Err(format!("Scan would take more than {MAX_SCAN_STEPS} steps"));
+    }
+    Ok((0..steps).map(|k| start_hz + k as f64 * step_hz).collect())
+}
+
+async fn run_frequency_scan(app_handle: tauri::AppHandle, frequencies: Vec<f64>, dwell: Duration, stop: Arc<AtomicBool>) {
+    let emitter = app_handle.clone();
+    let emit = move |event: &str, payload: serde_json::Value| {
+        if let Err(e) = emitter.emit_all(event, payload) {
+            eprintln!("Failed to emit {event}: {e}");
+        }
+    };
+    sweep_frequencies(&app_handle.state::<AppState>(), &app_handle.state::<SdrState>(), frequencies, dwell, stop, emit).await;
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+async fn sweep_frequencies(
+    state: &AppState,
+    sdr: &SdrState,
+    frequencies: Vec<f64>,
+    dwell: Duration,
+    stop: Arc<AtomicBool>,
+    mut emit: impl FnMut(&str, serde_json::Value),
+) {
+    let original_hz = state.sdr_config.read().map(|config| config.center_frequency_hz).ok();
+    let scan_start_ms = current_time_ms();
+    let mut peak_magnitudes = Vec::with_capacity(frequencies.len());
+
+    for (step, &frequency) in frequencies.iter().enumerate() {
+        if stop.load(Ordering::SeqCst) {
+            break;
+        }
+        if let Ok(mut config) = state.sdr_config.write() {
+            config.center_frequency_hz = frequency;
+        }
+        let step_start_ms = current_time_ms();
+        tokio::time::sleep(dwell).await;
+        peak_magnitudes.push(peak_magnitude_since(state, step_start_ms));
+
+        let progress = serde_json::json!({
+            "current_hz": frequency,
+            "pct_complete": ((step + 1) as f32 / frequencies.len() as f32 * 100.0),
+        });
+        emit("sdr-scan-progress", progress);
+    }
+
+    if let (Some(original_hz), Ok(mut config)) = (original_hz, state.sdr_config.write()) {
+        config.center_frequency_hz = original_hz;
+    }
+    if let Ok(mut scan_stop) = sdr.scan_stop.lock() {
+        // A stopped scan has already cleared the slot
+        if scan_stop.as_ref().map_or(false, |current| Arc::ptr_eq(current, &stop)) {
+            *scan_stop = None;
+        }
+    }
+    if stop.load(Ordering::SeqCst) {
+        return;
+    }
+
+    let result = ScanResult {
+        frequencies,
+        peak_magnitudes,
+        scan_start_ms,
+        scan_end_ms: current_time_ms(),
+    };
+    match serde_json::to_value(result) {
+        Ok(result) => emit("sdr-scan-complete", result),
+        Err(e) => eprintln!("Failed to serialize scan result: {e}"),
+    }
+}
+
+// Strongest bin of the FFT lines emitted since `since_ms`
+fn peak_magnitude_since(state: &AppState, since_ms: u64) -> f64 {
+    let waterfall = match state.waterfall.lock() {
+        Ok(waterfall) => waterfall,
+        Err(_) => return crate::SDR_MAGNITUDE_FLOOR_DB,
+    };
+    waterfall.lines.iter()
+        .zip(waterfall.timestamps_ms.iter())
+        .filter(|(_, &timestamp_ms)| timestamp_ms >= since_ms)
+        .flat_map(|(line, _)| line.iter().copied())
+        .fold(crate::SDR_MAGNITUDE_FLOOR_DB, f64::max)
+}
+
+// ===== BOOKMARKS =====
+
+#[tauri::command]
+pub fn add_sdr_bookmark(
+    app_handle: tauri::AppHandle,
+    bookmark: SdrBookmark,
+    sdr: State<SdrState>,
+) -> Result<(), String> {
+    let bookmark = validate_bookmark(bookmark)?;
+    let path = bookmarks_path(&app_handle)?;
+    let mut bookmarks = sdr.bookmarks.lock().map_err(|_| "Failed to lock SDR bookmarks")?;
+    *bookmarks = with_bookmark(&bookmarks, bookmark, &path)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn remove_sdr_bookmark(
+    app_handle: tauri::AppHandle,
+    id: String,
+    sdr: State<SdrState>,
+) -> Result<(), String> {
+    let path = bookmarks_path(&app_handle)?;
+    let mut bookmarks = sdr.bookmarks.lock().map_err(|_| "Failed to lock SDR bookmarks")?;
+    *bookmarks = without_bookmark(&bookmarks, &id, &path)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_sdr_bookmarks(sdr: State<SdrState>) -> Result<Vec<SdrBookmark>, String> {
+    let bookmarks = sdr.bookmarks.lock().map_err(|_| "Failed to lock SDR bookmarks")?;
+    Ok(bookmarks.clone())
+}
+
+// Tune to a bookmark in one config update; its gain is manual, so AGC is
+// switched off
+#[tauri::command]
+pub fn load_sdr_bookmark(
+    id: String,
+    sdr: State<SdrState>,
+    state: State<AppState>,
+) -> Result<(), String> {
+    let bookmark = sdr.bookmarks.lock()
+        .map_err(|_| "Failed to lock SDR bookmarks")?
+        .iter()
+        .find(|bookmark| bookmark.id == id)
+        .cloned()
+        .ok_or_else(|| format!("Bookmark {id} not found"))?;
+    crate::update_sdr_config(&state, |config| bookmark_config(&bookmark, config))
+}
+
+// Install bookmarks saved by earlier sessions
+pub fn load_bookmarks(app: &tauri::App) {
+    let path = match bookmarks_path(&app.handle()) {
+        Ok(path) => path,
+        Err(_) => return,
+    };
+    let loaded = match read_bookmarks(&path) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{e}");
+            return;
+        }
+    };
+    let sdr = app.state::<SdrState>();
+    let installed = match sdr.bookmarks.lock() {
+        Ok(mut bookmarks) => {
+            *bookmarks = loaded;
+            true
+        }
+        Err(_) => false,
+    };
+    if !installed {
+        eprintln!("Failed to install SDR bookmarks");
+    }
+}
+
+// The list with `bookmark` appended, once it is saved to `path`; the caller
+// installs it only then, so a failed write leaves memory matching disk
+fn with_bookmark(current: &[SdrBookmark], bookmark: SdrBookmark, path: &Path) -> Result<Vec<SdrBookmark>, String> {
+    if current.iter().any(|existing| existing.id == bookmark.id) {
+        return Err(format!("Bookmark {} already exists", bookmark.id));
+    }
+    if current.len() >= MAX_BOOKMARKS {
+        return Err(format!("At most {MAX_BOOKMARKS} bookmarks can be saved"));
+    }
+    let mut updated = current.to_vec();
+    updated.push(bookmark);
+    write_bookmarks(path, &updated)?;
+    Ok(updated)
+}
+
+// The list without bookmark `id`, once it is saved to `path`
+fn without_bookmark(current: &[SdrBookmark], id: &str, path: &Path) -> Result<Vec<SdrBookmark>, String> {
+    let index = current.iter()
+        .position(|bookmark| bookmark.id == id)
+        .ok_or_else(|| format!("Bookmark {id} not found"))?;
+    let mut updated = current.to_vec();
+    updated.remove(index);
+    write_bookmarks(path, &updated)?;
+    Ok(updated)
+}
+
+fn bookmark_config(bookmark: &SdrBookmark, current: SdrConfig) -> SdrConfig {
+    SdrConfig {
+        center_frequency_hz: bookmark.frequency_hz,
+        sample_rate_hz: bookmark.sample_rate_hz,
+        gain_db: bookmark.gain_db,
+        agc_enabled: false,
+        ..current
+    }
+}
+
+// The tuning must be one `set_sdr_config` would accept
+fn validate_bookmark(bookmark: SdrBookmark) -> Result<SdrBookmark, String> {
+    let id = bookmark.id.trim().to_string();
+    if id.is_empty() || id.chars().count() > MAX_BOOKMARK_ID_LENGTH {
+        return Err(format!("Bookmark id must be 1-{MAX_BOOKMARK_ID_LENGTH} characters"));
+    }
+    let label = bookmark.label.trim().to_string();
+    if label.is_empty() || label.chars().count() > MAX_SIGNAL_LABEL_LENGTH {
+        return Err(format!("Label must be 1-{MAX_SIGNAL_LABEL_LENGTH} characters"));
+    }
+    let bookmark = SdrBookmark { id, label, ..bookmark };
+    crate::validate_sdr_config(&bookmark_config(&bookmark, SdrConfig::default()))?;
+    Ok(bookmark)
+}
+
+fn bookmarks_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app_handle.path_resolver()
+        .app_data_dir()
+        .ok_or("App data directory unavailable")?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create app data directory: {e}"))?;
+    Ok(dir.join(BOOKMARKS_FILE))
+}
+
+fn read_bookmarks(path: &Path) -> Result<Vec<SdrBookmark>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read SDR bookmarks: {e}"))?;
+    let mut bookmarks: Vec<SdrBookmark> = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse SDR bookmarks: {e}"))?;
+    bookmarks.truncate(MAX_BOOKMARKS);
+    Ok(bookmarks)
+}
+
+fn write_bookmarks(path: &Path, bookmarks: &[SdrBookmark]) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(bookmarks)
+        .map_err(|e| format!("Failed to serialize SDR bookmarks: {e}"))?;
+    std::fs::write(path, contents)
+        .map_err(|e| format!("Failed to write SDR bookmarks: {e}"))
+}
+
+// ===== MODULE REGISTRATION =====
+
+pub fn init() -> SdrState {
+    SdrState::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Gaussian bump in dB over a -100 dBm floor
+    fn add_peak(magnitudes: &mut [f64], center_bin: f64, height_db: f64, width_bins: f64) {
+        for (i, magnitude) in magnitudes.iter_mut().enumerate() {
+            let offset = (i as f64 - center_bin) / width_bins;
+            *magnitude = magnitude.max(-100.0 + height_db * (-offset * offset).exp());
+        }
+    }
+
+    #[test]
+    fn detects_three_synthetic_peaks_at_their_frequencies() {
+        let mut line = vec![-100.0; crate::SDR_FFT_BINS];
+        add_peak(&mut line, 40.0, 60.0, 2.0);
+        add_peak(&mut line, 128.0, 40.0, 8.0);
+        add_peak(&mut line, 200.0, 50.0, 1.5);
+        // A shoulder 2 bins from the first peak, inside the merge distance
+        line[42] = -45.0;
+        line[43] = -46.0;
+
+        let state = AppState::default();
+        assert!(latest_peaks(&state, -70.0, 50_000.0).is_err());
+        state.waterfall.lock().unwrap().push(line, 0);
+
+        // 100 MHz center, 2 MS/s over 256 bins
+        let bin_hz = 2_000_000.0 / 256.0;
+        let start_hz = 99_000_000.0;
+        let peaks = latest_peaks(&state, -70.0, 50_000.0).unwrap();
+        let frequencies: Vec<f64> = peaks.iter().map(|peak| peak.frequency_hz).collect();
+        assert_eq!(frequencies, [start_hz + 40.0 * bin_hz, start_hz + 128.0 * bin_hz, start_hz + 200.0 * bin_hz]);
+        assert_eq!(peaks[0].magnitude_dbm, -40.0);
+        assert!((peaks[1].snr_db - 40.0).abs() < 1e-6, "got {}", peaks[1].snr_db);
+        // A Gaussian drops 3 dB at sqrt(ln(40 / 37)) widths either side of a 40 dB peak
+        let expected_width = 2.0 * 8.0 * (40.0_f64 / 37.0).ln().sqrt() * bin_hz;
+        assert!((peaks[1].bandwidth_hz - expected_width).abs() < 0.1 * expected_width, "got {}", peaks[1].bandwidth_hz);
+
+        // The weakest peak is below a higher threshold
+        assert_eq!(latest_peaks(&state, -55.0, 50_000.0).unwrap().len(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn scans_one_megahertz_in_ten_steps_then_completes() {
+        let frequencies = scan_frequencies(100_000_000.0, 101_000_000.0, 100_000.0).unwrap();
+        assert_eq!(frequencies.len(), 10);
+
+        let state = AppState::default();
+        let sdr = SdrState::new();
+        let stop = Arc::new(AtomicBool::new(false));
+        *sdr.scan_stop.lock().unwrap() = Some(stop.clone());
+        let mut events: Vec<(String, serde_json::Value)> = Vec::new();
+        let dwell = Duration::from_millis(u64::from(MIN_SCAN_DWELL_MS));
+        sweep_frequencies(&state, &sdr, frequencies, dwell, stop, |event, payload| {
+            events.push((event.to_string(), payload));
+        })
+        .await;
+
+        assert_eq!(events.len(), 11);
+        for (step, (event, progress)) in events[..10].iter().enumerate() {
+            assert_eq!(event, "sdr-scan-progress");
+            assert_eq!(progress["current_hz"], 100_000_000.0 + step as f64 * 100_000.0);
+            let pct_complete = progress["pct_complete"].as_f64().unwrap();
+            assert!((pct_complete - (step + 1) as f64 * 10.0).abs() < 1e-4, "got {pct_complete}");
+        }
+        let (event, result) = &events[10];
+        assert_eq!(event, "sdr-scan-complete");
+        assert_eq!(result["frequencies"].as_array().unwrap().len(), 10);
+        assert_eq!(result["peak_magnitudes"].as_array().unwrap().len(), 10);
+        // The tuning is restored and the scan slot freed
+        assert_eq!(state.sdr_config.read().unwrap().center_frequency_hz, SdrConfig::default().center_frequency_hz);
+        assert!(sdr.scan_stop.lock().unwrap().is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stopped_scan_emits_no_completion() {
+        let state = AppState::default();
+        let sdr = SdrState::new();
+        let stop = Arc::new(AtomicBool::new(true));
+        let mut events = Vec::new();
+        let frequencies = scan_frequencies(100_000_000.0, 101_000_000.0, 100_000.0).unwrap();
+        sweep_frequencies(&state, &sdr, frequencies, Duration::from_millis(100), stop, |event, _| {
+            events.push(event.to_string());
+        })
+        .await;
+        assert!(events.is_empty());
+    }
+
+    fn bookmark(id: &str, frequency_hz: f64) -> SdrBookmark {
+        SdrBookmark {
+            id: id.to_string(),
+            label: format!("{id} label"),
+            frequency_hz,
+            sample_rate_hz: 2_048_000.0,
+            gain_db: 20.7,
+        }
+    }
+
+    #[test]
+    fn bookmarks_round_trip_through_json_and_survive_failed_writes() {
+        let dir = std::env::temp_dir().join(format!("olympus-sdr-bookmarks-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(BOOKMARKS_FILE);
+        assert!(read_bookmarks(&path).unwrap().is_empty());
+
+        let saved = with_bookmark(&[], validate_bookmark(bookmark("noaa", 137_100_000.0)).unwrap(), &path).unwrap();
+        let saved = with_bookmark(&saved, bookmark("ais", 161_975_000.0), &path).unwrap();
+        assert!(with_bookmark(&saved, bookmark("ais", 162_025_000.0), &path).is_err());
+
+        let loaded = read_bookmarks(&path).unwrap();
+        let ids: Vec<&str> = loaded.iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(ids, ["noaa", "ais"]);
+        assert_eq!(loaded[1].frequency_hz, 161_975_000.0);
+        assert_eq!(loaded[1].sample_rate_hz, 2_048_000.0);
+        assert_eq!(loaded[1].gain_db, 20.7);
+        assert_eq!(loaded[0].label, "noaa label");
+
+        let remaining = without_bookmark(&loaded, "noaa", &path).unwrap();
+        assert_eq!(read_bookmarks(&path).unwrap().len(), 1);
+        assert!(without_bookmark(&remaining, "noaa", &path).is_err());
+
+        // A directory cannot be written as a file, so nothing is returned to install
+        assert!(with_bookmark(&remaining, bookmark("adsb", 1_090_000_000.0), &dir).is_err());
+        assert!(without_bookmark(&remaining, "ais", &dir).is_err());
+        assert_eq!(read_bookmarks(&path).unwrap().len(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
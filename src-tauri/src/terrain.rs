@@ -0,0 +1,476 @@
+// Terrain elevation and clearance backend
+// NASA JPL Power of 10 compliant implementation
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use tauri::{Manager, State};
+
+use crate::geo::{distance_m, interpolate};
+use crate::map_features::Coordinate;
+use crate::AppState;
+
+// ===== TYPE DEFINITIONS =====
+
+// Pluggable terrain elevation provider (meters above mean sea level)
+pub trait ElevationSource: Send + Sync {
+    fn elevation_m(&self, coordinate: &Coordinate) -> Option<f64>;
+    fn name(&self) -> &str;
+}
+
+// Fallback source treating all terrain as sea level
+pub struct FlatEarth;
+
+impl ElevationSource for FlatEarth {
+    fn elevation_m(&self, _coordinate: &Coordinate) -> Option<f64> {
+        Some(0.0)
+    }
+
+    fn name(&self) -> &str {
+        "flat-earth"
+    }
+}
+
+// Elevation from SRTM .hgt tiles cached in the app data directory
+pub struct SrtmSource {
+    tile_dir: PathBuf,
+    // Loaded tiles by name; None records a tile that is missing or unreadable
+    tiles: Mutex<HashMap<String, Option<Arc<HgtTile>>>>,
+}
+
+// One 1°×1° tile of big-endian i16 samples, rows from north to south
+struct HgtTile {
+    size: usize,
+    samples: Vec<i16>,
+}
+
+impl SrtmSource {
+    pub fn new(tile_dir: PathBuf) -> Self {
+        Self {
+            tile_dir,
+            tiles: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn tile(&self, name: &str) -> Option<Arc<HgtTile>> {
+        let mut tiles = self.tiles.lock().ok()?;
+        tiles.entry(name.to_string())
+            .or_insert_with(|| {
+                let data = std::fs::read(self.tile_dir.join(format!("{name}.hgt"))).ok()?;
+                HgtTile::parse(&data).ok().map(Arc::new)
+            })
+            .clone()
+    }
+}
+
+impl ElevationSource for SrtmSource {
+    fn elevation_m(&self, coordinate: &Coordinate) -> Option<f64> {
+        let lat_floor = coordinate.lat.floor();
+        let lng_floor = coordinate.lng.floor();
+        let tile = self.tile(&hgt_tile_name(lat_floor, lng_floor))?;
+        tile.elevation_m(lat_floor + 1.0 - coordinate.lat, coordinate.lng - lng_floor)
+    }
+
+    fn name(&self) -> &str {
+        "srtm"
+    }
+}
+
+impl HgtTile {
+    fn parse(data: &[u8]) -> Result<Self, String> {
+        let size = HGT_SIZES.iter()
+            .copied()
+            .find(|size| size * size * 2 == data.len())
+            .ok_or_else(|| format!("Unexpected HGT tile size of {} bytes", data.len()))?;
+        let samples = data.chunks_exact(2)
+            .map(|pair| i16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+        Ok(Self { size, samples })
+    }
+
+    // Bilinear elevation at fractional offsets (0..=1) south of the north edge
+    // and east of the west edge; None if any neighbouring sample is void
+    fn elevation_m(&self, south_frac: f64, east_frac: f64) -> Option<f64> {
+        let last = (self.size - 1) as f64;
+        let row = (south_frac * last).clamp(0.0, last);
+        let col = (east_frac * last).clamp(0.0, last);
+        let (row0, col0) = (row.floor() as usize, col.floor() as usize);
+        let (row1, col1) = ((row0 + 1).min(self.size - 1), (col0 + 1).min(self.size - 1));
+
+        let sample = |r: usize, c: usize| {
+            let value = self.samples[r * self.size + c];
+            (value != HGT_VOID).then(|| value as f64)
+        };
+        let (dr, dc) = (row - row0 as f64, col - col0 as f64);
+        let north = sample(row0, col0)? * (1.0 - dc) + sample(row0, col1)? * dc;
+        let south = sample(row1, col0)? * (1.0 - dc) + sample(row1, col1)? * dc;
+        Some(north * (1.0 - dr) + south * dr)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElevationPoint {
+    pub lat: f64,
+    pub lng: f64,
+    pub mission_alt_m: f64,
+    pub terrain_alt_m: f64,
+    pub clearance_m: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerrainViolation {
+    pub from_item_id: String,
+    pub to_item_id: String,
+    pub from: Coordinate,
+    pub to: Coordinate,
+    pub worst_clearance_m: f64,
+    pub worst_point: Coordinate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerrainClearanceReport {
+    pub min_agl_m: f64,
+    pub legs_checked: usize,
+    pub elevation_source: String,
+    pub violations: Vec<TerrainViolation>,
+}
+
+// Terrain is sampled at roughly the resolution of SRTM1 data
+const SAMPLE_SPACING_M: f64 = 30.0;
+// NASA JPL Rule 2: bound the sampling loop
+const MAX_SAMPLES_PER_LEG: usize = 10_000;
+const MAX_PROFILE_POINTS: usize = 100_000;
+
+// SRTM3 (3 arc-second) and SRTM1 (1 arc-second) tile dimensions
+const HGT_SIZES: [usize; 2] = [1201, 3601];
+const HGT_VOID: i16 = -32768;
+const SRTM_DIR: &str = "srtm";
+
+// ===== STATE MANAGEMENT =====
+
+pub struct TerrainState {
+    source: RwLock<Box<dyn ElevationSource>>,
+}
+
+impl TerrainState {
+    pub fn new() -> Self {
+        Self {
+            source: RwLock::new(Box::new(FlatEarth)),
+        }
+    }
+}
+
+// ===== CLEARANCE CHECK =====
+
+#[tauri::command]
+pub async fn check_terrain_clearance(
+    min_agl_m: f64,
+    state: State<'_, AppState>,
+    terrain: State<'_, TerrainState>,
+) -> Result<TerrainClearanceReport, String> {
+    if !min_agl_m.is_finite() || min_agl_m < 0.0 {
+        return Err("Minimum AGL must be a non-negative number".to_string());
+    }
+
+    let items = state.mission_items.lock()
+        .map_err(|_| "Failed to lock state")?
+        .clone();
+    let source = terrain.source.read()
+        .map_err(|_| "Failed to read elevation source")?;
+
+    // Mission altitudes are relative to the launch point, so anchor them
+    // to the terrain elevation under the first positioned item
+    let legs: Vec<(&crate::MissionItem, &crate::MissionItem)> = {
+        let positioned: Vec<&crate::MissionItem> = items.iter()
+            .filter(|item| item.is_on_flight_path())
+            .collect();
+        positioned.windows(2).map(|pair| (pair[0], pair[1])).collect()
+    };
+    let home_elevation_m = match legs.first() {
+        Some((first, _)) => source.elevation_m(&first.location())
+            .ok_or("No terrain data available at the launch point")?,
+        None => 0.0,
+    };
+
+    let mut violations = Vec::new();
+    for (from_item, to_item) in &legs {
+        let from = from_item.location();
+        let to = to_item.location();
+        if let Some((worst_clearance_m, worst_point)) =
+            worst_leg_clearance(source.as_ref(), &from, &to, home_elevation_m)
+        {
+            if worst_clearance_m < min_agl_m {
+                violations.push(TerrainViolation {
+                    from_item_id: from_item.id.clone(),
+                    to_item_id: to_item.id.clone(),
+                    from,
+                    to,
+                    worst_clearance_m,
+                    worst_point,
+                });
+            }
+        }
+    }
+
+    Ok(TerrainClearanceReport {
+        min_agl_m,
+        legs_checked: legs.len(),
+        elevation_source: source.name().to_string(),
+        violations,
+    })
+}
+
+// ===== ELEVATION PROFILE =====
+
+// Terrain and planned altitude (both AMSL) along the mission path every `resolution_m`
+#[tauri::command]
+pub async fn generate_elevation_profile(
+    app_handle: tauri::AppHandle,
+    resolution_m: f64,
+    state: State<'_, AppState>,
+    terrain: State<'_, TerrainState>,
+) -> Result<Vec<ElevationPoint>, String> {
+    if !resolution_m.is_finite() || resolution_m <= 0.0 {
+        return Err("Resolution must be a positive number".to_string());
+    }
+
+    let path: Vec<Coordinate> = state.mission_items.lock()
+        .map_err(|_| "Failed to lock state")?
+        .iter()
+        .filter(|item| item.is_on_flight_path())
+        .map(crate::MissionItem::location)
+        .collect();
+    let source = terrain.source.read()
+        .map_err(|_| "Failed to read elevation source")?;
+    let profile = elevation_profile(source.as_ref(), &path, resolution_m)?;
+
+    let conflicts = terrain_conflicts(&profile);
+    if !conflicts.is_empty() {
+        if let Err(e) = app_handle.emit_all("mission-terrain-conflict", &conflicts) {
+            eprintln!("Failed to emit terrain conflict: {e}");
+        }
+    }
+
+    Ok(profile)
+}
+
+// Store an SRTM .hgt tile (e.g. "N37W123") and start using SRTM elevations
+#[tauri::command]
+pub async fn import_srtm_tile(
+    app_handle: tauri::AppHandle,
+    tile_name: String,
+    data: Vec<u8>,
+    terrain: State<'_, TerrainState>,
+) -> Result<(), String> {
+    let tile_name = tile_name.trim().trim_end_matches(".hgt").to_uppercase();
+    if !is_valid_tile_name(&tile_name) {
+        return Err(format!("Invalid SRTM tile name: {}", tile_name));
+    }
+    HgtTile::parse(&data)?;
+
+    let dir = srtm_dir(&app_handle)?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create SRTM directory: {e}"))?;
+    std::fs::write(dir.join(format!("{tile_name}.hgt")), &data)
+        .map_err(|e| format!("Failed to write SRTM tile: {e}"))?;
+
+    // A fresh source drops any cached miss for this tile
+    let mut source = terrain.source.write()
+        .map_err(|_| "Failed to update elevation source")?;
+    *source = Box::new(SrtmSource::new(dir));
+    Ok(())
+}
+
+// Relative mission altitudes are anchored to the terrain under the first point
+fn elevation_profile(
+    source: &dyn ElevationSource,
+    path: &[Coordinate],
+    resolution_m: f64,
+) -> Result<Vec<ElevationPoint>, String> {
+    let home_elevation_m = match path.first() {
+        Some(first) => source.elevation_m(first)
+            .ok_or("No terrain data available at the launch point")?,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut profile = Vec::new();
+    for point in sample_path(path, resolution_m)? {
+        let terrain_alt_m = match source.elevation_m(&point) {
+            Some(elevation) => elevation,
+            None => continue,
+        };
+        let mission_alt_m = home_elevation_m + point.alt.unwrap_or(0.0);
+        profile.push(ElevationPoint {
+            lat: point.lat,
+            lng: point.lng,
+            mission_alt_m,
+            terrain_alt_m,
+            clearance_m: mission_alt_m - terrain_alt_m,
+        });
+    }
+    Ok(profile)
+}
+
+// Negative clearance means the planned path runs into terrain
+fn terrain_conflicts(profile: &[ElevationPoint]) -> Vec<&ElevationPoint> {
+    profile.iter()
+        .filter(|point| point.clearance_m < 0.0)
+        .collect()
+}
+
+// Points every `resolution_m` along the path, including each vertex once
+fn sample_path(path: &[Coordinate], resolution_m: f64) -> Result<Vec<Coordinate>, String> {
+    let mut points: Vec<Coordinate> = path.first().cloned().into_iter().collect();
+    for pair in path.windows(2) {
+        let steps = ((distance_m(&pair[0], &pair[1]) / resolution_m).ceil() as usize).max(1);
+        if points.len() + steps > MAX_PROFILE_POINTS {
+            return Err("Resolution too fine for the mission length".to_string());
+        }
+        points.extend((1..=steps).map(|i| interpolate(&pair[0], &pair[1], i as f64 / steps as f64)));
+    }
+    Ok(points)
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+// Lowest clearance along a leg and where it occurs; None when no terrain data is available
+fn worst_leg_clearance(
+    source: &dyn ElevationSource,
+    from: &Coordinate,
+    to: &Coordinate,
+    home_elevation_m: f64,
+) -> Option<(f64, Coordinate)> {
+    let leg_length_m = distance_m(from, to);
+    let samples = ((leg_length_m / SAMPLE_SPACING_M).ceil() as usize).clamp(1, MAX_SAMPLES_PER_LEG);
+
+    let mut worst: Option<(f64, Coordinate)> = None;
+    for i in 0..=samples {
+        let point = interpolate(from, to, i as f64 / samples as f64);
+        let terrain_m = match source.elevation_m(&point) {
+            Some(elevation) => elevation,
+            None => continue,
+        };
+        let planned_m = home_elevation_m + point.alt.unwrap_or(0.0);
+        let clearance_m = planned_m - terrain_m;
+
+        if worst.as_ref().map_or(true, |(w, _)| clearance_m < *w) {
+            worst = Some((clearance_m, point));
+        }
+    }
+    worst
+}
+
+// ===== SRTM TILES =====
+
+// Tile covering [lat, lat+1) × [lng, lng+1), named after its south-west corner
+fn hgt_tile_name(lat_floor: f64, lng_floor: f64) -> String {
+    format!(
+        "{}{:02}{}{:03}",
+        if lat_floor < 0.0 { 'S' } else { 'N' },
+        lat_floor.abs() as u32,
+        if lng_floor < 0.0 { 'W' } else { 'E' },
+        lng_floor.abs() as u32,
+    )
+}
+
+fn is_valid_tile_name(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    bytes.len() == 7
+        && matches!(bytes[0], b'N' | b'S')
+        && bytes[1..3].iter().all(u8::is_ascii_digit)
+        && matches!(bytes[3], b'E' | b'W')
+        && bytes[4..7].iter().all(u8::is_ascii_digit)
+}
+
+fn srtm_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app_handle.path_resolver()
+        .app_data_dir()
+        .ok_or("App data directory unavailable")?;
+    Ok(dir.join(SRTM_DIR))
+}
+
+fn has_hgt_tiles(dir: &Path) -> bool {
+    std::fs::read_dir(dir)
+        .map(|entries| entries
+            .filter_map(Result::ok)
+            .any(|entry| entry.path().extension().map_or(false, |ext| ext == "hgt")))
+        .unwrap_or(false)
+}
+
+// ===== MODULE REGISTRATION =====
+
+pub fn init() -> TerrainState {
+    TerrainState::new()
+}
+
+// Switch to SRTM elevations at startup when tiles were imported previously
+pub fn load_cached_tiles(app: &tauri::App) {
+    let dir = match srtm_dir(&app.handle()) {
+        Ok(dir) => dir,
+        Err(_) => return,
+    };
+    if !has_hgt_tiles(&dir) {
+        return;
+    }
+
+    let terrain = app.state::<TerrainState>();
+    let updated = match terrain.source.write() {
+        Ok(mut source) => {
+            *source = Box::new(SrtmSource::new(dir));
+            true
+        }
+        Err(_) => false,
+    };
+    if !updated {
+        eprintln!("Failed to install cached SRTM tiles");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 3 arc-second tile that is flat at 10 m apart from a 400 m cone
+    // centred on (lat_floor + 0.5, lng_floor + 0.5)
+    fn peak_tile() -> Vec<u8> {
+        let size = HGT_SIZES[0];
+        let centre = (size / 2) as f64;
+        let mut data = Vec::with_capacity(size * size * 2);
+        for row in 0..size {
+            for col in 0..size {
+                let distance = (row as f64 - centre).hypot(col as f64 - centre);
+                let height = 10.0 + (400.0 - distance * 20.0).max(0.0);
+                data.extend_from_slice(&(height as i16).to_be_bytes());
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn elevation_profile_finds_the_peak_conflict() {
+        let dir = std::env::temp_dir().join(format!("olympus-srtm-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("N37W123.hgt"), peak_tile()).unwrap();
+        let source = SrtmSource::new(dir.clone());
+
+        // West to east across the tile centre at 100 m above the launch point
+        let path = [
+            Coordinate { lat: 37.5, lng: -122.9, alt: Some(100.0) },
+            Coordinate { lat: 37.5, lng: -122.1, alt: Some(100.0) },
+        ];
+        let profile = elevation_profile(&source, &path, 50.0).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!((profile[0].terrain_alt_m - 10.0).abs() < 1e-6);
+        assert!((profile[0].clearance_m - 100.0).abs() < 1e-6);
+        let conflicts = terrain_conflicts(&profile);
+        assert!(!conflicts.is_empty());
+        let worst = conflicts.iter()
+            .min_by(|a, b| a.clearance_m.partial_cmp(&b.clearance_m).unwrap())
+            .unwrap();
+        assert!((worst.lng - -122.5).abs() < 0.001);
+        assert!((worst.terrain_alt_m - 410.0).abs() < 1.0);
+        // The cone rises above 110 m within 15 samples (0.0125°) of its centre
+        assert!(conflicts.iter().all(|point| (point.lng - -122.5).abs() < 0.013));
+    }
+}
@@ -0,0 +1,430 @@
+// Offline map tile cache backend
+// NASA JPL Power of 10 compliant implementation
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::State;
+
+use crate::map_features::ViewportBounds;
+
+// ===== TYPE DEFINITIONS =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub tile_count: u64,
+    pub total_bytes: u64,
+    // Last access of the least recently used tile; 0 when the cache is empty
+    pub oldest_timestamp_ms: u64,
+}
+
+// Slippy map tile address
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct TileKey {
+    z: u8,
+    x: u32,
+    y: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TileEntry {
+    bytes: u64,
+    last_access_ms: u64,
+}
+
+// In-memory view of the files under the cache directory
+#[derive(Debug, Default)]
+struct TileIndex {
+    tiles: HashMap<TileKey, TileEntry>,
+    total_bytes: u64,
+}
+
+impl TileIndex {
+    fn insert(&mut self, key: TileKey, entry: TileEntry) {
+        if let Some(previous) = self.tiles.insert(key, entry) {
+            self.total_bytes -= previous.bytes;
+        }
+        self.total_bytes += entry.bytes;
+    }
+
+    fn remove(&mut self, key: &TileKey) {
+        if let Some(previous) = self.tiles.remove(key) {
+            self.total_bytes -= previous.bytes;
+        }
+    }
+}
+
+const TILE_CACHE_DIR: &str = "tile_cache";
+const DEFAULT_MAX_CACHE_BYTES: u64 = 512 * 1024 * 1024;
+const MAX_ZOOM: u8 = 22;
+// NASA JPL Rule 2: bound the tiles fetched by one request
+const MAX_TILES_PER_REQUEST: usize = 10_000;
+const TILE_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(15);
+// Web Mercator does not reach the poles
+const MAX_MERCATOR_LAT: f64 = 85.051_128_78;
+
+// ===== STATE MANAGEMENT =====
+
+pub struct TileCacheState {
+    // Least recently used tiles are evicted once the cache grows past this
+    max_bytes: Mutex<u64>,
+    // Built from a directory scan on first use
+    index: Mutex<Option<TileIndex>>,
+}
+
+impl TileCacheState {
+    pub fn new() -> Self {
+        Self {
+            max_bytes: Mutex::new(DEFAULT_MAX_CACHE_BYTES),
+            index: Mutex::new(None),
+        }
+    }
+}
+
+// ===== TILE CACHE COMMANDS =====
+
+// NASA JPL Rule 4: Function under 60 lines
+// Download every tile in `bounds` for the zoom levels in `zoom_range` (inclusive).
+// `tile_urls` are templates with {z}/{x}/{y} placeholders, tried in order for each
+// tile. Tiles already cached are skipped. Returns the number of tiles downloaded.
+#[tauri::command]
+pub async fn cache_tiles(
+    app_handle: tauri::AppHandle,
+    tile_urls: Vec<String>,
+    zoom_range: (u8, u8),
+    bounds: ViewportBounds,
+    state: State<'_, TileCacheState>,
+) -> Result<u32, String> {
+    if tile_urls.is_empty() || !tile_urls.iter().all(|url| is_tile_template(url)) {
+        return Err("Tile URLs must contain {z}, {x} and {y} placeholders".to_string());
+    }
+    let dir = tile_cache_dir(&app_handle)?;
+    let missing = missing_tiles(&state, &dir, zoom_range, &bounds)?;
+
+    let client = reqwest::Client::builder()
+        .timeout(TILE_DOWNLOAD_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
+    let mut cached = 0;
+    let mut last_error = None;
+    for key in &missing {
+        match download_tile(&client, &tile_urls, *key).await {
+            Ok(data) => {
+                store_tile(&state, &dir, *key, &data)?;
+                cached += 1;
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    if cached == 0 {
+        if let Some(e) = last_error {
+            return Err(e);
+        }
+    }
+
+    let max_bytes = *state.max_bytes.lock().map_err(|_| "Failed to lock cache limit")?;
+    with_index(&state, &dir, |index| evict_lru(index, &dir, max_bytes))?;
+    Ok(cached)
+}
+
+// PNG bytes of a cached tile; marks the tile as recently used
+#[tauri::command]
+pub async fn get_cached_tile(
+    app_handle: tauri::AppHandle,
+    z: u8,
+    x: u32,
+    y: u32,
+    state: State<'_, TileCacheState>,
+) -> Result<Vec<u8>, String> {
+    let key = TileKey { z, x, y };
+    if !is_valid_tile(key) {
+        return Err(format!("Invalid tile {z}/{x}/{y}"));
+    }
+    let dir = tile_cache_dir(&app_handle)?;
+    let data = std::fs::read(tile_path(&dir, key))
+        .map_err(|_| format!("Tile {z}/{x}/{y} is not cached"))?;
+
+    let entry = TileEntry { bytes: data.len() as u64, last_access_ms: current_time_ms() };
+    with_index(&state, &dir, |index| index.insert(key, entry))?;
+    Ok(data)
+}
+
+#[tauri::command]
+pub async fn get_cache_stats(
+    app_handle: tauri::AppHandle,
+    state: State<'_, TileCacheState>,
+) -> Result<CacheStats, String> {
+    let dir = tile_cache_dir(&app_handle)?;
+    with_index(&state, &dir, |index| CacheStats {
+        tile_count: index.tiles.len() as u64,
+        total_bytes: index.total_bytes,
+        oldest_timestamp_ms: index.tiles.values().map(|t| t.last_access_ms).min().unwrap_or(0),
+    })
+}
+
+// Change the cache size limit, evicting immediately if the cache is already larger
+#[tauri::command]
+pub async fn set_tile_cache_limit(
+    app_handle: tauri::AppHandle,
+    max_bytes: u64,
+    state: State<'_, TileCacheState>,
+) -> Result<(), String> {
+    *state.max_bytes.lock().map_err(|_| "Failed to lock cache limit")? = max_bytes;
+    let dir = tile_cache_dir(&app_handle)?;
+    with_index(&state, &dir, |index| evict_lru(index, &dir, max_bytes))
+}
+
+// ===== TILE MATH =====
+
+// Tiles in `bounds` for the inclusive `zoom_range` that are not cached yet
+fn missing_tiles(
+    state: &TileCacheState,
+    dir: &Path,
+    zoom_range: (u8, u8),
+    bounds: &ViewportBounds,
+) -> Result<Vec<TileKey>, String> {
+    let (min_zoom, max_zoom) = zoom_range;
+    if min_zoom > max_zoom || max_zoom > MAX_ZOOM {
+        return Err(format!("Zoom range must be ascending and at most {MAX_ZOOM}"));
+    }
+    let wanted = tiles_in_bounds(bounds, min_zoom, max_zoom)?;
+    with_index(state, dir, |index| {
+        wanted.into_iter().filter(|key| !index.tiles.contains_key(key)).collect()
+    })
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+// Web Mercator tiles covering `bounds` at each zoom level
+fn tiles_in_bounds(bounds: &ViewportBounds, min_zoom: u8, max_zoom: u8) -> Result<Vec<TileKey>, String> {
+    let valid = [bounds.north, bounds.south, bounds.east, bounds.west].iter().all(|v| v.is_finite())
+        && bounds.north >= bounds.south
+        && bounds.east >= bounds.west;
+    if !valid {
+        return Err("Invalid tile bounds".to_string());
+    }
+
+    let mut keys = Vec::new();
+    for z in min_zoom..=max_zoom {
+        let (x_min, y_min) = tile_for(bounds.north, bounds.west, z);
+        let (x_max, y_max) = tile_for(bounds.south, bounds.east, z);
+        let count = (u64::from(x_max - x_min) + 1) * (u64::from(y_max - y_min) + 1);
+        if keys.len() as u64 + count > MAX_TILES_PER_REQUEST as u64 {
+            return Err(format!("Area needs more than {MAX_TILES_PER_REQUEST} tiles"));
+        }
+        for x in x_min..=x_max {
+            keys.extend((y_min..=y_max).map(|y| TileKey { z, x, y }));
+        }
+    }
+    Ok(keys)
+}
+
+// Tile containing a coordinate, clamped to the Web Mercator range
+fn tile_for(lat: f64, lng: f64, z: u8) -> (u32, u32) {
+    let n = f64::from(1u32 << z);
+    let lat = lat.clamp(-MAX_MERCATOR_LAT, MAX_MERCATOR_LAT).to_radians();
+    let x = ((lng.clamp(-180.0, 180.0) + 180.0) / 360.0 * n).floor();
+    let y = ((1.0 - lat.tan().asinh() / std::f64::consts::PI) / 2.0 * n).floor();
+    (x.clamp(0.0, n - 1.0) as u32, y.clamp(0.0, n - 1.0) as u32)
+}
+
+fn is_valid_tile(key: TileKey) -> bool {
+    key.z <= MAX_ZOOM && u64::from(key.x) < 1u64 << key.z && u64::from(key.y) < 1u64 << key.z
+}
+
+fn is_tile_template(url: &str) -> bool {
+    url.contains("{z}") && url.contains("{x}") && url.contains("{y}")
+}
+
+// ===== DOWNLOAD AND STORAGE =====
+
+// First template that serves the tile wins
+async fn download_tile(client: &reqwest::Client, templates: &[String], key: TileKey) -> Result<Vec<u8>, String> {
+    let mut last_error = String::new();
+    for template in templates {
+        let url = template
+            .replace("{z}", &key.z.to_string())
+            .replace("{x}", &key.x.to_string())
+            .replace("{y}", &key.y.to_string());
+        let response = client.get(&url).send().await.and_then(|r| r.error_for_status());
+        match response {
+            Ok(response) => match response.bytes().await {
+                Ok(bytes) => return Ok(bytes.to_vec()),
+                Err(e) => last_error = format!("Failed to read tile from {url}: {e}"),
+            },
+            Err(e) => last_error = format!("Failed to download {url}: {e}"),
+        }
+    }
+    Err(last_error)
+}
+
+fn store_tile(state: &TileCacheState, dir: &Path, key: TileKey, data: &[u8]) -> Result<(), String> {
+    let path = tile_path(dir, key);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    std::fs::write(&path, data).map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+
+    let entry = TileEntry { bytes: data.len() as u64, last_access_ms: current_time_ms() };
+    with_index(state, dir, |index| index.insert(key, entry))
+}
+
+// Delete least recently used tiles until the cache fits in `max_bytes`
+fn evict_lru(index: &mut TileIndex, dir: &Path, max_bytes: u64) {
+    if index.total_bytes <= max_bytes {
+        return;
+    }
+    let mut by_age: Vec<(TileKey, u64)> = index.tiles.iter()
+        .map(|(key, entry)| (*key, entry.last_access_ms))
+        .collect();
+    by_age.sort_by_key(|(_, last_access_ms)| *last_access_ms);
+
+    for (key, _) in by_age {
+        if index.total_bytes <= max_bytes {
+            break;
+        }
+        if let Err(e) = std::fs::remove_file(tile_path(dir, key)) {
+            eprintln!("Failed to evict tile {}/{}/{}: {e}", key.z, key.x, key.y);
+        }
+        index.remove(&key);
+    }
+}
+
+// Run `f` on the index, scanning the cache directory the first time
+fn with_index<T>(
+    state: &TileCacheState,
+    dir: &Path,
+    f: impl FnOnce(&mut TileIndex) -> T,
+) -> Result<T, String> {
+    let mut index = state.index.lock().map_err(|_| "Failed to lock tile index")?;
+    Ok(f(index.get_or_insert_with(|| scan_tile_dir(dir))))
+}
+
+// NASA JPL Rule 4: Function under 60 lines
+// Index {dir}/{z}/{x}/{y}.png files, using modification time as the last access
+fn scan_tile_dir(dir: &Path) -> TileIndex {
+    let mut index = TileIndex::default();
+    for (z, z_dir) in numbered_entries(dir) {
+        for (x, x_dir) in numbered_entries(&z_dir) {
+            for (y, path) in numbered_entries(&x_dir) {
+                let key = match u8::try_from(z) {
+                    Ok(z) => TileKey { z, x, y },
+                    Err(_) => continue,
+                };
+                let metadata = match std::fs::metadata(&path) {
+                    Ok(metadata) if metadata.is_file() && is_valid_tile(key) => metadata,
+                    _ => continue,
+                };
+                let last_access_ms = metadata.modified().map(system_time_ms).unwrap_or(0);
+                index.insert(key, TileEntry { bytes: metadata.len(), last_access_ms });
+            }
+        }
+    }
+    index
+}
+
+// Entries named "<n>" or "<n>.png" in `dir`
+fn numbered_entries(dir: &Path) -> Vec<(u32, PathBuf)> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let number = name.strip_suffix(".png").unwrap_or(&name).parse().ok()?;
+            Some((number, entry.path()))
+        })
+        .collect()
+}
+
+fn tile_path(dir: &Path, key: TileKey) -> PathBuf {
+    dir.join(key.z.to_string()).join(key.x.to_string()).join(format!("{}.png", key.y))
+}
+
+fn tile_cache_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app_handle.path_resolver()
+        .app_data_dir()
+        .ok_or("App data directory unavailable")?;
+    Ok(dir.join(TILE_CACHE_DIR))
+}
+
+fn current_time_ms() -> u64 {
+    system_time_ms(SystemTime::now())
+}
+
+fn system_time_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// ===== MODULE REGISTRATION =====
+
+pub fn init() -> TileCacheState {
+    TileCacheState::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("olympus-tiles-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn cached_files(dir: &Path) -> usize {
+        numbered_entries(dir).iter()
+            .flat_map(|(_, z_dir)| numbered_entries(z_dir))
+            .map(|(_, x_dir)| numbered_entries(&x_dir).len())
+            .sum()
+    }
+
+    #[test]
+    fn caching_ten_tiles_writes_ten_files() {
+        let dir = temp_cache_dir("ten");
+        let state = TileCacheState::new();
+        // Zoom 4 columns 0-4 and rows 4-5
+        let bounds = ViewportBounds { north: 60.0, south: 45.0, east: -80.0, west: -179.0 };
+
+        let missing = missing_tiles(&state, &dir, (4, 4), &bounds).unwrap();
+        assert_eq!(missing.len(), 10);
+        for key in &missing {
+            store_tile(&state, &dir, *key, b"mock png").unwrap();
+        }
+        assert_eq!(cached_files(&dir), 10);
+
+        // A second pass has nothing left to fetch, and a fresh index finds the files
+        assert!(missing_tiles(&state, &dir, (4, 4), &bounds).unwrap().is_empty());
+        assert_eq!(scan_tile_dir(&dir).total_bytes, 80);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn eviction_removes_least_recently_used_tiles() {
+        let dir = temp_cache_dir("lru");
+        let state = TileCacheState::new();
+        let keys = [TileKey { z: 1, x: 0, y: 0 }, TileKey { z: 1, x: 1, y: 0 }, TileKey { z: 1, x: 0, y: 1 }];
+        for key in keys {
+            store_tile(&state, &dir, key, &[0u8; 100]).unwrap();
+        }
+
+        with_index(&state, &dir, |index| {
+            // The first tile was read most recently, so the second is the oldest
+            for (key, last_access_ms) in keys.iter().zip([3, 1, 2]) {
+                index.insert(*key, TileEntry { bytes: 100, last_access_ms });
+            }
+            evict_lru(index, &dir, 250);
+            assert_eq!(index.total_bytes, 200);
+            assert!(!index.tiles.contains_key(&keys[1]));
+        }).unwrap();
+        assert!(!tile_path(&dir, keys[1]).exists());
+        assert!(tile_path(&dir, keys[0]).exists() && tile_path(&dir, keys[2]).exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
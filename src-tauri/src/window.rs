@@ -0,0 +1,100 @@
+// FFT window functions
+// NASA JPL Power of 10 compliant implementation
+
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+// ===== TYPE DEFINITIONS =====
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowFunction {
+    Rectangular,
+    Hann,
+    BlackmanHarris,
+    FlatTop,
+}
+
+impl Default for WindowFunction {
+    fn default() -> Self {
+        WindowFunction::Hann
+    }
+}
+
+// ===== CONSTANTS =====
+
+// NASA JPL Rule 2: bound the coefficient table
+const MAX_WINDOW_SIZE: usize = 65_536;
+// 4-term Blackman-Harris, -92 dB sidelobes
+const BLACKMAN_HARRIS: [f64; 4] = [0.35875, 0.48829, 0.14128, 0.01168];
+// 5-term flat-top (as in MATLAB flattopwin), for amplitude accuracy
+const FLAT_TOP: [f64; 5] = [0.215_578_95, 0.416_631_58, 0.277_263_158, 0.083_578_947, 0.006_947_368];
+
+// ===== COEFFICIENTS =====
+
+// Symmetric window of `size` points; the ends of Hann are exactly zero
+pub fn coefficients(window: WindowFunction, size: usize) -> Vec<f64> {
+    if size == 1 {
+        return vec![1.0];
+    }
+    let span = (size - 1) as f64;
+    (0..size)
+        .map(|n| {
+            let phase = 2.0 * PI * n as f64 / span;
+            match window {
+                WindowFunction::Rectangular => 1.0,
+                WindowFunction::Hann => 0.5 - 0.5 * phase.cos(),
+                WindowFunction::BlackmanHarris => cosine_sum(&BLACKMAN_HARRIS, phase),
+                WindowFunction::FlatTop => cosine_sum(&FLAT_TOP, phase),
+            }
+        })
+        .collect()
+}
+
+// a0 - a1 cos(x) + a2 cos(2x) - a3 cos(3x) + ...
+fn cosine_sum(terms: &[f64], phase: f64) -> f64 {
+    terms.iter()
+        .enumerate()
+        .map(|(k, a)| {
+            let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+            sign * a * (k as f64 * phase).cos()
+        })
+        .sum()
+}
+
+// Shape of a window, for overlaying on the spectrum
+#[tauri::command]
+pub fn get_window_coefficients(window: WindowFunction, size: usize) -> Result<Vec<f64>, String> {
+    if size == 0 || size > MAX_WINDOW_SIZE {
+        return Err(format!("Window size must be between 1 and {MAX_WINDOW_SIZE}"));
+    }
+    Ok(coefficients(window, size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hann_window_is_zero_at_the_ends_and_one_in_the_middle() {
+        // An odd length puts a sample exactly on the peak
+        let hann = get_window_coefficients(WindowFunction::Hann, 257).unwrap();
+        assert_eq!(hann.len(), 257);
+        assert!(hann[0].abs() < 1e-9, "first {}", hann[0]);
+        assert!((hann[128] - 1.0).abs() < 1e-9, "middle {}", hann[128]);
+        assert!(hann[256].abs() < 1e-9, "last {}", hann[256]);
+    }
+
+    #[test]
+    fn windows_are_symmetric_and_sized() {
+        for window in [WindowFunction::Rectangular, WindowFunction::Hann, WindowFunction::BlackmanHarris, WindowFunction::FlatTop] {
+            let values = coefficients(window, 256);
+            for (front, back) in values.iter().zip(values.iter().rev()) {
+                assert!((front - back).abs() < 1e-9, "{window:?} is not symmetric");
+            }
+        }
+        assert!(get_window_coefficients(WindowFunction::Hann, 0).is_err());
+        assert!(get_window_coefficients(WindowFunction::Hann, MAX_WINDOW_SIZE + 1).is_err());
+        assert_eq!(get_window_coefficients(WindowFunction::FlatTop, 1).unwrap(), [1.0]);
+    }
+}
@@ -13,6 +13,16 @@ export interface VehicleInfo {
   capabilities: string[];
   armed: boolean;
   flight_mode: string;
+  battery?: BatteryInfo;
+}
+
+export interface BatteryInfo {
+  voltage_v: number;
+  current_a?: number;
+  consumed_mah?: number;
+  remaining_pct?: number;
+  cell_voltages: number[];
+  timestamp_ms: number;
 }
 
 export interface Parameter {
@@ -23,6 +33,92 @@ export interface Parameter {
   min_value?: number;
   max_value?: number;
   units?: string;
+  increment?: number;
+  default_value?: number;
+  values: ParamValueLabel[];
+  bitmask: ParamBitLabel[];
+}
+
+export interface ParameterMatch {
+  parameter: Parameter;
+  modified: boolean;
+}
+
+export interface ParameterSearchResult {
+  matches: ParameterMatch[];
+  total_count: number;
+}
+
+export interface ParamValueLabel {
+  value: number;
+  label: string;
+}
+
+export interface ParamBitLabel {
+  bit: number;
+  label: string;
+}
+
+export interface ParameterMetadata {
+  param_id: string;
+  display_name?: string;
+  description?: string;
+  units?: string;
+  min_value?: number;
+  max_value?: number;
+  increment?: number;
+  default_value?: number;
+  values: ParamValueLabel[];
+  bitmask: ParamBitLabel[];
+  reboot_required: boolean;
+  read_only: boolean;
+}
+
+export type ArduPilotFirmware =
+  | 'ArduCopter'
+  | 'ArduPlane'
+  | 'Rover'
+  | 'ArduSub'
+  | 'AntennaTracker'
+  | 'Blimp';
+
+export interface ParameterSyncProgress {
+  received: number;
+  total: number;
+  percent: number;
+}
+
+export interface ParamFileEntry {
+  param_id: string;
+  file_value: number;
+  current_value?: number;
+  changed: boolean;
+  unknown: boolean;
+}
+
+export interface ParamFileDiff {
+  entries: ParamFileEntry[];
+  changed_count: number;
+  unknown_count: number;
+}
+
+export interface ParamApplyFailure {
+  param_id: string;
+  error: string;
+}
+
+export interface ParamApplyReport {
+  dry_run: boolean;
+  planned: ParamFileEntry[];
+  applied_count: number;
+  failures: ParamApplyFailure[];
+}
+
+export interface ParamApplyProgress {
+  completed: number;
+  total: number;
+  param_id: string;
+  error?: string;
 }
 
 export interface CalibrationResult {
@@ -41,6 +137,154 @@ export interface ConnectionStatus {
   messages_received: number;
   messages_sent: number;
   link_quality: number;
+  jitter_ms: number;
+  packet_loss_ratio: number;
+}
+
+export interface PreflightStatus {
+  sensors_present: number;
+  sensors_enabled: number;
+  sensors_health: number;
+  timestamp_ms: number;
+}
+
+export interface SensorCheck {
+  sensor_name: string;
+  present: boolean;
+  enabled: boolean;
+  healthy: boolean;
+}
+
+export interface PreflightCheckReport {
+  status: PreflightStatus;
+  checks: SensorCheck[];
+  failing_checks: string[];
+}
+
+export interface EkfStatus {
+  velocity_variance: number;
+  pos_horiz_variance: number;
+  pos_vert_variance: number;
+  compass_variance: number;
+  terrain_alt_variance: number;
+  flags: number;
+  timestamp_ms: number;
+}
+
+export interface VibrationData {
+  accel_x: number;
+  accel_y: number;
+  accel_z: number;
+  clipping_0: number;
+  clipping_1: number;
+  clipping_2: number;
+  timestamp_ms: number;
+}
+
+// Latest report from one ESC; motor IDs start at 1
+export interface EscTelemetry {
+  motor_id: number;
+  rpm: number;
+  voltage_mv: number;
+  current_ca: number;
+  temperature_cdeg: number;
+  totalcurrent_mah: number;
+  timestamp_ms: number;
+}
+
+// Payload of `esc-warning` events
+export interface EscWarning {
+  motor_id: number;
+  telemetry: EscTelemetry;
+  reasons: string[];
+}
+
+export interface AttitudeData {
+  roll_deg: number;
+  pitch_deg: number;
+  yaw_deg: number;
+  roll_rate_dps: number;
+  pitch_rate_dps: number;
+  yaw_rate_dps: number;
+  timestamp_ms: number;
+}
+
+export interface VfrData {
+  airspeed_ms: number;
+  groundspeed_ms: number;
+  heading_deg: number;
+  throttle_pct: number;
+  alt_m: number;
+  climb_ms: number;
+  timestamp_ms: number;
+}
+
+export interface GpsStatus {
+  fix_type: string;
+  has_3d_fix: boolean;
+  satellites?: number;
+  hdop?: number;
+  vdop?: number;
+  ground_course_deg?: number;
+  timestamp_ms: number;
+}
+
+export interface TelemetryRates {
+  attitude_hz: number;
+  vfr_hz: number;
+  position_hz: number;
+}
+
+export interface ConsoleOutput {
+  data: string;
+  timestamp_ms: number;
+}
+
+export type StatusTextSeverity =
+  | 'emergency'
+  | 'alert'
+  | 'critical'
+  | 'error'
+  | 'warning'
+  | 'notice'
+  | 'info'
+  | 'debug';
+
+export interface StatusText {
+  system_id: number;
+  component_id: number;
+  severity: StatusTextSeverity;
+  text: string;
+  timestamp_ms: number;
+}
+
+export interface ArmFailure {
+  severity: StatusTextSeverity;
+  message: string;
+  timestamp_ms: number;
+}
+
+export interface ArmDisarmResult {
+  accepted: boolean;
+  error?: string;
+  // Pre-arm failures the vehicle reported when it refused
+  failures: ArmFailure[];
+}
+
+export interface MotorTestResult {
+  motor_id: number;
+  success: boolean;
+  status: 'passed' | 'failed' | 'aborted';
+  error?: string;
+  // Peaks from ESC telemetry, when the ESCs report it
+  measured_rpm?: number;
+  current_draw_a?: number;
+}
+
+export interface RelayStats {
+  forwarded_count: number;
+  dropped_count: number;
+  output_addr: string;
 }
 
 // Connection Commands
@@ -56,15 +300,75 @@ export async function getVehicleInfo(): Promise<VehicleInfo> {
   return await invoke('get_vehicle_info');
 }
 
+export async function setCommandTimeout(timeoutMs: number): Promise<void> {
+  return await invoke('set_command_timeout', { timeoutMs });
+}
+
+export async function setConnectTimeout(timeoutMs: number): Promise<void> {
+  return await invoke('set_connect_timeout', { timeoutMs });
+}
+
 // Parameter Commands
 export async function getDroneParameters(): Promise<Parameter[]> {
   return await invoke('get_drone_parameters');
 }
 
-export async function setDroneParameter(paramId: string, value: number): Promise<void> {
+// Resolves with the value the vehicle confirmed, which it may have clamped
+export async function setDroneParameter(paramId: string, value: number): Promise<number> {
   return await invoke('set_drone_parameter', { paramId, value });
 }
 
+export async function syncParametersFromVehicle(): Promise<number> {
+  return await invoke('sync_parameters_from_vehicle');
+}
+
+// Resolves with the number of parameters still missing after the retry rounds
+export async function refreshParameters(): Promise<number> {
+  return await invoke('refresh_parameters');
+}
+
+export async function areParametersSynced(): Promise<boolean> {
+  return await invoke('are_parameters_synced');
+}
+
+// Writes a QGC / Mission Planner .param file; resolves with the parameter count
+export async function exportParameters(path: string, allowPartial = false): Promise<number> {
+  return await invoke('export_parameters', { path, allowPartial });
+}
+
+// Id prefix matches come first; group is the id prefix before the first underscore
+export async function searchParameters(
+  query: string,
+  group: string | undefined,
+  modifiedOnly: boolean,
+  limit: number
+): Promise<ParameterSearchResult> {
+  return await invoke('search_parameters', { query, group, modifiedOnly, limit });
+}
+
+export async function loadParamFile(path: string): Promise<ParamFileDiff> {
+  return await invoke('load_param_file', { path });
+}
+
+// Pass dryRun to review the planned writes without touching the vehicle
+export async function applyParamFile(
+  path: string,
+  onlyChanged: boolean,
+  dryRun: boolean
+): Promise<ParamApplyReport> {
+  return await invoke('apply_param_file', { path, onlyChanged, dryRun });
+}
+
+// Loads the firmware's parameter definitions (the connected vehicle's by
+// default); resolves with the number of definitions
+export async function loadParameterMetadata(firmware?: ArduPilotFirmware): Promise<number> {
+  return await invoke('load_parameter_metadata', { firmware });
+}
+
+export async function getParameterMetadata(paramId: string): Promise<ParameterMetadata> {
+  return await invoke('get_parameter_metadata', { paramId });
+}
+
 // Motor Test Commands
 export async function testMotor(
   motorId: number,
@@ -74,22 +378,183 @@ export async function testMotor(
   return await invoke('test_motor', { motorId, throttle, durationMs });
 }
 
+// Tests each motor in turn, emitting `motor-test-progress`; a failure or
+// emergency stop aborts the remaining motors
+export async function testAllMotorsSequence(
+  throttle: number,
+  durationMsPerMotor: number
+): Promise<MotorTestResult[]> {
+  return await invoke('test_all_motors_sequence', { throttle, durationMsPerMotor });
+}
+
 export async function emergencyStop(): Promise<void> {
   return await invoke('emergency_stop');
 }
 
+// Clears the emergency stop latch so arming and motor tests are accepted again
+export async function resetEmergencyStop(): Promise<void> {
+  return await invoke('reset_emergency_stop');
+}
+
+// Rally Point Commands
+export async function uploadRallyPointsToVehicle(): Promise<number> {
+  return await invoke('upload_rally_points_to_vehicle');
+}
+
+// Arming Commands
+export async function getPreflightStatus(): Promise<PreflightCheckReport> {
+  return await invoke('get_preflight_status');
+}
+
+export async function getEkfStatus(): Promise<EkfStatus> {
+  return await invoke('get_ekf_status');
+}
+
+export async function setEkfVarianceThreshold(threshold: number): Promise<void> {
+  return await invoke('set_ekf_variance_threshold', { threshold });
+}
+
+// force overrides the vehicle's pre-arm checks, not the ground station's
+export async function armVehicle(force = false): Promise<ArmDisarmResult> {
+  return await invoke('arm_vehicle', { force });
+}
+
+export async function disarmVehicle(force = false): Promise<ArmDisarmResult> {
+  return await invoke('disarm_vehicle', { force });
+}
+
+// Flight Mode Commands
+export async function getAvailableFlightModes(): Promise<string[]> {
+  return await invoke('get_available_flight_modes');
+}
+
+// Resolves once the vehicle's heartbeat reports the new mode
+export async function setFlightMode(mode: string): Promise<void> {
+  return await invoke('set_flight_mode', { mode });
+}
+
+// Guided Commands
+// alt is relative to home; autoSwitch puts the vehicle in its guided mode first.
+// The backend also emits goto-accepted / goto-rejected.
+export async function gotoLocation(
+  lat: number,
+  lng: number,
+  alt: number,
+  groundSpeed?: number,
+  autoSwitch = false
+): Promise<void> {
+  return await invoke('goto_location', { lat, lng, alt, groundSpeed, autoSwitch });
+}
+
+// Takeoff Commands
+// altitude is relative to home; the climb is reported through takeoff-progress
+// events until its status is reached, timeout or aborted
+export async function commandTakeoff(altitude: number): Promise<void> {
+  return await invoke('command_takeoff', { altitude });
+}
+
+// Reboot Commands
+export async function rebootVehicle(
+  rebootAutopilot: boolean,
+  rebootCompanion: boolean,
+  safetyConfirmed: boolean,
+  reconnectAfterReboot: boolean
+): Promise<void> {
+  return await invoke('reboot_vehicle', {
+    rebootAutopilot,
+    rebootCompanion,
+    safetyConfirmed,
+    reconnectAfterReboot
+  });
+}
+
+// Vibration Commands
+export async function getVibrationStatus(): Promise<VibrationData> {
+  return await invoke('get_vibration_status');
+}
+
+export async function getVibrationHistory(seconds: number): Promise<VibrationData[]> {
+  return await invoke('get_vibration_history', { seconds });
+}
+
+// ESC Commands
+export async function getEscTelemetry(): Promise<EscTelemetry[]> {
+  return await invoke('get_esc_telemetry');
+}
+
+// Battery Commands
+export async function setBatteryThresholds(
+  warnPct: number,
+  criticalPct: number,
+  warnCellV: number
+): Promise<void> {
+  return await invoke('set_battery_thresholds', { warnPct, criticalPct, warnCellV });
+}
+
+// Telemetry Commands
+export async function getLatestAttitude(): Promise<AttitudeData> {
+  return await invoke('get_latest_attitude');
+}
+
+export async function getLatestVfr(): Promise<VfrData> {
+  return await invoke('get_latest_vfr');
+}
+
+export async function getGpsStatus(): Promise<GpsStatus> {
+  return await invoke('get_gps_status');
+}
+
+export async function getTelemetryRates(): Promise<TelemetryRates> {
+  return await invoke('get_telemetry_rates');
+}
+
+export async function setTelemetryRates(rates: TelemetryRates): Promise<void> {
+  return await invoke('set_telemetry_rates', { rates });
+}
+
+// Status Text Commands
+export async function getStatustextLog(): Promise<StatusText[]> {
+  return await invoke('get_statustext_log');
+}
+
+// MAVLink Console Commands
+export async function startMavlinkConsole(): Promise<void> {
+  return await invoke('start_mavlink_console');
+}
+
+export async function stopMavlinkConsole(): Promise<void> {
+  return await invoke('stop_mavlink_console');
+}
+
+export async function mavlinkConsoleWrite(data: string): Promise<void> {
+  return await invoke('mavlink_console_write', { data });
+}
+
+// Relay Commands
+export async function startMavlinkRelay(outputAddr: string): Promise<void> {
+  return await invoke('start_mavlink_relay', { outputAddr });
+}
+
+export async function stopMavlinkRelay(outputAddr?: string): Promise<void> {
+  return await invoke('stop_mavlink_relay', { outputAddr });
+}
+
+export async function getRelayStats(): Promise<RelayStats[]> {
+  return await invoke('get_relay_stats');
+}
+
 // Calibration Commands
 export async function calibrateAccelerometer(): Promise<CalibrationResult> {
   return await invoke('calibrate_accelerometer');
 }
 
-export async function calibrateGyroscope(): Promise<CalibrationResult> {
+export async function calibrateGyroscope(): Promise<void> {
   return await invoke('calibrate_gyroscope');
 }
 
 // Helper function to format connection strings
 export function formatConnectionString(
-  type: 'serial' | 'udp' | 'tcp',
+  type: 'serial' | 'udp' | 'udpout' | 'tcp',
   host: string,
   port?: number,
   baudrate?: number
@@ -99,6 +564,8 @@ export function formatConnectionString(
       return `${host}:${baudrate || 57600}`;
     case 'udp':
       return `udp://${host}:${port || 14550}`;
+    case 'udpout':
+      return `udpout://${host}:${port || 14550}`;
     case 'tcp':
       return `tcp://${host}:${port || 5760}`;
     default:
@@ -112,7 +579,8 @@ export const CONNECTION_PRESETS = {
   MAVPROXY: 'udp://127.0.0.1:14550',
   USB_PIXHAWK: '/dev/ttyACM0:115200',
   SERIAL_PIXHAWK: '/dev/ttyUSB0:57600',
-  WINDOWS_COM: 'COM3:57600'
+  WINDOWS_COM: 'COM3:57600',
+  SIMULATED: 'sim://vehicle'
 } as const;
 
 // Safety constants
@@ -16,25 +16,48 @@ export interface WaypointParams {
   min_pitch?: number;
   abort_alt?: number;
   precision_land?: boolean;
+  spline?: boolean;
+  delay_s?: number | null;
 }
 
 export interface MissionItem {
   id: string;
-  type: 'takeoff' | 'waypoint' | 'loiter' | 'land';
+  type: 'takeoff' | 'waypoint' | 'loiter' | 'land' | 'delay';
   name: string;
   sequence?: number;
   lat?: number;
   lng?: number;
   altitude?: number;
   params: WaypointParams;
+  /** null for items the vehicle does not fly to (delays, jumps, ROI cancel) */
   position?: {
     lat: number;
     lng: number;
     alt: number;
-  };
+  } | null;
   description?: string;
 }
 
+/**
+ * Edit lock held while the vehicle executes the uploaded mission
+ */
+export interface MissionLockStatus {
+  locked: boolean;
+  reason?: string | null;
+}
+
+/**
+ * Response of `get_mission_data`
+ */
+export interface MissionData {
+  items: MissionItem[];
+  lock: MissionLockStatus;
+  /** Bumped on every change to the active mission */
+  revision: number;
+  /** Content hash of `items`, for detecting drift from the vehicle */
+  content_hash: string;
+}
+
 export interface MapViewerProps {
   selectedItemId?: string | null;
   missionItems?: MissionItem[];
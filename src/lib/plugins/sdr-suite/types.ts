@@ -18,6 +18,28 @@ export interface SdrSettings {
   bandwidth: number;
 }
 
+export type WindowFunction = 'rectangular' | 'hann' | 'blackman_harris' | 'flat_top';
+
+export interface SdrConfig {
+  center_frequency_hz: number;
+  sample_rate_hz: number;
+  gain_db: number;
+  ppm_correction: number;
+  window?: WindowFunction;
+  // gain_db must be 0 while AGC is enabled
+  agc_enabled?: boolean;
+  agc_target_dbm?: number;
+  iq_correction?: IqCorrection;
+}
+
+// gain_imbalance is the fractional excess gain of I over Q
+export interface IqCorrection {
+  dc_offset_i: number;
+  dc_offset_q: number;
+  gain_imbalance: number;
+  phase_imbalance_deg: number;
+}
+
 export interface SdrState {
   connected: boolean;
   recording: boolean;
@@ -25,6 +47,46 @@ export interface SdrState {
   error: string | null;
 }
 
+export interface WaterfallSnapshot {
+  lines: number[][];
+  timestamp_ms_start?: number;
+  timestamp_ms_end?: number;
+}
+
+export interface SignalPeak {
+  frequency_hz: number;
+  magnitude_dbm: number;
+  bandwidth_hz: number;
+  snr_db: number;
+}
+
+export interface SignalAnnotation {
+  frequency_hz: number;
+  label: string;
+  color: string;
+  updated_ms: number;
+}
+
+export interface ScanResult {
+  frequencies: number[];
+  peak_magnitudes: number[];
+  scan_start_ms: number;
+  scan_end_ms: number;
+}
+
+export interface SdrBookmark {
+  id: string;
+  label: string;
+  frequency_hz: number;
+  sample_rate_hz: number;
+  gain_db: number;
+}
+
+export interface ScanProgress {
+  current_hz: number;
+  pct_complete: number;
+}
+
 export interface WaterfallData {
   data: number[][];
   width: number;
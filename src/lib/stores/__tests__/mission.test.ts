@@ -291,12 +291,12 @@ describe('Mission Store', () => {
     test('should reorder mission items successfully', async () => {
       vi.mocked(invokeTauriCommand).mockResolvedValue(undefined);
 
-      // Move waypoint-1 (index 1) to index 2
-      await reorderMissionItem('waypoint-1', 2);
+      // Move waypoint-1 (index 1) before land-1 (index 3)
+      await reorderMissionItem('waypoint-1', 3);
 
       expect(invokeTauriCommand).toHaveBeenCalledWith('reorder_mission_item', {
         item_id: 'waypoint-1',
-        new_index: 2
+        new_index: 3
       });
 
       const reorderedItems = get(missionItems);
@@ -304,6 +304,43 @@ describe('Mission Store', () => {
       expect(reorderedItems[1].id).toBe('waypoint-2');
     });
 
+    test('should move an item to the start with index 0', async () => {
+      vi.mocked(invokeTauriCommand).mockResolvedValue(undefined);
+
+      await reorderMissionItem('waypoint-2', 0);
+
+      expect(get(missionItems).map((item) => item.id)).toEqual([
+        'waypoint-2',
+        'takeoff-1',
+        'waypoint-1',
+        'land-1'
+      ]);
+    });
+
+    test('should move an item to the end with the list length', async () => {
+      vi.mocked(invokeTauriCommand).mockResolvedValue(undefined);
+
+      await reorderMissionItem('waypoint-1', mockMissionItems.length);
+
+      expect(get(missionItems).map((item) => item.id)).toEqual([
+        'takeoff-1',
+        'waypoint-2',
+        'land-1',
+        'waypoint-1'
+      ]);
+    });
+
+    test('should leave the order unchanged when moving an item onto itself', async () => {
+      vi.mocked(invokeTauriCommand).mockResolvedValue(undefined);
+
+      await reorderMissionItem('waypoint-1', 1);
+      await reorderMissionItem('waypoint-1', 2);
+
+      expect(get(missionItems).map((item) => item.id)).toEqual(
+        mockMissionItems.map((item) => item.id)
+      );
+    });
+
     test('should handle reordering errors', async () => {
       const errorMessage = 'Failed to reorder mission item';
       vi.mocked(invokeTauriCommand).mockRejectedValue(new Error(errorMessage));
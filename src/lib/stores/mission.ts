@@ -28,7 +28,7 @@ import { BoundedArray } from '../utils/bounded-array';
 import { executeCommand } from '../utils/connection-manager';
 import { invokeTauriCommand, safeTauriInvoke } from '../utils/tauri';
 
-import type { MissionItem, WaypointParams } from '../plugins/mission-planner/types';
+import type { MissionData, MissionItem, WaypointParams } from '../plugins/mission-planner/types';
 
 /**
  * Mission state interface
@@ -356,10 +356,11 @@ async function loadFromTauriBackend(): Promise<MissionItem[] | null> {
   }
 
   try {
-    const items = await safeTauriInvoke<MissionItem[]>('get_mission_data', undefined, {
+    const data = await safeTauriInvoke<MissionData>('get_mission_data', undefined, {
       showNotification: false,
       suppressConsoleError: true
     });
+    const items = data?.items;
 
     if (items && items.length > 0) {
       console.log(`Loaded ${items.length} mission items from backend`);
@@ -632,7 +633,7 @@ function validateMissionItem(item: MissionItem, context?: string): void {
 /**
  * Reorder mission item
  * @param itemId - ID of the item to reorder
- * @param newIndex - New index position
+ * @param newIndex - Index in the current list to insert before (list length moves to the end)
  */
 export async function reorderMissionItem(itemId: string, newIndex: number): Promise<void> {
   // NASA JPL Rule 5: Validate inputs
@@ -658,7 +659,8 @@ export async function reorderMissionItem(itemId: string, newIndex: number): Prom
 
       if (currentIndex !== -1) {
         const [movedItem] = items.splice(currentIndex, 1);
-        items.splice(newIndex, 0, movedItem);
+        // Removing the item shifts later indices down by one
+        items.splice(newIndex > currentIndex ? newIndex - 1 : newIndex, 0, movedItem);
       }
 
       return {
@@ -761,7 +761,7 @@ export const PluginCommands = {
 /**
  * Mission planning interfaces - imported from plugin types
  */
-import type { WaypointParams, MissionItem } from '../plugins/mission-planner/types';
+import type { WaypointParams, MissionItem, MissionData } from '../plugins/mission-planner/types';
 
 /**
  * Mission planning command wrappers with enhanced error handling
@@ -821,10 +821,10 @@ export const MissionCommands = {
   /**
    * Get mission data
    * @param options - API invocation options
-   * @returns Array of mission items
+   * @returns Mission items and the edit lock status
    */
-  async getMissionData(options: ApiInvocationOptions = {}): Promise<MissionItem[]> {
-    return protectedTauriInvoke<MissionItem[]>('get_mission_data', undefined, 'mission', {
+  async getMissionData(options: ApiInvocationOptions = {}): Promise<MissionData> {
+    return protectedTauriInvoke<MissionData>('get_mission_data', undefined, 'mission', {
       notificationTitle: 'Failed to Load Mission Data',
       retryAttempts: 2,
       ...options
@@ -920,23 +920,33 @@ export const MissionCommands = {
   },
 
   /**
-   * Safely get mission data (returns empty array on error)
+   * Safely get mission data (returns an empty, unlocked mission on error)
    * @param options - API invocation options
    */
-  async safeGetMissionData(options: ApiInvocationOptions = {}): Promise<MissionItem[]> {
-    const result = await safeTauriInvoke<MissionItem[]>('get_mission_data', undefined, {
+  async safeGetMissionData(options: ApiInvocationOptions = {}): Promise<MissionData> {
+    const result = await safeTauriInvoke<MissionData>('get_mission_data', undefined, {
       notificationTitle: 'Failed to Load Mission Data',
       retryAttempts: 2,
       ...options
     });
-    return result || [];
+    return result || { items: [], lock: { locked: false }, revision: 0, content_hash: '' };
   }
 };
 
 /**
  * SDR Suite interfaces - imported from plugin types
  */
-import type { SdrSettings, SdrState } from '../plugins/sdr-suite/types';
+import type {
+  IqCorrection,
+  SdrBookmark,
+  SdrConfig,
+  SdrSettings,
+  SdrState,
+  SignalAnnotation,
+  SignalPeak,
+  WaterfallSnapshot,
+  WindowFunction
+} from '../plugins/sdr-suite/types';
 
 /**
  * SDR Suite command wrappers with enhanced error handling
@@ -1031,6 +1041,260 @@ export const SdrCommands = {
     });
   },
 
+  /**
+   * Get receiver tuning used by the FFT stream
+   * @param options - API invocation options
+   * @returns Current SDR configuration
+   */
+  async getSdrConfig(options: ApiInvocationOptions = {}): Promise<SdrConfig> {
+    return protectedTauriInvoke<SdrConfig>('get_sdr_config', undefined, 'sdr', {
+      notificationTitle: 'Failed to Get SDR Configuration',
+      retryAttempts: 2,
+      showNotification: false,
+      ...options
+    });
+  },
+
+  /**
+   * Set receiver tuning; applies from the next FFT frame
+   * @param config - Center frequency, sample rate, gain and PPM correction
+   * @param options - API invocation options
+   */
+  async setSdrConfig(config: SdrConfig, options: ApiInvocationOptions = {}): Promise<void> {
+    return protectedTauriInvoke<void>('set_sdr_config', { config }, 'sdr', {
+      notificationTitle: 'Failed to Set SDR Configuration',
+      ...options
+    });
+  },
+
+  /**
+   * Self-test of IQ calibration on a synthetic tone with a known imbalance;
+   * it does not measure the attached receiver
+   * @param options - API invocation options
+   * @returns Correction estimated for the synthetic imbalance
+   */
+  async runIqCalibrationSelfTest(options: ApiInvocationOptions = {}): Promise<IqCorrection> {
+    return protectedTauriInvoke<IqCorrection>('iq_calibration_self_test', undefined, 'sdr', {
+      notificationTitle: 'IQ Calibration Self-Test Failed',
+      ...options
+    });
+  },
+
+  /**
+   * Let the FFT stream steer the gain toward a target level
+   * @param targetDbm - Average magnitude to hold, in dBm
+   * @param options - API invocation options
+   */
+  async enableAgc(targetDbm: number, options: ApiInvocationOptions = {}): Promise<void> {
+    return protectedTauriInvoke<void>('enable_agc', { targetDbm }, 'sdr', {
+      notificationTitle: 'Failed to Enable AGC',
+      ...options
+    });
+  },
+
+  /**
+   * Return to manual gain, keeping the gain AGC last chose
+   * @param options - API invocation options
+   */
+  async disableAgc(options: ApiInvocationOptions = {}): Promise<void> {
+    return protectedTauriInvoke<void>('disable_agc', undefined, 'sdr', {
+      notificationTitle: 'Failed to Disable AGC',
+      ...options
+    });
+  },
+
+  /**
+   * Get the coefficients of a window function
+   * @param window - Window function
+   * @param size - Number of coefficients
+   * @param options - API invocation options
+   * @returns Window shape for overlaying on the spectrum
+   */
+  async getWindowCoefficients(
+    window: WindowFunction,
+    size: number,
+    options: ApiInvocationOptions = {}
+  ): Promise<number[]> {
+    return protectedTauriInvoke<number[]>('get_window_coefficients', { window, size }, 'sdr', {
+      notificationTitle: 'Failed to Get Window Coefficients',
+      showNotification: false,
+      ...options
+    });
+  },
+
+  /**
+   * Get the most recent waterfall lines, oldest first
+   * @param lines - Maximum number of lines
+   * @param options - API invocation options
+   * @returns Lines with the time span they cover
+   */
+  async getWaterfallSnapshot(
+    lines: number,
+    options: ApiInvocationOptions = {}
+  ): Promise<WaterfallSnapshot> {
+    return protectedTauriInvoke<WaterfallSnapshot>('get_waterfall_snapshot', { lines }, 'sdr', {
+      notificationTitle: 'Failed to Get Waterfall',
+      showNotification: false, // Polled by the waterfall display
+      ...options
+    });
+  },
+
+  /**
+   * Set how many lines the waterfall history keeps
+   * @param lines - Depth between 100 and 4096 lines
+   * @param options - API invocation options
+   */
+  async setWaterfallDepth(lines: number, options: ApiInvocationOptions = {}): Promise<void> {
+    return protectedTauriInvoke<void>('set_waterfall_depth', { lines }, 'sdr', {
+      notificationTitle: 'Failed to Set Waterfall Depth',
+      ...options
+    });
+  },
+
+  /**
+   * Find signal peaks in the most recent FFT line
+   * @param thresholdDbm - Minimum peak magnitude
+   * @param minSeparationHz - Closer peaks are merged into the strongest
+   * @param options - API invocation options
+   * @returns Peaks in order of frequency
+   */
+  async detectSignalPeaks(
+    thresholdDbm: number,
+    minSeparationHz: number,
+    options: ApiInvocationOptions = {}
+  ): Promise<SignalPeak[]> {
+    return protectedTauriInvoke<SignalPeak[]>(
+      'detect_signal_peaks',
+      { thresholdDbm, minSeparationHz },
+      'sdr',
+      {
+        notificationTitle: 'Failed to Detect Signal Peaks',
+        showNotification: false,
+        ...options
+      }
+    );
+  },
+
+  /**
+   * Label a frequency on the spectrum
+   * @param frequencyHz - Signal frequency in Hz
+   * @param label - Annotation text
+   * @param color - Display color as #RRGGBB
+   * @param options - API invocation options
+   */
+  async annotateSignal(
+    frequencyHz: number,
+    label: string,
+    color: string,
+    options: ApiInvocationOptions = {}
+  ): Promise<void> {
+    return protectedTauriInvoke<void>('annotate_signal', { frequencyHz, label, color }, 'sdr', {
+      notificationTitle: 'Failed to Annotate Signal',
+      ...options
+    });
+  },
+
+  /**
+   * Get all signal annotations
+   * @param options - API invocation options
+   * @returns Annotations in order of frequency
+   */
+  async getSignalAnnotations(options: ApiInvocationOptions = {}): Promise<SignalAnnotation[]> {
+    return protectedTauriInvoke<SignalAnnotation[]>('get_signal_annotations', undefined, 'sdr', {
+      notificationTitle: 'Failed to Get Signal Annotations',
+      showNotification: false,
+      ...options
+    });
+  },
+
+  /**
+   * Sweep a frequency range; progress and the result arrive as
+   * `sdr-scan-progress` and `sdr-scan-complete` events
+   * @param startHz - First frequency in Hz
+   * @param stopHz - End of the range in Hz (exclusive)
+   * @param stepHz - Step between frequencies in Hz
+   * @param dwellMs - Time spent at each frequency
+   * @param options - API invocation options
+   */
+  async startFrequencyScan(
+    startHz: number,
+    stopHz: number,
+    stepHz: number,
+    dwellMs: number,
+    options: ApiInvocationOptions = {}
+  ): Promise<void> {
+    return protectedTauriInvoke<void>(
+      'start_frequency_scan',
+      { startHz, stopHz, stepHz, dwellMs },
+      'sdr',
+      {
+        notificationTitle: 'Failed to Start Frequency Scan',
+        ...options
+      }
+    );
+  },
+
+  /**
+   * Abort the running frequency scan
+   * @param options - API invocation options
+   */
+  async stopFrequencyScan(options: ApiInvocationOptions = {}): Promise<void> {
+    return protectedTauriInvoke<void>('stop_frequency_scan', undefined, 'sdr', {
+      notificationTitle: 'Failed to Stop Frequency Scan',
+      ...options
+    });
+  },
+
+  /**
+   * Save a frequency bookmark
+   * @param bookmark - Bookmark with a unique id
+   * @param options - API invocation options
+   */
+  async addSdrBookmark(bookmark: SdrBookmark, options: ApiInvocationOptions = {}): Promise<void> {
+    return protectedTauriInvoke<void>('add_sdr_bookmark', { bookmark }, 'sdr', {
+      notificationTitle: 'Failed to Add Bookmark',
+      ...options
+    });
+  },
+
+  /**
+   * Delete a frequency bookmark
+   * @param id - Bookmark id
+   * @param options - API invocation options
+   */
+  async removeSdrBookmark(id: string, options: ApiInvocationOptions = {}): Promise<void> {
+    return protectedTauriInvoke<void>('remove_sdr_bookmark', { id }, 'sdr', {
+      notificationTitle: 'Failed to Remove Bookmark',
+      ...options
+    });
+  },
+
+  /**
+   * Get saved frequency bookmarks
+   * @param options - API invocation options
+   * @returns Bookmarks in the order they were added
+   */
+  async getSdrBookmarks(options: ApiInvocationOptions = {}): Promise<SdrBookmark[]> {
+    const result = await protectedTauriInvoke<SdrBookmark[]>('get_sdr_bookmarks', undefined, 'sdr', {
+      notificationTitle: 'Failed to Get Bookmarks',
+      showNotification: false,
+      ...options
+    });
+    return result || [];
+  },
+
+  /**
+   * Tune to a bookmark's frequency, sample rate and gain; disables AGC
+   * @param id - Bookmark id
+   * @param options - API invocation options
+   */
+  async loadSdrBookmark(id: string, options: ApiInvocationOptions = {}): Promise<void> {
+    return protectedTauriInvoke<void>('load_sdr_bookmark', { id }, 'sdr', {
+      notificationTitle: 'Failed to Load Bookmark',
+      ...options
+    });
+  },
+
   /**
    * Set sample rate
    * @param sampleRate - Sample rate in Hz